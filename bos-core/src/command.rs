@@ -0,0 +1,111 @@
+// ===================================================================
+// DÉCOUPAGE DES LIGNES DE COMMANDE DU SHELL
+// ===================================================================
+
+/// Sépare une ligne de commande en (commande, arguments)
+///
+/// Les espaces en début et fin de ligne sont ignorés. La commande est
+/// le premier mot ; les arguments sont le reste de la ligne, tel quel
+/// (après le premier espace).
+///
+/// Exemple : `"  echo bonjour  le monde "` → `("echo", "bonjour  le monde")`
+pub fn parse_command(line: &str) -> (&str, &str) {
+    let line = line.trim();
+    match line.find(' ') {
+        Some(pos) => (&line[..pos], &line[pos + 1..]),
+        None => (line, ""),
+    }
+}
+
+/// Itérateur sur les arguments séparés par des espaces (les espaces
+/// multiples sont ignorés)
+pub fn split_args(args: &str) -> impl Iterator<Item = &str> {
+    args.split(' ').filter(|arg| !arg.is_empty())
+}
+
+/// Sépare un `&` final (lancement en arrière-plan) du reste de la ligne
+///
+/// Exemple : `"run hello &"` → `("run hello", true)`
+pub fn split_background(line: &str) -> (&str, bool) {
+    let line = line.trim_end();
+    match line.strip_suffix('&') {
+        Some(rest) => (rest.trim_end(), true),
+        None => (line, false),
+    }
+}
+
+/// Redirection de la sortie d'une commande vers un fichier
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Redirect<'a> {
+    /// Chemin du fichier
+    pub path: &'a str,
+    /// `>>` : ajouter à la fin au lieu de remplacer le contenu
+    pub append: bool,
+}
+
+/// Sépare une redirection `> fichier` ou `>> fichier` du reste de la
+/// ligne (le dernier `>` de la ligne)
+///
+/// Exemple : `"echo a > /tmp/b"` → `("echo a", Some(Redirect { path: "/tmp/b", append: false }))`.
+/// Un `>` sans fichier donne un chemin vide.
+pub fn split_redirect(line: &str) -> (&str, Option<Redirect<'_>>) {
+    let Some(index) = line.rfind('>') else {
+        return (line, None);
+    };
+    let path = line[index + 1..].trim();
+    match line[..index].strip_suffix('>') {
+        Some(rest) => (rest.trim_end(), Some(Redirect { path, append: true })),
+        None => (line[..index].trim_end(), Some(Redirect { path, append: false })),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_command_without_args() {
+        assert_eq!(parse_command("help"), ("help", ""));
+    }
+
+    #[test]
+    fn parses_command_with_args() {
+        assert_eq!(parse_command("echo bonjour le monde"), ("echo", "bonjour le monde"));
+    }
+
+    #[test]
+    fn trims_surrounding_spaces() {
+        assert_eq!(parse_command("   about   "), ("about", ""));
+        assert_eq!(parse_command("  echo   x "), ("echo", "  x"));
+    }
+
+    #[test]
+    fn empty_line() {
+        assert_eq!(parse_command(""), ("", ""));
+        assert_eq!(parse_command("    "), ("", ""));
+    }
+
+    #[test]
+    fn splits_args() {
+        let args: Vec<&str> = split_args(" a  b c ").collect();
+        assert_eq!(args, ["a", "b", "c"]);
+        assert_eq!(split_args("").count(), 0);
+    }
+
+    #[test]
+    fn splits_background_marker() {
+        assert_eq!(split_background("run hello &"), ("run hello", true));
+        assert_eq!(split_background("hello a&  "), ("hello a", true));
+        assert_eq!(split_background("hello"), ("hello", false));
+        assert_eq!(split_background("&"), ("", true));
+    }
+
+    #[test]
+    fn splits_redirection() {
+        let redirect = |path, append| Some(Redirect { path, append });
+        assert_eq!(split_redirect("echo a > /tmp/b"), ("echo a", redirect("/tmp/b", false)));
+        assert_eq!(split_redirect("echo a>>b "), ("echo a", redirect("b", true)));
+        assert_eq!(split_redirect("cat x >"), ("cat x", redirect("", false)));
+        assert_eq!(split_redirect("echo a"), ("echo a", None));
+    }
+}
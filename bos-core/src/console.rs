@@ -0,0 +1,97 @@
+// ===================================================================
+// ARITHMÉTIQUE DU CURSEUR DE LA CONSOLE TEXTE
+// ===================================================================
+//
+// La console VGA fait 80 colonnes × 25 lignes. Chaque cellule occupe
+// 2 octets dans la mémoire vidéo (caractère + couleur), et le curseur
+// du kernel est exprimé en octets (donc toujours un multiple de 2).
+//
+// Quand le curseur dépasse la fin de l'écran, il revient au début.
+
+/// Nombre de colonnes
+pub const WIDTH: usize = 80;
+/// Nombre de lignes
+pub const HEIGHT: usize = 25;
+/// Octets par cellule (1 pour le caractère, 1 pour la couleur)
+pub const BYTES_PER_CELL: usize = 2;
+/// Octets par ligne
+pub const LINE_SIZE: usize = WIDTH * BYTES_PER_CELL;
+/// Taille totale de l'écran en octets
+pub const SIZE: usize = WIDTH * HEIGHT * BYTES_PER_CELL;
+
+/// Ramène le curseur au début de l'écran s'il a dépassé la fin
+pub fn wrap(cursor: usize) -> usize {
+    if cursor >= SIZE {
+        0
+    } else {
+        cursor
+    }
+}
+
+/// Position du début de la ligne suivante
+///
+/// Division entière pour obtenir le numéro de ligne actuel,
+/// puis +1 pour passer à la ligne suivante.
+pub fn newline(cursor: usize) -> usize {
+    wrap(((cursor / LINE_SIZE) + 1) * LINE_SIZE)
+}
+
+/// Position après l'écriture d'un caractère
+pub fn advance(cursor: usize) -> usize {
+    cursor + BYTES_PER_CELL
+}
+
+/// Position après un retour arrière, ou `None` si on est déjà au début
+pub fn backspace(cursor: usize) -> Option<usize> {
+    cursor.checked_sub(BYTES_PER_CELL)
+}
+
+/// Position du curseur matériel (en cellules, pas en octets)
+pub fn hardware_position(cursor: usize) -> u16 {
+    (cursor / BYTES_PER_CELL) as u16
+}
+
+/// Ligne et colonne d'une position
+pub fn row_col(cursor: usize) -> (usize, usize) {
+    (cursor / LINE_SIZE, (cursor % LINE_SIZE) / BYTES_PER_CELL)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn newline_goes_to_start_of_next_line() {
+        assert_eq!(newline(0), LINE_SIZE);
+        assert_eq!(newline(10), LINE_SIZE);
+        assert_eq!(newline(LINE_SIZE), 2 * LINE_SIZE);
+        assert_eq!(row_col(newline(LINE_SIZE + 6)), (2, 0));
+    }
+
+    #[test]
+    fn newline_on_last_line_wraps_to_top() {
+        assert_eq!(newline(SIZE - LINE_SIZE), 0);
+        assert_eq!(newline(SIZE - 2), 0);
+    }
+
+    #[test]
+    fn wrap_only_past_end() {
+        assert_eq!(wrap(SIZE - 2), SIZE - 2);
+        assert_eq!(wrap(SIZE), 0);
+        assert_eq!(wrap(SIZE + 40), 0);
+    }
+
+    #[test]
+    fn advance_and_backspace() {
+        assert_eq!(advance(0), 2);
+        assert_eq!(backspace(2), Some(0));
+        assert_eq!(backspace(0), None);
+    }
+
+    #[test]
+    fn hardware_position_is_in_cells() {
+        assert_eq!(hardware_position(0), 0);
+        assert_eq!(hardware_position(LINE_SIZE), WIDTH as u16);
+        assert_eq!(hardware_position(SIZE - 2), (WIDTH * HEIGHT - 1) as u16);
+    }
+}
@@ -0,0 +1,196 @@
+// ===================================================================
+// DÉMANGLAGE DES SYMBOLES RUST (FORMAT "LEGACY")
+// ===================================================================
+//
+// Le compilateur encode les chemins Rust dans les noms de symboles.
+// Le kernel est compilé avec `-C symbol-mangling-version=legacy`
+// (voir .cargo/config.toml), qui produit des noms de la forme :
+//
+//   _ZN3bos5panic12kernel_panic17h0123456789abcdefE
+//
+// c'est-à-dire "_ZN", puis chaque composant du chemin préfixé par sa
+// longueur, un hash "h" + 16 chiffres hexadécimaux, et enfin "E".
+// Résultat démanglé : bos::panic::kernel_panic
+//
+// Certains caractères sont échappés : $LT$ → <, $GT$ → >, $u20$ → ' ', etc.
+
+use core::fmt;
+
+/// Symbole à démangler, affichable avec `{}`
+///
+/// Si le nom n'est pas au format legacy, il est affiché tel quel.
+pub struct Demangle<'a>(pub &'a str);
+
+/// Retourne les composants du chemin d'un symbole legacy (sans "_ZN" ni "E")
+fn legacy_body(symbol: &str) -> Option<&str> {
+    let inner = symbol
+        .strip_prefix("_ZN")
+        .or_else(|| symbol.strip_prefix("__ZN"))
+        .or_else(|| symbol.strip_prefix("ZN"))?;
+    // Il peut y avoir un suffixe après le "E" final (ex : ".llvm.1234")
+    let end = inner.rfind('E')?;
+    Some(&inner[..end])
+}
+
+/// Itère sur les composants (longueur + texte) d'un corps de symbole
+struct Components<'a> {
+    rest: &'a str,
+}
+
+impl<'a> Iterator for Components<'a> {
+    type Item = Result<&'a str, ()>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.rest.is_empty() {
+            return None;
+        }
+        let digits = self.rest.bytes().take_while(|b| b.is_ascii_digit()).count();
+        let len = self.rest[..digits].parse::<usize>();
+        let component = match len {
+            Ok(len) if digits > 0 && self.rest.len() >= digits + len => {
+                let component = &self.rest[digits..digits + len];
+                self.rest = &self.rest[digits + len..];
+                component
+            }
+            _ => {
+                self.rest = "";
+                return Some(Err(()));
+            }
+        };
+        Some(Ok(component))
+    }
+}
+
+/// Indique si un composant est le hash final ("h" + 16 chiffres hexadécimaux)
+fn is_hash(component: &str) -> bool {
+    component.len() == 17
+        && component.starts_with('h')
+        && component[1..].bytes().all(|b| b.is_ascii_hexdigit())
+}
+
+/// Écrit un composant en remplaçant les séquences d'échappement
+fn write_component(f: &mut fmt::Formatter, mut component: &str) -> fmt::Result {
+    // Un '_' est ajouté devant les composants qui commencent par '$'
+    if component.starts_with("_$") {
+        component = &component[1..];
+    }
+    while !component.is_empty() {
+        if let Some(rest) = component.strip_prefix("..") {
+            f.write_str("::")?;
+            component = rest;
+        } else if component.starts_with('$') {
+            let end = match component[1..].find('$') {
+                Some(end) => end + 1,
+                None => return f.write_str(component),
+            };
+            let escape = &component[1..end];
+            let replacement = match escape {
+                "SP" => "@",
+                "BP" => "*",
+                "RF" => "&",
+                "LT" => "<",
+                "GT" => ">",
+                "LP" => "(",
+                "RP" => ")",
+                "C" => ",",
+                _ => "",
+            };
+            if !replacement.is_empty() {
+                f.write_str(replacement)?;
+            } else if let Some(c) = escape
+                .strip_prefix('u')
+                .and_then(|hex| u32::from_str_radix(hex, 16).ok())
+                .and_then(char::from_u32)
+            {
+                write!(f, "{}", c)?;
+            } else {
+                // Échappement inconnu : on le recopie tel quel
+                f.write_str(&component[..=end])?;
+            }
+            component = &component[end + 1..];
+        } else {
+            let end = component
+                .find(['$', '.'])
+                .unwrap_or(component.len());
+            // Un '.' isolé (pas "..") est recopié tel quel
+            let end = if end == 0 { 1 } else { end };
+            f.write_str(&component[..end])?;
+            component = &component[end..];
+        }
+    }
+    Ok(())
+}
+
+impl fmt::Display for Demangle<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let body = match legacy_body(self.0) {
+            Some(body) => body,
+            None => return f.write_str(self.0),
+        };
+        // Vérifier d'abord que tout le corps est bien formé
+        if (Components { rest: body }).any(|c| c.is_err()) {
+            return f.write_str(self.0);
+        }
+        let count = Components { rest: body }.count();
+        let mut first = true;
+        for (i, component) in (Components { rest: body }).flatten().enumerate() {
+            if i == count - 1 && is_hash(component) {
+                break;
+            }
+            if !first {
+                f.write_str("::")?;
+            }
+            first = false;
+            write_component(f, component)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn demangle(symbol: &str) -> String {
+        Demangle(symbol).to_string()
+    }
+
+    #[test]
+    fn demangles_simple_path() {
+        assert_eq!(
+            demangle("_ZN3bos5panic12kernel_panic17h0123456789abcdefE"),
+            "bos::panic::kernel_panic"
+        );
+    }
+
+    #[test]
+    fn demangles_escapes() {
+        assert_eq!(
+            demangle("_ZN4core3ptr85drop_in_place$LT$std..rt..lang_start$LT$$LP$$RP$$GT$..$u7b$$u7b$closure$u7d$$u7d$$GT$17h0123456789abcdefE"),
+            "core::ptr::drop_in_place<std::rt::lang_start<()>::{{closure}}>"
+        );
+        assert_eq!(
+            demangle("_ZN56_$LT$bos..vga..VgaWriter$u20$as$u20$core..fmt..Write$GT$9write_str17h0123456789abcdefE"),
+            "<bos::vga::VgaWriter as core::fmt::Write>::write_str"
+        );
+    }
+
+    #[test]
+    fn keeps_non_hash_last_component() {
+        assert_eq!(demangle("_ZN3foo3barE"), "foo::bar");
+    }
+
+    #[test]
+    fn ignores_llvm_suffix() {
+        assert_eq!(demangle("_ZN3foo3bar17h0123456789abcdefE.llvm.42"), "foo::bar");
+    }
+
+    #[test]
+    fn leaves_other_names_unchanged() {
+        assert_eq!(demangle("_start"), "_start");
+        assert_eq!(demangle("memcpy"), "memcpy");
+        assert_eq!(demangle("_RNvCs1234_3bos4main"), "_RNvCs1234_3bos4main");
+        // Corps mal formé : longueur qui dépasse
+        assert_eq!(demangle("_ZN99fooE"), "_ZN99fooE");
+    }
+}
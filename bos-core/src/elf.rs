@@ -0,0 +1,537 @@
+// ===================================================================
+// LECTURE DE FICHIERS ELF64
+// ===================================================================
+//
+// Le format ELF (Executable and Linkable Format) est celui des
+// exécutables produits par rustc/lld. Un fichier ELF64 contient :
+// - Un en-tête (64 octets) : type, architecture, point d'entrée,
+//   position des tables de segments et de sections
+// - Des "program headers" : les segments à charger en mémoire
+// - Des "section headers" : les sections (.text, .symtab, .strtab...)
+//
+// Ce module ne fait que lire : il ne charge rien en mémoire. Toutes les
+// lectures sont vérifiées (un fichier tronqué ou corrompu donne une
+// erreur ou `None`, jamais un panic).
+
+use core::fmt;
+
+/// Taille de l'en-tête ELF64
+pub const HEADER_SIZE: usize = 64;
+
+/// Type de section : table des symboles
+pub const SHT_SYMTAB: u32 = 2;
+/// Type de section : table de chaînes
+pub const SHT_STRTAB: u32 = 3;
+
+/// Type de symbole : fonction
+pub const STT_FUNC: u8 = 2;
+
+/// Type de fichier : exécutable à adresse fixe
+pub const ET_EXEC: u16 = 2;
+/// Architecture : x86-64
+pub const EM_X86_64: u16 = 0x3E;
+
+/// Type de segment : à charger en mémoire
+pub const PT_LOAD: u32 = 1;
+/// Drapeau de segment : exécutable
+pub const PF_X: u32 = 1;
+/// Drapeau de segment : modifiable
+pub const PF_W: u32 = 2;
+/// Drapeau de segment : lisible
+pub const PF_R: u32 = 4;
+
+/// Erreurs de lecture d'un fichier ELF
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ElfError {
+    /// Le fichier est plus court que l'en-tête
+    TooShort,
+    /// Les 4 premiers octets ne sont pas "\x7fELF"
+    BadMagic,
+    /// Pas un fichier 64 bits
+    NotElf64,
+    /// Pas en little-endian
+    NotLittleEndian,
+    /// Une table (sections/segments) dépasse la fin du fichier
+    Truncated,
+}
+
+impl fmt::Display for ElfError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let msg = match self {
+            ElfError::TooShort => "fichier trop court",
+            ElfError::BadMagic => "signature ELF invalide",
+            ElfError::NotElf64 => "pas un ELF 64 bits",
+            ElfError::NotLittleEndian => "pas en little-endian",
+            ElfError::Truncated => "fichier tronque",
+        };
+        f.write_str(msg)
+    }
+}
+
+// Lectures little-endian vérifiées
+fn read_u16(data: &[u8], offset: usize) -> Option<u16> {
+    let bytes = data.get(offset..offset.checked_add(2)?)?;
+    Some(u16::from_le_bytes([bytes[0], bytes[1]]))
+}
+
+fn read_u32(data: &[u8], offset: usize) -> Option<u32> {
+    let bytes = data.get(offset..offset.checked_add(4)?)?;
+    Some(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+}
+
+fn read_u64(data: &[u8], offset: usize) -> Option<u64> {
+    let bytes = data.get(offset..offset.checked_add(8)?)?;
+    let mut buf = [0u8; 8];
+    buf.copy_from_slice(bytes);
+    Some(u64::from_le_bytes(buf))
+}
+
+/// En-tête ELF64 (champs utiles seulement)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Header {
+    /// Type de fichier (2 = exécutable, 3 = partagé/PIE)
+    pub file_type: u16,
+    /// Architecture (0x3E = x86-64)
+    pub machine: u16,
+    /// Adresse du point d'entrée
+    pub entry: u64,
+    /// Position de la table des program headers
+    pub ph_offset: u64,
+    /// Position de la table des section headers
+    pub sh_offset: u64,
+    /// Taille d'une entrée de program header
+    pub ph_entry_size: u16,
+    /// Nombre de program headers
+    pub ph_count: u16,
+    /// Taille d'une entrée de section header
+    pub sh_entry_size: u16,
+    /// Nombre de section headers
+    pub sh_count: u16,
+    /// Index de la section contenant les noms des sections
+    pub sh_string_index: u16,
+}
+
+impl Header {
+    /// Lit et valide l'en-tête ELF64 au début de `data`
+    pub fn parse(data: &[u8]) -> Result<Header, ElfError> {
+        if data.len() < HEADER_SIZE {
+            return Err(ElfError::TooShort);
+        }
+        if data[0..4] != [0x7f, b'E', b'L', b'F'] {
+            return Err(ElfError::BadMagic);
+        }
+        if data[4] != 2 {
+            return Err(ElfError::NotElf64);
+        }
+        if data[5] != 1 {
+            return Err(ElfError::NotLittleEndian);
+        }
+        // Les lectures ne peuvent pas échouer : data fait au moins 64 octets
+        let field16 = |offset| read_u16(data, offset).unwrap_or(0);
+        Ok(Header {
+            file_type: field16(16),
+            machine: field16(18),
+            entry: read_u64(data, 24).unwrap_or(0),
+            ph_offset: read_u64(data, 32).unwrap_or(0),
+            sh_offset: read_u64(data, 40).unwrap_or(0),
+            ph_entry_size: field16(54),
+            ph_count: field16(56),
+            sh_entry_size: field16(58),
+            sh_count: field16(60),
+            sh_string_index: field16(62),
+        })
+    }
+
+    /// Position de la fin de la table des sections
+    ///
+    /// lld place cette table à la fin du fichier : c'est donc une bonne
+    /// estimation de la taille d'un ELF dont on ne connaît que le début.
+    pub fn section_table_end(&self) -> u64 {
+        self.sh_offset + self.sh_count as u64 * self.sh_entry_size as u64
+    }
+}
+
+/// Section header (champs utiles seulement)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SectionHeader {
+    /// Offset du nom dans la table des noms de sections
+    pub name: u32,
+    /// Type de section (SHT_*)
+    pub kind: u32,
+    /// Adresse en mémoire (0 si la section n'est pas chargée)
+    pub addr: u64,
+    /// Position dans le fichier
+    pub offset: u64,
+    /// Taille en octets
+    pub size: u64,
+    /// Section liée (pour une table de symboles : sa table de chaînes)
+    pub link: u32,
+    /// Taille d'une entrée (pour les tables)
+    pub entry_size: u64,
+}
+
+/// Program header (segment)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ProgramHeader {
+    /// Type de segment (PT_*)
+    pub kind: u32,
+    /// Droits (PF_X = 1, PF_W = 2, PF_R = 4)
+    pub flags: u32,
+    /// Position dans le fichier
+    pub offset: u64,
+    /// Adresse virtuelle de chargement
+    pub vaddr: u64,
+    /// Taille dans le fichier
+    pub file_size: u64,
+    /// Taille en mémoire (> file_size pour le .bss)
+    pub mem_size: u64,
+    /// Alignement
+    pub align: u64,
+}
+
+impl ProgramHeader {
+    /// Indique si `addr` est dans ce segment une fois chargé
+    pub fn contains(&self, addr: u64) -> bool {
+        addr >= self.vaddr && addr - self.vaddr < self.mem_size
+    }
+}
+
+/// Un symbole de la table des symboles
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Symbol<'a> {
+    /// Nom (brut, tel que produit par le compilateur)
+    pub name: &'a str,
+    /// Adresse
+    pub value: u64,
+    /// Taille en octets (0 si inconnue)
+    pub size: u64,
+    /// Type (STT_*)
+    pub kind: u8,
+}
+
+/// Un fichier ELF64 validé
+#[derive(Debug, Clone, Copy)]
+pub struct ElfFile<'a> {
+    data: &'a [u8],
+    header: Header,
+}
+
+impl<'a> ElfFile<'a> {
+    /// Valide l'en-tête et les tables de sections/segments
+    pub fn parse(data: &'a [u8]) -> Result<ElfFile<'a>, ElfError> {
+        let header = Header::parse(data)?;
+        let ph_end = header.ph_offset + header.ph_count as u64 * header.ph_entry_size as u64;
+        if header.section_table_end() > data.len() as u64 || ph_end > data.len() as u64 {
+            return Err(ElfError::Truncated);
+        }
+        Ok(ElfFile { data, header })
+    }
+
+    /// L'en-tête du fichier
+    pub fn header(&self) -> &Header {
+        &self.header
+    }
+
+    /// Contenu brut du fichier
+    pub fn data(&self) -> &'a [u8] {
+        self.data
+    }
+
+    /// Lit le section header numéro `index`
+    pub fn section_header(&self, index: u16) -> Option<SectionHeader> {
+        if index >= self.header.sh_count {
+            return None;
+        }
+        let base = self.header.sh_offset as usize + index as usize * self.header.sh_entry_size as usize;
+        Some(SectionHeader {
+            name: read_u32(self.data, base)?,
+            kind: read_u32(self.data, base + 4)?,
+            addr: read_u64(self.data, base + 16)?,
+            offset: read_u64(self.data, base + 24)?,
+            size: read_u64(self.data, base + 32)?,
+            link: read_u32(self.data, base + 40)?,
+            entry_size: read_u64(self.data, base + 56)?,
+        })
+    }
+
+    /// Itère sur tous les section headers
+    pub fn section_headers(&self) -> impl Iterator<Item = SectionHeader> + '_ {
+        (0..self.header.sh_count).filter_map(move |i| self.section_header(i))
+    }
+
+    /// Lit le program header numéro `index`
+    pub fn program_header(&self, index: u16) -> Option<ProgramHeader> {
+        if index >= self.header.ph_count {
+            return None;
+        }
+        let base = self.header.ph_offset as usize + index as usize * self.header.ph_entry_size as usize;
+        Some(ProgramHeader {
+            kind: read_u32(self.data, base)?,
+            flags: read_u32(self.data, base + 4)?,
+            offset: read_u64(self.data, base + 8)?,
+            vaddr: read_u64(self.data, base + 16)?,
+            file_size: read_u64(self.data, base + 32)?,
+            mem_size: read_u64(self.data, base + 40)?,
+            align: read_u64(self.data, base + 48)?,
+        })
+    }
+
+    /// Itère sur tous les program headers
+    pub fn program_headers(&self) -> impl Iterator<Item = ProgramHeader> + '_ {
+        (0..self.header.ph_count).filter_map(move |i| self.program_header(i))
+    }
+
+    /// Contenu d'une section dans le fichier
+    pub fn section_data(&self, section: &SectionHeader) -> Option<&'a [u8]> {
+        let start = usize::try_from(section.offset).ok()?;
+        let end = start.checked_add(usize::try_from(section.size).ok()?)?;
+        self.data.get(start..end)
+    }
+
+    /// Contenu d'un segment dans le fichier (`file_size` octets)
+    pub fn segment_data(&self, segment: &ProgramHeader) -> Option<&'a [u8]> {
+        let start = usize::try_from(segment.offset).ok()?;
+        let end = start.checked_add(usize::try_from(segment.file_size).ok()?)?;
+        self.data.get(start..end)
+    }
+
+    /// Indique si `addr` tombe dans un segment exécutable
+    pub fn is_code_address(&self, addr: u64) -> bool {
+        self.program_headers()
+            .any(|ph| ph.kind == PT_LOAD && ph.flags & PF_X != 0 && ph.contains(addr))
+    }
+
+    /// Table des symboles (.symtab), si le fichier n'a pas été strippé
+    pub fn symbol_table(&self) -> Option<SymbolTable<'a>> {
+        let symtab = self.section_headers().find(|s| s.kind == SHT_SYMTAB)?;
+        let strtab = self.section_header(symtab.link as u16)?;
+        if strtab.kind != SHT_STRTAB || symtab.entry_size < 24 {
+            return None;
+        }
+        Some(SymbolTable {
+            symbols: self.section_data(&symtab)?,
+            strings: self.section_data(&strtab)?,
+            entry_size: symtab.entry_size as usize,
+        })
+    }
+}
+
+/// Table des symboles d'un fichier ELF
+#[derive(Debug, Clone, Copy)]
+pub struct SymbolTable<'a> {
+    symbols: &'a [u8],
+    strings: &'a [u8],
+    entry_size: usize,
+}
+
+impl<'a> SymbolTable<'a> {
+    /// Nombre de symboles
+    pub fn len(&self) -> usize {
+        self.symbols.len() / self.entry_size
+    }
+
+    /// Indique si la table est vide
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Lit une chaîne terminée par un zéro dans la table de chaînes
+    fn string_at(&self, offset: usize) -> Option<&'a str> {
+        let bytes = self.strings.get(offset..)?;
+        let end = bytes.iter().position(|&b| b == 0)?;
+        core::str::from_utf8(&bytes[..end]).ok()
+    }
+
+    /// Lit le symbole numéro `index`
+    pub fn get(&self, index: usize) -> Option<Symbol<'a>> {
+        let base = index.checked_mul(self.entry_size)?;
+        let name = read_u32(self.symbols, base)?;
+        let info = *self.symbols.get(base + 4)?;
+        Some(Symbol {
+            name: self.string_at(name as usize)?,
+            value: read_u64(self.symbols, base + 8)?,
+            size: read_u64(self.symbols, base + 16)?,
+            kind: info & 0xF,
+        })
+    }
+
+    /// Itère sur tous les symboles
+    pub fn iter(&self) -> impl Iterator<Item = Symbol<'a>> + '_ {
+        (0..self.len()).filter_map(move |i| self.get(i))
+    }
+
+    /// Trouve la fonction qui contient `addr`, avec le décalage depuis son début
+    ///
+    /// Si aucune fonction ne contient exactement l'adresse (symbole de
+    /// taille 0), on prend la fonction qui la précède au plus près.
+    pub fn lookup(&self, addr: u64) -> Option<(Symbol<'a>, u64)> {
+        let mut best: Option<Symbol<'a>> = None;
+        for symbol in self.iter() {
+            if symbol.kind != STT_FUNC || symbol.value > addr {
+                continue;
+            }
+            if symbol.size != 0 && addr - symbol.value < symbol.size {
+                return Some((symbol, addr - symbol.value));
+            }
+            if symbol.size == 0 && best.is_none_or(|b| symbol.value > b.value) {
+                best = Some(symbol);
+            }
+        }
+        best.map(|symbol| (symbol, addr - symbol.value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Construit un petit ELF64 avec 2 fonctions et 1 segment exécutable
+    fn build_test_elf() -> Vec<u8> {
+        let strtab = b"\0alpha\0beta\0";
+        let shstrtab = b"\0.symtab\0.strtab\0.shstrtab\0";
+
+        // Symboles : nul, alpha (0x1000, 0x20 octets), beta (0x1040, taille 0)
+        let mut symtab = vec![0u8; 24];
+        for (name, value, size) in [(1u32, 0x1000u64, 0x20u64), (7, 0x1040, 0)] {
+            let mut sym = vec![0u8; 24];
+            sym[0..4].copy_from_slice(&name.to_le_bytes());
+            sym[4] = STT_FUNC;
+            sym[8..16].copy_from_slice(&value.to_le_bytes());
+            sym[16..24].copy_from_slice(&size.to_le_bytes());
+            symtab.extend(sym);
+        }
+
+        let ph_offset = HEADER_SIZE;
+        let symtab_offset = ph_offset + 56;
+        let strtab_offset = symtab_offset + symtab.len();
+        let shstrtab_offset = strtab_offset + strtab.len();
+        let sh_offset = shstrtab_offset + shstrtab.len();
+
+        let mut elf = vec![0u8; HEADER_SIZE];
+        elf[0..4].copy_from_slice(&[0x7f, b'E', b'L', b'F']);
+        elf[4] = 2;
+        elf[5] = 1;
+        elf[16..18].copy_from_slice(&2u16.to_le_bytes());
+        elf[18..20].copy_from_slice(&0x3Eu16.to_le_bytes());
+        elf[24..32].copy_from_slice(&0x1000u64.to_le_bytes());
+        elf[32..40].copy_from_slice(&(ph_offset as u64).to_le_bytes());
+        elf[40..48].copy_from_slice(&(sh_offset as u64).to_le_bytes());
+        elf[54..56].copy_from_slice(&56u16.to_le_bytes());
+        elf[56..58].copy_from_slice(&1u16.to_le_bytes());
+        elf[58..60].copy_from_slice(&64u16.to_le_bytes());
+        elf[60..62].copy_from_slice(&4u16.to_le_bytes());
+        elf[62..64].copy_from_slice(&3u16.to_le_bytes());
+
+        // Segment de code : 0x1000..0x1100, R+X
+        let mut ph = vec![0u8; 56];
+        ph[0..4].copy_from_slice(&PT_LOAD.to_le_bytes());
+        ph[4..8].copy_from_slice(&(PF_X | 4).to_le_bytes());
+        ph[16..24].copy_from_slice(&0x1000u64.to_le_bytes());
+        ph[32..40].copy_from_slice(&0x100u64.to_le_bytes());
+        ph[40..48].copy_from_slice(&0x100u64.to_le_bytes());
+        elf.extend(ph);
+
+        elf.extend(&symtab);
+        elf.extend(strtab);
+        elf.extend(shstrtab);
+
+        // Sections : nulle, .symtab, .strtab, .shstrtab
+        let sections = [
+            (0u32, 0u32, 0usize, 0usize, 0u32, 0u64),
+            (1, SHT_SYMTAB, symtab_offset, symtab.len(), 2, 24),
+            (9, SHT_STRTAB, strtab_offset, strtab.len(), 0, 0),
+            (17, SHT_STRTAB, shstrtab_offset, shstrtab.len(), 0, 0),
+        ];
+        for (name, kind, offset, size, link, entry_size) in sections {
+            let mut sh = vec![0u8; 64];
+            sh[0..4].copy_from_slice(&name.to_le_bytes());
+            sh[4..8].copy_from_slice(&kind.to_le_bytes());
+            sh[24..32].copy_from_slice(&(offset as u64).to_le_bytes());
+            sh[32..40].copy_from_slice(&(size as u64).to_le_bytes());
+            sh[40..44].copy_from_slice(&link.to_le_bytes());
+            sh[56..64].copy_from_slice(&entry_size.to_le_bytes());
+            elf.extend(sh);
+        }
+        elf
+    }
+
+    #[test]
+    fn parses_header() {
+        let elf = build_test_elf();
+        let header = Header::parse(&elf).unwrap();
+        assert_eq!(header.machine, 0x3E);
+        assert_eq!(header.entry, 0x1000);
+        assert_eq!(header.sh_count, 4);
+        assert_eq!(header.section_table_end(), elf.len() as u64);
+    }
+
+    #[test]
+    fn rejects_invalid_files() {
+        let elf = build_test_elf();
+        assert_eq!(Header::parse(&elf[..10]), Err(ElfError::TooShort));
+        let mut bad = elf.clone();
+        bad[0] = 0;
+        assert_eq!(Header::parse(&bad), Err(ElfError::BadMagic));
+        let mut bad = elf.clone();
+        bad[4] = 1;
+        assert_eq!(Header::parse(&bad), Err(ElfError::NotElf64));
+        assert_eq!(ElfFile::parse(&elf[..elf.len() - 1]).err(), Some(ElfError::Truncated));
+    }
+
+    #[test]
+    fn reads_symbols() {
+        let elf = build_test_elf();
+        let file = ElfFile::parse(&elf).unwrap();
+        let symbols = file.symbol_table().unwrap();
+        assert_eq!(symbols.len(), 3);
+        let names: Vec<&str> = symbols.iter().map(|s| s.name).collect();
+        assert_eq!(names, ["", "alpha", "beta"]);
+    }
+
+    #[test]
+    fn looks_up_addresses() {
+        let elf = build_test_elf();
+        let symbols = ElfFile::parse(&elf).unwrap().symbol_table().unwrap();
+        let (sym, offset) = symbols.lookup(0x1010).unwrap();
+        assert_eq!((sym.name, offset), ("alpha", 0x10));
+        // Après la fin d'alpha : beta (taille 0) n'est pas encore atteint
+        assert!(symbols.lookup(0x1030).is_none());
+        let (sym, offset) = symbols.lookup(0x1050).unwrap();
+        assert_eq!((sym.name, offset), ("beta", 0x10));
+        assert!(symbols.lookup(0x10).is_none());
+    }
+
+    #[test]
+    fn detects_code_addresses() {
+        let elf = build_test_elf();
+        let file = ElfFile::parse(&elf).unwrap();
+        assert!(file.is_code_address(0x1000));
+        assert!(file.is_code_address(0x10ff));
+        assert!(!file.is_code_address(0x1100));
+        assert!(!file.is_code_address(0));
+    }
+
+    #[test]
+    fn reads_own_test_binary() {
+        // Le binaire de test de l'hôte est un vrai ELF64 non strippé
+        let data = std::fs::read(std::env::current_exe().unwrap()).unwrap();
+        let file = ElfFile::parse(&data).unwrap();
+        assert_eq!(file.header().machine, 0x3E);
+        let symbols = file.symbol_table().unwrap();
+        assert!(symbols.iter().any(|s| s.name.contains("reads_own_test_binary")));
+    }
+
+    #[test]
+    fn never_panics_on_garbage() {
+        let mut elf = build_test_elf();
+        for i in HEADER_SIZE..elf.len() {
+            elf[i] = elf[i].wrapping_mul(31).wrapping_add(7);
+            if let Ok(file) = ElfFile::parse(&elf) {
+                if let Some(symbols) = file.symbol_table() {
+                    let _ = symbols.lookup(0x1010);
+                }
+                let _ = file.is_code_address(0x1000);
+            }
+        }
+    }
+}
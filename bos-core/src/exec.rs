@@ -0,0 +1,358 @@
+// ===================================================================
+// CHARGEMENT D'UN EXÉCUTABLE : VALIDATION ET PILE INITIALE
+// ===================================================================
+//
+// Avant de projeter un ELF dans l'espace d'un processus, le kernel
+// vérifie qu'il est chargeable (`check_image`) : exécutable x86-64 à
+// adresse fixe, segments PT_LOAD lisibles dans le fichier, tous dans
+// la zone utilisateur et sans page commune, point d'entrée dans un
+// segment exécutable. Les droits d'un segment (R/W/X) deviennent ceux
+// de ses pages (`segment_flags`).
+//
+// Au démarrage, la pile du programme suit l'ABI System V (comme Linux) :
+//
+//   haut   chaînes des arguments puis de l'environnement
+//          (alignement sur 16 octets)
+//          auxv : paires (type, valeur), terminées par AT_NULL
+//          envp : pointeurs vers les chaînes, terminés par 0
+//          argv : pointeurs vers les chaînes, terminés par 0
+//   RSP →  argc
+
+use core::fmt;
+use core::ops::Range;
+
+use crate::elf::{ElfError, ElfFile, ProgramHeader, EM_X86_64, ET_EXEC, PF_W, PF_X, PT_LOAD};
+use crate::memory::PAGE_SIZE;
+use crate::paging::PageFlags;
+
+/// Fin du vecteur auxiliaire
+pub const AT_NULL: u64 = 0;
+/// Entrée à ignorer
+pub const AT_IGNORE: u64 = 1;
+/// Adresse des program headers en mémoire
+pub const AT_PHDR: u64 = 3;
+/// Taille d'un program header
+pub const AT_PHENT: u64 = 4;
+/// Nombre de program headers
+pub const AT_PHNUM: u64 = 5;
+/// Taille d'une page
+pub const AT_PAGESZ: u64 = 6;
+/// Point d'entrée du programme
+pub const AT_ENTRY: u64 = 9;
+
+/// Nombre d'entrées du vecteur auxiliaire (sans AT_NULL)
+pub const AUXV_COUNT: usize = 5;
+
+/// Exécutable impossible à charger
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImageError {
+    /// Fichier ELF invalide
+    Elf(ElfError),
+    /// Pas un exécutable à adresse fixe (objet, bibliothèque, PIE)
+    NotExecutable,
+    /// Pas un programme x86-64
+    WrongMachine,
+    /// Aucun segment à charger
+    NoSegments,
+    /// Segment incohérent (taille, position dans le fichier)
+    BadSegment,
+    /// Segment hors de la zone utilisateur
+    OutsideUserSpace,
+    /// Deux segments partagent une page
+    OverlappingSegments,
+    /// Point d'entrée hors des segments exécutables
+    BadEntry,
+    /// Arguments et environnement trop grands pour la pile
+    StackOverflow,
+}
+
+impl From<ElfError> for ImageError {
+    fn from(error: ElfError) -> ImageError {
+        ImageError::Elf(error)
+    }
+}
+
+impl fmt::Display for ImageError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let message = match self {
+            ImageError::Elf(error) => return write!(f, "ELF invalide : {}", error),
+            ImageError::NotExecutable => "pas un executable",
+            ImageError::WrongMachine => "pas un programme x86-64",
+            ImageError::NoSegments => "aucun segment a charger",
+            ImageError::BadSegment => "segment invalide",
+            ImageError::OutsideUserSpace => "segment hors de la zone utilisateur",
+            ImageError::OverlappingSegments => "segments qui se chevauchent",
+            ImageError::BadEntry => "point d'entree hors du code",
+            ImageError::StackOverflow => "arguments trop longs",
+        };
+        f.write_str(message)
+    }
+}
+
+/// Pages couvertes par un segment une fois chargé
+pub fn segment_pages(segment: &ProgramHeader) -> Range<u64> {
+    let start = segment.vaddr - segment.vaddr % PAGE_SIZE;
+    let end = (segment.vaddr + segment.mem_size).div_ceil(PAGE_SIZE) * PAGE_SIZE;
+    start..end
+}
+
+/// Droits des pages d'un segment (sans USER, ajouté par le kernel)
+pub fn segment_flags(flags: u32) -> PageFlags {
+    let mut page_flags = PageFlags::empty();
+    if flags & PF_W != 0 {
+        page_flags |= PageFlags::WRITABLE;
+    }
+    if flags & PF_X == 0 {
+        page_flags |= PageFlags::NO_EXECUTE;
+    }
+    page_flags
+}
+
+/// Segments à charger
+fn load_segments<'a>(file: &'a ElfFile) -> impl Iterator<Item = ProgramHeader> + 'a {
+    file.program_headers().filter(|segment| segment.kind == PT_LOAD)
+}
+
+/// Vérifie qu'un exécutable peut être chargé dans la zone `user`
+pub fn check_image(file: &ElfFile, user: Range<u64>) -> Result<(), ImageError> {
+    let header = file.header();
+    if header.file_type != ET_EXEC {
+        return Err(ImageError::NotExecutable);
+    }
+    if header.machine != EM_X86_64 {
+        return Err(ImageError::WrongMachine);
+    }
+    if load_segments(file).next().is_none() {
+        return Err(ImageError::NoSegments);
+    }
+    for segment in load_segments(file) {
+        if segment.file_size > segment.mem_size || file.segment_data(&segment).is_none() {
+            return Err(ImageError::BadSegment);
+        }
+        let end = segment.vaddr.checked_add(segment.mem_size).ok_or(ImageError::BadSegment)?;
+        if segment.vaddr < user.start || end > user.end {
+            return Err(ImageError::OutsideUserSpace);
+        }
+    }
+    // Chaque page appartient à un seul segment, avec ses droits
+    for (i, a) in load_segments(file).enumerate() {
+        for b in load_segments(file).skip(i + 1) {
+            let (a, b) = (segment_pages(&a), segment_pages(&b));
+            if a.start < b.end && b.start < a.end {
+                return Err(ImageError::OverlappingSegments);
+            }
+        }
+    }
+    if !file.is_code_address(header.entry) {
+        return Err(ImageError::BadEntry);
+    }
+    Ok(())
+}
+
+/// Adresse des program headers une fois le fichier chargé (s'ils sont
+/// dans un segment)
+pub fn program_headers_address(file: &ElfFile) -> Option<u64> {
+    let offset = file.header().ph_offset;
+    load_segments(file)
+        .find(|segment| offset >= segment.offset && offset - segment.offset < segment.file_size)
+        .map(|segment| segment.vaddr + (offset - segment.offset))
+}
+
+/// Vecteur auxiliaire transmis au programme
+pub fn auxiliary_vector(file: &ElfFile) -> [(u64, u64); AUXV_COUNT] {
+    let header = file.header();
+    let phdr = match program_headers_address(file) {
+        Some(address) => (AT_PHDR, address),
+        None => (AT_IGNORE, 0),
+    };
+    [
+        phdr,
+        (AT_PHENT, header.ph_entry_size as u64),
+        (AT_PHNUM, header.ph_count as u64),
+        (AT_PAGESZ, PAGE_SIZE),
+        (AT_ENTRY, header.entry),
+    ]
+}
+
+/// Écrit un mot de 64 bits dans la pile
+fn put(stack: &mut [u8], offset: usize, value: u64) {
+    stack[offset..offset + 8].copy_from_slice(&value.to_le_bytes());
+}
+
+/// Construit le haut de la pile initiale d'un programme
+///
+/// `stack` représente la mémoire de `top - stack.len()` à `top`.
+/// Retourne la valeur de RSP au démarrage (alignée sur 16 octets,
+/// pointant sur argc) ; seuls les octets à partir de RSP sont utilisés.
+pub fn build_stack(
+    stack: &mut [u8],
+    top: u64,
+    args: &[&str],
+    env: &[&str],
+    auxv: &[(u64, u64)],
+) -> Result<u64, ImageError> {
+    let base = top - stack.len() as u64;
+    let strings_size: usize = args.iter().chain(env).map(|s| s.len() + 1).sum();
+    // argc, argv + 0, envp + 0, auxv + AT_NULL
+    let words = 1 + (args.len() + 1) + (env.len() + 1) + 2 * (auxv.len() + 1);
+    let strings = stack.len().checked_sub(strings_size).ok_or(ImageError::StackOverflow)?;
+    let table = strings.checked_sub(words * 8).ok_or(ImageError::StackOverflow)?;
+    // RSP est aligné sur 16 octets : base l'est aussi si `top` l'est
+    let table = table.checked_sub((base as usize + table) % 16).ok_or(ImageError::StackOverflow)?;
+
+    let mut string = strings;
+    let mut word = table;
+    put(stack, word, args.len() as u64);
+    word += 8;
+    for list in [args, env] {
+        for s in list {
+            stack[string..string + s.len()].copy_from_slice(s.as_bytes());
+            stack[string + s.len()] = 0;
+            put(stack, word, base + string as u64);
+            string += s.len() + 1;
+            word += 8;
+        }
+        put(stack, word, 0);
+        word += 8;
+    }
+    for &(kind, value) in auxv.iter().chain(&[(AT_NULL, 0)]) {
+        put(stack, word, kind);
+        put(stack, word + 8, value);
+        word += 16;
+    }
+    Ok(base + table as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::elf::{HEADER_SIZE, PF_R};
+
+    const USER: Range<u64> = 0x40_0000..0x80_0000;
+
+    /// Construit un exécutable avec les segments (droits, adresse,
+    /// taille dans le fichier, taille en mémoire)
+    fn build_exec(entry: u64, segments: &[(u32, u64, u64, u64)]) -> Vec<u8> {
+        let ph_size = 56;
+        let data_offset = HEADER_SIZE + segments.len() * ph_size;
+        let mut elf = vec![0u8; HEADER_SIZE];
+        elf[0..4].copy_from_slice(&[0x7f, b'E', b'L', b'F']);
+        elf[4] = 2;
+        elf[5] = 1;
+        elf[16..18].copy_from_slice(&ET_EXEC.to_le_bytes());
+        elf[18..20].copy_from_slice(&EM_X86_64.to_le_bytes());
+        elf[24..32].copy_from_slice(&entry.to_le_bytes());
+        elf[32..40].copy_from_slice(&(HEADER_SIZE as u64).to_le_bytes());
+        elf[54..56].copy_from_slice(&(ph_size as u16).to_le_bytes());
+        elf[56..58].copy_from_slice(&(segments.len() as u16).to_le_bytes());
+        let mut offset = data_offset as u64;
+        for &(flags, vaddr, file_size, mem_size) in segments {
+            let mut ph = vec![0u8; ph_size];
+            ph[0..4].copy_from_slice(&PT_LOAD.to_le_bytes());
+            ph[4..8].copy_from_slice(&flags.to_le_bytes());
+            ph[8..16].copy_from_slice(&offset.to_le_bytes());
+            ph[16..24].copy_from_slice(&vaddr.to_le_bytes());
+            ph[32..40].copy_from_slice(&file_size.to_le_bytes());
+            ph[40..48].copy_from_slice(&mem_size.to_le_bytes());
+            elf.extend(ph);
+            offset += file_size;
+        }
+        elf.resize(offset as usize, 0x90);
+        elf
+    }
+
+    fn check(elf: &[u8]) -> Result<(), ImageError> {
+        check_image(&ElfFile::parse(elf).unwrap(), USER)
+    }
+
+    #[test]
+    fn accepts_valid_executable() {
+        let elf = build_exec(
+            0x40_1000,
+            &[(PF_R | PF_X, 0x40_1000, 0x20, 0x20), (PF_R | PF_W, 0x40_2000, 0x10, 0x3000)],
+        );
+        assert_eq!(check(&elf), Ok(()));
+        let file = ElfFile::parse(&elf).unwrap();
+        let auxv = auxiliary_vector(&file);
+        assert_eq!(auxv[0], (AT_IGNORE, 0));
+        assert_eq!(auxv[2], (AT_PHNUM, 2));
+        assert_eq!(auxv[4], (AT_ENTRY, 0x40_1000));
+    }
+
+    #[test]
+    fn rejects_bad_executables() {
+        let code = (PF_R | PF_X, 0x40_1000, 0x20, 0x20);
+        let mut elf = build_exec(0x40_1000, &[code]);
+        elf[16] = 3;
+        assert_eq!(check(&elf), Err(ImageError::NotExecutable));
+        let mut elf = build_exec(0x40_1000, &[code]);
+        elf[18] = 3;
+        assert_eq!(check(&elf), Err(ImageError::WrongMachine));
+        assert_eq!(check(&build_exec(0x40_1000, &[])), Err(ImageError::NoSegments));
+        let bss_first = (PF_R | PF_X, 0x40_1000, 0x20, 0x10);
+        assert_eq!(check(&build_exec(0x40_1000, &[bss_first])), Err(ImageError::BadSegment));
+        let kernel = (PF_R | PF_X, 0x1000, 0x20, 0x20);
+        assert_eq!(check(&build_exec(0x1000, &[kernel])), Err(ImageError::OutsideUserSpace));
+        let same_page = (PF_R | PF_W, 0x40_1800, 0x10, 0x10);
+        assert_eq!(check(&build_exec(0x40_1000, &[code, same_page])), Err(ImageError::OverlappingSegments));
+        let data = (PF_R | PF_W, 0x40_2000, 0x10, 0x10);
+        assert_eq!(check(&build_exec(0x40_2000, &[code, data])), Err(ImageError::BadEntry));
+        // Fichier tronqué : le segment dépasse la fin
+        let mut elf = build_exec(0x40_1000, &[code]);
+        elf.truncate(elf.len() - 1);
+        assert_eq!(check(&elf), Err(ImageError::BadSegment));
+    }
+
+    #[test]
+    fn finds_program_headers_in_first_segment() {
+        // Segment qui commence au début du fichier, comme avec lld
+        let mut elf = build_exec(0x40_0100, &[(PF_R | PF_X, 0x40_0000, 0x200, 0x200)]);
+        elf[64 + 8..64 + 16].copy_from_slice(&0u64.to_le_bytes());
+        let file = ElfFile::parse(&elf).unwrap();
+        assert_eq!(program_headers_address(&file), Some(0x40_0000 + HEADER_SIZE as u64));
+        assert_eq!(auxiliary_vector(&file)[0], (AT_PHDR, 0x40_0040));
+    }
+
+    #[test]
+    fn converts_segment_flags() {
+        assert_eq!(segment_flags(PF_R | PF_X), PageFlags::empty());
+        assert_eq!(segment_flags(PF_R | PF_W), PageFlags::WRITABLE | PageFlags::NO_EXECUTE);
+        assert_eq!(segment_flags(PF_R), PageFlags::NO_EXECUTE);
+        let segment = ProgramHeader { kind: PT_LOAD, flags: 0, offset: 0, vaddr: 0x1ff0, file_size: 0, mem_size: 0x20, align: 0 };
+        assert_eq!(segment_pages(&segment), 0x1000..0x3000);
+    }
+
+    #[test]
+    fn builds_initial_stack() {
+        let top = 0x8000;
+        let mut stack = [0u8; 256];
+        let base = top - stack.len() as u64;
+        let rsp = build_stack(&mut stack, top, &["prog", "-v"], &["A=1"], &[(AT_PAGESZ, 4096)]).unwrap();
+        assert_eq!(rsp % 16, 0);
+        let word = |address: u64| {
+            let offset = (address - base) as usize;
+            u64::from_le_bytes(stack[offset..offset + 8].try_into().unwrap())
+        };
+        let string = |address: u64| {
+            let offset = (address - base) as usize;
+            let len = stack[offset..].iter().position(|&b| b == 0).unwrap();
+            std::str::from_utf8(&stack[offset..offset + len]).unwrap().to_string()
+        };
+        assert_eq!(word(rsp), 2);
+        assert_eq!(string(word(rsp + 8)), "prog");
+        assert_eq!(string(word(rsp + 16)), "-v");
+        assert_eq!(word(rsp + 24), 0);
+        assert_eq!(string(word(rsp + 32)), "A=1");
+        assert_eq!(word(rsp + 40), 0);
+        assert_eq!((word(rsp + 48), word(rsp + 56)), (AT_PAGESZ, 4096));
+        assert_eq!((word(rsp + 64), word(rsp + 72)), (AT_NULL, 0));
+    }
+
+    #[test]
+    fn rejects_arguments_too_long() {
+        let mut stack = [0u8; 64];
+        let long = "x".repeat(60);
+        assert_eq!(build_stack(&mut stack, 0x8000, &[&long], &[], &[]), Err(ImageError::StackOverflow));
+        assert_eq!(build_stack(&mut stack, 0x8000, &["a", "b", "c"], &[], &[(AT_PAGESZ, 1)]), Err(ImageError::StackOverflow));
+    }
+}
@@ -0,0 +1,289 @@
+// ===================================================================
+// ALLOCATEUR DE FRAMES PHYSIQUES (BITMAP)
+// ===================================================================
+//
+// La mémoire physique est découpée en frames de 4 Kio. Un bit par
+// frame indique s'il est occupé (1) ou libre (0) :
+//
+//   mot 0 : bits 0..63  → frames 0 à 63
+//   mot 1 : bits 0..63  → frames 64 à 127 ...
+//
+// Au départ tout est occupé ; le kernel libère ensuite les régions
+// utilisables de la carte mémoire (`add_free_range`). On peut allouer
+// un frame isolé ou plusieurs frames contigus (pour le DMA, où le
+// périphérique ne connaît que les adresses physiques), et les libérer.
+// Le bitmap lui-même est fourni par l'appelant (aucune allocation).
+
+use crate::memory::PAGE_SIZE;
+
+/// Erreur lors de la libération d'un frame
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FrameError {
+    /// Frame en dehors de la mémoire gérée
+    OutOfRange(u64),
+    /// Frame déjà libre (double libération)
+    NotAllocated(u64),
+}
+
+/// Statistiques de l'allocateur, en nombre de frames
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FrameStats {
+    /// Frames utilisables (libérés par `add_free_range`)
+    pub usable: u64,
+    /// Frames actuellement libres
+    pub free: u64,
+    /// Nombre d'allocations réussies depuis le démarrage
+    pub allocations: u64,
+    /// Nombre de libérations depuis le démarrage
+    pub frees: u64,
+}
+
+impl FrameStats {
+    /// Frames utilisables actuellement occupés
+    pub fn used(&self) -> u64 {
+        self.usable - self.free
+    }
+}
+
+/// Nombre de mots de 64 bits nécessaires pour `frame_count` frames
+pub const fn bitmap_words(frame_count: u64) -> usize {
+    frame_count.div_ceil(64) as usize
+}
+
+/// Allocateur de frames à bitmap
+pub struct FrameBitmap<'a> {
+    /// Un bit par frame : 1 = occupé
+    words: &'a mut [u64],
+    /// Nombre de frames décrits par le bitmap
+    frame_count: u64,
+    /// Mot où reprendre la recherche d'un frame libre
+    next_word: usize,
+    stats: FrameStats,
+}
+
+impl<'a> FrameBitmap<'a> {
+    /// Crée un allocateur pour les frames 0 à `frame_count`, tous occupés
+    ///
+    /// `words` doit contenir au moins `bitmap_words(frame_count)` mots.
+    pub fn new(words: &'a mut [u64], frame_count: u64) -> FrameBitmap<'a> {
+        let words = &mut words[..bitmap_words(frame_count)];
+        words.fill(u64::MAX);
+        FrameBitmap {
+            words,
+            frame_count,
+            next_word: 0,
+            stats: FrameStats::default(),
+        }
+    }
+
+    /// Nombre de frames décrits par le bitmap
+    pub fn frame_count(&self) -> u64 {
+        self.frame_count
+    }
+
+    /// Statistiques actuelles
+    pub fn stats(&self) -> FrameStats {
+        self.stats
+    }
+
+    /// Indique si un frame est libre
+    pub fn is_free(&self, frame: u64) -> bool {
+        frame < self.frame_count && self.words[(frame / 64) as usize] & (1 << (frame % 64)) == 0
+    }
+
+    fn set_used(&mut self, frame: u64) {
+        self.words[(frame / 64) as usize] |= 1 << (frame % 64);
+    }
+
+    fn set_free(&mut self, frame: u64) {
+        self.words[(frame / 64) as usize] &= !(1 << (frame % 64));
+    }
+
+    /// Déclare utilisables les frames [start, end) (carte mémoire)
+    pub fn add_free_range(&mut self, start: u64, end: u64) {
+        for frame in start..end.min(self.frame_count) {
+            if !self.is_free(frame) {
+                self.set_free(frame);
+                self.stats.usable += 1;
+                self.stats.free += 1;
+            }
+        }
+    }
+
+    /// Retire définitivement les frames [start, end) de la mémoire libre
+    /// (par exemple ceux qui contiennent le bitmap lui-même)
+    pub fn reserve_range(&mut self, start: u64, end: u64) {
+        for frame in start..end.min(self.frame_count) {
+            if self.is_free(frame) {
+                self.set_used(frame);
+                self.stats.usable -= 1;
+                self.stats.free -= 1;
+            }
+        }
+    }
+
+    /// Alloue un frame, retourne son numéro
+    pub fn allocate(&mut self) -> Option<u64> {
+        let count = self.words.len();
+        for i in 0..count {
+            let index = (self.next_word + i) % count;
+            let word = self.words[index];
+            if word == u64::MAX {
+                continue;
+            }
+            let frame = index as u64 * 64 + (!word).trailing_zeros() as u64;
+            // Les bits au-delà du dernier frame restent à 1 : toujours valide
+            self.set_used(frame);
+            self.next_word = index;
+            self.stats.free -= 1;
+            self.stats.allocations += 1;
+            return Some(frame);
+        }
+        None
+    }
+
+    /// Alloue `count` frames contigus dont le premier est un multiple de
+    /// `align` frames ; retourne le numéro du premier
+    pub fn allocate_contiguous(&mut self, count: u64, align: u64) -> Option<u64> {
+        if count == 0 || count > self.stats.free {
+            return None;
+        }
+        let align = align.max(1);
+        let mut start = 0;
+        while start + count <= self.frame_count {
+            // Premier frame occupé dans [start, start + count)
+            match (start..start + count).find(|&frame| !self.is_free(frame)) {
+                Some(used) => start = (used + 1).next_multiple_of(align),
+                None => {
+                    for frame in start..start + count {
+                        self.set_used(frame);
+                    }
+                    self.stats.free -= count;
+                    self.stats.allocations += 1;
+                    return Some(start);
+                }
+            }
+        }
+        None
+    }
+
+    /// Libère un frame alloué
+    pub fn free(&mut self, frame: u64) -> Result<(), FrameError> {
+        self.free_contiguous(frame, 1)
+    }
+
+    /// Libère `count` frames contigus alloués par `allocate_contiguous`
+    ///
+    /// Rien n'est libéré si l'un des frames est invalide ou déjà libre.
+    pub fn free_contiguous(&mut self, start: u64, count: u64) -> Result<(), FrameError> {
+        for frame in start..start + count {
+            if frame >= self.frame_count {
+                return Err(FrameError::OutOfRange(frame));
+            }
+            if self.is_free(frame) {
+                return Err(FrameError::NotAllocated(frame));
+            }
+        }
+        for frame in start..start + count {
+            self.set_free(frame);
+        }
+        self.next_word = (start / 64) as usize;
+        self.stats.free += count;
+        self.stats.frees += 1;
+        Ok(())
+    }
+}
+
+/// Adresse physique du début d'un frame
+pub const fn frame_address(frame: u64) -> u64 {
+    frame * PAGE_SIZE
+}
+
+/// Frame contenant une adresse physique
+pub const fn frame_containing(address: u64) -> u64 {
+    address / PAGE_SIZE
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn starts_fully_used() {
+        let mut words = [0; 4];
+        let mut bitmap = FrameBitmap::new(&mut words, 200);
+        assert_eq!(bitmap.allocate(), None);
+        assert_eq!(bitmap.stats(), FrameStats::default());
+        assert!(!bitmap.is_free(10));
+    }
+
+    #[test]
+    fn allocates_and_frees() {
+        let mut words = [0; 4];
+        let mut bitmap = FrameBitmap::new(&mut words, 200);
+        bitmap.add_free_range(10, 13);
+        assert_eq!(bitmap.stats().usable, 3);
+        assert_eq!(bitmap.allocate(), Some(10));
+        assert_eq!(bitmap.allocate(), Some(11));
+        assert_eq!(bitmap.allocate(), Some(12));
+        assert_eq!(bitmap.allocate(), None);
+        assert_eq!(bitmap.stats().used(), 3);
+
+        assert_eq!(bitmap.free(11), Ok(()));
+        assert_eq!(bitmap.free(11), Err(FrameError::NotAllocated(11)));
+        assert_eq!(bitmap.free(500), Err(FrameError::OutOfRange(500)));
+        assert_eq!(bitmap.allocate(), Some(11));
+        assert_eq!(bitmap.stats().allocations, 4);
+        assert_eq!(bitmap.stats().frees, 1);
+    }
+
+    #[test]
+    fn never_allocates_past_the_end() {
+        let mut words = [0; 2];
+        let mut bitmap = FrameBitmap::new(&mut words, 70);
+        bitmap.add_free_range(68, 100);
+        assert_eq!(bitmap.stats().usable, 2);
+        assert_eq!(bitmap.allocate(), Some(68));
+        assert_eq!(bitmap.allocate(), Some(69));
+        assert_eq!(bitmap.allocate(), None);
+    }
+
+    #[test]
+    fn reserves_frames() {
+        let mut words = [0; 1];
+        let mut bitmap = FrameBitmap::new(&mut words, 64);
+        bitmap.add_free_range(0, 8);
+        bitmap.reserve_range(0, 2);
+        assert_eq!(bitmap.stats().usable, 6);
+        assert_eq!(bitmap.allocate(), Some(2));
+    }
+
+    #[test]
+    fn allocates_contiguous_aligned_runs() {
+        let mut words = [0; 4];
+        let mut bitmap = FrameBitmap::new(&mut words, 256);
+        bitmap.add_free_range(0, 256);
+        bitmap.reserve_range(5, 6);
+        // 8 frames alignés sur 8 : 0..8 contient le frame 5 réservé
+        assert_eq!(bitmap.allocate_contiguous(8, 8), Some(8));
+        // Le trou 0..5 sert encore aux petites allocations
+        assert_eq!(bitmap.allocate_contiguous(5, 1), Some(0));
+        assert_eq!(bitmap.allocate_contiguous(100, 64), Some(64));
+        assert_eq!(bitmap.allocate_contiguous(200, 1), None);
+        assert_eq!(bitmap.stats().free, 255 - 8 - 5 - 100);
+
+        assert_eq!(bitmap.free_contiguous(8, 8), Ok(()));
+        assert_eq!(bitmap.allocate_contiguous(8, 8), Some(8));
+        // Libération partiellement invalide : rien n'est libéré
+        assert_eq!(bitmap.free_contiguous(60, 8), Err(FrameError::NotAllocated(60)));
+        assert!(!bitmap.is_free(64));
+    }
+
+    #[test]
+    fn converts_addresses() {
+        assert_eq!(frame_address(3), 0x3000);
+        assert_eq!(frame_containing(0x3fff), 3);
+        assert_eq!(bitmap_words(64), 1);
+        assert_eq!(bitmap_words(65), 2);
+    }
+}
@@ -0,0 +1,374 @@
+// ===================================================================
+// PROTOCOLE GDB REMOTE SERIAL (RSP)
+// ===================================================================
+//
+// GDB dialogue avec un "stub" par paquets ASCII :
+//
+//   $<données>#<checksum>
+//
+// où le checksum est la somme des octets des données modulo 256, en
+// deux chiffres hexadécimaux. Le destinataire acquitte avec '+' (reçu)
+// ou '-' (à renvoyer). L'octet 0x03 seul (Ctrl+C) demande un arrêt.
+//
+// Ce module contient la partie pure du protocole : décodage des
+// paquets, conversions hexadécimales et analyse des commandes. La
+// partie matérielle (port série, registres, mémoire) est dans le
+// kernel (src/gdb.rs).
+
+/// Taille maximale d'un paquet (annoncée à GDB via qSupported)
+pub const PACKET_SIZE: usize = 4096;
+
+/// Octet envoyé par GDB pour interrompre le programme (Ctrl+C)
+pub const INTERRUPT: u8 = 0x03;
+
+/// Nombre de registres dans la réponse au paquet `g` :
+/// rax..r15, rip (8 octets chacun) puis eflags, cs, ss, ds, es, fs, gs (4 octets)
+pub const REGISTER_COUNT: usize = 24;
+
+/// Taille en octets du registre GDB numéro `index`
+pub fn register_size(index: usize) -> Option<usize> {
+    match index {
+        0..=16 => Some(8),
+        17..=23 => Some(4),
+        _ => None,
+    }
+}
+
+/// Somme de contrôle d'un paquet
+pub fn checksum(data: &[u8]) -> u8 {
+    data.iter().fold(0u8, |sum, &b| sum.wrapping_add(b))
+}
+
+/// Valeur d'un chiffre hexadécimal
+pub fn hex_digit(c: u8) -> Option<u8> {
+    match c {
+        b'0'..=b'9' => Some(c - b'0'),
+        b'a'..=b'f' => Some(c - b'a' + 10),
+        b'A'..=b'F' => Some(c - b'A' + 10),
+        _ => None,
+    }
+}
+
+/// Chiffre hexadécimal (minuscule) d'une valeur de 0 à 15
+pub fn to_hex_digit(value: u8) -> u8 {
+    b"0123456789abcdef"[(value & 0xF) as usize]
+}
+
+/// Lit un nombre hexadécimal (big-endian, comme les adresses de GDB)
+pub fn parse_hex(text: &[u8]) -> Option<u64> {
+    if text.is_empty() || text.len() > 16 {
+        return None;
+    }
+    text.iter()
+        .try_fold(0u64, |value, &c| Some(value << 4 | hex_digit(c)? as u64))
+}
+
+/// Décode une suite d'octets en hexadécimal ("2a00" → [0x2a, 0x00])
+///
+/// Retourne le nombre d'octets écrits dans `out`.
+pub fn decode_hex(text: &[u8], out: &mut [u8]) -> Option<usize> {
+    if !text.len().is_multiple_of(2) || text.len() / 2 > out.len() {
+        return None;
+    }
+    for (i, pair) in text.chunks(2).enumerate() {
+        out[i] = hex_digit(pair[0])? << 4 | hex_digit(pair[1])?;
+    }
+    Some(text.len() / 2)
+}
+
+/// Résultat de l'ajout d'un octet au décodeur
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Decoded {
+    /// Paquet incomplet (ou octet hors paquet ignoré)
+    Pending,
+    /// Paquet complet et valide, de la longueur donnée
+    Packet(usize),
+    /// Paquet complet mais checksum incorrect (répondre '-')
+    BadChecksum,
+    /// Demande d'interruption (Ctrl+C)
+    Interrupt,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum State {
+    /// En attente du '$'
+    Idle,
+    /// Lecture des données jusqu'au '#'
+    Data,
+    /// Premier chiffre du checksum
+    Checksum1,
+    /// Second chiffre du checksum
+    Checksum2(u8),
+}
+
+/// Décodeur de paquets, alimenté octet par octet
+pub struct PacketDecoder {
+    buffer: [u8; PACKET_SIZE],
+    len: usize,
+    state: State,
+}
+
+impl PacketDecoder {
+    /// Crée un décodeur vide
+    pub const fn new() -> PacketDecoder {
+        PacketDecoder {
+            buffer: [0; PACKET_SIZE],
+            len: 0,
+            state: State::Idle,
+        }
+    }
+
+    /// Ajoute un octet reçu
+    pub fn push(&mut self, byte: u8) -> Decoded {
+        match self.state {
+            State::Idle => match byte {
+                b'$' => {
+                    self.len = 0;
+                    self.state = State::Data;
+                }
+                INTERRUPT => return Decoded::Interrupt,
+                // '+' et '-' de GDB, bruit sur la ligne : ignorés
+                _ => {}
+            },
+            State::Data => match byte {
+                b'#' => self.state = State::Checksum1,
+                // Un '$' au milieu recommence un nouveau paquet
+                b'$' => self.len = 0,
+                _ => {
+                    if self.len < PACKET_SIZE {
+                        self.buffer[self.len] = byte;
+                        self.len += 1;
+                    }
+                }
+            },
+            State::Checksum1 => match hex_digit(byte) {
+                Some(high) => self.state = State::Checksum2(high),
+                None => {
+                    self.state = State::Idle;
+                    return Decoded::BadChecksum;
+                }
+            },
+            State::Checksum2(high) => {
+                self.state = State::Idle;
+                return match hex_digit(byte) {
+                    Some(low) if high << 4 | low == checksum(self.packet()) => Decoded::Packet(self.len),
+                    _ => Decoded::BadChecksum,
+                };
+            }
+        }
+        Decoded::Pending
+    }
+
+    /// Données du dernier paquet
+    pub fn packet(&self) -> &[u8] {
+        &self.buffer[..self.len]
+    }
+}
+
+impl Default for PacketDecoder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Commande envoyée par GDB
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Command<'a> {
+    /// `?` : raison du dernier arrêt
+    HaltReason,
+    /// `g` : lire tous les registres
+    ReadRegisters,
+    /// `G<hex>` : écrire tous les registres
+    WriteRegisters(&'a [u8]),
+    /// `p<n>` : lire un registre
+    ReadRegister(usize),
+    /// `P<n>=<hex>` : écrire un registre
+    WriteRegister(usize, &'a [u8]),
+    /// `m<adresse>,<longueur>` : lire la mémoire
+    ReadMemory { addr: u64, len: usize },
+    /// `M<adresse>,<longueur>:<hex>` : écrire la mémoire
+    WriteMemory { addr: u64, data: &'a [u8] },
+    /// `Z0,<adresse>,<type>` : poser un breakpoint logiciel
+    InsertBreakpoint(u64),
+    /// `z0,<adresse>,<type>` : retirer un breakpoint logiciel
+    RemoveBreakpoint(u64),
+    /// `c[<adresse>]` : reprendre l'exécution
+    Continue(Option<u64>),
+    /// `s[<adresse>]` : exécuter une seule instruction
+    Step(Option<u64>),
+    /// `D` : GDB se détache
+    Detach,
+    /// `k` : GDB termine la session
+    Kill,
+    /// `q...` : requête générale (qSupported, qAttached...)
+    Query(&'a [u8]),
+    /// `H...` : choix du thread (un seul thread ici)
+    SetThread,
+    /// Commande non supportée : réponse vide
+    Unsupported,
+    /// Commande supportée mais mal formée : réponse "E01"
+    Malformed,
+}
+
+/// Sépare `text` au premier `sep`
+fn split_once(text: &[u8], sep: u8) -> Option<(&[u8], &[u8])> {
+    let pos = text.iter().position(|&b| b == sep)?;
+    Some((&text[..pos], &text[pos + 1..]))
+}
+
+/// Adresse optionnelle de `c` et `s`
+fn optional_address(text: &[u8]) -> Option<Option<u64>> {
+    if text.is_empty() {
+        Some(None)
+    } else {
+        parse_hex(text).map(Some)
+    }
+}
+
+/// Adresse d'un paquet `Z0,<adresse>,<type>` ou `z0,...`
+fn breakpoint_address(text: &[u8]) -> Option<Option<u64>> {
+    let (kind, rest) = split_once(text, b',')?;
+    if kind != b"0" {
+        // Breakpoints matériels et watchpoints : non supportés
+        return Some(None);
+    }
+    let (addr, _) = split_once(rest, b',')?;
+    Some(Some(parse_hex(addr)?))
+}
+
+/// Analyse les données d'un paquet
+pub fn parse_command(packet: &[u8]) -> Command<'_> {
+    let (&first, rest) = match packet.split_first() {
+        Some(split) => split,
+        None => return Command::Unsupported,
+    };
+    let command = match first {
+        b'?' => Some(Command::HaltReason),
+        b'g' => Some(Command::ReadRegisters),
+        b'G' => Some(Command::WriteRegisters(rest)),
+        b'p' => parse_hex(rest).map(|n| Command::ReadRegister(n as usize)),
+        b'P' => split_once(rest, b'=')
+            .and_then(|(n, value)| Some(Command::WriteRegister(parse_hex(n)? as usize, value))),
+        b'm' => split_once(rest, b',').and_then(|(addr, len)| {
+            Some(Command::ReadMemory {
+                addr: parse_hex(addr)?,
+                len: parse_hex(len)? as usize,
+            })
+        }),
+        b'M' => split_once(rest, b':').and_then(|(range, data)| {
+            let (addr, len) = split_once(range, b',')?;
+            let len = parse_hex(len)? as usize;
+            if data.len() != len * 2 {
+                return None;
+            }
+            Some(Command::WriteMemory { addr: parse_hex(addr)?, data })
+        }),
+        b'Z' => match breakpoint_address(rest) {
+            Some(Some(addr)) => Some(Command::InsertBreakpoint(addr)),
+            Some(None) => return Command::Unsupported,
+            None => None,
+        },
+        b'z' => match breakpoint_address(rest) {
+            Some(Some(addr)) => Some(Command::RemoveBreakpoint(addr)),
+            Some(None) => return Command::Unsupported,
+            None => None,
+        },
+        b'c' => optional_address(rest).map(Command::Continue),
+        b's' => optional_address(rest).map(Command::Step),
+        b'D' => Some(Command::Detach),
+        b'k' => Some(Command::Kill),
+        b'q' => Some(Command::Query(rest)),
+        b'H' => Some(Command::SetThread),
+        _ => return Command::Unsupported,
+    };
+    command.unwrap_or(Command::Malformed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn decode(decoder: &mut PacketDecoder, bytes: &[u8]) -> Decoded {
+        let mut last = Decoded::Pending;
+        for &b in bytes {
+            last = decoder.push(b);
+        }
+        last
+    }
+
+    #[test]
+    fn computes_checksum() {
+        assert_eq!(checksum(b"OK"), 0x9a);
+        assert_eq!(checksum(b""), 0);
+    }
+
+    #[test]
+    fn decodes_valid_packet() {
+        let mut decoder = PacketDecoder::new();
+        assert_eq!(decode(&mut decoder, b"+$g#67"), Decoded::Packet(1));
+        assert_eq!(decoder.packet(), b"g");
+        assert_eq!(decode(&mut decoder, b"$m1000,4#8e"), Decoded::Packet(7));
+        assert_eq!(decoder.packet(), b"m1000,4");
+    }
+
+    #[test]
+    fn rejects_bad_checksum() {
+        let mut decoder = PacketDecoder::new();
+        assert_eq!(decode(&mut decoder, b"$g#00"), Decoded::BadChecksum);
+        assert_eq!(decode(&mut decoder, b"$g#z"), Decoded::BadChecksum);
+    }
+
+    #[test]
+    fn detects_interrupt() {
+        let mut decoder = PacketDecoder::new();
+        assert_eq!(decoder.push(INTERRUPT), Decoded::Interrupt);
+    }
+
+    #[test]
+    fn hex_conversions() {
+        assert_eq!(parse_hex(b"ffff800000000000"), Some(0xffff_8000_0000_0000));
+        assert_eq!(parse_hex(b""), None);
+        assert_eq!(parse_hex(b"12x"), None);
+        let mut out = [0u8; 4];
+        assert_eq!(decode_hex(b"2a00fF", &mut out), Some(3));
+        assert_eq!(out[..3], [0x2a, 0x00, 0xff]);
+        assert_eq!(decode_hex(b"2a0", &mut out), None);
+        assert_eq!(decode_hex(b"0102030405", &mut out), None);
+        assert_eq!(to_hex_digit(0xb), b'b');
+    }
+
+    #[test]
+    fn parses_commands() {
+        assert_eq!(parse_command(b"?"), Command::HaltReason);
+        assert_eq!(parse_command(b"g"), Command::ReadRegisters);
+        assert_eq!(parse_command(b"p10"), Command::ReadRegister(16));
+        assert_eq!(parse_command(b"P10=0010200000000000"), Command::WriteRegister(16, b"0010200000000000"));
+        assert_eq!(parse_command(b"m201000,40"), Command::ReadMemory { addr: 0x201000, len: 0x40 });
+        assert_eq!(parse_command(b"M201000,2:cc90"), Command::WriteMemory { addr: 0x201000, data: b"cc90" });
+        assert_eq!(parse_command(b"Z0,201000,1"), Command::InsertBreakpoint(0x201000));
+        assert_eq!(parse_command(b"z0,201000,1"), Command::RemoveBreakpoint(0x201000));
+        assert_eq!(parse_command(b"c"), Command::Continue(None));
+        assert_eq!(parse_command(b"s201000"), Command::Step(Some(0x201000)));
+        assert_eq!(parse_command(b"qSupported:multiprocess+"), Command::Query(b"Supported:multiprocess+"));
+        assert_eq!(parse_command(b"Hg0"), Command::SetThread);
+        assert_eq!(parse_command(b"D"), Command::Detach);
+    }
+
+    #[test]
+    fn handles_unsupported_and_malformed() {
+        assert_eq!(parse_command(b""), Command::Unsupported);
+        assert_eq!(parse_command(b"vCont?"), Command::Unsupported);
+        assert_eq!(parse_command(b"Z2,1000,8"), Command::Unsupported);
+        assert_eq!(parse_command(b"m1000"), Command::Malformed);
+        assert_eq!(parse_command(b"M1000,2:cc"), Command::Malformed);
+        assert_eq!(parse_command(b"cxyz"), Command::Malformed);
+    }
+
+    #[test]
+    fn register_sizes() {
+        assert_eq!(register_size(0), Some(8));
+        assert_eq!(register_size(16), Some(8));
+        assert_eq!(register_size(17), Some(4));
+        assert_eq!(register_size(REGISTER_COUNT), None);
+    }
+}
@@ -0,0 +1,324 @@
+// ===================================================================
+// ALLOCATEUR DU TAS (LISTE CHAÎNÉE DE BLOCS LIBRES)
+// ===================================================================
+//
+// Les blocs libres du tas forment une liste chaînée triée par adresse.
+// L'en-tête de chaque bloc (taille + suivant) est écrit dans le bloc
+// lui-même : la liste ne coûte aucune mémoire supplémentaire.
+//
+//   tas : [libre 64][occupé 32][libre 128][occupé 16][libre 4096]
+//          │                    ▲  │                  ▲
+//          └────────────────────┘  └──────────────────┘
+//
+// - Allocation : premier bloc assez grand (first fit), découpé en
+//   [reste avant l'alignement][allocation][reste après]
+// - Libération : le bloc est réinséré à sa place et fusionné avec ses
+//   voisins libres, ce qui limite la fragmentation
+//
+// Toutes les tailles et adresses sont des multiples de 16 octets
+// (BLOCK_ALIGN) : un reste de découpe est donc soit vide, soit assez
+// grand pour contenir un en-tête.
+
+use core::alloc::Layout;
+use core::ptr;
+
+/// Granularité des blocs (et alignement minimum des allocations)
+pub const BLOCK_ALIGN: usize = 16;
+
+/// En-tête d'un bloc libre
+#[repr(C)]
+struct FreeBlock {
+    size: usize,
+    next: *mut FreeBlock,
+}
+
+/// Statistiques du tas
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct HeapStats {
+    /// Taille totale du tas
+    pub size: usize,
+    /// Octets alloués (arrondis à 16 octets)
+    pub used: usize,
+    /// Nombre d'allocations réussies depuis le démarrage
+    pub allocations: u64,
+    /// Nombre de libérations depuis le démarrage
+    pub frees: u64,
+    /// Nombre d'allocations refusées faute de place
+    pub failures: u64,
+}
+
+impl HeapStats {
+    /// Octets libres
+    pub fn free(&self) -> usize {
+        self.size - self.used
+    }
+}
+
+/// État de la liste des blocs libres (mesure de la fragmentation)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FreeListInfo {
+    /// Nombre de blocs libres
+    pub blocks: usize,
+    /// Taille du plus grand bloc libre
+    pub largest: usize,
+}
+
+const fn align_up(addr: usize, align: usize) -> usize {
+    (addr + align - 1) & !(align - 1)
+}
+
+/// Tas géré par une liste chaînée de blocs libres
+pub struct LinkedListHeap {
+    head: *mut FreeBlock,
+    stats: HeapStats,
+}
+
+// Le tas ne contient que des adresses : il peut changer de thread
+unsafe impl Send for LinkedListHeap {}
+
+impl LinkedListHeap {
+    /// Tas vide : toutes les allocations échouent avant `init`
+    pub const fn empty() -> LinkedListHeap {
+        LinkedListHeap {
+            head: ptr::null_mut(),
+            stats: HeapStats {
+                size: 0,
+                used: 0,
+                allocations: 0,
+                frees: 0,
+                failures: 0,
+            },
+        }
+    }
+
+    /// Donne au tas la zone mémoire [start, start + size)
+    ///
+    /// # Safety
+    /// La zone doit être accessible en écriture, inutilisée par ailleurs,
+    /// et le rester tant que le tas existe. `init` n'est appelé qu'une fois.
+    pub unsafe fn init(&mut self, start: usize, size: usize) {
+        let aligned = align_up(start, BLOCK_ALIGN);
+        let size = size.saturating_sub(aligned - start) & !(BLOCK_ALIGN - 1);
+        self.head = ptr::null_mut();
+        self.stats = HeapStats { size, ..HeapStats::default() };
+        if size > 0 {
+            self.insert(aligned, size);
+        }
+    }
+
+    /// Ajoute la zone [start, start + size) au tas (tas qui grandit)
+    ///
+    /// # Safety
+    /// Mêmes conditions que `init` pour la nouvelle zone, qui ne doit
+    /// pas recouvrir celles déjà données au tas.
+    pub unsafe fn extend(&mut self, start: usize, size: usize) {
+        let aligned = align_up(start, BLOCK_ALIGN);
+        let size = size.saturating_sub(aligned - start) & !(BLOCK_ALIGN - 1);
+        if size > 0 {
+            self.stats.size += size;
+            self.insert(aligned, size);
+        }
+    }
+
+    /// Statistiques actuelles
+    pub fn stats(&self) -> HeapStats {
+        self.stats
+    }
+
+    /// Parcourt la liste des blocs libres
+    pub fn free_list_info(&self) -> FreeListInfo {
+        let mut info = FreeListInfo::default();
+        let mut block = self.head;
+        while !block.is_null() {
+            unsafe {
+                info.blocks += 1;
+                info.largest = info.largest.max((*block).size);
+                block = (*block).next;
+            }
+        }
+        info
+    }
+
+    /// Taille et alignement réellement utilisés pour une allocation
+    fn block_layout(layout: Layout) -> (usize, usize) {
+        let size = align_up(layout.size().max(1), BLOCK_ALIGN);
+        (size, layout.align().max(BLOCK_ALIGN))
+    }
+
+    /// Alloue un bloc, retourne un pointeur nul s'il n'y a pas de place
+    pub fn allocate(&mut self, layout: Layout) -> *mut u8 {
+        let (size, align) = Self::block_layout(layout);
+        let mut link: *mut *mut FreeBlock = &mut self.head;
+        unsafe {
+            while !(*link).is_null() {
+                let block = *link;
+                let start = block as usize;
+                let end = start + (*block).size;
+                let alloc_start = align_up(start, align);
+                if let Some(alloc_end) = alloc_start.checked_add(size).filter(|&e| e <= end) {
+                    // Retirer le bloc, puis rendre les restes avant et après
+                    *link = (*block).next;
+                    if alloc_end < end {
+                        self.insert(alloc_end, end - alloc_end);
+                    }
+                    if alloc_start > start {
+                        self.insert(start, alloc_start - start);
+                    }
+                    self.stats.used += size;
+                    self.stats.allocations += 1;
+                    return alloc_start as *mut u8;
+                }
+                link = &mut (*block).next;
+            }
+        }
+        self.stats.failures += 1;
+        ptr::null_mut()
+    }
+
+    /// Libère un bloc alloué par `allocate`
+    ///
+    /// # Safety
+    /// `ptr` doit venir de `allocate` sur ce tas avec le même `layout`,
+    /// et ne pas avoir déjà été libéré.
+    pub unsafe fn deallocate(&mut self, ptr: *mut u8, layout: Layout) {
+        let (size, _) = Self::block_layout(layout);
+        self.insert(ptr as usize, size);
+        self.stats.used -= size;
+        self.stats.frees += 1;
+    }
+
+    /// Insère un bloc libre à sa place, en le fusionnant avec ses voisins
+    unsafe fn insert(&mut self, addr: usize, size: usize) {
+        let mut prev: *mut FreeBlock = ptr::null_mut();
+        let mut next = self.head;
+        while !next.is_null() && (next as usize) < addr {
+            prev = next;
+            next = (*next).next;
+        }
+
+        let block = addr as *mut FreeBlock;
+        block.write(FreeBlock { size, next });
+        // Fusion avec le bloc suivant s'il est contigu
+        if !next.is_null() && addr + size == next as usize {
+            (*block).size += (*next).size;
+            (*block).next = (*next).next;
+        }
+        // Fusion avec le bloc précédent s'il est contigu
+        if prev.is_null() {
+            self.head = block;
+        } else if prev as usize + (*prev).size == addr {
+            (*prev).size += (*block).size;
+            (*prev).next = (*block).next;
+        } else {
+            (*prev).next = block;
+        }
+    }
+}
+
+impl Default for LinkedListHeap {
+    fn default() -> Self {
+        Self::empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Zone de test alignée sur 16 octets
+    fn arena(size: usize) -> Vec<u128> {
+        vec![0; size / 16]
+    }
+
+    fn heap_on(arena: &mut [u128]) -> LinkedListHeap {
+        let mut heap = LinkedListHeap::empty();
+        unsafe { heap.init(arena.as_mut_ptr() as usize, arena.len() * 16) };
+        heap
+    }
+
+    fn layout(size: usize, align: usize) -> Layout {
+        Layout::from_size_align(size, align).unwrap()
+    }
+
+    #[test]
+    fn empty_heap_fails() {
+        let mut heap = LinkedListHeap::empty();
+        assert!(heap.allocate(layout(8, 8)).is_null());
+        assert_eq!(heap.stats().failures, 1);
+    }
+
+    #[test]
+    fn allocates_aligned_blocks() {
+        let mut memory = arena(4096);
+        let mut heap = heap_on(&mut memory);
+        let a = heap.allocate(layout(10, 1));
+        let b = heap.allocate(layout(100, 256));
+        assert!(!a.is_null() && !b.is_null());
+        assert!((a as usize).is_multiple_of(16));
+        assert!((b as usize).is_multiple_of(256));
+        assert_eq!(heap.stats().used, 16 + 112);
+        unsafe {
+            heap.deallocate(a, layout(10, 1));
+            heap.deallocate(b, layout(100, 256));
+        }
+        assert_eq!(heap.stats().used, 0);
+        assert_eq!(heap.free_list_info(), FreeListInfo { blocks: 1, largest: 4096 });
+    }
+
+    #[test]
+    fn coalesces_freed_neighbours() {
+        let mut memory = arena(1024);
+        let mut heap = heap_on(&mut memory);
+        let l = layout(256, 16);
+        let blocks: Vec<_> = (0..4).map(|_| heap.allocate(l)).collect();
+        assert!(heap.allocate(l).is_null());
+        // Libérer dans le désordre : les blocs 1 et 2 doivent fusionner
+        unsafe {
+            heap.deallocate(blocks[1], l);
+            heap.deallocate(blocks[3], l);
+            heap.deallocate(blocks[2], l);
+        }
+        assert_eq!(heap.free_list_info(), FreeListInfo { blocks: 1, largest: 768 });
+        assert!(!heap.allocate(layout(700, 16)).is_null());
+    }
+
+    #[test]
+    fn reuses_freed_memory() {
+        let mut memory = arena(512);
+        let mut heap = heap_on(&mut memory);
+        for _ in 0..100 {
+            let p = heap.allocate(layout(500, 8));
+            assert!(!p.is_null());
+            unsafe {
+                p.write_bytes(0xAB, 500);
+                heap.deallocate(p, layout(500, 8));
+            }
+        }
+        assert_eq!(heap.stats().allocations, 100);
+        assert_eq!(heap.stats().frees, 100);
+        assert_eq!(heap.stats().free(), 512);
+    }
+
+    #[test]
+    fn rounds_unaligned_region() {
+        let mut memory = arena(256);
+        let mut heap = LinkedListHeap::empty();
+        unsafe { heap.init(memory.as_mut_ptr() as usize + 4, 250) };
+        // 12 octets perdus pour l'alignement, puis arrondi à 16
+        assert_eq!(heap.stats().size, 224);
+    }
+
+    #[test]
+    fn extends_with_contiguous_region() {
+        let mut memory = arena(1024);
+        let start = memory.as_mut_ptr() as usize;
+        let mut heap = LinkedListHeap::empty();
+        unsafe { heap.init(start, 512) };
+        assert!(heap.allocate(layout(768, 16)).is_null());
+        // La zone ajoutée juste après fusionne avec le bloc libre
+        unsafe { heap.extend(start + 512, 512) };
+        assert_eq!(heap.stats().size, 1024);
+        assert_eq!(heap.free_list_info().blocks, 1);
+        assert!(!heap.allocate(layout(768, 16)).is_null());
+    }
+}
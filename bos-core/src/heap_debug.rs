@@ -0,0 +1,354 @@
+// ===================================================================
+// DÉBOGAGE DU TAS : ZONES ROUGES ET SUIVI DES ALLOCATIONS
+// ===================================================================
+//
+// Chaque allocation est agrandie pour contenir un en-tête de suivi et
+// deux zones rouges ("red zones") remplies d'un motif connu :
+//
+//   [en-tête][zone rouge avant][données demandées][zone rouge après]
+//                              ▲
+//                              pointeur rendu à l'appelant
+//
+// À la libération, les zones rouges sont vérifiées : un octet modifié
+// signale une écriture hors limites (avant le début ou après la fin).
+// Les en-têtes des allocations vivantes forment une liste chaînée qui
+// permet de lister les allocations jamais libérées (fuites), avec
+// l'endroit du code qui les a faites (adresses de retour).
+
+use core::alloc::Layout;
+use core::fmt;
+use core::mem::size_of;
+use core::ptr;
+
+/// Taille minimale de chaque zone rouge
+pub const REDZONE_SIZE: usize = 16;
+/// Motif des zones rouges
+pub const REDZONE_BYTE: u8 = 0xFD;
+/// Nombre d'adresses de retour conservées par allocation
+pub const SITE_FRAMES: usize = 8;
+
+/// Signature d'un en-tête d'allocation vivante
+const MAGIC_LIVE: u64 = 0xA110_CA7E_D0D0_CAFE;
+/// Signature d'un en-tête après libération
+const MAGIC_FREED: u64 = 0xF4EE_D0D0_DEAD_BEEF;
+
+/// En-tête placé devant chaque allocation
+#[repr(C)]
+struct AllocationHeader {
+    magic: u64,
+    size: usize,
+    /// Distance entre l'en-tête et les données
+    offset: usize,
+    sequence: u64,
+    prev: *mut AllocationHeader,
+    next: *mut AllocationHeader,
+    site: [u64; SITE_FRAMES],
+}
+
+/// Description d'une allocation vivante
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AllocationInfo {
+    /// Adresse rendue à l'appelant
+    pub address: usize,
+    /// Taille demandée
+    pub size: usize,
+    /// Numéro d'ordre (la première allocation suivie porte le numéro 0)
+    pub sequence: u64,
+    /// Adresses de retour au moment de l'allocation (0 = inconnue)
+    pub site: [u64; SITE_FRAMES],
+}
+
+/// Erreur détectée sur une allocation
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HeapDebugError {
+    /// En-tête illisible : pointeur invalide, ou en-tête écrasé
+    InvalidPointer(usize),
+    /// Allocation déjà libérée
+    DoubleFree(usize),
+    /// Libérée avec une autre taille que celle allouée
+    SizeMismatch { info: AllocationInfo, freed: usize },
+    /// Écriture avant le début des données
+    Underflow(AllocationInfo),
+    /// Écriture après la fin des données
+    Overflow(AllocationInfo),
+}
+
+impl HeapDebugError {
+    /// Allocation concernée, quand son en-tête est encore lisible
+    pub fn info(&self) -> Option<AllocationInfo> {
+        match self {
+            HeapDebugError::InvalidPointer(_) | HeapDebugError::DoubleFree(_) => None,
+            HeapDebugError::SizeMismatch { info, .. } => Some(*info),
+            HeapDebugError::Underflow(info) | HeapDebugError::Overflow(info) => Some(*info),
+        }
+    }
+}
+
+impl fmt::Display for HeapDebugError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            HeapDebugError::InvalidPointer(addr) => write!(f, "{:#x} : pointeur invalide ou en-tete ecrase", addr),
+            HeapDebugError::DoubleFree(addr) => write!(f, "{:#x} : double liberation", addr),
+            HeapDebugError::SizeMismatch { info, freed } => write!(
+                f,
+                "{:#x} : liberee avec {} octets au lieu de {}",
+                info.address, freed, info.size
+            ),
+            HeapDebugError::Underflow(info) => {
+                write!(f, "{:#x} : ecriture avant le debut ({} octets)", info.address, info.size)
+            }
+            HeapDebugError::Overflow(info) => {
+                write!(f, "{:#x} : ecriture apres la fin ({} octets)", info.address, info.size)
+            }
+        }
+    }
+}
+
+/// Distance entre le début du bloc réel et les données
+fn data_offset(layout: Layout) -> usize {
+    let align = layout.align().max(REDZONE_SIZE);
+    (size_of::<AllocationHeader>() + REDZONE_SIZE).next_multiple_of(align)
+}
+
+/// Vérifie qu'une zone est entièrement remplie du motif
+unsafe fn is_intact(start: *const u8, len: usize) -> bool {
+    (0..len).all(|i| *start.add(i) == REDZONE_BYTE)
+}
+
+/// Suivi des allocations vivantes
+pub struct Tracker {
+    head: *mut AllocationHeader,
+    live: usize,
+    live_bytes: usize,
+    next_sequence: u64,
+}
+
+// Le suivi ne contient que des adresses : il peut changer de thread
+unsafe impl Send for Tracker {}
+
+impl Tracker {
+    /// Aucune allocation suivie
+    pub const fn new() -> Tracker {
+        Tracker {
+            head: ptr::null_mut(),
+            live: 0,
+            live_bytes: 0,
+            next_sequence: 0,
+        }
+    }
+
+    /// Bloc à demander réellement pour une allocation de `layout`
+    pub fn outer_layout(layout: Layout) -> Option<Layout> {
+        let size = data_offset(layout).checked_add(layout.size())?.checked_add(REDZONE_SIZE)?;
+        Layout::from_size_align(size, layout.align().max(REDZONE_SIZE)).ok()
+    }
+
+    /// Nombre d'allocations vivantes et leur taille totale
+    pub fn live(&self) -> (usize, usize) {
+        (self.live, self.live_bytes)
+    }
+
+    /// Prépare le bloc `base` (de taille `outer_layout(layout)`) et
+    /// retourne le pointeur à rendre à l'appelant
+    ///
+    /// # Safety
+    /// `base` doit pointer vers un bloc libre de taille `outer_layout(layout)`.
+    pub unsafe fn track(&mut self, base: *mut u8, layout: Layout, site: [u64; SITE_FRAMES]) -> *mut u8 {
+        let offset = data_offset(layout);
+        let data = base.add(offset);
+        let header = base as *mut AllocationHeader;
+        header.write(AllocationHeader {
+            magic: MAGIC_LIVE,
+            size: layout.size(),
+            offset,
+            sequence: self.next_sequence,
+            prev: ptr::null_mut(),
+            next: self.head,
+            site,
+        });
+        if !self.head.is_null() {
+            (*self.head).prev = header;
+        }
+        self.head = header;
+
+        // Zones rouges : tout l'espace entre l'en-tête et les données, puis après
+        let front = base.add(size_of::<AllocationHeader>());
+        ptr::write_bytes(front, REDZONE_BYTE, data as usize - front as usize);
+        ptr::write_bytes(data.add(layout.size()), REDZONE_BYTE, REDZONE_SIZE);
+
+        self.next_sequence += 1;
+        self.live += 1;
+        self.live_bytes += layout.size();
+        data
+    }
+
+    /// Vérifie et retire du suivi l'allocation `data`, retourne le bloc
+    /// réel à libérer
+    ///
+    /// # Safety
+    /// `data` doit avoir été retourné par `track` avec le même `layout`.
+    pub unsafe fn untrack(&mut self, data: *mut u8, layout: Layout) -> Result<*mut u8, HeapDebugError> {
+        let base = data.sub(data_offset(layout));
+        let header = base as *mut AllocationHeader;
+        match (*header).magic {
+            MAGIC_LIVE => {}
+            MAGIC_FREED => return Err(HeapDebugError::DoubleFree(data as usize)),
+            _ => return Err(HeapDebugError::InvalidPointer(data as usize)),
+        }
+        let info = Self::info(header);
+        if info.size != layout.size() {
+            return Err(HeapDebugError::SizeMismatch { info, freed: layout.size() });
+        }
+        Self::check(header)?;
+
+        if (*header).prev.is_null() {
+            self.head = (*header).next;
+        } else {
+            (*(*header).prev).next = (*header).next;
+        }
+        if !(*header).next.is_null() {
+            (*(*header).next).prev = (*header).prev;
+        }
+        (*header).magic = MAGIC_FREED;
+        self.live -= 1;
+        self.live_bytes -= info.size;
+        Ok(base)
+    }
+
+    unsafe fn info(header: *const AllocationHeader) -> AllocationInfo {
+        AllocationInfo {
+            address: header as usize + (*header).offset,
+            size: (*header).size,
+            sequence: (*header).sequence,
+            site: (*header).site,
+        }
+    }
+
+    /// Vérifie les zones rouges d'une allocation vivante
+    unsafe fn check(header: *const AllocationHeader) -> Result<(), HeapDebugError> {
+        let info = Self::info(header);
+        let front = (header as *const u8).add(size_of::<AllocationHeader>());
+        if !is_intact(front, info.address - front as usize) {
+            return Err(HeapDebugError::Underflow(info));
+        }
+        if !is_intact((info.address + info.size) as *const u8, REDZONE_SIZE) {
+            return Err(HeapDebugError::Overflow(info));
+        }
+        Ok(())
+    }
+
+    /// Parcourt les allocations vivantes, de la plus récente à la plus ancienne
+    pub fn for_each(&self, mut f: impl FnMut(AllocationInfo)) {
+        let mut header = self.head;
+        while !header.is_null() {
+            unsafe {
+                f(Self::info(header));
+                header = (*header).next;
+            }
+        }
+    }
+
+    /// Vérifie les zones rouges de toutes les allocations vivantes
+    pub fn check_all(&self, mut f: impl FnMut(HeapDebugError)) {
+        let mut header = self.head;
+        while !header.is_null() {
+            unsafe {
+                if let Err(error) = Self::check(header) {
+                    f(error);
+                }
+                header = (*header).next;
+            }
+        }
+    }
+}
+
+impl Default for Tracker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::alloc::alloc;
+
+    /// Alloue via le suivi, avec l'allocateur de l'hôte en dessous
+    fn allocate(tracker: &mut Tracker, layout: Layout, site: u64) -> *mut u8 {
+        let outer = Tracker::outer_layout(layout).unwrap();
+        unsafe { tracker.track(alloc(outer), layout, [site; SITE_FRAMES]) }
+    }
+
+    fn free(tracker: &mut Tracker, data: *mut u8, layout: Layout) -> Result<(), HeapDebugError> {
+        let base = unsafe { tracker.untrack(data, layout)? };
+        // Le bloc n'est pas rendu à l'hôte : l'en-tête reste lisible
+        // pour détecter une double libération
+        let _ = base;
+        Ok(())
+    }
+
+    fn layout(size: usize, align: usize) -> Layout {
+        Layout::from_size_align(size, align).unwrap()
+    }
+
+    #[test]
+    fn tracks_live_allocations() {
+        let mut tracker = Tracker::new();
+        let a = allocate(&mut tracker, layout(10, 1), 0x1000);
+        let b = allocate(&mut tracker, layout(100, 64), 0x2000);
+        assert!((b as usize).is_multiple_of(64));
+        assert_eq!(tracker.live(), (2, 110));
+
+        let mut seen = Vec::new();
+        tracker.for_each(|info| seen.push((info.address, info.size, info.sequence, info.site[0])));
+        assert_eq!(seen, [(b as usize, 100, 1, 0x2000), (a as usize, 10, 0, 0x1000)]);
+
+        free(&mut tracker, a, layout(10, 1)).unwrap();
+        assert_eq!(tracker.live(), (1, 100));
+        free(&mut tracker, b, layout(100, 64)).unwrap();
+        assert_eq!(tracker.live(), (0, 0));
+    }
+
+    #[test]
+    fn detects_overflow_and_underflow() {
+        let mut tracker = Tracker::new();
+        let l = layout(32, 8);
+        let a = allocate(&mut tracker, l, 0);
+        let b = allocate(&mut tracker, l, 0);
+        unsafe {
+            *a.add(32) = 0;
+            *b.sub(1) = 0;
+        }
+        let mut errors = Vec::new();
+        tracker.check_all(|error| errors.push(error));
+        assert_eq!(errors.len(), 2);
+        assert!(matches!(free(&mut tracker, a, l), Err(HeapDebugError::Overflow(info)) if info.address == a as usize));
+        assert!(matches!(free(&mut tracker, b, l), Err(HeapDebugError::Underflow(_))));
+        // Une allocation en erreur reste suivie
+        assert_eq!(tracker.live().0, 2);
+    }
+
+    #[test]
+    fn detects_double_free_and_bad_sizes() {
+        let mut tracker = Tracker::new();
+        let l = layout(24, 8);
+        let a = allocate(&mut tracker, l, 0);
+        assert!(matches!(
+            free(&mut tracker, a, layout(16, 8)),
+            Err(HeapDebugError::SizeMismatch { freed: 16, .. })
+        ));
+        free(&mut tracker, a, l).unwrap();
+        assert_eq!(free(&mut tracker, a, l), Err(HeapDebugError::DoubleFree(a as usize)));
+
+        let mut junk = [0u64; 32];
+        let fake = unsafe { (junk.as_mut_ptr() as *mut u8).add(data_offset(l)) };
+        assert_eq!(free(&mut tracker, fake, l), Err(HeapDebugError::InvalidPointer(fake as usize)));
+    }
+
+    #[test]
+    fn displays_errors() {
+        let info = AllocationInfo { address: 0x1000, size: 8, sequence: 0, site: [0; SITE_FRAMES] };
+        assert_eq!(HeapDebugError::Overflow(info).to_string(), "0x1000 : ecriture apres la fin (8 octets)");
+        assert_eq!(HeapDebugError::DoubleFree(0x20).to_string(), "0x20 : double liberation");
+    }
+}
@@ -0,0 +1,164 @@
+// ===================================================================
+// DÉCODAGE DES SCANCODES DU CLAVIER
+// ===================================================================
+//
+// Scancodes (Scan Code Set 1) :
+// - "Make code" : envoyé quand la touche est pressée (bit 7 = 0)
+// - "Break code" : envoyé quand la touche est relâchée (bit 7 = 1)
+//
+// On ignore les break codes car on veut afficher seulement quand
+// la touche est pressée, pas relâchée.
+//
+// `KeyDecoder` suit en plus l'état de la touche Ctrl (pressée ou
+// relâchée) pour reconnaître les combinaisons comme Ctrl+C.
+
+// Table de conversion des scancodes (Scan Code Set 1, layout AZERTY français)
+// Index = scancode, Valeur = caractère ASCII correspondant
+// '\0' = touche sans caractère (Shift, Ctrl, etc.)
+// Note : Les caractères accentués (é, è, ç, à, ù) sont remplacés par leur équivalent non-accenté
+//        car ils ne sont pas dans ASCII standard. Pour un support complet, il faudrait utiliser UTF-8.
+pub static SCANCODE_TABLE: [char; 58] = [
+    '\0', '\0', '&', 'e', '"', '\'', '(', '-', 'e', '_', 'c', 'a', ')', '=', '\x08', // 0-14 : Ligne chiffres AZERTY
+    '\t', 'a', 'z', 'e', 'r', 't', 'y', 'u', 'i', 'o', 'p', '^', '$', '\n', // 15-28 : AZERTY première ligne
+    '\0', 'q', 's', 'd', 'f', 'g', 'h', 'j', 'k', 'l', 'm', 'u', '~', // 29-42 : AZERTY deuxième ligne (ù→u)
+    '\0', '<', 'w', 'x', 'c', 'v', 'b', 'n', ',', ';', ':', '!', '\0', '*', // 43-55 : AZERTY troisième ligne
+    '\0', ' ', // 56-57 : Alt, Espace
+];
+
+/// Indique si le scancode est un "break code" (touche relâchée)
+pub fn is_break_code(scancode: u8) -> bool {
+    scancode & 0x80 != 0
+}
+
+/// Convertit un scancode en caractère
+///
+/// Retourne `None` pour les touches relâchées, les scancodes hors de la
+/// table et les touches spéciales (Shift, Ctrl, etc.) marquées '\0'.
+pub fn decode_scancode(scancode: u8) -> Option<char> {
+    if is_break_code(scancode) {
+        return None;
+    }
+    match SCANCODE_TABLE.get(scancode as usize) {
+        Some(&c) if c != '\0' => Some(c),
+        _ => None,
+    }
+}
+
+/// Scancode de la touche Ctrl (gauche ; la droite envoie 0xE0 avant)
+pub const CTRL_SCANCODE: u8 = 0x1D;
+
+/// Touche pressée, décodée
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Key {
+    /// Caractère tapé
+    Char(char),
+    /// Touche pressée avec Ctrl (la lettre, en minuscule)
+    Ctrl(char),
+}
+
+impl Key {
+    /// Caractère transmis à un programme : les combinaisons Ctrl+lettre
+    /// donnent les caractères de contrôle ASCII (Ctrl+D = 0x04)
+    pub fn to_char(self) -> Option<char> {
+        match self {
+            Key::Char(c) => Some(c),
+            Key::Ctrl(c @ 'a'..='z') => Some((c as u8 - b'a' + 1) as char),
+            Key::Ctrl(_) => None,
+        }
+    }
+}
+
+/// Décodeur qui suit l'état de la touche Ctrl
+#[derive(Debug, Default, Clone, Copy)]
+pub struct KeyDecoder {
+    ctrl: bool,
+}
+
+impl KeyDecoder {
+    /// Crée un décodeur, Ctrl relâchée
+    pub const fn new() -> KeyDecoder {
+        KeyDecoder { ctrl: false }
+    }
+
+    /// Indique si Ctrl est pressée
+    pub fn ctrl(&self) -> bool {
+        self.ctrl
+    }
+
+    /// Traite un scancode, retourne la touche pressée s'il y en a une
+    pub fn feed(&mut self, scancode: u8) -> Option<Key> {
+        if scancode & 0x7F == CTRL_SCANCODE {
+            self.ctrl = !is_break_code(scancode);
+            return None;
+        }
+        let c = decode_scancode(scancode)?;
+        Some(if self.ctrl { Key::Ctrl(c) } else { Key::Char(c) })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decodes_azerty_letters() {
+        assert_eq!(decode_scancode(0x10), Some('a'));
+        assert_eq!(decode_scancode(0x11), Some('z'));
+        assert_eq!(decode_scancode(0x1E), Some('q'));
+        assert_eq!(decode_scancode(0x2C), Some('w'));
+        assert_eq!(decode_scancode(0x27), Some('m'));
+    }
+
+    #[test]
+    fn decodes_control_keys() {
+        assert_eq!(decode_scancode(0x1C), Some('\n'));
+        assert_eq!(decode_scancode(0x0E), Some('\x08'));
+        assert_eq!(decode_scancode(0x39), Some(' '));
+    }
+
+    #[test]
+    fn ignores_break_codes() {
+        assert_eq!(decode_scancode(0x10 | 0x80), None);
+        assert!(is_break_code(0x9C));
+        assert!(!is_break_code(0x1C));
+    }
+
+    #[test]
+    fn ignores_modifiers_and_unknown_scancodes() {
+        assert_eq!(decode_scancode(0x1D), None); // Ctrl
+        assert_eq!(decode_scancode(0x2A), None); // Shift gauche
+        assert_eq!(decode_scancode(0x3B), None); // F1 (hors table)
+    }
+
+    #[test]
+    fn tracks_ctrl_key() {
+        let mut decoder = KeyDecoder::new();
+        assert_eq!(decoder.feed(0x2E), Some(Key::Char('c')));
+        assert_eq!(decoder.feed(CTRL_SCANCODE), None);
+        assert!(decoder.ctrl());
+        assert_eq!(decoder.feed(0x2E), Some(Key::Ctrl('c')));
+        assert_eq!(decoder.feed(0x2E | 0x80), None);
+        assert_eq!(decoder.feed(CTRL_SCANCODE | 0x80), None);
+        assert!(!decoder.ctrl());
+        assert_eq!(decoder.feed(0x2E), Some(Key::Char('c')));
+        // Ctrl droite : préfixe 0xE0 ignoré
+        assert_eq!(decoder.feed(0xE0), None);
+        assert_eq!(decoder.feed(CTRL_SCANCODE), None);
+        assert!(decoder.ctrl());
+    }
+
+    #[test]
+    fn converts_ctrl_keys_to_control_characters() {
+        assert_eq!(Key::Ctrl('c').to_char(), Some('\x03'));
+        assert_eq!(Key::Ctrl('d').to_char(), Some('\x04'));
+        assert_eq!(Key::Char('d').to_char(), Some('d'));
+        assert_eq!(Key::Ctrl('\n').to_char(), None);
+    }
+
+    #[test]
+    fn never_panics_on_any_byte() {
+        for scancode in 0..=u8::MAX {
+            let _ = decode_scancode(scancode);
+        }
+    }
+}
@@ -0,0 +1,54 @@
+// ===================================================================
+// BOS-CORE : LOGIQUE PURE DU KERNEL BOS
+// ===================================================================
+//
+// Cette bibliothèque regroupe le code du kernel qui ne touche pas au
+// matériel (pas de ports I/O, pas d'assembleur, pas d'adresses fixes) :
+// - Décodage des scancodes du clavier
+// - Découpage des lignes de commande du shell
+// - Arithmétique du curseur de la console texte
+// - Lecture des fichiers ELF64 et démanglage des symboles
+// - Validation des exécutables et pile initiale d'un programme
+// - Protocole GDB Remote Serial (paquets et commandes)
+// - Niveaux et tampon circulaire du journal du kernel
+// - Carte de la mémoire physique et allocateur de frames (bitmap)
+// - Droits des tables de pages et codes d'erreur des page faults
+// - Allocateur du tas à liste chaînée de blocs libres et caches slab
+// - Zones rouges et suivi des allocations (mode de débogage du tas)
+// - File circulaire de taille fixe (scancodes, tâches prêtes)
+// - Ordonnanceur des threads du kernel (tourniquet)
+// - Numéros et codes d'erreur des appels système
+// - Statut de fin des processus (waitpid)
+// - Signaux : masques, actions et trame de signal
+// - Archives tar (initrd)
+// - Types du système de fichiers virtuel : erreurs, options, chemins
+// - Décodage de la date de l'horloge temps réel (RTC)
+//
+// Elle est `no_std` pour être utilisée par le kernel, mais en mode test
+// elle est compilée avec std pour l'hôte : les tests s'exécutent avec
+// un simple `cargo test-core` sur Linux, sans QEMU.
+
+#![cfg_attr(not(test), no_std)]
+
+pub mod command;
+pub mod console;
+pub mod demangle;
+pub mod elf;
+pub mod exec;
+pub mod frame;
+pub mod gdb;
+pub mod heap;
+pub mod heap_debug;
+pub mod keyboard;
+pub mod log;
+pub mod memory;
+pub mod paging;
+pub mod process;
+pub mod ring;
+pub mod rtc;
+pub mod sched;
+pub mod signal;
+pub mod slab;
+pub mod syscall;
+pub mod tar;
+pub mod vfs;
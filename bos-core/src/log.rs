@@ -0,0 +1,219 @@
+// ===================================================================
+// JOURNAL DU KERNEL : NIVEAUX ET TAMPON CIRCULAIRE
+// ===================================================================
+//
+// Partie pure du journal du kernel (voir src/log.rs dans le kernel) :
+// - Les niveaux de gravité, du plus grave (Error) au plus bavard (Trace)
+// - Le tampon circulaire qui conserve les dernières lignes du journal
+//   (lu par la commande `dmesg`). Quand il est plein, les lignes les
+//   plus anciennes sont effacées, toujours en entier.
+
+use core::fmt;
+
+/// Niveau de gravité d'un message
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[repr(u8)]
+pub enum Level {
+    Error = 1,
+    Warn = 2,
+    Info = 3,
+    Debug = 4,
+    Trace = 5,
+}
+
+impl Level {
+    /// Tous les niveaux, du plus grave au plus bavard
+    pub const ALL: [Level; 5] = [Level::Error, Level::Warn, Level::Info, Level::Debug, Level::Trace];
+
+    /// Nom du niveau en minuscules (utilisé par la commande `loglevel`)
+    pub fn name(self) -> &'static str {
+        match self {
+            Level::Error => "error",
+            Level::Warn => "warn",
+            Level::Info => "info",
+            Level::Debug => "debug",
+            Level::Trace => "trace",
+        }
+    }
+
+    /// Retrouve un niveau à partir de son nom ou de son numéro (1 à 5)
+    pub fn parse(text: &str) -> Option<Level> {
+        match text.parse::<u8>() {
+            Ok(value) => Level::from_u8(value),
+            Err(_) => Level::ALL.iter().copied().find(|level| level.name().eq_ignore_ascii_case(text)),
+        }
+    }
+
+    /// Retrouve un niveau à partir de sa valeur numérique
+    pub fn from_u8(value: u8) -> Option<Level> {
+        Level::ALL.iter().copied().find(|level| *level as u8 == value)
+    }
+}
+
+impl fmt::Display for Level {
+    /// Nom en majuscules, complété à 5 caractères pour aligner le journal
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Level::Error => "ERROR",
+            Level::Warn => "WARN",
+            Level::Info => "INFO",
+            Level::Debug => "DEBUG",
+            Level::Trace => "TRACE",
+        };
+        f.pad(name)
+    }
+}
+
+/// Tampon circulaire de lignes de texte, de capacité `N` octets
+pub struct LogRing<const N: usize> {
+    buffer: [u8; N],
+    /// Indice du premier octet de la plus ancienne ligne
+    start: usize,
+    /// Nombre d'octets occupés
+    len: usize,
+}
+
+impl<const N: usize> LogRing<N> {
+    /// Crée un tampon vide
+    pub const fn new() -> LogRing<N> {
+        LogRing {
+            buffer: [0; N],
+            start: 0,
+            len: 0,
+        }
+    }
+
+    /// Nombre d'octets occupés
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Indique si le tampon est vide
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Vide le tampon
+    pub fn clear(&mut self) {
+        self.start = 0;
+        self.len = 0;
+    }
+
+    /// Ajoute une ligne (un '\n' final est ajouté si absent)
+    ///
+    /// Une ligne plus longue que le tampon est tronquée.
+    pub fn push_line(&mut self, line: &[u8]) {
+        if N == 0 {
+            return;
+        }
+        let line = line.strip_suffix(b"\n").unwrap_or(line);
+        let line = &line[..line.len().min(N - 1)];
+        let needed = line.len() + 1;
+        // Effacer les plus anciennes lignes jusqu'à avoir la place
+        while N - self.len < needed {
+            self.drop_oldest_line();
+        }
+        for &byte in line {
+            self.push_byte(byte);
+        }
+        self.push_byte(b'\n');
+    }
+
+    fn push_byte(&mut self, byte: u8) {
+        self.buffer[(self.start + self.len) % N] = byte;
+        self.len += 1;
+    }
+
+    fn drop_oldest_line(&mut self) {
+        while self.len > 0 {
+            let byte = self.buffer[self.start];
+            self.start = (self.start + 1) % N;
+            self.len -= 1;
+            if byte == b'\n' {
+                break;
+            }
+        }
+    }
+
+    /// Contenu du tampon, du plus ancien au plus récent, en deux morceaux
+    /// (le second est vide si le contenu ne fait pas le tour du tampon)
+    pub fn as_slices(&self) -> (&[u8], &[u8]) {
+        let end = self.start + self.len;
+        if end <= N {
+            (&self.buffer[self.start..end], &[])
+        } else {
+            (&self.buffer[self.start..], &self.buffer[..end - N])
+        }
+    }
+}
+
+impl<const N: usize> Default for LogRing<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn contents<const N: usize>(ring: &LogRing<N>) -> String {
+        let (a, b) = ring.as_slices();
+        let mut bytes = a.to_vec();
+        bytes.extend_from_slice(b);
+        String::from_utf8(bytes).unwrap()
+    }
+
+    #[test]
+    fn levels_are_ordered() {
+        assert!(Level::Error < Level::Warn);
+        assert!(Level::Debug < Level::Trace);
+        assert_eq!(Level::from_u8(3), Some(Level::Info));
+        assert_eq!(Level::from_u8(0), None);
+    }
+
+    #[test]
+    fn parses_levels() {
+        assert_eq!(Level::parse("warn"), Some(Level::Warn));
+        assert_eq!(Level::parse("DEBUG"), Some(Level::Debug));
+        assert_eq!(Level::parse("1"), Some(Level::Error));
+        assert_eq!(Level::parse("verbose"), None);
+    }
+
+    #[test]
+    fn displays_padded_level() {
+        assert_eq!(format!("{:<5}|", Level::Info), "INFO |");
+        assert_eq!(format!("{}", Level::Error), "ERROR");
+    }
+
+    #[test]
+    fn keeps_lines_in_order() {
+        let mut ring = LogRing::<64>::new();
+        ring.push_line(b"un");
+        ring.push_line(b"deux\n");
+        assert_eq!(contents(&ring), "un\ndeux\n");
+        ring.clear();
+        assert!(ring.is_empty());
+    }
+
+    #[test]
+    fn drops_oldest_whole_lines() {
+        let mut ring = LogRing::<16>::new();
+        ring.push_line(b"aaaa");
+        ring.push_line(b"bbbb");
+        ring.push_line(b"cccc");
+        // 15 octets occupés : "dddd" force l'effacement de "aaaa"
+        ring.push_line(b"dddd");
+        assert_eq!(contents(&ring), "bbbb\ncccc\ndddd\n");
+        let (first, second) = ring.as_slices();
+        assert!(!first.is_empty() && !second.is_empty());
+    }
+
+    #[test]
+    fn truncates_long_lines() {
+        let mut ring = LogRing::<8>::new();
+        ring.push_line(b"abcdefghijkl");
+        assert_eq!(contents(&ring), "abcdefg\n");
+        assert_eq!(ring.len(), 8);
+    }
+}
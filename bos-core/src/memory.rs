@@ -0,0 +1,193 @@
+// ===================================================================
+// CARTE DE LA MÉMOIRE PHYSIQUE
+// ===================================================================
+//
+// Le bootloader transmet au kernel la liste des régions de mémoire
+// physique (obtenue du BIOS via E820), en y ajoutant les zones qu'il
+// occupe lui-même (kernel, tables de pages, pile...). Ce module
+// décrit ces régions indépendamment du bootloader et en calcule un
+// résumé (commande `meminfo` du shell).
+
+use core::fmt::{self, Write};
+
+/// Taille d'une page / d'un frame physique
+pub const PAGE_SIZE: u64 = 4096;
+
+/// Catégorie d'une région de mémoire physique
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RegionKind {
+    /// Libre, utilisable par le kernel
+    Usable,
+    /// Réservée par le matériel ou le BIOS (ou défectueuse)
+    Reserved,
+    /// Tables ACPI (récupérable ou NVS)
+    Acpi,
+    /// Image du kernel et sa pile
+    Kernel,
+    /// Structures du bootloader : tables de pages, BootInfo...
+    Bootloader,
+}
+
+impl RegionKind {
+    /// Nom affiché par `meminfo`
+    pub fn name(self) -> &'static str {
+        match self {
+            RegionKind::Usable => "utilisable",
+            RegionKind::Reserved => "reserve",
+            RegionKind::Acpi => "ACPI",
+            RegionKind::Kernel => "kernel",
+            RegionKind::Bootloader => "bootloader",
+        }
+    }
+}
+
+/// Une région de mémoire physique [start, end)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Region {
+    pub start: u64,
+    pub end: u64,
+    pub kind: RegionKind,
+}
+
+impl Region {
+    /// Taille en octets
+    pub fn size(&self) -> u64 {
+        self.end.saturating_sub(self.start)
+    }
+
+    /// Nombre de frames de 4 Kio entièrement contenus dans la région
+    pub fn frame_count(&self) -> u64 {
+        let start = self.start.div_ceil(PAGE_SIZE);
+        let end = self.end / PAGE_SIZE;
+        end.saturating_sub(start)
+    }
+}
+
+/// Totaux par catégorie, en octets
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MemorySummary {
+    pub usable: u64,
+    pub reserved: u64,
+    pub acpi: u64,
+    pub kernel: u64,
+    pub bootloader: u64,
+    /// Fin de la plus haute région
+    pub highest_address: u64,
+}
+
+impl MemorySummary {
+    /// Calcule les totaux d'une liste de régions
+    pub fn from_regions(regions: impl IntoIterator<Item = Region>) -> MemorySummary {
+        let mut summary = MemorySummary::default();
+        for region in regions {
+            let total = match region.kind {
+                RegionKind::Usable => &mut summary.usable,
+                RegionKind::Reserved => &mut summary.reserved,
+                RegionKind::Acpi => &mut summary.acpi,
+                RegionKind::Kernel => &mut summary.kernel,
+                RegionKind::Bootloader => &mut summary.bootloader,
+            };
+            *total += region.size();
+            summary.highest_address = summary.highest_address.max(region.end);
+        }
+        summary
+    }
+
+    /// Mémoire vive présente (tout sauf les zones réservées)
+    pub fn installed(&self) -> u64 {
+        self.usable + self.acpi + self.kernel + self.bootloader
+    }
+}
+
+/// Taille affichée avec l'unité la plus adaptée : `Size(3 << 20)` → "3 Mio"
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Size(pub u64);
+
+impl fmt::Display for Size {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        const UNITS: [&str; 4] = ["o", "Kio", "Mio", "Gio"];
+        let mut value = self.0;
+        let mut unit = 0;
+        // Changer d'unité tant que la valeur reste un nombre entier
+        while unit < UNITS.len() - 1 && value >= 1024 && value.is_multiple_of(1024) {
+            value /= 1024;
+            unit += 1;
+        }
+        // Au-delà de 10 240 d'une unité, arrondir à l'unité supérieure
+        while unit < UNITS.len() - 1 && value >= 10 * 1024 {
+            value /= 1024;
+            unit += 1;
+        }
+        // Formater dans un petit tampon pour respecter la largeur demandée
+        let mut text = SmallText { bytes: [0; 24], len: 0 };
+        let _ = write!(text, "{} {}", value, UNITS[unit]);
+        f.pad(core::str::from_utf8(&text.bytes[..text.len]).unwrap_or(""))
+    }
+}
+
+/// Tampon de texte de taille fixe (sans allocation)
+struct SmallText {
+    bytes: [u8; 24],
+    len: usize,
+}
+
+impl fmt::Write for SmallText {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let end = self.len + s.len();
+        if end > self.bytes.len() {
+            return Err(fmt::Error);
+        }
+        self.bytes[self.len..end].copy_from_slice(s.as_bytes());
+        self.len = end;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn region(start: u64, end: u64, kind: RegionKind) -> Region {
+        Region { start, end, kind }
+    }
+
+    #[test]
+    fn region_sizes() {
+        let r = region(0x1000, 0x5000, RegionKind::Usable);
+        assert_eq!(r.size(), 0x4000);
+        assert_eq!(r.frame_count(), 4);
+        // Frames partiels aux bords : ignorés
+        assert_eq!(region(0x1800, 0x5800, RegionKind::Usable).frame_count(), 3);
+        assert_eq!(region(0x5000, 0x1000, RegionKind::Usable).size(), 0);
+    }
+
+    #[test]
+    fn summarizes_regions() {
+        let summary = MemorySummary::from_regions([
+            region(0, 0x1000, RegionKind::Reserved),
+            region(0x1000, 0x9f000, RegionKind::Usable),
+            region(0x100000, 0x400000, RegionKind::Usable),
+            region(0x400000, 0x500000, RegionKind::Kernel),
+            region(0x500000, 0x510000, RegionKind::Bootloader),
+            region(0x7fe0000, 0x8000000, RegionKind::Acpi),
+        ]);
+        assert_eq!(summary.usable, 0x9e000 + 0x300000);
+        assert_eq!(summary.reserved, 0x1000);
+        assert_eq!(summary.kernel, 0x100000);
+        assert_eq!(summary.bootloader, 0x10000);
+        assert_eq!(summary.acpi, 0x20000);
+        assert_eq!(summary.highest_address, 0x8000000);
+        assert_eq!(summary.installed(), summary.usable + 0x100000 + 0x10000 + 0x20000);
+    }
+
+    #[test]
+    fn formats_sizes() {
+        assert_eq!(Size(512).to_string(), "512 o");
+        assert_eq!(Size(4096).to_string(), "4 Kio");
+        assert_eq!(Size(3 << 20).to_string(), "3 Mio");
+        assert_eq!(Size(0x9e000).to_string(), "632 Kio");
+        assert_eq!(Size(127 * 1024 * 1024 + 4096).to_string(), "127 Mio");
+        assert_eq!(Size(2 << 30).to_string(), "2 Gio");
+        assert_eq!(format!("{:>7}|", Size(4096)), "  4 Kio|");
+    }
+}
@@ -0,0 +1,257 @@
+// ===================================================================
+// TABLES DE PAGES x86_64 : ENTRÉES, DROITS ET CODES D'ERREUR
+// ===================================================================
+//
+// Une adresse virtuelle de 48 bits est découpée en 4 index de 9 bits
+// (un par niveau de table : PML4, PDPT, PD, PT) et un décalage de
+// 12 bits dans la page :
+//
+//   47      39 38     30 29     21 20     12 11         0
+//   [ PML4   ][ PDPT   ][ PD     ][ PT     ][ décalage  ]
+//
+// Chaque entrée de table (64 bits) contient l'adresse physique de la
+// table suivante (ou de la page) et des bits de droits. Ce module
+// décrit ces bits et décode le code d'erreur d'un page fault ; le
+// parcours des tables lui-même est dans le kernel (src/paging.rs).
+
+use core::fmt;
+use core::ops::{BitOr, BitOrAssign};
+
+/// Nombre d'entrées dans une table de pages
+pub const ENTRY_COUNT: usize = 512;
+
+/// Bits de l'adresse physique dans une entrée
+pub const ADDRESS_MASK: u64 = 0x000F_FFFF_FFFF_F000;
+
+/// Index dans la table du niveau donné (4 = PML4 ... 1 = PT)
+pub const fn table_index(virt: u64, level: usize) -> usize {
+    ((virt >> (12 + 9 * (level as u32 - 1))) & 0x1FF) as usize
+}
+
+/// Une adresse est canonique si ses bits 48 à 63 recopient le bit 47
+pub const fn is_canonical(virt: u64) -> bool {
+    ((virt as i64) << 16 >> 16) as u64 == virt
+}
+
+/// Bits de droits d'une entrée de table de pages
+#[derive(Clone, Copy, PartialEq, Eq, Default)]
+pub struct PageFlags(u64);
+
+impl PageFlags {
+    /// Entrée valide
+    pub const PRESENT: PageFlags = PageFlags(1 << 0);
+    /// Écriture autorisée
+    pub const WRITABLE: PageFlags = PageFlags(1 << 1);
+    /// Accessible depuis le ring 3
+    pub const USER: PageFlags = PageFlags(1 << 2);
+    /// Écriture immédiate en mémoire (write-through)
+    pub const WRITE_THROUGH: PageFlags = PageFlags(1 << 3);
+    /// Cache désactivé (registres de périphériques, MMIO)
+    pub const NO_CACHE: PageFlags = PageFlags(1 << 4);
+    /// Mis à 1 par le CPU à chaque accès
+    pub const ACCESSED: PageFlags = PageFlags(1 << 5);
+    /// Mis à 1 par le CPU à chaque écriture
+    pub const DIRTY: PageFlags = PageFlags(1 << 6);
+    /// Page de 2 Mio (PD) ou 1 Gio (PDPT)
+    pub const HUGE: PageFlags = PageFlags(1 << 7);
+    /// Conservée dans le TLB lors d'un changement de CR3
+    pub const GLOBAL: PageFlags = PageFlags(1 << 8);
+    /// Exécution interdite (nécessite EFER.NXE, activé par le bootloader)
+    pub const NO_EXECUTE: PageFlags = PageFlags(1 << 63);
+
+    /// Tous les bits de droits (le reste de l'entrée est l'adresse)
+    pub const ALL: PageFlags = PageFlags(!ADDRESS_MASK);
+
+    /// Aucun bit
+    pub const fn empty() -> PageFlags {
+        PageFlags(0)
+    }
+
+    /// Extrait les droits d'une entrée brute
+    pub const fn from_entry(entry: u64) -> PageFlags {
+        PageFlags(entry & !ADDRESS_MASK)
+    }
+
+    /// Valeur brute
+    pub const fn bits(self) -> u64 {
+        self.0
+    }
+
+    /// Contient tous les bits de `other`
+    pub const fn contains(self, other: PageFlags) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    /// Retire les bits de `other`
+    pub const fn without(self, other: PageFlags) -> PageFlags {
+        PageFlags(self.0 & !other.0)
+    }
+
+    /// Droits d'une table intermédiaire menant à une page avec ces droits
+    ///
+    /// Le CPU combine les droits de tous les niveaux : les tables
+    /// intermédiaires sont permissives, la page finale décide.
+    pub const fn for_parent_table(self) -> PageFlags {
+        PageFlags(Self::PRESENT.0 | Self::WRITABLE.0 | (self.0 & Self::USER.0))
+    }
+}
+
+impl BitOr for PageFlags {
+    type Output = PageFlags;
+
+    fn bitor(self, other: PageFlags) -> PageFlags {
+        PageFlags(self.0 | other.0)
+    }
+}
+
+impl BitOrAssign for PageFlags {
+    fn bitor_assign(&mut self, other: PageFlags) {
+        self.0 |= other.0;
+    }
+}
+
+impl fmt::Debug for PageFlags {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "PageFlags({})", self)
+    }
+}
+
+impl fmt::Display for PageFlags {
+    /// Droits au format "rwx" suivi des attributs : `rw- user global nocache`
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if !self.contains(PageFlags::PRESENT) {
+            return f.write_str("---");
+        }
+        f.write_str("r")?;
+        f.write_str(if self.contains(PageFlags::WRITABLE) { "w" } else { "-" })?;
+        f.write_str(if self.contains(PageFlags::NO_EXECUTE) { "-" } else { "x" })?;
+        for (flag, name) in [
+            (PageFlags::USER, "user"),
+            (PageFlags::GLOBAL, "global"),
+            (PageFlags::NO_CACHE, "nocache"),
+            (PageFlags::WRITE_THROUGH, "writethrough"),
+            (PageFlags::HUGE, "huge"),
+        ] {
+            if self.contains(flag) {
+                write!(f, " {}", name)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Code d'erreur empilé par le CPU lors d'un page fault
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PageFaultError(pub u64);
+
+impl PageFaultError {
+    /// La page était présente (violation de droits, sinon page absente)
+    pub const fn protection_violation(self) -> bool {
+        self.0 & (1 << 0) != 0
+    }
+
+    /// L'accès était une écriture (sinon une lecture)
+    pub const fn write(self) -> bool {
+        self.0 & (1 << 1) != 0
+    }
+
+    /// L'accès venait du ring 3
+    pub const fn user(self) -> bool {
+        self.0 & (1 << 2) != 0
+    }
+
+    /// Un bit réservé d'une entrée était à 1 (table corrompue)
+    pub const fn reserved_bit(self) -> bool {
+        self.0 & (1 << 3) != 0
+    }
+
+    /// L'accès était la lecture d'une instruction (NX)
+    pub const fn instruction_fetch(self) -> bool {
+        self.0 & (1 << 4) != 0
+    }
+
+    /// Refusé par une clé de protection (PKU)
+    pub const fn protection_key(self) -> bool {
+        self.0 & (1 << 5) != 0
+    }
+
+    /// Accès à la pile fantôme (CET)
+    pub const fn shadow_stack(self) -> bool {
+        self.0 & (1 << 6) != 0
+    }
+}
+
+impl fmt::Display for PageFaultError {
+    /// Description lisible : "ecriture, mode noyau, page absente"
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let access = if self.instruction_fetch() {
+            "execution"
+        } else if self.write() {
+            "ecriture"
+        } else {
+            "lecture"
+        };
+        let mode = if self.user() { "mode utilisateur" } else { "mode noyau" };
+        let cause = if self.protection_violation() { "violation de droits" } else { "page absente" };
+        write!(f, "{}, {}, {}", access, mode, cause)?;
+        if self.reserved_bit() {
+            f.write_str(", bit reserve")?;
+        }
+        if self.protection_key() {
+            f.write_str(", cle de protection")?;
+        }
+        if self.shadow_stack() {
+            f.write_str(", pile fantome")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn splits_virtual_addresses() {
+        let virt = (1 << 39) * 3 + (1 << 30) * 5 + (1 << 21) * 7 + (1 << 12) * 9 + 0x123;
+        assert_eq!(table_index(virt, 4), 3);
+        assert_eq!(table_index(virt, 3), 5);
+        assert_eq!(table_index(virt, 2), 7);
+        assert_eq!(table_index(virt, 1), 9);
+        assert_eq!(table_index(0xFFFF_FFFF_FFFF_F000, 4), 511);
+    }
+
+    #[test]
+    fn checks_canonical_addresses() {
+        assert!(is_canonical(0x0000_7fff_ffff_f000));
+        assert!(is_canonical(0xFFFF_8000_0000_0000));
+        assert!(!is_canonical(0x0000_8000_0000_0000));
+    }
+
+    #[test]
+    fn combines_flags() {
+        let flags = PageFlags::PRESENT | PageFlags::WRITABLE | PageFlags::NO_EXECUTE;
+        assert!(flags.contains(PageFlags::WRITABLE));
+        assert!(!flags.contains(PageFlags::USER));
+        assert_eq!(flags.without(PageFlags::WRITABLE).bits(), (1 << 63) | 1);
+        assert_eq!(PageFlags::from_entry(0x8000_0000_0020_3007).bits(), (1 << 63) | 7);
+        assert_eq!((flags | PageFlags::USER).for_parent_table().bits(), 0b111);
+        assert_eq!(flags.for_parent_table().bits(), 0b11);
+    }
+
+    #[test]
+    fn displays_flags() {
+        let flags = PageFlags::PRESENT | PageFlags::WRITABLE | PageFlags::NO_EXECUTE | PageFlags::NO_CACHE;
+        assert_eq!(flags.to_string(), "rw- nocache");
+        assert_eq!((PageFlags::PRESENT | PageFlags::USER).to_string(), "r-x user");
+        assert_eq!(PageFlags::empty().to_string(), "---");
+    }
+
+    #[test]
+    fn decodes_page_fault_errors() {
+        assert_eq!(PageFaultError(0b010).to_string(), "ecriture, mode noyau, page absente");
+        assert_eq!(PageFaultError(0b101).to_string(), "lecture, mode utilisateur, violation de droits");
+        assert_eq!(PageFaultError(0b10001).to_string(), "execution, mode noyau, violation de droits");
+        assert_eq!(PageFaultError(0b1001).to_string(), "lecture, mode noyau, violation de droits, bit reserve");
+    }
+}
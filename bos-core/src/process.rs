@@ -0,0 +1,95 @@
+// ===================================================================
+// PROCESSUS : STATUT DE FIN ET ATTENTE DES ENFANTS
+// ===================================================================
+//
+// Partagé entre le kernel et les programmes utilisateur (libbos).
+//
+// Un processus se termine de lui-même (exit, avec un code) ou est tué
+// par un signal (voir signal.rs) : kill, Ctrl+C, ou une faute du
+// programme. Son parent récupère ce statut avec waitpid, encodé comme
+// sous Linux :
+// - fin normale : code (8 bits) << 8
+// - tué : numéro du signal (7 bits)
+
+use core::fmt;
+
+use crate::signal::signal_name;
+
+/// Argument `pid` de waitpid : n'importe quel enfant (-1 sous Linux)
+pub const WAIT_ANY: u64 = u64::MAX;
+
+/// Option de waitpid : retourner 0 au lieu d'attendre si aucun enfant
+/// n'est terminé
+pub const WNOHANG: u64 = 1;
+
+/// Comment un processus s'est terminé
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExitStatus {
+    /// Fin normale, avec son code de sortie
+    Exited(i32),
+    /// Tué par un signal
+    Killed(u8),
+}
+
+impl ExitStatus {
+    /// Indique une fin normale avec le code 0
+    pub fn success(self) -> bool {
+        self == ExitStatus::Exited(0)
+    }
+
+    /// Statut transmis par waitpid
+    ///
+    /// Seuls les 8 bits de poids faible du code de sortie sont gardés.
+    pub fn encode(self) -> u32 {
+        match self {
+            ExitStatus::Exited(code) => (code as u32 & 0xff) << 8,
+            ExitStatus::Killed(signal) => signal as u32 & 0x7f,
+        }
+    }
+
+    /// Statut d'après la valeur transmise par waitpid
+    pub fn decode(status: u32) -> ExitStatus {
+        match status & 0x7f {
+            0 => ExitStatus::Exited(((status >> 8) & 0xff) as i32),
+            signal => ExitStatus::Killed(signal as u8),
+        }
+    }
+}
+
+impl fmt::Display for ExitStatus {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ExitStatus::Exited(code) => write!(f, "code {}", code),
+            ExitStatus::Killed(signal) => match signal_name(signal) {
+                Some(name) => write!(f, "tue par {}", name),
+                None => write!(f, "tue par le signal {}", signal),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::signal::{SIGINT, SIGKILL, SIGSEGV};
+
+    #[test]
+    fn status_round_trips() {
+        for status in [ExitStatus::Exited(0), ExitStatus::Exited(42), ExitStatus::Killed(SIGKILL)] {
+            assert_eq!(ExitStatus::decode(status.encode()), status);
+        }
+        assert_eq!(ExitStatus::Exited(3).encode(), 0x300);
+        assert_eq!(ExitStatus::Killed(SIGSEGV).encode(), 11);
+        // Comme sous Unix, exit(-1) donne le code 255
+        assert_eq!(ExitStatus::decode(ExitStatus::Exited(-1).encode()), ExitStatus::Exited(255));
+    }
+
+    #[test]
+    fn displays_status() {
+        assert!(ExitStatus::Exited(0).success());
+        assert!(!ExitStatus::Killed(SIGINT).success());
+        assert_eq!(format!("{}", ExitStatus::Exited(1)), "code 1");
+        assert_eq!(format!("{}", ExitStatus::Killed(SIGINT)), "tue par SIGINT");
+        assert_eq!(format!("{}", ExitStatus::Killed(30)), "tue par le signal 30");
+    }
+}
@@ -0,0 +1,170 @@
+// ===================================================================
+// FILE CIRCULAIRE DE TAILLE FIXE
+// ===================================================================
+//
+// File FIFO de `N` éléments stockée dans un tableau : aucune allocation,
+// elle peut donc être remplie depuis un handler d'interruption (scancodes
+// du clavier, identifiants des tâches à réveiller).
+//
+//   [ . . a b c d . . ]      start = 2, len = 4
+//         ▲       ▲
+//         pop     push
+//
+// La file ne se synchronise pas elle-même : côté kernel, on y accède
+// interruptions désactivées.
+
+use core::mem::MaybeUninit;
+
+/// File circulaire de capacité `N`
+pub struct RingBuffer<T: Copy, const N: usize> {
+    items: [MaybeUninit<T>; N],
+    /// Indice du plus ancien élément
+    start: usize,
+    /// Nombre d'éléments dans la file
+    len: usize,
+}
+
+impl<T: Copy, const N: usize> RingBuffer<T, N> {
+    /// Crée une file vide
+    pub const fn new() -> RingBuffer<T, N> {
+        RingBuffer {
+            items: [const { MaybeUninit::uninit() }; N],
+            start: 0,
+            len: 0,
+        }
+    }
+
+    /// Capacité de la file
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Nombre d'éléments dans la file
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Indique si la file est vide
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Indique si la file est pleine
+    pub fn is_full(&self) -> bool {
+        self.len == N
+    }
+
+    /// Ajoute un élément à la fin, ou le rend si la file est pleine
+    pub fn push(&mut self, item: T) -> Result<(), T> {
+        if self.is_full() {
+            return Err(item);
+        }
+        self.items[(self.start + self.len) % N] = MaybeUninit::new(item);
+        self.len += 1;
+        Ok(())
+    }
+
+    /// Retire le plus ancien élément
+    pub fn pop(&mut self) -> Option<T> {
+        if self.is_empty() {
+            return None;
+        }
+        // L'élément a été écrit par `push` et n'a pas encore été retiré
+        let item = unsafe { self.items[self.start].assume_init() };
+        self.start = (self.start + 1) % N;
+        self.len -= 1;
+        Some(item)
+    }
+
+    /// Ne garde que les éléments pour lesquels `keep` est vrai, dans l'ordre
+    pub fn retain(&mut self, mut keep: impl FnMut(&T) -> bool) {
+        let len = self.len;
+        let mut kept = 0;
+        for i in 0..len {
+            let item = unsafe { self.items[(self.start + i) % N].assume_init() };
+            if keep(&item) {
+                self.items[(self.start + kept) % N] = MaybeUninit::new(item);
+                kept += 1;
+            }
+        }
+        self.len = kept;
+    }
+
+    /// Vide la file
+    pub fn clear(&mut self) {
+        self.start = 0;
+        self.len = 0;
+    }
+}
+
+impl<T: Copy, const N: usize> Default for RingBuffer<T, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pops_in_push_order() {
+        let mut ring: RingBuffer<u8, 4> = RingBuffer::new();
+        assert_eq!(ring.pop(), None);
+        for i in 1..=3 {
+            ring.push(i).unwrap();
+        }
+        assert_eq!(ring.len(), 3);
+        assert_eq!(ring.pop(), Some(1));
+        assert_eq!(ring.pop(), Some(2));
+        assert_eq!(ring.pop(), Some(3));
+        assert!(ring.is_empty());
+    }
+
+    #[test]
+    fn refuses_items_when_full() {
+        let mut ring: RingBuffer<u32, 2> = RingBuffer::new();
+        ring.push(10).unwrap();
+        ring.push(20).unwrap();
+        assert!(ring.is_full());
+        assert_eq!(ring.push(30), Err(30));
+        assert_eq!(ring.pop(), Some(10));
+        ring.push(30).unwrap();
+        assert_eq!(ring.pop(), Some(20));
+        assert_eq!(ring.pop(), Some(30));
+    }
+
+    #[test]
+    fn wraps_around_many_times() {
+        let mut ring: RingBuffer<usize, 3> = RingBuffer::new();
+        for i in 0..100 {
+            ring.push(i).unwrap();
+            ring.push(i + 1000).unwrap();
+            assert_eq!(ring.pop(), Some(i));
+            assert_eq!(ring.pop(), Some(i + 1000));
+        }
+        ring.push(1).unwrap();
+        ring.clear();
+        assert_eq!(ring.pop(), None);
+    }
+
+    #[test]
+    fn retain_keeps_order() {
+        let mut ring: RingBuffer<u8, 4> = RingBuffer::new();
+        // Éléments à cheval sur la fin du tableau
+        ring.push(0).unwrap();
+        ring.push(0).unwrap();
+        ring.pop();
+        ring.pop();
+        for i in 1..=4 {
+            ring.push(i).unwrap();
+        }
+        ring.retain(|&i| i != 2);
+        assert_eq!(ring.len(), 3);
+        ring.push(5).unwrap();
+        assert_eq!(ring.pop(), Some(1));
+        assert_eq!(ring.pop(), Some(3));
+        assert_eq!(ring.pop(), Some(4));
+        assert_eq!(ring.pop(), Some(5));
+    }
+}
@@ -0,0 +1,207 @@
+// ===================================================================
+// HORLOGE TEMPS RÉEL (RTC) : DÉCODAGE DES REGISTRES CMOS
+// ===================================================================
+//
+// La RTC du PC donne la date et l'heure dans des registres de la CMOS
+// (lus par src/rtc.rs). Selon le registre d'état B, les valeurs sont
+// en BCD ou en binaire, et l'heure sur 12 ou 24 heures (bit 7 : après
+// midi). L'année n'a que deux chiffres : BOS la place après 2000.
+//
+// Les dates sont converties en secondes depuis le 1er janvier 1970
+// (UTC), comme les dates des fichiers.
+
+use core::fmt;
+
+/// Registre des secondes
+pub const REG_SECONDS: u8 = 0x00;
+/// Registre des minutes
+pub const REG_MINUTES: u8 = 0x02;
+/// Registre des heures
+pub const REG_HOURS: u8 = 0x04;
+/// Registre du jour du mois
+pub const REG_DAY: u8 = 0x07;
+/// Registre du mois
+pub const REG_MONTH: u8 = 0x08;
+/// Registre de l'année (deux chiffres)
+pub const REG_YEAR: u8 = 0x09;
+/// Registre d'état A
+pub const REG_STATUS_A: u8 = 0x0A;
+/// Registre d'état B
+pub const REG_STATUS_B: u8 = 0x0B;
+
+/// État A : mise à jour de l'horloge en cours, registres instables
+pub const STATUS_A_UPDATING: u8 = 0x80;
+/// État B : heures sur 24 heures
+pub const STATUS_B_24_HOURS: u8 = 0x02;
+/// État B : valeurs en binaire (sinon en BCD)
+pub const STATUS_B_BINARY: u8 = 0x04;
+
+/// Bit "après midi" du registre des heures, en mode 12 heures
+const HOURS_PM: u8 = 0x80;
+
+/// Registres de la date, dans l'ordre de `RTC_REGISTERS`
+pub type RawTime = [u8; 6];
+
+/// Registres à lire pour une date : secondes, minutes, heures, jour,
+/// mois, année
+pub const RTC_REGISTERS: [u8; 6] = [REG_SECONDS, REG_MINUTES, REG_HOURS, REG_DAY, REG_MONTH, REG_YEAR];
+
+/// Une date et une heure (UTC)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DateTime {
+    pub year: u32,
+    pub month: u8,
+    pub day: u8,
+    pub hour: u8,
+    pub minute: u8,
+    pub second: u8,
+}
+
+/// Valeur binaire d'un octet BCD (0x59 → 59)
+pub fn from_bcd(value: u8) -> u8 {
+    (value >> 4) * 10 + (value & 0x0F)
+}
+
+/// Nombre de jours entre le 1er janvier 1970 et une date
+fn days_from_civil(year: u32, month: u8, day: u8) -> i64 {
+    // Les années commencent en mars : le 29 février est le dernier jour
+    let year = year as i64 - (month <= 2) as i64;
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let month = month as i64;
+    let day_of_year = (153 * (month + if month > 2 { -3 } else { 9 }) + 2) / 5 + day as i64 - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// Nombre de jours du mois
+fn days_in_month(year: u32, month: u8) -> u8 {
+    match month {
+        2 if year.is_multiple_of(4) && (!year.is_multiple_of(100) || year.is_multiple_of(400)) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+impl DateTime {
+    /// Décode les registres de la RTC selon le registre d'état B
+    ///
+    /// `None` si une valeur est hors limites (lecture pendant une mise
+    /// à jour, horloge non réglée).
+    pub fn decode(raw: RawTime, status_b: u8) -> Option<DateTime> {
+        let binary = status_b & STATUS_B_BINARY != 0;
+        let value = |byte: u8| if binary { byte } else { from_bcd(byte) };
+        let [second, minute, hours, day, month, year] = raw;
+        let mut hour = value(hours & !HOURS_PM);
+        if status_b & STATUS_B_24_HOURS == 0 {
+            // 12 heures : 12 AM est minuit, 12 PM midi
+            hour %= 12;
+            if hours & HOURS_PM != 0 {
+                hour += 12;
+            }
+        }
+        let time = DateTime {
+            year: 2000 + value(year) as u32,
+            month: value(month),
+            day: value(day),
+            hour,
+            minute: value(minute),
+            second: value(second),
+        };
+        time.is_valid().then_some(time)
+    }
+
+    /// Les champs forment-ils une date existante ?
+    pub fn is_valid(&self) -> bool {
+        (1..=12).contains(&self.month)
+            && self.day >= 1
+            && self.day <= days_in_month(self.year, self.month)
+            && self.hour < 24
+            && self.minute < 60
+            && self.second < 60
+    }
+
+    /// Secondes depuis le 1er janvier 1970
+    pub fn unix_time(&self) -> u64 {
+        let days = days_from_civil(self.year, self.month, self.day).max(0) as u64;
+        days * 86_400 + self.hour as u64 * 3600 + self.minute as u64 * 60 + self.second as u64
+    }
+
+    /// Date correspondant à `seconds` secondes depuis le 1er janvier 1970
+    pub fn from_unix(seconds: u64) -> DateTime {
+        let days = (seconds / 86_400) as i64 + 719_468;
+        let seconds_of_day = seconds % 86_400;
+        let era = days.div_euclid(146_097);
+        let day_of_era = days - era * 146_097;
+        let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let month_index = (5 * day_of_year + 2) / 153;
+        let day = (day_of_year - (153 * month_index + 2) / 5 + 1) as u8;
+        let month = if month_index < 10 { month_index + 3 } else { month_index - 9 } as u8;
+        let year = (year_of_era + era * 400 + (month <= 2) as i64) as u32;
+        DateTime {
+            year,
+            month,
+            day,
+            hour: (seconds_of_day / 3600) as u8,
+            minute: (seconds_of_day / 60 % 60) as u8,
+            second: (seconds_of_day % 60) as u8,
+        }
+    }
+}
+
+impl fmt::Display for DateTime {
+    /// Format "2024-03-01 14:05:09"
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{:04}-{:02}-{:02} {:02}:{:02}:{:02}",
+            self.year, self.month, self.day, self.hour, self.minute, self.second
+        )
+    }
+}
+
+// ===================================================================
+// TESTS
+// ===================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decodes_bcd_24_hours() {
+        let raw = [0x09, 0x05, 0x14, 0x01, 0x03, 0x24];
+        let time = DateTime::decode(raw, STATUS_B_24_HOURS).unwrap();
+        assert_eq!(time, DateTime { year: 2024, month: 3, day: 1, hour: 14, minute: 5, second: 9 });
+        assert_eq!(time.to_string(), "2024-03-01 14:05:09");
+    }
+
+    #[test]
+    fn decodes_binary_12_hours() {
+        let status = STATUS_B_BINARY;
+        assert_eq!(DateTime::decode([0, 0, 12, 1, 1, 30], status).unwrap().hour, 0);
+        assert_eq!(DateTime::decode([0, 0, 12 | 0x80, 1, 1, 30], status).unwrap().hour, 12);
+        assert_eq!(DateTime::decode([0, 0, 3 | 0x80, 1, 1, 30], status).unwrap().hour, 15);
+    }
+
+    #[test]
+    fn rejects_invalid_dates() {
+        assert_eq!(DateTime::decode([0, 0, 0, 0x30, 0x02, 0x24], STATUS_B_24_HOURS), None);
+        assert_eq!(DateTime::decode([0x60, 0, 0, 1, 1, 0x24], STATUS_B_24_HOURS), None);
+        assert!(DateTime::decode([0, 0, 0, 0x29, 0x02, 0x24], STATUS_B_24_HOURS).is_some());
+    }
+
+    #[test]
+    fn converts_unix_time() {
+        let epoch = DateTime { year: 1970, month: 1, day: 1, hour: 0, minute: 0, second: 0 };
+        assert_eq!(epoch.unix_time(), 0);
+        let time = DateTime { year: 2024, month: 3, day: 1, hour: 14, minute: 5, second: 9 };
+        assert_eq!(time.unix_time(), 1_709_301_909);
+        assert_eq!(DateTime::from_unix(1_709_301_909), time);
+        assert_eq!(DateTime::from_unix(0), epoch);
+        let leap = DateTime { year: 2000, month: 2, day: 29, hour: 23, minute: 59, second: 59 };
+        assert_eq!(DateTime::from_unix(leap.unix_time()), leap);
+    }
+}
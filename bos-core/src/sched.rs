@@ -0,0 +1,359 @@
+// ===================================================================
+// ORDONNANCEUR DES THREADS DU KERNEL (TOURNIQUET)
+// ===================================================================
+//
+// L'ordonnanceur ne connaît que l'état des threads : le kernel garde à
+// côté leur pile et leur contexte, et fait le changement de contexte
+// quand `schedule` lui indique quel thread doit prendre la main.
+//
+// Les threads prêts attendent dans une file FIFO (tourniquet, ou
+// "round-robin") :
+//
+//   actif : A      file : [B, C]
+//   schedule() →   actif : B      file : [C, A]
+//
+// - Un thread actif rend la main à la fin de sa tranche de temps
+//   (`tick`), ou volontairement (yield, sleep, blocage, fin)
+// - Un thread endormi revient dans la file quand son échéance passe
+// - Le thread "idle" n'est jamais dans la file : il ne tourne que
+//   quand aucun autre thread n'est prêt
+
+use core::fmt;
+
+use crate::ring::RingBuffer;
+
+/// État d'un thread
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ThreadState {
+    /// Dans la file, attend son tour
+    Ready,
+    /// En cours d'exécution
+    Running,
+    /// Endormi jusqu'au tick `until`
+    Sleeping { until: u64 },
+    /// Attend un événement (réveillé par `unblock`)
+    Blocked,
+    /// Terminé, sa pile peut être libérée
+    Exited,
+}
+
+impl ThreadState {
+    /// Nom affiché par `ps`
+    pub fn name(self) -> &'static str {
+        match self {
+            ThreadState::Ready => "pret",
+            ThreadState::Running => "actif",
+            ThreadState::Sleeping { .. } => "endormi",
+            ThreadState::Blocked => "bloque",
+            ThreadState::Exited => "termine",
+        }
+    }
+}
+
+impl fmt::Display for ThreadState {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.pad(self.name())
+    }
+}
+
+/// Informations sur un thread
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ThreadInfo {
+    /// Identifiant unique (jamais réutilisé)
+    pub id: u64,
+    pub name: &'static str,
+    pub state: ThreadState,
+    /// Ticks du timer passés à s'exécuter
+    pub ticks: u64,
+    /// Nombre de fois où le thread a pris la main
+    pub switches: u64,
+}
+
+/// Ordonnanceur de `N` threads au plus
+pub struct Scheduler<const N: usize> {
+    threads: [Option<ThreadInfo>; N],
+    ready: RingBuffer<usize, N>,
+    /// Emplacement du thread actif
+    current: usize,
+    /// Emplacement du thread idle
+    idle: Option<usize>,
+    next_id: u64,
+    /// Ticks consommés de la tranche de temps actuelle
+    slice_used: u64,
+}
+
+impl<const N: usize> Scheduler<N> {
+    /// Ordonnanceur sans thread
+    pub const fn new() -> Scheduler<N> {
+        Scheduler {
+            threads: [None; N],
+            ready: RingBuffer::new(),
+            current: 0,
+            idle: None,
+            next_id: 0,
+            slice_used: 0,
+        }
+    }
+
+    /// Réserve un emplacement libre pour un nouveau thread
+    fn insert(&mut self, name: &'static str, state: ThreadState) -> Option<usize> {
+        let slot = self.threads.iter().position(Option::is_none)?;
+        self.threads[slot] = Some(ThreadInfo {
+            id: self.next_id,
+            name,
+            state,
+            ticks: 0,
+            switches: 0,
+        });
+        self.next_id += 1;
+        Some(slot)
+    }
+
+    /// Ajoute un thread prêt, retourne son emplacement (None si la table est pleine)
+    pub fn add(&mut self, name: &'static str) -> Option<usize> {
+        let slot = self.insert(name, ThreadState::Ready)?;
+        // Au plus N threads, donc au plus N entrées dans la file
+        let _ = self.ready.push(slot);
+        Some(slot)
+    }
+
+    /// Enregistre le code qui s'exécute déjà (le démarrage du kernel)
+    /// comme thread actif
+    pub fn add_running(&mut self, name: &'static str) -> Option<usize> {
+        let slot = self.insert(name, ThreadState::Running)?;
+        self.current = slot;
+        self.slice_used = 0;
+        Some(slot)
+    }
+
+    /// Ajoute le thread idle, exécuté seulement quand la file est vide
+    pub fn add_idle(&mut self, name: &'static str) -> Option<usize> {
+        let slot = self.insert(name, ThreadState::Ready)?;
+        self.idle = Some(slot);
+        Some(slot)
+    }
+
+    /// Emplacement du thread actif
+    pub fn current(&self) -> usize {
+        self.current
+    }
+
+    /// Informations sur le thread d'un emplacement
+    pub fn get(&self, slot: usize) -> Option<&ThreadInfo> {
+        self.threads.get(slot)?.as_ref()
+    }
+
+    /// Emplacement du thread d'identifiant `id`
+    pub fn find(&self, id: u64) -> Option<usize> {
+        self.iter().find(|(_, info)| info.id == id).map(|(slot, _)| slot)
+    }
+
+    /// Parcourt les threads (emplacement, informations)
+    pub fn iter(&self) -> impl Iterator<Item = (usize, &ThreadInfo)> {
+        self.threads
+            .iter()
+            .enumerate()
+            .filter_map(|(slot, info)| Some((slot, info.as_ref()?)))
+    }
+
+    fn state(&self, slot: usize) -> Option<ThreadState> {
+        Some(self.get(slot)?.state)
+    }
+
+    fn set_state(&mut self, slot: usize, state: ThreadState) {
+        if let Some(info) = self.threads[slot].as_mut() {
+            info.state = state;
+        }
+    }
+
+    /// Remet un thread dans la file
+    fn make_ready(&mut self, slot: usize) {
+        self.set_state(slot, ThreadState::Ready);
+        if Some(slot) != self.idle {
+            let _ = self.ready.push(slot);
+        }
+    }
+
+    /// Tick du timer : compte le temps du thread actif et réveille les
+    /// threads endormis dont l'échéance est passée
+    ///
+    /// Retourne true si le thread actif doit céder la place : tranche
+    /// de `slice` ticks épuisée, ou thread idle alors qu'un autre est prêt.
+    pub fn tick(&mut self, now: u64, slice: u64) -> bool {
+        if let Some(info) = self.threads[self.current].as_mut() {
+            info.ticks += 1;
+        }
+        self.slice_used += 1;
+        for slot in 0..N {
+            if let Some(ThreadState::Sleeping { until }) = self.state(slot) {
+                if until <= now {
+                    self.make_ready(slot);
+                }
+            }
+        }
+        !self.ready.is_empty() && (self.slice_used >= slice || Some(self.current) == self.idle)
+    }
+
+    /// Endort le thread actif jusqu'au tick `until` (appeler `schedule` ensuite)
+    pub fn sleep_current(&mut self, until: u64) {
+        self.set_state(self.current, ThreadState::Sleeping { until });
+    }
+
+    /// Bloque le thread actif (appeler `schedule` ensuite)
+    pub fn block_current(&mut self) {
+        self.set_state(self.current, ThreadState::Blocked);
+    }
+
+    /// Marque le thread actif comme terminé (appeler `schedule` ensuite)
+    pub fn exit_current(&mut self) {
+        self.set_state(self.current, ThreadState::Exited);
+    }
+
+    /// Réveille un thread bloqué ou endormi
+    ///
+    /// Retourne false si le thread n'attendait pas.
+    pub fn unblock(&mut self, slot: usize) -> bool {
+        match self.state(slot) {
+            Some(ThreadState::Blocked | ThreadState::Sleeping { .. }) => {
+                self.make_ready(slot);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Choisit le prochain thread
+    ///
+    /// Retourne `Some((ancien, nouveau))` s'il faut changer de contexte,
+    /// None si le thread actif continue. Le thread actif, s'il peut encore
+    /// s'exécuter, passe en fin de file.
+    pub fn schedule(&mut self) -> Option<(usize, usize)> {
+        let current = self.current;
+        let runnable = self.state(current) == Some(ThreadState::Running);
+        let next = match self.ready.pop() {
+            Some(slot) => slot,
+            None if runnable => {
+                self.slice_used = 0;
+                return None;
+            }
+            // Personne d'autre : le thread idle (il doit exister)
+            None => self.idle.expect("aucun thread pret et pas de thread idle"),
+        };
+        if next == current {
+            self.set_state(current, ThreadState::Running);
+            self.slice_used = 0;
+            return None;
+        }
+        if runnable {
+            self.make_ready(current);
+        }
+        self.set_state(next, ThreadState::Running);
+        if let Some(info) = self.threads[next].as_mut() {
+            info.switches += 1;
+        }
+        self.current = next;
+        self.slice_used = 0;
+        Some((current, next))
+    }
+
+    /// Retire un thread terminé (qui n'est pas le thread actif)
+    pub fn remove(&mut self, slot: usize) -> Option<ThreadInfo> {
+        if slot == self.current || self.state(slot) != Some(ThreadState::Exited) {
+            return None;
+        }
+        self.threads[slot].take()
+    }
+}
+
+impl<const N: usize> Default for Scheduler<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scheduler() -> Scheduler<4> {
+        let mut sched = Scheduler::new();
+        assert_eq!(sched.add_running("kernel"), Some(0));
+        assert_eq!(sched.add_idle("idle"), Some(1));
+        sched
+    }
+
+    #[test]
+    fn round_robin_order() {
+        let mut sched = scheduler();
+        let a = sched.add("a").unwrap();
+        let b = sched.add("b").unwrap();
+        assert_eq!(sched.add("plein"), None);
+        assert_eq!(sched.schedule(), Some((0, a)));
+        assert_eq!(sched.schedule(), Some((a, b)));
+        assert_eq!(sched.schedule(), Some((b, 0)));
+        assert_eq!(sched.schedule(), Some((0, a)));
+        assert_eq!(sched.get(a).unwrap().switches, 2);
+        assert_eq!(sched.get(b).unwrap().state, ThreadState::Ready);
+    }
+
+    #[test]
+    fn keeps_running_when_alone() {
+        let mut sched = scheduler();
+        assert_eq!(sched.schedule(), None);
+        assert_eq!(sched.get(0).unwrap().state, ThreadState::Running);
+        // Seul thread prêt : pas de préemption
+        assert!(!sched.tick(1, 1));
+    }
+
+    #[test]
+    fn preempts_after_time_slice() {
+        let mut sched = scheduler();
+        sched.add("a").unwrap();
+        assert!(!sched.tick(1, 3));
+        assert!(!sched.tick(2, 3));
+        assert!(sched.tick(3, 3));
+        assert_eq!(sched.get(0).unwrap().ticks, 3);
+    }
+
+    #[test]
+    fn sleeping_threads_wake_up() {
+        let mut sched = scheduler();
+        sched.sleep_current(10);
+        // Rien d'autre à faire : le thread idle prend la main
+        assert_eq!(sched.schedule(), Some((0, 1)));
+        assert!(!sched.tick(9, 5));
+        assert_eq!(sched.get(0).unwrap().state.name(), "endormi");
+        // Échéance passée : idle cède la place tout de suite
+        assert!(sched.tick(10, 5));
+        assert_eq!(sched.schedule(), Some((1, 0)));
+        assert_eq!(sched.get(1).unwrap().state, ThreadState::Ready);
+    }
+
+    #[test]
+    fn blocked_threads_wait_for_unblock() {
+        let mut sched = scheduler();
+        let a = sched.add("a").unwrap();
+        sched.block_current();
+        assert_eq!(sched.schedule(), Some((0, a)));
+        assert_eq!(sched.schedule(), None);
+        assert!(!sched.unblock(a));
+        assert!(sched.unblock(0));
+        assert_eq!(sched.schedule(), Some((a, 0)));
+    }
+
+    #[test]
+    fn exited_threads_are_removed() {
+        let mut sched = scheduler();
+        let a = sched.add("a").unwrap();
+        assert_eq!(sched.schedule(), Some((0, a)));
+        assert_eq!(sched.remove(a), None);
+        sched.exit_current();
+        assert_eq!(sched.schedule(), Some((a, 0)));
+        let info = sched.remove(a).unwrap();
+        assert_eq!((info.id, info.state), (2, ThreadState::Exited));
+        assert_eq!(sched.find(2), None);
+        // L'emplacement est réutilisé, pas l'identifiant
+        assert_eq!(sched.add("b"), Some(a));
+        assert_eq!(sched.get(a).unwrap().id, 3);
+    }
+}
@@ -0,0 +1,682 @@
+// ===================================================================
+// SIGNAUX : NUMÉROS, MASQUES, ACTIONS ET TRAME DE SIGNAL
+// ===================================================================
+//
+// Partagé entre le kernel et les programmes utilisateur (libbos).
+//
+// Un signal est une notification envoyée à un processus : kill,
+// Ctrl+C (SIGINT), Ctrl+Z (SIGTSTP), une faute du programme
+// (SIGSEGV, SIGFPE...), une alarme (SIGALRM). Numéros de Linux.
+//
+// Chaque processus a :
+// - des signaux en attente (`pending`), remis un par un, du plus
+//   petit numéro au plus grand, quand il va revenir en ring 3
+// - un masque de signaux bloqués (`blocked`) : ils restent en
+//   attente jusqu'à ce qu'il les débloque (SIGKILL et SIGSTOP ne
+//   peuvent pas être bloqués)
+// - une action par signal : celle par défaut (terminer, terminer
+//   avec un rapport, ignorer, arrêter, continuer), ignorer, ou un
+//   handler installé par le programme (sigaction)
+//
+// Pour appeler un handler, le kernel empile une `SignalFrame` sous la
+// pile du programme : tous ses registres et le masque à rétablir. Le
+// handler est appelé avec le numéro du signal dans RDI et retourne
+// vers le `restorer` (fourni par libbos), qui fait l'appel système
+// sigreturn avec l'adresse de la trame pour reprendre là où le
+// programme a été interrompu.
+
+use core::mem::size_of;
+
+/// Fin de connexion du terminal
+pub const SIGHUP: u8 = 1;
+/// Interruption depuis le clavier (Ctrl+C)
+pub const SIGINT: u8 = 2;
+/// Demande de fin avec rapport
+pub const SIGQUIT: u8 = 3;
+/// Instruction invalide
+pub const SIGILL: u8 = 4;
+/// Point d'arrêt (INT3)
+pub const SIGTRAP: u8 = 5;
+/// Arrêt demandé par le programme lui-même (abort)
+pub const SIGABRT: u8 = 6;
+/// Erreur de bus
+pub const SIGBUS: u8 = 7;
+/// Erreur arithmétique (division par zéro)
+pub const SIGFPE: u8 = 8;
+/// Arrêt forcé (kill -9), ne peut être ni bloqué ni intercepté
+pub const SIGKILL: u8 = 9;
+/// Signal libre pour les programmes
+pub const SIGUSR1: u8 = 10;
+/// Accès mémoire invalide
+pub const SIGSEGV: u8 = 11;
+/// Signal libre pour les programmes
+pub const SIGUSR2: u8 = 12;
+/// Écriture dans un tube sans lecteur
+pub const SIGPIPE: u8 = 13;
+/// Fin d'une alarme (appel système alarm)
+pub const SIGALRM: u8 = 14;
+/// Demande de fin (signal par défaut de kill)
+pub const SIGTERM: u8 = 15;
+/// Un enfant s'est terminé ou arrêté
+pub const SIGCHLD: u8 = 17;
+/// Reprise d'un processus arrêté
+pub const SIGCONT: u8 = 18;
+/// Arrêt forcé, ne peut être ni bloqué ni intercepté
+pub const SIGSTOP: u8 = 19;
+/// Arrêt depuis le clavier (Ctrl+Z)
+pub const SIGTSTP: u8 = 20;
+
+/// Plus grand numéro de signal
+pub const MAX_SIGNAL: u8 = 64;
+
+/// Handler de sigaction : action par défaut
+pub const SIG_DFL: u64 = 0;
+/// Handler de sigaction : ignorer le signal
+pub const SIG_IGN: u64 = 1;
+
+/// Opération de sigprocmask : bloquer les signaux de l'ensemble
+pub const SIG_BLOCK: u64 = 0;
+/// Opération de sigprocmask : débloquer les signaux de l'ensemble
+pub const SIG_UNBLOCK: u64 = 1;
+/// Opération de sigprocmask : remplacer le masque
+pub const SIG_SETMASK: u64 = 2;
+
+/// Zone sous RSP que le code d'une fonction peut utiliser sans
+/// déplacer RSP (System V) : la trame de signal est placée en dessous
+pub const RED_ZONE: u64 = 128;
+
+/// Nom d'un signal
+pub fn signal_name(signal: u8) -> Option<&'static str> {
+    let name = match signal {
+        SIGHUP => "SIGHUP",
+        SIGINT => "SIGINT",
+        SIGQUIT => "SIGQUIT",
+        SIGILL => "SIGILL",
+        SIGTRAP => "SIGTRAP",
+        SIGABRT => "SIGABRT",
+        SIGBUS => "SIGBUS",
+        SIGFPE => "SIGFPE",
+        SIGKILL => "SIGKILL",
+        SIGUSR1 => "SIGUSR1",
+        SIGSEGV => "SIGSEGV",
+        SIGUSR2 => "SIGUSR2",
+        SIGPIPE => "SIGPIPE",
+        SIGALRM => "SIGALRM",
+        SIGTERM => "SIGTERM",
+        SIGCHLD => "SIGCHLD",
+        SIGCONT => "SIGCONT",
+        SIGSTOP => "SIGSTOP",
+        SIGTSTP => "SIGTSTP",
+        _ => return None,
+    };
+    Some(name)
+}
+
+/// Numéro d'un signal d'après son nom, avec ou sans "SIG" ("TERM",
+/// "SIGKILL"), ou son numéro ("9")
+pub fn parse_signal(text: &str) -> Option<u8> {
+    if let Ok(signal) = text.parse::<u8>() {
+        return (signal <= MAX_SIGNAL).then_some(signal);
+    }
+    let name = text.strip_prefix("SIG").unwrap_or(text);
+    (1..=MAX_SIGNAL).find(|&signal| signal_name(signal).is_some_and(|full| &full[3..] == name))
+}
+
+/// Indique si `signal` est un numéro de signal valide (1 à 64)
+pub fn is_valid(signal: u8) -> bool {
+    (1..=MAX_SIGNAL).contains(&signal)
+}
+
+/// Ensemble de signaux : le bit `n - 1` représente le signal `n`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SigSet(pub u64);
+
+impl SigSet {
+    /// Ensemble vide
+    pub const fn empty() -> SigSet {
+        SigSet(0)
+    }
+
+    /// Ensemble d'un seul signal
+    pub const fn of(signal: u8) -> SigSet {
+        SigSet(1 << (signal - 1))
+    }
+
+    /// Signaux qui ne peuvent être ni bloqués ni interceptés
+    pub const fn unblockable() -> SigSet {
+        SigSet(SigSet::of(SIGKILL).0 | SigSet::of(SIGSTOP).0)
+    }
+
+    pub fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    pub fn contains(self, signal: u8) -> bool {
+        is_valid(signal) && self.0 & SigSet::of(signal).0 != 0
+    }
+
+    pub fn insert(&mut self, signal: u8) {
+        self.0 |= SigSet::of(signal).0;
+    }
+
+    pub fn remove(&mut self, signal: u8) {
+        self.0 &= !SigSet::of(signal).0;
+    }
+
+    pub fn union(self, other: SigSet) -> SigSet {
+        SigSet(self.0 | other.0)
+    }
+
+    pub fn difference(self, other: SigSet) -> SigSet {
+        SigSet(self.0 & !other.0)
+    }
+
+    /// Plus petit signal de l'ensemble
+    pub fn first(self) -> Option<u8> {
+        (!self.is_empty()).then(|| self.0.trailing_zeros() as u8 + 1)
+    }
+}
+
+/// Action par défaut d'un signal
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DefaultAction {
+    /// Terminer le processus
+    Terminate,
+    /// Terminer le processus avec un rapport (registres, adresse
+    /// fautive) : l'équivalent du core dump
+    Core,
+    /// Ne rien faire
+    Ignore,
+    /// Arrêter le processus jusqu'à SIGCONT
+    Stop,
+    /// Reprendre le processus s'il est arrêté
+    Continue,
+}
+
+/// Action par défaut du signal `signal` (POSIX)
+pub fn default_action(signal: u8) -> DefaultAction {
+    match signal {
+        SIGQUIT | SIGILL | SIGTRAP | SIGABRT | SIGBUS | SIGFPE | SIGSEGV => DefaultAction::Core,
+        SIGCHLD => DefaultAction::Ignore,
+        SIGCONT => DefaultAction::Continue,
+        SIGSTOP | SIGTSTP => DefaultAction::Stop,
+        _ => DefaultAction::Terminate,
+    }
+}
+
+/// Description d'une action passée à l'appel système sigaction
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SigAction {
+    /// Adresse du handler, ou SIG_DFL, ou SIG_IGN
+    pub handler: u64,
+    /// Signaux bloqués en plus pendant le handler (le signal reçu
+    /// l'est toujours)
+    pub mask: u64,
+    /// Adresse où le handler retourne : elle doit faire sigreturn
+    pub restorer: u64,
+}
+
+/// Ce qu'un processus fait d'un signal
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    /// Action par défaut (voir `default_action`)
+    Default,
+    /// Ignorer le signal
+    Ignore,
+    /// Appeler un handler du programme
+    Handler { handler: u64, mask: SigSet, restorer: u64 },
+}
+
+impl Action {
+    /// Action décrite par `raw` (None si un handler n'a pas de restorer)
+    pub fn from_raw(raw: SigAction) -> Option<Action> {
+        let action = match raw.handler {
+            SIG_DFL => Action::Default,
+            SIG_IGN => Action::Ignore,
+            handler if raw.restorer != 0 => Action::Handler {
+                handler,
+                mask: SigSet(raw.mask).difference(SigSet::unblockable()),
+                restorer: raw.restorer,
+            },
+            _ => return None,
+        };
+        Some(action)
+    }
+
+    /// Description de l'action pour sigaction
+    pub fn to_raw(self) -> SigAction {
+        match self {
+            Action::Default => SigAction { handler: SIG_DFL, ..SigAction::default() },
+            Action::Ignore => SigAction { handler: SIG_IGN, ..SigAction::default() },
+            Action::Handler { handler, mask, restorer } => SigAction { handler, mask: mask.0, restorer },
+        }
+    }
+}
+
+/// Ce que le kernel doit faire du prochain signal (voir `Signals::take_next`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Delivery {
+    /// Terminer le processus
+    Terminate(u8),
+    /// Terminer le processus avec un rapport
+    Core(u8),
+    /// Arrêter le processus
+    Stop(u8),
+    /// Appeler le handler ; `blocked` est le masque à rétablir au retour
+    Handler { signal: u8, handler: u64, restorer: u64, blocked: SigSet },
+}
+
+/// Erreur de `Signals::set_action`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InvalidSignal;
+
+/// État des signaux d'un processus
+#[derive(Debug, Clone)]
+pub struct Signals {
+    pending: SigSet,
+    blocked: SigSet,
+    /// Action du signal `n` à l'index `n - 1`
+    actions: [Action; MAX_SIGNAL as usize],
+}
+
+impl Default for Signals {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Signals {
+    /// Aucun signal en attente ni bloqué, actions par défaut
+    pub const fn new() -> Signals {
+        Signals {
+            pending: SigSet::empty(),
+            blocked: SigSet::empty(),
+            actions: [Action::Default; MAX_SIGNAL as usize],
+        }
+    }
+
+    /// Signaux en attente
+    pub fn pending(&self) -> SigSet {
+        self.pending
+    }
+
+    /// Masque des signaux bloqués
+    pub fn blocked(&self) -> SigSet {
+        self.blocked
+    }
+
+    /// Action du signal `signal`
+    pub fn action(&self, signal: u8) -> Action {
+        self.actions[signal as usize - 1]
+    }
+
+    /// Change l'action du signal `signal`, retourne l'ancienne
+    ///
+    /// Un signal en attente qui devient ignoré est abandonné.
+    pub fn set_action(&mut self, signal: u8, action: Action) -> Result<Action, InvalidSignal> {
+        if !is_valid(signal) || SigSet::unblockable().contains(signal) {
+            return Err(InvalidSignal);
+        }
+        let old = core::mem::replace(&mut self.actions[signal as usize - 1], action);
+        if self.is_ignored(signal) {
+            self.pending.remove(signal);
+        }
+        Ok(old)
+    }
+
+    /// Change le masque des signaux bloqués (SIG_BLOCK, SIG_UNBLOCK ou
+    /// SIG_SETMASK), retourne l'ancien
+    pub fn set_blocked(&mut self, how: u64, set: SigSet) -> Result<SigSet, InvalidSignal> {
+        let old = self.blocked;
+        let blocked = match how {
+            SIG_BLOCK => old.union(set),
+            SIG_UNBLOCK => old.difference(set),
+            SIG_SETMASK => set,
+            _ => return Err(InvalidSignal),
+        };
+        self.blocked = blocked.difference(SigSet::unblockable());
+        Ok(old)
+    }
+
+    /// Indique si `signal` serait abandonné dès son envoi
+    fn is_ignored(&self, signal: u8) -> bool {
+        match self.action(signal) {
+            Action::Ignore => true,
+            Action::Default => default_action(signal) == DefaultAction::Ignore,
+            Action::Handler { .. } => false,
+        }
+    }
+
+    /// Met `signal` en attente
+    ///
+    /// Un signal ignoré est abandonné. SIGCONT annule les arrêts en
+    /// attente, et un arrêt annule un SIGCONT en attente. Retourne
+    /// false si le signal a été abandonné.
+    pub fn raise(&mut self, signal: u8) -> bool {
+        if signal == SIGCONT {
+            self.pending.remove(SIGSTOP);
+            self.pending.remove(SIGTSTP);
+        } else if default_action(signal) == DefaultAction::Stop {
+            self.pending.remove(SIGCONT);
+        }
+        if self.is_ignored(signal) && !SigSet::unblockable().contains(signal) {
+            return false;
+        }
+        self.pending.insert(signal);
+        true
+    }
+
+    /// Met en attente `signal`, levé par une faute du programme : s'il
+    /// est bloqué ou ignoré, il reprend son action par défaut (sinon le
+    /// programme recommencerait sans fin l'instruction fautive)
+    pub fn force(&mut self, signal: u8) {
+        if self.blocked.contains(signal) || self.action(signal) == Action::Ignore {
+            self.blocked.remove(signal);
+            self.actions[signal as usize - 1] = Action::Default;
+        }
+        self.pending.insert(signal);
+    }
+
+    /// Indique si un signal en attente n'est pas bloqué
+    pub fn has_deliverable(&self) -> bool {
+        !self.pending.difference(self.blocked).is_empty()
+    }
+
+    /// Retire le prochain signal à remettre et retourne ce qu'il faut en
+    /// faire (None s'il n'y en a pas)
+    ///
+    /// Pour un handler, le signal et le masque de l'action sont ajoutés
+    /// aux signaux bloqués ; `Delivery::Handler` donne le masque à
+    /// rétablir au retour du handler.
+    pub fn take_next(&mut self) -> Option<Delivery> {
+        while let Some(signal) = self.pending.difference(self.blocked).first() {
+            self.pending.remove(signal);
+            let delivery = match self.action(signal) {
+                Action::Ignore => continue,
+                Action::Handler { handler, mask, restorer } => {
+                    let blocked = self.blocked;
+                    self.blocked = blocked.union(mask).union(SigSet::of(signal));
+                    Delivery::Handler { signal, handler, restorer, blocked }
+                }
+                Action::Default => match default_action(signal) {
+                    DefaultAction::Terminate => Delivery::Terminate(signal),
+                    DefaultAction::Core => Delivery::Core(signal),
+                    DefaultAction::Stop => Delivery::Stop(signal),
+                    // La reprise a lieu dès l'envoi (voir le kernel)
+                    DefaultAction::Ignore | DefaultAction::Continue => continue,
+                },
+            };
+            return Some(delivery);
+        }
+        None
+    }
+
+    /// Rétablit le masque sauvegardé dans une trame de signal
+    pub fn restore_blocked(&mut self, blocked: SigSet) {
+        self.blocked = blocked.difference(SigSet::unblockable());
+    }
+}
+
+/// Registres d'un programme interrompu par un signal
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Registers {
+    pub rax: u64,
+    pub rbx: u64,
+    pub rcx: u64,
+    pub rdx: u64,
+    pub rsi: u64,
+    pub rdi: u64,
+    pub rbp: u64,
+    pub rsp: u64,
+    pub r8: u64,
+    pub r9: u64,
+    pub r10: u64,
+    pub r11: u64,
+    pub r12: u64,
+    pub r13: u64,
+    pub r14: u64,
+    pub r15: u64,
+    pub rip: u64,
+    pub rflags: u64,
+}
+
+/// Trame empilée par le kernel avant d'appeler un handler
+///
+/// Le handler la trouve à RSP : `restorer` est son adresse de retour.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SignalFrame {
+    pub restorer: u64,
+    pub signal: u64,
+    /// Masque des signaux bloqués à rétablir
+    pub blocked: u64,
+    pub registers: Registers,
+}
+
+impl SignalFrame {
+    /// Taille de la trame en octets
+    pub const SIZE: usize = size_of::<SignalFrame>();
+
+    /// Adresse de la trame pour un programme interrompu avec la pile
+    /// `rsp`
+    ///
+    /// Elle est placée sous la zone rouge, et alignée comme à l'entrée
+    /// d'une fonction : RSP + 8 multiple de 16, l'adresse de retour
+    /// venant d'être empilée.
+    pub fn address(rsp: u64) -> u64 {
+        (rsp.wrapping_sub(RED_ZONE + SignalFrame::SIZE as u64) & !15).wrapping_sub(8)
+    }
+
+    /// Octets de la trame, tels que copiés sur la pile du programme
+    pub fn to_bytes(&self) -> [u8; SignalFrame::SIZE] {
+        let mut bytes = [0u8; SignalFrame::SIZE];
+        for (chunk, word) in bytes.chunks_exact_mut(8).zip(self.words()) {
+            chunk.copy_from_slice(&word.to_le_bytes());
+        }
+        bytes
+    }
+
+    /// Trame lue sur la pile du programme
+    pub fn from_bytes(bytes: &[u8; SignalFrame::SIZE]) -> SignalFrame {
+        let mut words = bytes.chunks_exact(8).map(|chunk| u64::from_le_bytes(chunk.try_into().unwrap()));
+        let mut next = || words.next().unwrap();
+        SignalFrame {
+            restorer: next(),
+            signal: next(),
+            blocked: next(),
+            registers: Registers {
+                rax: next(),
+                rbx: next(),
+                rcx: next(),
+                rdx: next(),
+                rsi: next(),
+                rdi: next(),
+                rbp: next(),
+                rsp: next(),
+                r8: next(),
+                r9: next(),
+                r10: next(),
+                r11: next(),
+                r12: next(),
+                r13: next(),
+                r14: next(),
+                r15: next(),
+                rip: next(),
+                rflags: next(),
+            },
+        }
+    }
+
+    /// Champs dans l'ordre de la structure
+    fn words(&self) -> [u64; SignalFrame::SIZE / 8] {
+        let r = &self.registers;
+        [
+            self.restorer,
+            self.signal,
+            self.blocked,
+            r.rax,
+            r.rbx,
+            r.rcx,
+            r.rdx,
+            r.rsi,
+            r.rdi,
+            r.rbp,
+            r.rsp,
+            r.r8,
+            r.r9,
+            r.r10,
+            r.r11,
+            r.r12,
+            r.r13,
+            r.r14,
+            r.r15,
+            r.rip,
+            r.rflags,
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn names_and_parsing() {
+        assert_eq!(signal_name(SIGSEGV), Some("SIGSEGV"));
+        assert_eq!(signal_name(16), None);
+        assert_eq!(parse_signal("9"), Some(SIGKILL));
+        assert_eq!(parse_signal("TSTP"), Some(SIGTSTP));
+        assert_eq!(parse_signal("SIGCONT"), Some(SIGCONT));
+        assert_eq!(parse_signal("65"), None);
+        assert_eq!(parse_signal("FOO"), None);
+    }
+
+    #[test]
+    fn sets() {
+        let mut set = SigSet::empty();
+        assert_eq!(set.first(), None);
+        set.insert(SIGTERM);
+        set.insert(SIGINT);
+        set.insert(MAX_SIGNAL);
+        assert!(set.contains(SIGINT) && set.contains(MAX_SIGNAL));
+        assert!(!set.contains(SIGKILL) && !set.contains(0));
+        assert_eq!(set.first(), Some(SIGINT));
+        set.remove(SIGINT);
+        assert_eq!(set.first(), Some(SIGTERM));
+        assert_eq!(set.difference(SigSet::of(SIGTERM)).first(), Some(MAX_SIGNAL));
+    }
+
+    #[test]
+    fn default_actions() {
+        let mut signals = Signals::new();
+        signals.raise(SIGTERM);
+        signals.raise(SIGSEGV);
+        assert_eq!(signals.take_next(), Some(Delivery::Core(SIGSEGV)));
+        assert_eq!(signals.take_next(), Some(Delivery::Terminate(SIGTERM)));
+        assert_eq!(signals.take_next(), None);
+        // SIGCHLD est ignoré par défaut : abandonné dès l'envoi
+        assert!(!signals.raise(SIGCHLD));
+        assert!(signals.pending().is_empty());
+    }
+
+    #[test]
+    fn blocked_signals_wait() {
+        let mut signals = Signals::new();
+        let old = signals.set_blocked(SIG_BLOCK, SigSet::of(SIGINT).union(SigSet::of(SIGKILL)));
+        assert_eq!(old, Ok(SigSet::empty()));
+        // SIGKILL ne peut pas être bloqué
+        assert_eq!(signals.blocked(), SigSet::of(SIGINT));
+        signals.raise(SIGINT);
+        assert!(!signals.has_deliverable());
+        assert_eq!(signals.take_next(), None);
+        signals.set_blocked(SIG_UNBLOCK, SigSet::of(SIGINT)).unwrap();
+        assert!(signals.has_deliverable());
+        assert_eq!(signals.take_next(), Some(Delivery::Terminate(SIGINT)));
+        assert_eq!(signals.set_blocked(7, SigSet::empty()), Err(InvalidSignal));
+    }
+
+    #[test]
+    fn handlers_block_their_signal() {
+        let mut signals = Signals::new();
+        let action = Action::Handler { handler: 0x1000, mask: SigSet::of(SIGTERM), restorer: 0x2000 };
+        assert_eq!(signals.set_action(SIGINT, action), Ok(Action::Default));
+        assert_eq!(signals.set_action(SIGKILL, Action::Ignore), Err(InvalidSignal));
+        assert_eq!(signals.set_action(0, Action::Ignore), Err(InvalidSignal));
+        signals.raise(SIGINT);
+        let delivery = signals.take_next();
+        assert_eq!(
+            delivery,
+            Some(Delivery::Handler { signal: SIGINT, handler: 0x1000, restorer: 0x2000, blocked: SigSet::empty() })
+        );
+        // Pendant le handler, SIGINT et SIGTERM attendent
+        signals.raise(SIGINT);
+        signals.raise(SIGTERM);
+        assert_eq!(signals.take_next(), None);
+        signals.restore_blocked(SigSet::empty());
+        assert!(matches!(signals.take_next(), Some(Delivery::Handler { signal: SIGINT, .. })));
+    }
+
+    #[test]
+    fn ignoring_discards_pending() {
+        let mut signals = Signals::new();
+        signals.set_blocked(SIG_SETMASK, SigSet::of(SIGUSR1)).unwrap();
+        signals.raise(SIGUSR1);
+        signals.set_action(SIGUSR1, Action::Ignore).unwrap();
+        assert!(signals.pending().is_empty());
+        assert!(!signals.raise(SIGUSR1));
+    }
+
+    #[test]
+    fn stop_and_continue_cancel_each_other() {
+        let mut signals = Signals::new();
+        signals.set_blocked(SIG_SETMASK, SigSet::of(SIGTSTP).union(SigSet::of(SIGCONT))).unwrap();
+        signals.raise(SIGTSTP);
+        signals.raise(SIGCONT);
+        assert_eq!(signals.pending(), SigSet::of(SIGCONT));
+        signals.raise(SIGSTOP);
+        assert_eq!(signals.pending(), SigSet::of(SIGSTOP));
+        assert_eq!(signals.take_next(), Some(Delivery::Stop(SIGSTOP)));
+    }
+
+    #[test]
+    fn faults_override_blocking_and_ignoring() {
+        let mut signals = Signals::new();
+        signals.set_action(SIGSEGV, Action::Ignore).unwrap();
+        signals.set_blocked(SIG_BLOCK, SigSet::of(SIGFPE)).unwrap();
+        signals.force(SIGSEGV);
+        assert_eq!(signals.take_next(), Some(Delivery::Core(SIGSEGV)));
+        signals.force(SIGFPE);
+        assert_eq!(signals.take_next(), Some(Delivery::Core(SIGFPE)));
+        assert_eq!(signals.action(SIGSEGV), Action::Default);
+    }
+
+    #[test]
+    fn raw_actions() {
+        assert_eq!(Action::from_raw(SigAction::default()), Some(Action::Default));
+        assert_eq!(Action::from_raw(SigAction { handler: SIG_IGN, ..SigAction::default() }), Some(Action::Ignore));
+        // Un handler sans restorer ne pourrait pas revenir
+        assert_eq!(Action::from_raw(SigAction { handler: 0x1000, ..SigAction::default() }), None);
+        let raw = SigAction { handler: 0x1000, mask: SigSet::of(SIGKILL).union(SigSet::of(SIGINT)).0, restorer: 0x2000 };
+        let action = Action::from_raw(raw).unwrap();
+        assert_eq!(action.to_raw().mask, SigSet::of(SIGINT).0);
+    }
+
+    #[test]
+    fn frame_layout() {
+        assert_eq!(SignalFrame::SIZE, 21 * 8);
+        let rsp = 0x1000_0000_1000;
+        let address = SignalFrame::address(rsp);
+        // Alignement d'une entrée de fonction, sous la zone rouge
+        assert_eq!((address + 8) % 16, 0);
+        assert!(address + SignalFrame::SIZE as u64 <= rsp - RED_ZONE);
+        let mut frame = SignalFrame { restorer: 1, signal: 2, blocked: 3, ..SignalFrame::default() };
+        frame.registers.rax = 4;
+        frame.registers.rflags = 5;
+        let bytes = frame.to_bytes();
+        assert_eq!(&bytes[..8], &1u64.to_le_bytes());
+        assert_eq!(&bytes[24..32], &4u64.to_le_bytes());
+        assert_eq!(SignalFrame::from_bytes(&bytes), frame);
+    }
+}
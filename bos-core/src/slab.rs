@@ -0,0 +1,450 @@
+// ===================================================================
+// ALLOCATEUR SLAB (OBJETS DE TAILLE FIXE)
+// ===================================================================
+//
+// Un cache distribue des objets d'une seule taille. Il découpe des
+// pages de 4 Kio ("slabs") en cases de cette taille :
+//
+//   page : [en-tête][objet][objet][objet]...[objet]
+//
+// L'en-tête d'un slab chaîne ses cases libres ; un objet libéré
+// retrouve son slab en arrondissant son adresse à la page. Les slabs
+// sont rangés dans deux listes : "partiels" (au moins une case libre)
+// et "pleins". Un slab redevenu vide est rendu à la source de pages,
+// sauf s'il est le dernier slab partiel (évite d'allouer et libérer
+// une page en boucle).
+//
+// Empoisonnement (optionnel) : une case libérée est remplie de 0x6B
+// et une case allouée de 0x5A. Si une case libre n'est plus remplie
+// de 0x6B au moment de la réallouer, quelqu'un a écrit dedans après
+// l'avoir libérée (use-after-free).
+
+use core::fmt;
+use core::mem::size_of;
+use core::ptr;
+
+/// Taille d'un slab (une page)
+pub const SLAB_SIZE: usize = 4096;
+
+/// Motif d'une case libre empoisonnée
+pub const POISON_FREE: u8 = 0x6B;
+/// Motif d'une case fraîchement allouée (empoisonnement)
+pub const POISON_ALLOC: u8 = 0x5A;
+
+/// Fournit les pages des slabs (le kernel utilise l'allocateur de frames)
+pub trait PageSource {
+    /// Retourne l'adresse d'une page de SLAB_SIZE octets alignée sur SLAB_SIZE
+    fn allocate_page(&mut self) -> Option<usize>;
+    /// Rend une page obtenue par `allocate_page`
+    fn free_page(&mut self, page: usize);
+}
+
+/// Erreur détectée par un cache
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SlabError {
+    /// Plus de page disponible
+    OutOfMemory,
+    /// L'adresse n'appartient pas à un slab de ce cache
+    ForeignObject(usize),
+    /// L'adresse n'est pas le début d'une case
+    Misaligned(usize),
+    /// La case est déjà libre (double libération)
+    DoubleFree(usize),
+    /// Une case libre a été modifiée après sa libération
+    UseAfterFree(usize),
+}
+
+impl fmt::Display for SlabError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SlabError::OutOfMemory => f.write_str("plus de page disponible"),
+            SlabError::ForeignObject(addr) => write!(f, "{:#x} n'appartient pas a ce cache", addr),
+            SlabError::Misaligned(addr) => write!(f, "{:#x} n'est pas le debut d'un objet", addr),
+            SlabError::DoubleFree(addr) => write!(f, "double liberation de {:#x}", addr),
+            SlabError::UseAfterFree(addr) => write!(f, "{:#x} modifie apres sa liberation", addr),
+        }
+    }
+}
+
+/// Statistiques d'un cache
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SlabStats {
+    /// Objets actuellement alloués
+    pub active_objects: usize,
+    /// Cases disponibles dans tous les slabs
+    pub total_objects: usize,
+    /// Nombre de slabs (pages)
+    pub slabs: usize,
+    /// Allocations réussies depuis la création
+    pub allocations: u64,
+    /// Libérations depuis la création
+    pub frees: u64,
+}
+
+/// En-tête placé au début de chaque slab
+#[repr(C)]
+struct SlabHeader {
+    /// Identifie le cache propriétaire (dérivé de l'adresse du cache)
+    magic: usize,
+    prev: *mut SlabHeader,
+    next: *mut SlabHeader,
+    /// Première case libre (chaque case libre contient l'adresse de la suivante)
+    free: *mut usize,
+    in_use: usize,
+}
+
+/// Une liste doublement chaînée de slabs
+struct SlabList {
+    head: *mut SlabHeader,
+}
+
+impl SlabList {
+    const fn new() -> SlabList {
+        SlabList { head: ptr::null_mut() }
+    }
+
+    unsafe fn push(&mut self, slab: *mut SlabHeader) {
+        (*slab).prev = ptr::null_mut();
+        (*slab).next = self.head;
+        if !self.head.is_null() {
+            (*self.head).prev = slab;
+        }
+        self.head = slab;
+    }
+
+    unsafe fn remove(&mut self, slab: *mut SlabHeader) {
+        if (*slab).prev.is_null() {
+            self.head = (*slab).next;
+        } else {
+            (*(*slab).prev).next = (*slab).next;
+        }
+        if !(*slab).next.is_null() {
+            (*(*slab).next).prev = (*slab).prev;
+        }
+    }
+}
+
+/// Un cache d'objets de taille fixe
+pub struct SlabCache {
+    name: &'static str,
+    /// Taille d'une case (multiple de 16, au moins la taille d'un pointeur)
+    object_size: usize,
+    /// Décalage de la première case dans le slab
+    first_object: usize,
+    /// Nombre de cases par slab
+    capacity: usize,
+    poison: bool,
+    /// Slabs ayant au moins une case libre
+    partial: SlabList,
+    /// Slabs sans case libre
+    full: SlabList,
+    stats: SlabStats,
+}
+
+// Le cache ne contient que des adresses : il peut changer de thread
+unsafe impl Send for SlabCache {}
+
+impl SlabCache {
+    /// Crée un cache d'objets de `object_size` octets
+    ///
+    /// Les objets sont alignés sur la plus grande puissance de 2 qui
+    /// divise leur taille arrondie (16 au minimum, 1024 au maximum).
+    pub const fn new(name: &'static str, object_size: usize, poison: bool) -> SlabCache {
+        let min = if object_size < 16 { 16 } else { object_size };
+        let object_size = (min + 15) & !15;
+        let align = {
+            let lowest_bit = object_size & object_size.wrapping_neg();
+            if lowest_bit > 1024 {
+                1024
+            } else {
+                lowest_bit
+            }
+        };
+        let first_object = (size_of::<SlabHeader>() + align - 1) & !(align - 1);
+        let capacity = if first_object + object_size <= SLAB_SIZE {
+            (SLAB_SIZE - first_object) / object_size
+        } else {
+            0
+        };
+        SlabCache {
+            name,
+            object_size,
+            first_object,
+            capacity,
+            poison,
+            partial: SlabList::new(),
+            full: SlabList::new(),
+            stats: SlabStats {
+                active_objects: 0,
+                total_objects: 0,
+                slabs: 0,
+                allocations: 0,
+                frees: 0,
+            },
+        }
+    }
+
+    /// Nom du cache (affiché par `slabinfo`)
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Taille réelle d'une case
+    pub fn object_size(&self) -> usize {
+        self.object_size
+    }
+
+    /// Nombre de cases par slab
+    pub fn objects_per_slab(&self) -> usize {
+        self.capacity
+    }
+
+    /// Indique si les cases libres sont empoisonnées
+    pub fn poisoned(&self) -> bool {
+        self.poison
+    }
+
+    /// Statistiques actuelles
+    pub fn stats(&self) -> SlabStats {
+        self.stats
+    }
+
+    /// Signature écrite dans les slabs de ce cache
+    fn magic(&self) -> usize {
+        // Dépend de l'adresse du cache : un cache ne doit pas être
+        // déplacé après sa première allocation
+        self as *const SlabCache as usize ^ 0x51AB_51AB
+    }
+
+    /// Alloue un objet
+    pub fn allocate(&mut self, pages: &mut dyn PageSource) -> Result<*mut u8, SlabError> {
+        if self.capacity == 0 {
+            return Err(SlabError::OutOfMemory);
+        }
+        unsafe {
+            if self.partial.head.is_null() {
+                let slab = self.new_slab(pages)?;
+                self.partial.push(slab);
+            }
+            let slab = self.partial.head;
+            let object = (*slab).free;
+            if self.poison && !self.is_poisoned(object as *mut u8) {
+                return Err(SlabError::UseAfterFree(object as usize));
+            }
+            (*slab).free = *object as *mut usize;
+            (*slab).in_use += 1;
+            if (*slab).free.is_null() {
+                self.partial.remove(slab);
+                self.full.push(slab);
+            }
+            if self.poison {
+                ptr::write_bytes(object as *mut u8, POISON_ALLOC, self.object_size);
+            }
+            self.stats.active_objects += 1;
+            self.stats.allocations += 1;
+            Ok(object as *mut u8)
+        }
+    }
+
+    /// Libère un objet alloué par ce cache
+    ///
+    /// # Safety
+    /// Si `object` se trouve dans un slab de ce cache, il ne doit plus
+    /// être utilisé après l'appel.
+    pub unsafe fn free(&mut self, object: *mut u8, pages: &mut dyn PageSource) -> Result<(), SlabError> {
+        let address = object as usize;
+        let slab = (address & !(SLAB_SIZE - 1)) as *mut SlabHeader;
+        if slab.is_null() || (*slab).magic != self.magic() {
+            return Err(SlabError::ForeignObject(address));
+        }
+        let offset = address - slab as usize;
+        if offset < self.first_object || !(offset - self.first_object).is_multiple_of(self.object_size) {
+            return Err(SlabError::Misaligned(address));
+        }
+        if self.is_in_free_list(slab, address) {
+            return Err(SlabError::DoubleFree(address));
+        }
+
+        let was_full = (*slab).free.is_null();
+        if self.poison {
+            ptr::write_bytes(object, POISON_FREE, self.object_size);
+        }
+        let object = object as *mut usize;
+        *object = (*slab).free as usize;
+        (*slab).free = object;
+        (*slab).in_use -= 1;
+        if was_full {
+            self.full.remove(slab);
+            self.partial.push(slab);
+        }
+        self.stats.active_objects -= 1;
+        self.stats.frees += 1;
+
+        // Rendre un slab vide, sauf s'il est le seul partiel
+        if (*slab).in_use == 0 && !((*slab).prev.is_null() && (*slab).next.is_null()) {
+            self.partial.remove(slab);
+            (*slab).magic = 0;
+            self.stats.slabs -= 1;
+            self.stats.total_objects -= self.capacity;
+            pages.free_page(slab as usize);
+        }
+        Ok(())
+    }
+
+    /// Prépare une nouvelle page : en-tête puis chaînage de toutes les cases
+    unsafe fn new_slab(&mut self, pages: &mut dyn PageSource) -> Result<*mut SlabHeader, SlabError> {
+        let page = pages.allocate_page().ok_or(SlabError::OutOfMemory)?;
+        let slab = page as *mut SlabHeader;
+        let mut free: *mut usize = ptr::null_mut();
+        // Chaîner à l'envers pour que la première case soit servie en premier
+        for index in (0..self.capacity).rev() {
+            let object = (page + self.first_object + index * self.object_size) as *mut usize;
+            if self.poison {
+                ptr::write_bytes(object as *mut u8, POISON_FREE, self.object_size);
+            }
+            *object = free as usize;
+            free = object;
+        }
+        slab.write(SlabHeader {
+            magic: self.magic(),
+            prev: ptr::null_mut(),
+            next: ptr::null_mut(),
+            free,
+            in_use: 0,
+        });
+        self.stats.slabs += 1;
+        self.stats.total_objects += self.capacity;
+        Ok(slab)
+    }
+
+    /// Vérifie qu'une case libre est restée remplie du motif (hors chaînage)
+    unsafe fn is_poisoned(&self, object: *mut u8) -> bool {
+        (size_of::<usize>()..self.object_size).all(|i| *object.add(i) == POISON_FREE)
+    }
+
+    unsafe fn is_in_free_list(&self, slab: *mut SlabHeader, address: usize) -> bool {
+        let mut free = (*slab).free;
+        while !free.is_null() {
+            if free as usize == address {
+                return true;
+            }
+            free = *free as *mut usize;
+        }
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Pages de test alignées, prises dans un grand tableau
+    struct TestPages {
+        memory: Vec<u8>,
+        next: usize,
+        freed: Vec<usize>,
+        limit: usize,
+    }
+
+    impl TestPages {
+        fn new(limit: usize) -> TestPages {
+            TestPages { memory: vec![0; (limit + 1) * SLAB_SIZE], next: 0, freed: Vec::new(), limit }
+        }
+    }
+
+    impl PageSource for TestPages {
+        fn allocate_page(&mut self) -> Option<usize> {
+            if let Some(page) = self.freed.pop() {
+                return Some(page);
+            }
+            if self.next == self.limit {
+                return None;
+            }
+            let base = (self.memory.as_ptr() as usize).next_multiple_of(SLAB_SIZE);
+            self.next += 1;
+            Some(base + (self.next - 1) * SLAB_SIZE)
+        }
+
+        fn free_page(&mut self, page: usize) {
+            self.freed.push(page);
+        }
+    }
+
+    #[test]
+    fn computes_geometry() {
+        let cache = SlabCache::new("test", 24, false);
+        assert_eq!(cache.object_size(), 32);
+        assert_eq!(cache.objects_per_slab(), (4096 - 64) / 32);
+        let cache = SlabCache::new("test", 1024, false);
+        assert_eq!(cache.objects_per_slab(), 3);
+        assert_eq!(SlabCache::new("test", 1, false).object_size(), 16);
+        assert_eq!(SlabCache::new("test", 8192, false).objects_per_slab(), 0);
+    }
+
+    #[test]
+    fn allocates_distinct_aligned_objects() {
+        let mut pages = TestPages::new(4);
+        let mut cache = SlabCache::new("test", 64, false);
+        let a = cache.allocate(&mut pages).unwrap();
+        let b = cache.allocate(&mut pages).unwrap();
+        assert_ne!(a, b);
+        assert!((a as usize).is_multiple_of(64));
+        assert_eq!(cache.stats().active_objects, 2);
+        assert_eq!(cache.stats().slabs, 1);
+        unsafe {
+            cache.free(a, &mut pages).unwrap();
+            assert_eq!(cache.free(a, &mut pages), Err(SlabError::DoubleFree(a as usize)));
+            assert_eq!(cache.free(b.add(8), &mut pages), Err(SlabError::Misaligned(b as usize + 8)));
+        }
+        // La case libérée est réutilisée en premier
+        assert_eq!(cache.allocate(&mut pages).unwrap(), a);
+    }
+
+    #[test]
+    fn grows_and_returns_empty_slabs() {
+        let mut pages = TestPages::new(4);
+        let mut cache = SlabCache::new("test", 1024, false);
+        let objects: Vec<_> = (0..7).map(|_| cache.allocate(&mut pages).unwrap()).collect();
+        assert_eq!(cache.stats().slabs, 3);
+        assert_eq!(cache.stats().total_objects, 9);
+        unsafe {
+            for &object in &objects {
+                cache.free(object, &mut pages).unwrap();
+            }
+        }
+        // Un seul slab vide est gardé
+        assert_eq!(cache.stats().slabs, 1);
+        assert_eq!(pages.freed.len(), 2);
+        assert_eq!(cache.stats().frees, 7);
+    }
+
+    #[test]
+    fn reports_out_of_memory_and_foreign_objects() {
+        let mut pages = TestPages::new(1);
+        let mut cache = SlabCache::new("test", 2048, false);
+        assert!(cache.allocate(&mut pages).is_ok());
+        assert_eq!(cache.allocate(&mut pages), Err(SlabError::OutOfMemory));
+
+        let mut other = SlabCache::new("autre", 2048, false);
+        let mut other_pages = TestPages::new(1);
+        let object = other.allocate(&mut other_pages).unwrap();
+        unsafe {
+            assert_eq!(cache.free(object, &mut pages), Err(SlabError::ForeignObject(object as usize)));
+        }
+    }
+
+    #[test]
+    fn poisoning_detects_use_after_free() {
+        let mut pages = TestPages::new(1);
+        let mut cache = SlabCache::new("test", 32, true);
+        let object = cache.allocate(&mut pages).unwrap();
+        unsafe {
+            assert_eq!(*object.add(31), POISON_ALLOC);
+            cache.free(object, &mut pages).unwrap();
+            assert_eq!(*object.add(31), POISON_FREE);
+            // Écriture après libération
+            *object.add(20) = 0;
+        }
+        assert_eq!(cache.allocate(&mut pages), Err(SlabError::UseAfterFree(object as usize)));
+    }
+}
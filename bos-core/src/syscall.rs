@@ -0,0 +1,287 @@
+// ===================================================================
+// APPELS SYSTÈME : NUMÉROS, ERREURS ET CONVENTION D'APPEL
+// ===================================================================
+//
+// Partagé entre le kernel (qui les traite) et les programmes
+// utilisateur (qui les font avec l'instruction SYSCALL).
+//
+// Convention (celle de Linux x86-64) :
+// - RAX : numéro de l'appel système
+// - RDI, RSI, RDX, R10, R8, R9 : arguments 1 à 6
+// - RAX au retour : résultat (>= 0) ou erreur (-code)
+// - RCX et R11 sont écrasés par SYSCALL/SYSRET (RIP et RFLAGS)
+
+use core::fmt;
+
+/// `write(fd, buffer, longueur)` : retourne le nombre d'octets écrits
+pub const SYS_WRITE: u64 = 0;
+/// `read(fd, buffer, longueur)` : attend au moins un octet, retourne
+/// le nombre d'octets lus
+pub const SYS_READ: u64 = 1;
+/// `exit(code)` : termine le processus, ne retourne pas
+pub const SYS_EXIT: u64 = 2;
+/// `yield()` : cède le CPU aux autres threads
+pub const SYS_YIELD: u64 = 3;
+/// `sleep(ms)` : dort au moins `ms` millisecondes (moins si un signal
+/// l'interrompt : erreur `Interrupted`)
+pub const SYS_SLEEP: u64 = 4;
+/// `getpid()` : identifiant du processus
+pub const SYS_GETPID: u64 = 5;
+/// `brk(fin)` : déplace la fin du tas du processus, retourne la fin
+/// obtenue (l'ancienne si impossible, la fin actuelle pour `brk(0)`)
+pub const SYS_BRK: u64 = 6;
+/// `spawn(nom, longueur, argv, argc)` : lance un programme dans un
+/// processus enfant, retourne son pid. `argv` est un tableau de `argc`
+/// paires (adresse, longueur), la première étant le nom du programme
+pub const SYS_SPAWN: u64 = 7;
+/// `waitpid(pid, statut, options)` : attend la fin d'un enfant (`pid`
+/// ou `WAIT_ANY`), écrit son statut (u32, voir bos_core::process) si
+/// `statut` n'est pas nul et retourne son pid ; 0 avec `WNOHANG` si
+/// aucun enfant n'est terminé
+pub const SYS_WAITPID: u64 = 8;
+/// `kill(pid, signal)` : envoie le signal `signal` au processus `pid`
+/// (le signal 0 vérifie seulement qu'il existe)
+pub const SYS_KILL: u64 = 9;
+/// `getppid()` : identifiant du processus parent (0 : le kernel)
+pub const SYS_GETPPID: u64 = 10;
+/// `sigaction(signal, action, ancienne)` : change l'action d'un signal
+/// si `action` n'est pas nul et écrit l'ancienne si `ancienne` n'est
+/// pas nul (`SigAction`, voir bos_core::signal)
+pub const SYS_SIGACTION: u64 = 11;
+/// `sigprocmask(operation, masque, ancien)` : change le masque des
+/// signaux bloqués (u64) si `masque` n'est pas nul et écrit l'ancien si
+/// `ancien` n'est pas nul
+pub const SYS_SIGPROCMASK: u64 = 12;
+/// `sigreturn(trame)` : fin d'un handler de signal, reprend le
+/// programme avec les registres sauvegardés dans la trame ; ne retourne
+/// pas
+pub const SYS_SIGRETURN: u64 = 13;
+/// `alarm(ms)` : envoie SIGALRM au processus dans `ms` millisecondes
+/// (0 : annule), retourne le temps restant de l'alarme précédente
+pub const SYS_ALARM: u64 = 14;
+/// `open(chemin, longueur, options)` : ouvre un fichier (options
+/// O_RDONLY, O_CREAT... de bos_core::vfs), retourne son descripteur
+pub const SYS_OPEN: u64 = 15;
+/// `close(fd)` : ferme un descripteur
+pub const SYS_CLOSE: u64 = 16;
+/// `lseek(fd, décalage, origine)` : déplace la position d'un fichier
+/// ouvert (SEEK_SET, SEEK_CUR, SEEK_END), retourne la nouvelle position
+pub const SYS_LSEEK: u64 = 17;
+
+/// Nombre d'appels système
+pub const SYSCALL_COUNT: usize = 18;
+
+/// Nom d'un appel système (journal du kernel)
+pub fn name(number: u64) -> Option<&'static str> {
+    let name = match number {
+        SYS_WRITE => "write",
+        SYS_READ => "read",
+        SYS_EXIT => "exit",
+        SYS_YIELD => "yield",
+        SYS_SLEEP => "sleep",
+        SYS_GETPID => "getpid",
+        SYS_BRK => "brk",
+        SYS_SPAWN => "spawn",
+        SYS_WAITPID => "waitpid",
+        SYS_KILL => "kill",
+        SYS_GETPPID => "getppid",
+        SYS_SIGACTION => "sigaction",
+        SYS_SIGPROCMASK => "sigprocmask",
+        SYS_SIGRETURN => "sigreturn",
+        SYS_ALARM => "alarm",
+        SYS_OPEN => "open",
+        SYS_CLOSE => "close",
+        SYS_LSEEK => "lseek",
+        _ => return None,
+    };
+    Some(name)
+}
+
+/// Erreur d'un appel système, retournée dans RAX sous la forme -code
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SyscallError {
+    /// Numéro d'appel système inconnu
+    NoSuchSyscall,
+    /// Descripteur de fichier invalide
+    BadFileDescriptor,
+    /// Adresse hors de l'espace utilisateur ou non projetée
+    BadAddress,
+    /// Argument invalide
+    InvalidArgument,
+    /// Fichier ou programme introuvable
+    NotFound,
+    /// Processus introuvable
+    NoSuchProcess,
+    /// Appel interrompu par un signal
+    Interrupted,
+    /// Fichier qui n'est pas un exécutable valide
+    ExecFormat,
+    /// Aucun processus enfant à attendre
+    NoChild,
+    /// Ressource épuisée pour le moment (threads...)
+    TryAgain,
+    /// Mémoire insuffisante
+    OutOfMemory,
+    /// Fichier ou point de montage en cours d'utilisation
+    Busy,
+    /// Le fichier existe déjà
+    AlreadyExists,
+    /// Lien ou renommage entre deux systèmes de fichiers
+    CrossDevice,
+    /// Un composant du chemin n'est pas un répertoire
+    NotADirectory,
+    /// Le fichier est un répertoire
+    IsADirectory,
+    /// Trop de fichiers ouverts par le processus
+    TooManyFiles,
+    /// Plus de place sur le système de fichiers
+    NoSpace,
+    /// Système de fichiers en lecture seule
+    ReadOnly,
+    /// Nom de fichier trop long
+    NameTooLong,
+    /// Répertoire non vide
+    NotEmpty,
+    /// Trop de liens symboliques à suivre
+    TooManyLinks,
+}
+
+impl SyscallError {
+    const ALL: [SyscallError; 22] = [
+        SyscallError::NoSuchSyscall,
+        SyscallError::BadFileDescriptor,
+        SyscallError::BadAddress,
+        SyscallError::InvalidArgument,
+        SyscallError::NotFound,
+        SyscallError::NoSuchProcess,
+        SyscallError::Interrupted,
+        SyscallError::ExecFormat,
+        SyscallError::NoChild,
+        SyscallError::TryAgain,
+        SyscallError::OutOfMemory,
+        SyscallError::Busy,
+        SyscallError::AlreadyExists,
+        SyscallError::CrossDevice,
+        SyscallError::NotADirectory,
+        SyscallError::IsADirectory,
+        SyscallError::TooManyFiles,
+        SyscallError::NoSpace,
+        SyscallError::ReadOnly,
+        SyscallError::NameTooLong,
+        SyscallError::NotEmpty,
+        SyscallError::TooManyLinks,
+    ];
+
+    /// Code de l'erreur (mêmes valeurs que les errno de Linux)
+    pub const fn code(self) -> u64 {
+        match self {
+            SyscallError::NoSuchSyscall => 38,
+            SyscallError::BadFileDescriptor => 9,
+            SyscallError::BadAddress => 14,
+            SyscallError::InvalidArgument => 22,
+            SyscallError::NotFound => 2,
+            SyscallError::NoSuchProcess => 3,
+            SyscallError::Interrupted => 4,
+            SyscallError::ExecFormat => 8,
+            SyscallError::NoChild => 10,
+            SyscallError::TryAgain => 11,
+            SyscallError::OutOfMemory => 12,
+            SyscallError::Busy => 16,
+            SyscallError::AlreadyExists => 17,
+            SyscallError::CrossDevice => 18,
+            SyscallError::NotADirectory => 20,
+            SyscallError::IsADirectory => 21,
+            SyscallError::TooManyFiles => 24,
+            SyscallError::NoSpace => 28,
+            SyscallError::ReadOnly => 30,
+            SyscallError::NameTooLong => 36,
+            SyscallError::NotEmpty => 39,
+            SyscallError::TooManyLinks => 40,
+        }
+    }
+
+    /// Erreur correspondant à un code
+    pub fn from_code(code: u64) -> Option<SyscallError> {
+        SyscallError::ALL.into_iter().find(|error| error.code() == code)
+    }
+}
+
+impl fmt::Display for SyscallError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let message = match self {
+            SyscallError::NoSuchSyscall => "appel systeme inconnu",
+            SyscallError::BadFileDescriptor => "descripteur de fichier invalide",
+            SyscallError::BadAddress => "adresse invalide",
+            SyscallError::InvalidArgument => "argument invalide",
+            SyscallError::NotFound => "introuvable",
+            SyscallError::NoSuchProcess => "processus introuvable",
+            SyscallError::Interrupted => "appel interrompu",
+            SyscallError::ExecFormat => "executable invalide",
+            SyscallError::NoChild => "aucun processus enfant",
+            SyscallError::TryAgain => "ressource temporairement indisponible",
+            SyscallError::OutOfMemory => "memoire insuffisante",
+            SyscallError::Busy => "en cours d'utilisation",
+            SyscallError::AlreadyExists => "existe deja",
+            SyscallError::CrossDevice => "systemes de fichiers differents",
+            SyscallError::NotADirectory => "pas un repertoire",
+            SyscallError::IsADirectory => "est un repertoire",
+            SyscallError::TooManyFiles => "trop de fichiers ouverts",
+            SyscallError::NoSpace => "plus de place sur le systeme de fichiers",
+            SyscallError::ReadOnly => "systeme de fichiers en lecture seule",
+            SyscallError::NameTooLong => "nom trop long",
+            SyscallError::NotEmpty => "repertoire non vide",
+            SyscallError::TooManyLinks => "trop de liens symboliques",
+        };
+        f.write_str(message)
+    }
+}
+
+/// Valeur de RAX au retour d'un appel système
+pub fn encode_result(result: Result<u64, SyscallError>) -> u64 {
+    match result {
+        Ok(value) => value,
+        Err(error) => error.code().wrapping_neg(),
+    }
+}
+
+/// Résultat d'un appel système d'après RAX
+///
+/// Les valeurs de -4095 à -1 sont des erreurs, comme sous Linux.
+pub fn decode_result(rax: u64) -> Result<u64, u64> {
+    if rax > (-4096i64) as u64 {
+        Err(rax.wrapping_neg())
+    } else {
+        Ok(rax)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn names_cover_all_syscalls() {
+        for number in 0..SYSCALL_COUNT as u64 {
+            assert!(name(number).is_some());
+        }
+        assert_eq!(name(SYSCALL_COUNT as u64), None);
+        assert_eq!(name(SYS_GETPID), Some("getpid"));
+    }
+
+    #[test]
+    fn results_round_trip() {
+        assert_eq!(decode_result(encode_result(Ok(42))), Ok(42));
+        let rax = encode_result(Err(SyscallError::BadAddress));
+        assert_eq!(rax as i64, -14);
+        assert_eq!(decode_result(rax), Err(14));
+        assert_eq!(SyscallError::from_code(14), Some(SyscallError::BadAddress));
+        assert_eq!(SyscallError::from_code(1), None);
+    }
+
+    #[test]
+    fn error_codes_are_distinct() {
+        for error in SyscallError::ALL {
+            assert_eq!(SyscallError::from_code(error.code()), Some(error));
+        }
+    }
+}
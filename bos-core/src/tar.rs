@@ -0,0 +1,411 @@
+// ===================================================================
+// ARCHIVES TAR (FORMAT USTAR)
+// ===================================================================
+//
+// L'initrd de BOS est une archive tar : une suite de fichiers, chacun
+// précédé d'un en-tête de 512 octets, et terminée par deux blocs de
+// zéros. Les nombres de l'en-tête sont écrits en octal ASCII ; son
+// checksum est la somme de ses octets, le champ du checksum lui-même
+// compté comme des espaces.
+//
+//     0    nom (100)         257  "ustar\0" ou "ustar " (GNU)
+//     100  mode (8)          265  propriétaire, groupe (32 + 32)
+//     124  taille (12)       329  périphérique (8 + 8)
+//     136  date (12)         345  préfixe du nom (155)
+//     148  checksum (8)
+//     156  type (1)
+//     157  cible du lien (100)
+//
+// Un chemin de plus de 100 octets est coupé en préfixe et nom sur un
+// '/'. Les données de chaque fichier suivent son en-tête, complétées
+// à un multiple de 512 octets.
+//
+// La lecture est vérifiée (une archive tronquée ou corrompue donne une
+// erreur, jamais un panic) ; l'écriture d'un en-tête sert au script de
+// build, qui construit l'archive.
+
+use core::fmt;
+use core::str;
+
+/// Taille d'un bloc (en-têtes et données)
+pub const BLOCK_SIZE: usize = 512;
+
+/// Longueur maximale du champ du nom
+const NAME_SIZE: usize = 100;
+/// Longueur maximale du préfixe du nom
+const PREFIX_SIZE: usize = 155;
+
+// Position des champs de l'en-tête
+const MODE: usize = 100;
+const SIZE: usize = 124;
+const MTIME: usize = 136;
+const CHECKSUM: usize = 148;
+const TYPEFLAG: usize = 156;
+const LINKNAME: usize = 157;
+const MAGIC: usize = 257;
+const PREFIX: usize = 345;
+
+/// Type d'une entrée de l'archive
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EntryKind {
+    /// Fichier ordinaire ('0', ou '\0' dans les vieilles archives)
+    File,
+    /// Lien symbolique ('2')
+    Symlink,
+    /// Répertoire ('5')
+    Directory,
+    /// Autre type (périphérique, lien dur...), ignoré par BOS
+    Other(u8),
+}
+
+impl EntryKind {
+    fn from_flag(flag: u8) -> EntryKind {
+        match flag {
+            b'0' | 0 => EntryKind::File,
+            b'2' => EntryKind::Symlink,
+            b'5' => EntryKind::Directory,
+            other => EntryKind::Other(other),
+        }
+    }
+
+    fn flag(self) -> u8 {
+        match self {
+            EntryKind::File => b'0',
+            EntryKind::Symlink => b'2',
+            EntryKind::Directory => b'5',
+            EntryKind::Other(flag) => flag,
+        }
+    }
+}
+
+/// Erreurs de lecture ou d'écriture d'une archive tar
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TarError {
+    /// Un en-tête ou des données dépassent la fin de l'archive
+    Truncated,
+    /// Le checksum de l'en-tête ne correspond pas
+    BadChecksum,
+    /// Pas un en-tête ustar
+    BadMagic,
+    /// Un nombre de l'en-tête n'est pas en octal
+    BadNumber,
+    /// Un nom n'est pas en UTF-8
+    BadName,
+    /// Un chemin ne tient pas dans l'en-tête
+    NameTooLong,
+}
+
+impl fmt::Display for TarError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let msg = match self {
+            TarError::Truncated => "archive tronquee",
+            TarError::BadChecksum => "checksum d'en-tete invalide",
+            TarError::BadMagic => "pas une archive ustar",
+            TarError::BadNumber => "nombre invalide dans un en-tete",
+            TarError::BadName => "nom invalide (pas en UTF-8)",
+            TarError::NameTooLong => "chemin trop long",
+        };
+        f.write_str(msg)
+    }
+}
+
+/// Une entrée de l'archive : son en-tête décodé et ses données
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Entry<'a> {
+    /// Début du chemin (vide pour les chemins courts)
+    pub prefix: &'a str,
+    /// Fin du chemin
+    pub name: &'a str,
+    pub kind: EntryKind,
+    /// Droits (rwxrwxrwx, en octal 0o755...)
+    pub mode: u32,
+    /// Date de modification (secondes depuis 1970)
+    pub mtime: u64,
+    /// Cible d'un lien symbolique
+    pub link: &'a str,
+    /// Contenu d'un fichier (vide pour les autres types)
+    pub data: &'a [u8],
+}
+
+impl<'a> Entry<'a> {
+    /// Composants du chemin, sans les vides ni les `.` (`./bin/` donne
+    /// `bin`)
+    pub fn components(&self) -> impl Iterator<Item = &'a str> {
+        self.prefix
+            .split('/')
+            .chain(self.name.split('/'))
+            .filter(|component| !component.is_empty() && *component != ".")
+    }
+}
+
+/// Parcourt les entrées de l'archive `data`
+pub fn entries(data: &[u8]) -> Entries<'_> {
+    Entries { data, offset: 0, done: false }
+}
+
+/// Itérateur sur les entrées d'une archive (voir `entries`)
+///
+/// S'arrête au premier bloc de zéros, à la fin des données ou après
+/// la première erreur.
+pub struct Entries<'a> {
+    data: &'a [u8],
+    offset: usize,
+    done: bool,
+}
+
+impl<'a> Iterator for Entries<'a> {
+    type Item = Result<Entry<'a>, TarError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done || self.offset == self.data.len() {
+            return None;
+        }
+        let result = self.read_entry();
+        match result {
+            Ok(None) => {
+                self.done = true;
+                None
+            }
+            Ok(Some(entry)) => Some(Ok(entry)),
+            Err(error) => {
+                self.done = true;
+                Some(Err(error))
+            }
+        }
+    }
+}
+
+impl<'a> Entries<'a> {
+    /// Lit l'entrée à `offset` et passe à la suivante ; `None` à la fin
+    /// de l'archive
+    fn read_entry(&mut self) -> Result<Option<Entry<'a>>, TarError> {
+        let header = self.data.get(self.offset..self.offset + BLOCK_SIZE).ok_or(TarError::Truncated)?;
+        if header.iter().all(|&byte| byte == 0) {
+            return Ok(None);
+        }
+        if &header[MAGIC..MAGIC + 5] != b"ustar" {
+            return Err(TarError::BadMagic);
+        }
+        if parse_octal(&header[CHECKSUM..CHECKSUM + 8])? != checksum(header) {
+            return Err(TarError::BadChecksum);
+        }
+        let kind = EntryKind::from_flag(header[TYPEFLAG]);
+        let size = parse_octal(&header[SIZE..SIZE + 12])?;
+        let start = self.offset + BLOCK_SIZE;
+        let size = usize::try_from(size).map_err(|_| TarError::Truncated)?;
+        let end = start.checked_add(size).ok_or(TarError::Truncated)?;
+        let data = self.data.get(start..end).ok_or(TarError::Truncated)?;
+        self.offset = start + size.div_ceil(BLOCK_SIZE) * BLOCK_SIZE;
+        // Le dernier bloc de données peut manquer de remplissage
+        self.offset = self.offset.min(self.data.len());
+        Ok(Some(Entry {
+            prefix: parse_str(&header[PREFIX..PREFIX + PREFIX_SIZE])?,
+            name: parse_str(&header[..NAME_SIZE])?,
+            kind,
+            mode: parse_octal(&header[MODE..MODE + 8])? as u32,
+            mtime: parse_octal(&header[MTIME..MTIME + 12])?,
+            link: parse_str(&header[LINKNAME..LINKNAME + NAME_SIZE])?,
+            data: if kind == EntryKind::File { data } else { &[] },
+        }))
+    }
+}
+
+/// Somme des octets de l'en-tête, le champ du checksum compté comme
+/// des espaces
+fn checksum(header: &[u8]) -> u64 {
+    header
+        .iter()
+        .enumerate()
+        .map(|(index, &byte)| match index {
+            CHECKSUM..=155 => b' ' as u64,
+            _ => byte as u64,
+        })
+        .sum()
+}
+
+/// Nombre en octal ASCII, entouré d'espaces ou de zéros
+fn parse_octal(field: &[u8]) -> Result<u64, TarError> {
+    let text = field.split(|&byte| byte == 0).next().unwrap_or(&[]);
+    let text = str::from_utf8(text).map_err(|_| TarError::BadNumber)?.trim_matches(' ');
+    if text.is_empty() {
+        return Ok(0);
+    }
+    u64::from_str_radix(text, 8).map_err(|_| TarError::BadNumber)
+}
+
+/// Chaîne terminée par un zéro (ou remplissant tout le champ)
+fn parse_str(field: &[u8]) -> Result<&str, TarError> {
+    let text = field.split(|&byte| byte == 0).next().unwrap_or(&[]);
+    str::from_utf8(text).map_err(|_| TarError::BadName)
+}
+
+/// Écrit `value` en octal sur tout le champ, terminé par un zéro
+fn write_octal(field: &mut [u8], value: u64) -> Result<(), TarError> {
+    let digits = field.len() - 1;
+    if digits < 22 && value >> (3 * digits) != 0 {
+        return Err(TarError::BadNumber);
+    }
+    for (index, byte) in field[..digits].iter_mut().enumerate() {
+        let shift = 3 * (digits - 1 - index);
+        *byte = b'0' + ((value >> shift) & 7) as u8;
+    }
+    field[digits] = 0;
+    Ok(())
+}
+
+/// Écrit l'en-tête ustar d'une entrée dans `block`
+///
+/// Le chemin est coupé en préfixe et nom s'il dépasse 100 octets ;
+/// `size` est la taille des données qui suivront l'en-tête.
+pub fn write_header(
+    block: &mut [u8; BLOCK_SIZE],
+    path: &str,
+    kind: EntryKind,
+    mode: u32,
+    size: u64,
+    mtime: u64,
+    link: &str,
+) -> Result<(), TarError> {
+    let (prefix, name) = split_path(path)?;
+    if link.len() > NAME_SIZE {
+        return Err(TarError::NameTooLong);
+    }
+    block.fill(0);
+    block[..name.len()].copy_from_slice(name.as_bytes());
+    block[PREFIX..PREFIX + prefix.len()].copy_from_slice(prefix.as_bytes());
+    block[LINKNAME..LINKNAME + link.len()].copy_from_slice(link.as_bytes());
+    write_octal(&mut block[MODE..MODE + 8], mode as u64)?;
+    write_octal(&mut block[108..116], 0)?;
+    write_octal(&mut block[116..124], 0)?;
+    write_octal(&mut block[SIZE..SIZE + 12], size)?;
+    write_octal(&mut block[MTIME..MTIME + 12], mtime)?;
+    block[TYPEFLAG] = kind.flag();
+    block[MAGIC..MAGIC + 8].copy_from_slice(b"ustar\x0000");
+    let sum = checksum(block);
+    write_octal(&mut block[CHECKSUM..CHECKSUM + 7], sum)?;
+    block[CHECKSUM + 7] = b' ';
+    Ok(())
+}
+
+/// Coupe `path` en (préfixe, nom) pour l'en-tête
+fn split_path(path: &str) -> Result<(&str, &str), TarError> {
+    if path.len() <= NAME_SIZE {
+        return Ok(("", path));
+    }
+    // Le '/' le plus à gauche qui laisse un nom assez court
+    path.match_indices('/')
+        .map(|(index, _)| index)
+        .find(|&index| path.len() - index - 1 <= NAME_SIZE)
+        .filter(|&index| index <= PREFIX_SIZE && index + 1 < path.len())
+        .map(|index| (&path[..index], &path[index + 1..]))
+        .ok_or(TarError::NameTooLong)
+}
+
+// ===================================================================
+// TESTS
+// ===================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Archive des entrées (chemin, type, données, cible du lien)
+    fn archive(entries: &[(&str, EntryKind, &[u8], &str)]) -> Vec<u8> {
+        let mut data = Vec::new();
+        for &(path, kind, content, link) in entries {
+            let mut block = [0u8; BLOCK_SIZE];
+            write_header(&mut block, path, kind, 0o644, content.len() as u64, 1_700_000_000, link).unwrap();
+            data.extend_from_slice(&block);
+            data.extend_from_slice(content);
+            data.resize(data.len().div_ceil(BLOCK_SIZE) * BLOCK_SIZE, 0);
+        }
+        data.resize(data.len() + 2 * BLOCK_SIZE, 0);
+        data
+    }
+
+    #[test]
+    fn reads_written_entries() {
+        let data = archive(&[
+            ("etc/", EntryKind::Directory, b"", ""),
+            ("etc/motd", EntryKind::File, b"Bonjour\n", ""),
+            ("motd", EntryKind::Symlink, b"", "etc/motd"),
+        ]);
+        let entries: Vec<_> = entries(&data).collect::<Result<_, _>>().unwrap();
+        assert_eq!(entries.len(), 3);
+        assert_eq!(entries[0].kind, EntryKind::Directory);
+        assert_eq!(entries[1].name, "etc/motd");
+        assert_eq!(entries[1].data, b"Bonjour\n");
+        assert_eq!(entries[1].mode, 0o644);
+        assert_eq!(entries[1].mtime, 1_700_000_000);
+        assert_eq!(entries[2].kind, EntryKind::Symlink);
+        assert_eq!(entries[2].link, "etc/motd");
+        assert!(entries[2].data.is_empty());
+    }
+
+    #[test]
+    fn data_is_padded_to_blocks() {
+        let big = [7u8; 600];
+        let data = archive(&[("a", EntryKind::File, &big, ""), ("b", EntryKind::File, b"x", "")]);
+        let entries: Vec<_> = entries(&data).collect::<Result<_, _>>().unwrap();
+        assert_eq!(entries[0].data.len(), 600);
+        assert_eq!(entries[1].name, "b");
+        assert_eq!(entries[1].data, b"x");
+    }
+
+    #[test]
+    fn long_paths_use_the_prefix() {
+        let path = format!("{}/{}", "d".repeat(120), "f".repeat(90));
+        let data = archive(&[(&path, EntryKind::File, b"", "")]);
+        let entry = entries(&data).next().unwrap().unwrap();
+        assert_eq!(entry.prefix.len(), 120);
+        assert_eq!(entry.name.len(), 90);
+        assert_eq!(entry.components().count(), 2);
+
+        let mut block = [0u8; BLOCK_SIZE];
+        let no_slash = "x".repeat(101);
+        assert_eq!(write_header(&mut block, &no_slash, EntryKind::File, 0, 0, 0, ""), Err(TarError::NameTooLong));
+    }
+
+    #[test]
+    fn components_skip_dots_and_slashes() {
+        let data = archive(&[("./bin//hello", EntryKind::File, b"", "")]);
+        let entry = entries(&data).next().unwrap().unwrap();
+        assert_eq!(entry.components().collect::<Vec<_>>(), ["bin", "hello"]);
+    }
+
+    #[test]
+    fn detects_corruption() {
+        let mut data = archive(&[("a", EntryKind::File, b"abc", "")]);
+        data[0] = b'b';
+        assert_eq!(entries(&data).next(), Some(Err(TarError::BadChecksum)));
+
+        let data = archive(&[("a", EntryKind::File, b"abc", "")]);
+        let mut iter = entries(&data[..BLOCK_SIZE + 1]);
+        assert_eq!(iter.next(), Some(Err(TarError::Truncated)));
+        assert_eq!(iter.next(), None);
+
+        assert_eq!(entries(&[1u8; BLOCK_SIZE]).next(), Some(Err(TarError::BadMagic)));
+        assert_eq!(entries(&[0u8; 100]).next(), Some(Err(TarError::Truncated)));
+    }
+
+    #[test]
+    fn ends_at_zero_block_or_end_of_data() {
+        let data = archive(&[("a", EntryKind::File, b"abc", "")]);
+        assert_eq!(entries(&data).count(), 1);
+        // Sans les blocs de fin
+        assert_eq!(entries(&data[..2 * BLOCK_SIZE]).count(), 1);
+        assert_eq!(entries(&[]).count(), 0);
+    }
+
+    #[test]
+    fn octal_numbers() {
+        assert_eq!(parse_octal(b"0000644\0"), Ok(0o644));
+        assert_eq!(parse_octal(b"  755 \0"), Ok(0o755));
+        assert_eq!(parse_octal(b"\0\0\0"), Ok(0));
+        assert_eq!(parse_octal(b"0009\0"), Err(TarError::BadNumber));
+        let mut field = [0u8; 8];
+        write_octal(&mut field, 0o755).unwrap();
+        assert_eq!(&field, b"0000755\0");
+        assert_eq!(write_octal(&mut field, 0o10000000), Err(TarError::BadNumber));
+    }
+}
@@ -0,0 +1,319 @@
+// ===================================================================
+// SYSTÈME DE FICHIERS VIRTUEL : TYPES COMMUNS
+// ===================================================================
+//
+// Ce que partagent le VFS du kernel (src/vfs.rs), les systèmes de
+// fichiers et les programmes utilisateur :
+// - Types de fichiers, métadonnées et erreurs
+// - Options de l'appel système open (O_RDONLY, O_CREAT...) et
+//   positions de lseek (SEEK_SET...), aux valeurs de Linux
+// - Découpage des chemins ("/etc/motd" → "etc", "motd")
+//
+// Un chemin commence par '/' (absolu) ou part du répertoire courant ;
+// les '/' répétés ou finaux sont ignorés, "." désigne le répertoire
+// lui-même et ".." son parent.
+
+use core::fmt;
+
+use crate::syscall::SyscallError;
+
+/// Longueur maximale d'un nom de fichier
+pub const MAX_NAME: usize = 255;
+/// Longueur maximale d'un chemin
+pub const MAX_PATH: usize = 4096;
+
+/// Ouverture en lecture seule
+pub const O_RDONLY: u64 = 0;
+/// Ouverture en écriture seule
+pub const O_WRONLY: u64 = 1;
+/// Ouverture en lecture et écriture
+pub const O_RDWR: u64 = 2;
+/// Masque du mode d'accès
+pub const O_ACCMODE: u64 = 3;
+/// Crée le fichier s'il n'existe pas
+pub const O_CREAT: u64 = 0o100;
+/// Avec O_CREAT : échoue si le fichier existe
+pub const O_EXCL: u64 = 0o200;
+/// Vide le fichier à l'ouverture
+pub const O_TRUNC: u64 = 0o1000;
+/// Chaque écriture se fait à la fin du fichier
+pub const O_APPEND: u64 = 0o2000;
+
+/// lseek : position absolue
+pub const SEEK_SET: u64 = 0;
+/// lseek : relative à la position courante
+pub const SEEK_CUR: u64 = 1;
+/// lseek : relative à la fin du fichier
+pub const SEEK_END: u64 = 2;
+
+/// Type d'un fichier
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileType {
+    File,
+    Directory,
+    Symlink,
+}
+
+impl FileType {
+    /// Nom affiché par le shell
+    pub fn name(self) -> &'static str {
+        match self {
+            FileType::File => "fichier",
+            FileType::Directory => "repertoire",
+            FileType::Symlink => "lien",
+        }
+    }
+}
+
+/// Informations sur un fichier
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Metadata {
+    pub kind: FileType,
+    /// Taille en octets (longueur de la cible pour un lien)
+    pub size: u64,
+    /// Droits (0o755...)
+    pub mode: u32,
+    /// Date de modification (secondes depuis 1970)
+    pub mtime: u64,
+}
+
+/// Erreurs des opérations sur les fichiers
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FsError {
+    /// Fichier ou répertoire introuvable
+    NotFound,
+    /// Un composant du chemin n'est pas un répertoire
+    NotADirectory,
+    /// Opération sur un fichier impossible sur un répertoire
+    IsADirectory,
+    /// Le fichier existe déjà
+    AlreadyExists,
+    /// Le répertoire n'est pas vide
+    NotEmpty,
+    /// Système de fichiers en lecture seule
+    ReadOnly,
+    /// Trop de liens symboliques (boucle)
+    TooManyLinks,
+    /// Nom ou chemin trop long
+    NameTooLong,
+    /// Point de montage ou fichier en cours d'utilisation
+    Busy,
+    /// Descripteur fermé, ou pas ouvert pour cette opération
+    BadFileDescriptor,
+    /// Plus de descripteur libre dans le processus
+    TooManyFiles,
+    /// Plus de place sur le système de fichiers
+    NoSpace,
+    /// Renommage d'un système de fichiers à un autre
+    CrossDevice,
+    /// Argument invalide (nom, option, position...)
+    InvalidArgument,
+}
+
+impl fmt::Display for FsError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let message = match self {
+            FsError::NotFound => "introuvable",
+            FsError::NotADirectory => "pas un repertoire",
+            FsError::IsADirectory => "est un repertoire",
+            FsError::AlreadyExists => "existe deja",
+            FsError::NotEmpty => "repertoire non vide",
+            FsError::ReadOnly => "systeme de fichiers en lecture seule",
+            FsError::TooManyLinks => "trop de liens symboliques",
+            FsError::NameTooLong => "nom trop long",
+            FsError::Busy => "en cours d'utilisation",
+            FsError::BadFileDescriptor => "descripteur de fichier invalide",
+            FsError::TooManyFiles => "trop de fichiers ouverts",
+            FsError::NoSpace => "plus de place sur le systeme de fichiers",
+            FsError::CrossDevice => "systemes de fichiers differents",
+            FsError::InvalidArgument => "argument invalide",
+        };
+        f.write_str(message)
+    }
+}
+
+impl From<FsError> for SyscallError {
+    fn from(error: FsError) -> SyscallError {
+        match error {
+            FsError::NotFound => SyscallError::NotFound,
+            FsError::NotADirectory => SyscallError::NotADirectory,
+            FsError::IsADirectory => SyscallError::IsADirectory,
+            FsError::AlreadyExists => SyscallError::AlreadyExists,
+            FsError::NotEmpty => SyscallError::NotEmpty,
+            FsError::ReadOnly => SyscallError::ReadOnly,
+            FsError::TooManyLinks => SyscallError::TooManyLinks,
+            FsError::NameTooLong => SyscallError::NameTooLong,
+            FsError::Busy => SyscallError::Busy,
+            FsError::BadFileDescriptor => SyscallError::BadFileDescriptor,
+            FsError::TooManyFiles => SyscallError::TooManyFiles,
+            FsError::NoSpace => SyscallError::NoSpace,
+            FsError::CrossDevice => SyscallError::CrossDevice,
+            FsError::InvalidArgument => SyscallError::InvalidArgument,
+        }
+    }
+}
+
+/// Options d'ouverture d'un fichier (O_RDONLY, O_CREAT...)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OpenFlags(u64);
+
+impl OpenFlags {
+    /// Options connues de BOS
+    const KNOWN: u64 = O_ACCMODE | O_CREAT | O_EXCL | O_TRUNC | O_APPEND;
+
+    /// Vérifie les options passées à open
+    pub fn from_raw(bits: u64) -> Result<OpenFlags, FsError> {
+        if bits & !Self::KNOWN != 0 || bits & O_ACCMODE == O_ACCMODE {
+            return Err(FsError::InvalidArgument);
+        }
+        Ok(OpenFlags(bits))
+    }
+
+    pub fn bits(self) -> u64 {
+        self.0
+    }
+
+    pub fn readable(self) -> bool {
+        self.0 & O_ACCMODE != O_WRONLY
+    }
+
+    pub fn writable(self) -> bool {
+        self.0 & O_ACCMODE != O_RDONLY
+    }
+
+    pub fn create(self) -> bool {
+        self.0 & O_CREAT != 0
+    }
+
+    pub fn exclusive(self) -> bool {
+        self.0 & O_EXCL != 0
+    }
+
+    pub fn truncate(self) -> bool {
+        self.0 & O_TRUNC != 0
+    }
+
+    pub fn append(self) -> bool {
+        self.0 & O_APPEND != 0
+    }
+}
+
+/// Nouvelle position d'un fichier ouvert pour lseek(offset, whence)
+///
+/// `current` est la position courante et `size` la taille du fichier ;
+/// une position négative est refusée, une position après la fin est
+/// permise.
+pub fn seek_position(current: u64, size: u64, offset: i64, whence: u64) -> Result<u64, FsError> {
+    let base = match whence {
+        SEEK_SET => 0,
+        SEEK_CUR => current,
+        SEEK_END => size,
+        _ => return Err(FsError::InvalidArgument),
+    };
+    base.checked_add_signed(offset).filter(|&position| position <= i64::MAX as u64).ok_or(FsError::InvalidArgument)
+}
+
+/// Le chemin part-il de la racine ?
+pub fn is_absolute(path: &str) -> bool {
+    path.starts_with('/')
+}
+
+/// Composants d'un chemin, sans les vides (`//`, '/' final) ni les `.`
+pub fn components(path: &str) -> impl DoubleEndedIterator<Item = &str> {
+    path.split('/').filter(|component| !component.is_empty() && *component != ".")
+}
+
+/// Sépare le dernier composant d'un chemin : ("/etc", "motd") pour
+/// "/etc/motd/", ("", "motd") pour "motd"
+///
+/// `None` si le chemin n'a pas de dernier nom utilisable (racine, "."
+/// ou "..").
+pub fn split_last(path: &str) -> Option<(&str, &str)> {
+    let path = path.trim_end_matches('/');
+    let (parent, name) = match path.rfind('/') {
+        Some(0) => ("/", &path[1..]),
+        Some(index) => (&path[..index], &path[index + 1..]),
+        None => ("", path),
+    };
+    match name {
+        "" | "." | ".." => None,
+        _ => Some((parent, name)),
+    }
+}
+
+/// Vérifie un nom de fichier à créer
+pub fn check_name(name: &str) -> Result<(), FsError> {
+    match name {
+        "" | "." | ".." => Err(FsError::InvalidArgument),
+        _ if name.contains('/') => Err(FsError::InvalidArgument),
+        _ if name.len() > MAX_NAME => Err(FsError::NameTooLong),
+        _ => Ok(()),
+    }
+}
+
+// ===================================================================
+// TESTS
+// ===================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn open_flags() {
+        let flags = OpenFlags::from_raw(O_RDONLY).unwrap();
+        assert!(flags.readable() && !flags.writable());
+        let flags = OpenFlags::from_raw(O_WRONLY | O_CREAT | O_TRUNC).unwrap();
+        assert!(!flags.readable() && flags.writable() && flags.create() && flags.truncate());
+        let flags = OpenFlags::from_raw(O_RDWR | O_APPEND).unwrap();
+        assert!(flags.readable() && flags.writable() && flags.append() && !flags.exclusive());
+        assert_eq!(OpenFlags::from_raw(O_ACCMODE), Err(FsError::InvalidArgument));
+        assert_eq!(OpenFlags::from_raw(0o40000), Err(FsError::InvalidArgument));
+    }
+
+    #[test]
+    fn seek_positions() {
+        assert_eq!(seek_position(5, 10, 3, SEEK_SET), Ok(3));
+        assert_eq!(seek_position(5, 10, -2, SEEK_CUR), Ok(3));
+        assert_eq!(seek_position(5, 10, 4, SEEK_END), Ok(14));
+        assert_eq!(seek_position(5, 10, -11, SEEK_END), Err(FsError::InvalidArgument));
+        assert_eq!(seek_position(5, 10, 0, 3), Err(FsError::InvalidArgument));
+        assert_eq!(seek_position(u64::MAX, 0, 1, SEEK_CUR), Err(FsError::InvalidArgument));
+    }
+
+    #[test]
+    fn path_components() {
+        assert!(is_absolute("/etc") && !is_absolute("etc"));
+        assert_eq!(components("//etc/./motd/").collect::<Vec<_>>(), ["etc", "motd"]);
+        assert_eq!(components("../a").collect::<Vec<_>>(), ["..", "a"]);
+        assert_eq!(components("/").count(), 0);
+    }
+
+    #[test]
+    fn split_last_component() {
+        assert_eq!(split_last("/etc/motd"), Some(("/etc", "motd")));
+        assert_eq!(split_last("/etc/"), Some(("/", "etc")));
+        assert_eq!(split_last("motd"), Some(("", "motd")));
+        assert_eq!(split_last("a/b"), Some(("a", "b")));
+        assert_eq!(split_last("/"), None);
+        assert_eq!(split_last("a/.."), None);
+        assert_eq!(split_last("."), None);
+    }
+
+    #[test]
+    fn names() {
+        assert_eq!(check_name("motd"), Ok(()));
+        assert_eq!(check_name(".."), Err(FsError::InvalidArgument));
+        assert_eq!(check_name("a/b"), Err(FsError::InvalidArgument));
+        assert_eq!(check_name(&"x".repeat(MAX_NAME + 1)), Err(FsError::NameTooLong));
+    }
+
+    #[test]
+    fn errors_map_to_syscall_errors() {
+        assert_eq!(SyscallError::from(FsError::NotFound).code(), 2);
+        assert_eq!(SyscallError::from(FsError::IsADirectory).code(), 21);
+        assert_eq!(SyscallError::from(FsError::ReadOnly).code(), 30);
+        assert_eq!(SyscallError::from(FsError::NoSpace).code(), 28);
+        assert_eq!(SyscallError::from(FsError::CrossDevice).code(), 18);
+    }
+}
@@ -0,0 +1,128 @@
+// ===================================================================
+// SCRIPT DE BUILD : PROGRAMMES UTILISATEUR ET INITRD
+// ===================================================================
+//
+// Les programmes d'exemple de libbos sont compilés pour leur propre
+// cible (x86_64-bos-user.json) par un cargo imbriqué, lancé depuis
+// libbos/ pour qu'il lise libbos/.cargo/config.toml.
+//
+// L'initrd est une archive tar (OUT_DIR/initrd.tar) avec le contenu du
+// répertoire initrd/ du dépôt, les programmes dans bin/ et les points de
+// montage vides mnt/ et tmp/. Le kernel l'inclut avec include_bytes!
+// (voir src/initrd.rs).
+
+use std::env;
+use std::fs;
+use std::os::unix::fs::PermissionsExt;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::UNIX_EPOCH;
+
+use bos_core::tar::{self, EntryKind, BLOCK_SIZE};
+
+/// Programmes compilés et inclus dans le kernel
+const PROGRAMS: &[&str] = &["hello", "signals", "wc"];
+
+/// Variables posées par le cargo du kernel, qui ne doivent pas passer
+/// au cargo imbriqué (drapeaux de la cible du kernel, clippy...)
+const HIDDEN_VARIABLES: &[&str] = &[
+    "CARGO_ENCODED_RUSTFLAGS",
+    "RUSTFLAGS",
+    "RUSTC_WRAPPER",
+    "RUSTC_WORKSPACE_WRAPPER",
+    "CARGO_TARGET_DIR",
+    "CARGO_BUILD_TARGET",
+    "CARGO_MAKEFLAGS",
+];
+
+fn main() {
+    let root = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap());
+    let libbos = root.join("libbos");
+    let out_dir = PathBuf::from(env::var("OUT_DIR").unwrap());
+    let target_dir = out_dir.join("user");
+
+    for path in ["src", "examples", "Cargo.toml", "x86_64-bos-user.json", ".cargo"] {
+        println!("cargo:rerun-if-changed={}", libbos.join(path).display());
+    }
+    println!("cargo:rerun-if-changed={}", root.join("bos-core/src").display());
+    println!("cargo:rerun-if-changed={}", root.join("initrd").display());
+
+    let mut archive = Vec::new();
+    add_tree(&mut archive, &root.join("initrd"), "");
+    // Répertoires des programmes et des points de montage
+    let examples = libbos.join("examples");
+    for dir in ["bin/", "mnt/", "tmp/"] {
+        add_entry(&mut archive, dir, EntryKind::Directory, 0o755, mtime(&examples), &[], "");
+    }
+
+    let cargo = env::var("CARGO").unwrap_or_else(|_| "cargo".into());
+    for program in PROGRAMS {
+        let mut command = Command::new(&cargo);
+        command
+            .current_dir(&libbos)
+            .args(["build", "--release", "--example", program, "--target-dir"])
+            .arg(&target_dir);
+        for variable in HIDDEN_VARIABLES {
+            command.env_remove(variable);
+        }
+        let status = command.status().expect("impossible de lancer cargo pour libbos");
+        assert!(status.success(), "échec de la compilation du programme {}", program);
+
+        let path = target_dir.join("x86_64-bos-user/release/examples").join(program);
+        let data = read(&path);
+        add_entry(&mut archive, &format!("bin/{}", program), EntryKind::File, 0o755, mtime(&path), &data, "");
+    }
+
+    // Fin de l'archive : deux blocs de zéros
+    archive.resize(archive.len() + 2 * BLOCK_SIZE, 0);
+    let initrd = out_dir.join("initrd.tar");
+    fs::write(&initrd, archive).unwrap_or_else(|error| panic!("écriture de {} : {}", initrd.display(), error));
+}
+
+/// Ajoute à l'archive le contenu du répertoire `dir`, sous le chemin
+/// `base` (trié par nom, pour une archive reproductible)
+fn add_tree(archive: &mut Vec<u8>, dir: &Path, base: &str) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    let mut entries: Vec<_> = entries.map(|entry| entry.unwrap().path()).collect();
+    entries.sort();
+    for path in entries {
+        let name = path.file_name().unwrap().to_str().expect("nom de fichier non UTF-8");
+        let archived = format!("{}{}", base, name);
+        let metadata = fs::symlink_metadata(&path).unwrap();
+        let mode = metadata.permissions().mode() & 0o777;
+        let mtime = mtime(&path);
+        if metadata.file_type().is_symlink() {
+            let target = fs::read_link(&path).unwrap();
+            let target = target.to_str().expect("cible de lien non UTF-8");
+            add_entry(archive, &archived, EntryKind::Symlink, mode, mtime, &[], target);
+        } else if metadata.is_dir() {
+            add_entry(archive, &format!("{}/", archived), EntryKind::Directory, mode, mtime, &[], "");
+            add_tree(archive, &path, &format!("{}/", archived));
+        } else {
+            add_entry(archive, &archived, EntryKind::File, mode, mtime, &read(&path), "");
+        }
+    }
+}
+
+/// Ajoute une entrée (en-tête et données complétées à un bloc) à l'archive
+fn add_entry(archive: &mut Vec<u8>, path: &str, kind: EntryKind, mode: u32, mtime: u64, data: &[u8], link: &str) {
+    let mut header = [0u8; BLOCK_SIZE];
+    tar::write_header(&mut header, path, kind, mode, data.len() as u64, mtime, link)
+        .unwrap_or_else(|error| panic!("initrd, {} : {}", path, error));
+    archive.extend_from_slice(&header);
+    archive.extend_from_slice(data);
+    archive.resize(archive.len().div_ceil(BLOCK_SIZE) * BLOCK_SIZE, 0);
+}
+
+/// Lit un fichier à mettre dans l'archive
+fn read(path: &Path) -> Vec<u8> {
+    fs::read(path).unwrap_or_else(|error| panic!("lecture de {} : {}", path.display(), error))
+}
+
+/// Date de modification d'un fichier (secondes depuis 1970)
+fn mtime(path: &Path) -> u64 {
+    let modified = fs::symlink_metadata(path).and_then(|metadata| metadata.modified());
+    modified.map_or(0, |time| time.duration_since(UNIX_EPOCH).map_or(0, |age| age.as_secs()))
+}
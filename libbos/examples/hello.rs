@@ -0,0 +1,43 @@
+// ===================================================================
+// HELLO : PREMIER PROGRAMME BOS EN RUST
+// ===================================================================
+//
+// Affiche son pid, ses arguments et son environnement, puis demande
+// un prénom au clavier. Inclus dans le kernel : `run hello [args]`.
+
+#![no_std]
+#![no_main]
+
+extern crate alloc;
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use libbos::{env, io, println, process};
+
+libbos::entry!(main);
+
+fn main() -> i32 {
+    println!("Bonjour depuis le ring 3 ! (pid {})", process::id());
+    let args: Vec<&str> = env::args().collect();
+    println!("Arguments : {:?}", args);
+    for (name, value) in env::vars() {
+        println!("  {}={}", name, value);
+    }
+    // Sans argument, le programme s'arrête là (utilisé par les tests)
+    if args.len() < 2 || args[1] != "-i" {
+        return 0;
+    }
+    libbos::print!("Votre prenom ? ");
+    let mut name = String::new();
+    match io::read_line(&mut name) {
+        Ok(_) => {
+            println!("Bonjour, {} !", name.trim());
+            0
+        }
+        Err(error) => {
+            libbos::eprintln!("lecture impossible : {}", error);
+            1
+        }
+    }
+}
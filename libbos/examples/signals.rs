@@ -0,0 +1,76 @@
+// ===================================================================
+// SIGNALS : DÉMONSTRATION DES SIGNAUX
+// ===================================================================
+//
+// - Sans argument : intercepte Ctrl+C (SIGINT) et programme une alarme
+//   (SIGALRM) toutes les 500 ms ; s'arrête après 3 Ctrl+C ou 10 alarmes
+// - `segv` : lit l'adresse 0x10 → le kernel le tue par SIGSEGV, avec
+//   un rapport (adresse fautive, registres)
+// - `fpe` : division par zéro → SIGFPE
+//
+// Inclus dans le kernel : `run signals [segv|fpe]`.
+
+#![no_std]
+#![no_main]
+
+use core::arch::asm;
+use core::sync::atomic::{AtomicU32, Ordering};
+
+use libbos::signal::{self, SIGALRM, SIGINT};
+use libbos::{env, println, process};
+
+libbos::entry!(main);
+
+/// Période de l'alarme
+const ALARM_MS: u64 = 500;
+
+static INTERRUPTS: AtomicU32 = AtomicU32::new(0);
+static ALARMS: AtomicU32 = AtomicU32::new(0);
+
+extern "C" fn on_interrupt(_signal: u8) {
+    let count = INTERRUPTS.fetch_add(1, Ordering::Relaxed) + 1;
+    println!("Ctrl+C recu ({}/3)", count);
+}
+
+extern "C" fn on_alarm(_signal: u8) {
+    ALARMS.fetch_add(1, Ordering::Relaxed);
+    signal::alarm(ALARM_MS);
+}
+
+fn main() -> i32 {
+    match env::args().nth(1) {
+        Some("segv") => unsafe {
+            let address = core::hint::black_box(0x10 as *const u8);
+            println!("Lecture de {:p}...", address);
+            core::ptr::read_volatile(address);
+        },
+        Some("fpe") => unsafe {
+            println!("Division par zero...");
+            asm!("div {0:e}", in(reg) 0u32, inout("eax") 1u32 => _, inout("edx") 0u32 => _);
+        },
+        Some(other) => {
+            libbos::eprintln!("usage : signals [segv|fpe] ({} inconnu)", other);
+            return 2;
+        }
+        None => {}
+    }
+    if signal::set_handler(SIGINT, on_interrupt).is_err() || signal::set_handler(SIGALRM, on_alarm).is_err() {
+        libbos::eprintln!("sigaction impossible");
+        return 1;
+    }
+    println!("Tapez Ctrl+C (3 fois pour quitter)");
+    signal::alarm(ALARM_MS);
+    let mut shown = 0;
+    while INTERRUPTS.load(Ordering::Relaxed) < 3 && shown < 10 {
+        // Interrompu par chaque signal
+        process::sleep_ms(10_000);
+        let alarms = ALARMS.load(Ordering::Relaxed);
+        if alarms != shown {
+            shown = alarms;
+            println!("Alarme {}", shown);
+        }
+    }
+    signal::alarm(0);
+    println!("Fin : {} Ctrl+C, {} alarmes", INTERRUPTS.load(Ordering::Relaxed), shown);
+    0
+}
@@ -0,0 +1,71 @@
+// ===================================================================
+// WC : COMPTE LES LIGNES, MOTS ET OCTETS DE FICHIERS
+// ===================================================================
+//
+// Lit chaque fichier avec l'appel système open (chemin depuis la
+// racine) et affiche ses lignes, mots et octets, puis le total s'il y
+// a plusieurs fichiers. Inclus dans le kernel : `wc /etc/motd`.
+
+#![no_std]
+#![no_main]
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+
+use libbos::io::{File, O_RDONLY};
+use libbos::{env, eprintln, println};
+
+libbos::entry!(main);
+
+/// Lignes, mots et octets
+#[derive(Default)]
+struct Counts {
+    lines: usize,
+    words: usize,
+    bytes: usize,
+}
+
+impl Counts {
+    fn of(data: &[u8]) -> Counts {
+        let lines = data.iter().filter(|&&byte| byte == b'\n').count();
+        let words = data.split(|byte| byte.is_ascii_whitespace()).filter(|word| !word.is_empty()).count();
+        Counts { lines, words, bytes: data.len() }
+    }
+
+    fn print(&self, name: &str) {
+        println!("{:>6} {:>6} {:>8} {}", self.lines, self.words, self.bytes, name);
+    }
+}
+
+fn main() -> i32 {
+    let paths: Vec<&str> = env::args().skip(1).collect();
+    if paths.is_empty() {
+        eprintln!("usage : wc <fichier>...");
+        return 2;
+    }
+    let mut total = Counts::default();
+    let mut status = 0;
+    for path in &paths {
+        let mut data = Vec::new();
+        let result = File::open(path, O_RDONLY).and_then(|file| {
+            let read = file.read_to_end(&mut data);
+            file.close()?;
+            read
+        });
+        if let Err(error) = result {
+            eprintln!("wc: {} : {}", path, error);
+            status = 1;
+            continue;
+        }
+        let counts = Counts::of(&data);
+        counts.print(path);
+        total.lines += counts.lines;
+        total.words += counts.words;
+        total.bytes += counts.bytes;
+    }
+    if paths.len() > 1 {
+        total.print("total");
+    }
+    status
+}
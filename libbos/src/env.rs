@@ -0,0 +1,82 @@
+// ===================================================================
+// ARGUMENTS, ENVIRONNEMENT ET VECTEUR AUXILIAIRE
+// ===================================================================
+//
+// Le kernel les dépose sur la pile du programme (voir bos_core::exec) ;
+// `_start` enregistre leur position avant d'appeler `main`. Les chaînes
+// restent sur la pile pendant toute la vie du programme.
+
+use core::ffi::CStr;
+use core::sync::atomic::{AtomicPtr, AtomicUsize, Ordering};
+
+pub use bos_core::exec::{AT_ENTRY, AT_NULL, AT_PAGESZ, AT_PHDR, AT_PHENT, AT_PHNUM};
+
+// Nombre d'arguments et tableaux de pointeurs laissés par le kernel
+static ARGC: AtomicUsize = AtomicUsize::new(0);
+static ARGV: AtomicPtr<*const u8> = AtomicPtr::new(core::ptr::null_mut());
+static ENVP: AtomicPtr<*const u8> = AtomicPtr::new(core::ptr::null_mut());
+static AUXV: AtomicPtr<u64> = AtomicPtr::new(core::ptr::null_mut());
+
+/// Enregistre la position des arguments, de l'environnement et de l'auxv
+///
+/// # Safety
+/// `stack` doit être le RSP de départ du programme.
+pub(crate) unsafe fn init(stack: *const u64) {
+    let argc = *stack as usize;
+    let argv = stack.add(1) as *mut *const u8;
+    let envp = argv.add(argc + 1);
+    let mut end = envp;
+    while !(*end).is_null() {
+        end = end.add(1);
+    }
+    ARGC.store(argc, Ordering::Relaxed);
+    ARGV.store(argv, Ordering::Relaxed);
+    ENVP.store(envp, Ordering::Relaxed);
+    AUXV.store(end.add(1) as *mut u64, Ordering::Relaxed);
+}
+
+/// Chaîne C laissée par le kernel (UTF-8 valide : elle vient du shell)
+unsafe fn string(ptr: *const u8) -> &'static str {
+    CStr::from_ptr(ptr.cast()).to_str().unwrap_or("")
+}
+
+/// Arguments du programme (le premier est son nom)
+pub fn args() -> impl Iterator<Item = &'static str> {
+    let argv = ARGV.load(Ordering::Relaxed);
+    (0..ARGC.load(Ordering::Relaxed)).map(move |i| unsafe { string(*argv.add(i)) })
+}
+
+/// Variables d'environnement, sous la forme (nom, valeur)
+pub fn vars() -> impl Iterator<Item = (&'static str, &'static str)> {
+    let mut envp = ENVP.load(Ordering::Relaxed);
+    core::iter::from_fn(move || unsafe {
+        if envp.is_null() || (*envp).is_null() {
+            return None;
+        }
+        let var = string(*envp);
+        envp = envp.add(1);
+        Some(var.split_once('=').unwrap_or((var, "")))
+    })
+}
+
+/// Valeur de la variable d'environnement `name`
+pub fn var(name: &str) -> Option<&'static str> {
+    vars().find(|&(key, _)| key == name).map(|(_, value)| value)
+}
+
+/// Valeur de l'entrée `kind` du vecteur auxiliaire (AT_PAGESZ...)
+pub fn auxv(kind: u64) -> Option<u64> {
+    let mut entry = AUXV.load(Ordering::Relaxed) as *const u64;
+    if entry.is_null() {
+        return None;
+    }
+    unsafe {
+        while *entry != AT_NULL {
+            if *entry == kind {
+                return Some(*entry.add(1));
+            }
+            entry = entry.add(2);
+        }
+    }
+    None
+}
@@ -0,0 +1,98 @@
+// ===================================================================
+// TAS DU PROGRAMME
+// ===================================================================
+//
+// L'allocateur global reprend l'allocateur à liste chaînée du kernel
+// (bos_core::heap). Le tas est vide au démarrage : quand une allocation
+// échoue, on l'agrandit d'au moins `GROW_SIZE` avec l'appel système brk,
+// puis on réessaie.
+
+use core::alloc::{GlobalAlloc, Layout};
+use core::cell::UnsafeCell;
+use core::sync::atomic::{AtomicBool, Ordering};
+
+use bos_core::heap::{HeapStats, LinkedListHeap};
+use bos_core::memory::PAGE_SIZE;
+
+use crate::syscall;
+
+/// Taille minimale d'un agrandissement du tas
+const GROW_SIZE: u64 = 64 * 1024;
+
+/// Tas et fin de la zone obtenue du kernel
+struct State {
+    heap: LinkedListHeap,
+    /// Fin actuelle (brk), 0 avant la première allocation
+    end: u64,
+}
+
+impl State {
+    /// Agrandit le tas pour qu'une allocation de `layout` y tienne
+    fn grow(&mut self, layout: Layout) -> bool {
+        if self.end == 0 {
+            self.end = syscall::brk(0);
+        }
+        let needed = (layout.size() + layout.align()) as u64;
+        let size = needed.max(GROW_SIZE).div_ceil(PAGE_SIZE) * PAGE_SIZE;
+        let start = self.end;
+        if syscall::brk(start + size) != start + size {
+            return false;
+        }
+        self.end = start + size;
+        unsafe { self.heap.extend(start as usize, size as usize) };
+        true
+    }
+}
+
+/// L'allocateur global du programme
+pub struct UserHeap {
+    state: UnsafeCell<State>,
+    /// Protège `state` (un gestionnaire de signal pourrait allouer)
+    locked: AtomicBool,
+}
+
+// L'accès à `state` passe par `locked`
+unsafe impl Sync for UserHeap {}
+
+impl UserHeap {
+    const fn new() -> UserHeap {
+        UserHeap {
+            state: UnsafeCell::new(State { heap: LinkedListHeap::empty(), end: 0 }),
+            locked: AtomicBool::new(false),
+        }
+    }
+
+    /// Exécute `f` sur le tas, verrou pris
+    fn with_state<R>(&self, f: impl FnOnce(&mut State) -> R) -> R {
+        while self.locked.swap(true, Ordering::Acquire) {
+            syscall::sched_yield();
+        }
+        let result = f(unsafe { &mut *self.state.get() });
+        self.locked.store(false, Ordering::Release);
+        result
+    }
+}
+
+unsafe impl GlobalAlloc for UserHeap {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        self.with_state(|state| {
+            let ptr = state.heap.allocate(layout);
+            if ptr.is_null() && state.grow(layout) {
+                return state.heap.allocate(layout);
+            }
+            ptr
+        })
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        self.with_state(|state| state.heap.deallocate(ptr, layout));
+    }
+}
+
+#[global_allocator]
+static HEAP: UserHeap = UserHeap::new();
+
+/// Statistiques du tas
+pub fn stats() -> HeapStats {
+    HEAP.with_state(|state| state.heap.stats())
+}
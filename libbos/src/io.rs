@@ -0,0 +1,183 @@
+// ===================================================================
+// ENTRÉES / SORTIES
+// ===================================================================
+//
+// Un `File` est un descripteur de fichier du processus. Les trois
+// premiers sont ouverts au démarrage : l'entrée standard (le clavier)
+// et les sorties standard et d'erreur (la console). `File::open` ouvre
+// un fichier du VFS, qui se lit, s'écrit et se parcourt (`seek`)
+// jusqu'à `close`.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::syscall::{self, SyscallError};
+
+pub use bos_core::vfs::{O_APPEND, O_CREAT, O_EXCL, O_RDONLY, O_RDWR, O_TRUNC, O_WRONLY, SEEK_CUR, SEEK_END, SEEK_SET};
+
+/// Entrée standard
+pub const STDIN: u64 = 0;
+/// Sortie standard
+pub const STDOUT: u64 = 1;
+/// Sortie d'erreur
+pub const STDERR: u64 = 2;
+
+/// Un descripteur de fichier
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct File {
+    fd: u64,
+}
+
+impl File {
+    /// Fichier désigné par le descripteur `fd`
+    pub const fn from_raw_fd(fd: u64) -> File {
+        File { fd }
+    }
+
+    /// Ouvre le fichier `path` avec les options `flags` (O_RDONLY,
+    /// O_WRONLY | O_CREAT...)
+    pub fn open(path: &str, flags: u64) -> Result<File, SyscallError> {
+        syscall::open(path, flags).map(File::from_raw_fd)
+    }
+
+    /// Descripteur du fichier
+    pub fn fd(&self) -> u64 {
+        self.fd
+    }
+
+    /// Ferme le descripteur
+    pub fn close(self) -> Result<(), SyscallError> {
+        syscall::close(self.fd)
+    }
+
+    /// Déplace la position (`whence` : SEEK_SET, SEEK_CUR ou SEEK_END),
+    /// retourne la nouvelle position
+    pub fn seek(&self, offset: i64, whence: u64) -> Result<u64, SyscallError> {
+        syscall::lseek(self.fd, offset, whence)
+    }
+
+    /// Lit au plus `buffer.len()` octets, retourne le nombre lu
+    pub fn read(&self, buffer: &mut [u8]) -> Result<usize, SyscallError> {
+        syscall::read(self.fd, buffer)
+    }
+
+    /// Lit jusqu'à la fin du fichier et ajoute le contenu à `data`,
+    /// retourne le nombre d'octets lus
+    pub fn read_to_end(&self, data: &mut Vec<u8>) -> Result<usize, SyscallError> {
+        let start = data.len();
+        let mut chunk = [0u8; 512];
+        loop {
+            match self.read(&mut chunk)? {
+                0 => return Ok(data.len() - start),
+                count => data.extend_from_slice(&chunk[..count]),
+            }
+        }
+    }
+
+    /// Écrit une partie de `buffer`, retourne le nombre d'octets écrits
+    pub fn write(&self, buffer: &[u8]) -> Result<usize, SyscallError> {
+        syscall::write(self.fd, buffer)
+    }
+
+    /// Écrit tout `buffer`
+    pub fn write_all(&self, mut buffer: &[u8]) -> Result<(), SyscallError> {
+        while !buffer.is_empty() {
+            let written = self.write(buffer)?;
+            buffer = &buffer[written..];
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Write for File {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.write_all(s.as_bytes()).map_err(|_| fmt::Error)
+    }
+}
+
+/// L'entrée standard
+pub fn stdin() -> File {
+    File::from_raw_fd(STDIN)
+}
+
+/// La sortie standard
+pub fn stdout() -> File {
+    File::from_raw_fd(STDOUT)
+}
+
+/// La sortie d'erreur
+pub fn stderr() -> File {
+    File::from_raw_fd(STDERR)
+}
+
+/// Lit une ligne sur l'entrée standard et l'ajoute à `line` (sans le
+/// '\n'), retourne le nombre de caractères ajoutés
+///
+/// Le backspace efface le dernier caractère de la ligne.
+pub fn read_line(line: &mut String) -> Result<usize, SyscallError> {
+    let start = line.len();
+    let mut pending = [0u8; 4];
+    let mut len = 0;
+    loop {
+        let mut byte = [0u8];
+        stdin().read(&mut byte)?;
+        match byte[0] {
+            b'\n' => return Ok(line.len() - start),
+            0x08 => {
+                if line.len() > start {
+                    line.pop();
+                }
+            }
+            byte => {
+                // Caractère UTF-8 reçu octet par octet
+                pending[len] = byte;
+                len += 1;
+                match core::str::from_utf8(&pending[..len]) {
+                    Ok(s) => {
+                        line.push_str(s);
+                        len = 0;
+                    }
+                    Err(error) if error.error_len().is_some() || len == pending.len() => len = 0,
+                    Err(_) => {}
+                }
+            }
+        }
+    }
+}
+
+#[doc(hidden)]
+pub fn _print(args: fmt::Arguments) {
+    let _ = fmt::Write::write_fmt(&mut stdout(), args);
+}
+
+#[doc(hidden)]
+pub fn _eprint(args: fmt::Arguments) {
+    let _ = fmt::Write::write_fmt(&mut stderr(), args);
+}
+
+/// Affiche sur la sortie standard
+#[macro_export]
+macro_rules! print {
+    ($($arg:tt)*) => ($crate::io::_print(format_args!($($arg)*)));
+}
+
+/// Affiche sur la sortie standard, avec un retour à la ligne
+#[macro_export]
+macro_rules! println {
+    () => ($crate::print!("\n"));
+    ($($arg:tt)*) => ($crate::print!("{}\n", format_args!($($arg)*)));
+}
+
+/// Affiche sur la sortie d'erreur
+#[macro_export]
+macro_rules! eprint {
+    ($($arg:tt)*) => ($crate::io::_eprint(format_args!($($arg)*)));
+}
+
+/// Affiche sur la sortie d'erreur, avec un retour à la ligne
+#[macro_export]
+macro_rules! eprintln {
+    () => ($crate::eprint!("\n"));
+    ($($arg:tt)*) => ($crate::eprint!("{}\n", format_args!($($arg)*)));
+}
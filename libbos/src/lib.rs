@@ -0,0 +1,40 @@
+// ===================================================================
+// LIBBOS : BIBLIOTHÈQUE DES PROGRAMMES UTILISATEUR DE BOS
+// ===================================================================
+//
+// Tout ce qu'il faut pour écrire un programme BOS en Rust `no_std` :
+// - Le point d'entrée `_start` : il lit argc, argv, envp et l'auxv sur
+//   la pile, puis appelle la fonction déclarée avec `entry!`
+// - Un panic handler qui affiche le message et termine le programme
+// - Les appels système (`syscall`), avec les numéros de bos-core
+// - Un allocateur global qui agrandit le tas avec l'appel système brk :
+//   `alloc` (Box, Vec, String...) est disponible
+// - Les entrées/sorties (`io`) : `print!`, `println!`, `File`, stdin
+// - Les arguments et l'environnement (`env`), le processus (`process`)
+// - Les signaux (`signal`) : handlers, masque, alarme
+//
+// Un programme minimal :
+//
+//     #![no_std]
+//     #![no_main]
+//
+//     libbos::entry!(main);
+//
+//     fn main() {
+//         libbos::println!("Bonjour !");
+//     }
+
+#![no_std]
+
+extern crate alloc;
+
+pub mod env;
+pub mod heap;
+pub mod io;
+pub mod process;
+pub mod signal;
+pub mod syscall;
+
+mod start;
+
+pub use start::Termination;
@@ -0,0 +1,68 @@
+// ===================================================================
+// PROCESSUS
+// ===================================================================
+//
+// Le processus en cours, et ses enfants : `spawn` lance un programme,
+// `wait` attend sa fin et donne son statut (code de sortie ou signal).
+
+use alloc::vec::Vec;
+
+use bos_core::process::{WAIT_ANY, WNOHANG};
+
+pub use bos_core::process::ExitStatus;
+
+use crate::syscall::{self, SyscallError};
+
+/// Termine le programme avec le code `code`
+pub fn exit(code: i32) -> ! {
+    syscall::exit(code)
+}
+
+/// Identifiant du processus
+pub fn id() -> u64 {
+    syscall::getpid()
+}
+
+/// Identifiant du processus parent (0 : lancé par le kernel)
+pub fn parent_id() -> u64 {
+    syscall::getppid()
+}
+
+/// Cède le CPU aux autres threads
+pub fn yield_now() {
+    syscall::sched_yield();
+}
+
+/// Dort au moins `ms` millisecondes
+pub fn sleep_ms(ms: u64) {
+    syscall::sleep(ms);
+}
+
+/// Lance le programme `program` avec les arguments `args`, retourne le
+/// pid du processus enfant
+pub fn spawn(program: &str, args: &[&str]) -> Result<u64, SyscallError> {
+    let mut argv = Vec::with_capacity(args.len() + 1);
+    argv.push(program);
+    argv.extend_from_slice(args);
+    syscall::spawn(program, &argv)
+}
+
+/// Attend la fin de l'enfant `pid`
+pub fn wait(pid: u64) -> Result<ExitStatus, SyscallError> {
+    syscall::waitpid(pid, 0).map(|(_, status)| ExitStatus::decode(status))
+}
+
+/// Attend la fin de n'importe quel enfant, retourne son pid et son statut
+pub fn wait_any() -> Result<(u64, ExitStatus), SyscallError> {
+    syscall::waitpid(WAIT_ANY, 0).map(|(pid, status)| (pid, ExitStatus::decode(status)))
+}
+
+/// Statut de l'enfant `pid` s'il est terminé, sans attendre
+pub fn try_wait(pid: u64) -> Result<Option<ExitStatus>, SyscallError> {
+    syscall::waitpid(pid, WNOHANG).map(|(pid, status)| (pid != 0).then(|| ExitStatus::decode(status)))
+}
+
+/// Envoie le signal `signal` au processus `pid` (voir `signal`)
+pub fn kill(pid: u64, signal: u8) -> Result<(), SyscallError> {
+    syscall::kill(pid, signal)
+}
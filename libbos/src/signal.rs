@@ -0,0 +1,97 @@
+// ===================================================================
+// SIGNAUX
+// ===================================================================
+//
+// Un programme peut intercepter un signal (Ctrl+C, SIGALRM, SIGSEGV...)
+// avec un handler, l'ignorer, ou le bloquer le temps d'une opération.
+//
+// Le kernel appelle le handler avec le numéro du signal sur une trame
+// qu'il a empilée ; le handler retourne vers `__libbos_restorer`, qui
+// fait sigreturn pour reprendre le programme là où il a été interrompu.
+//
+//     extern "C" fn on_interrupt(_signal: u8) {
+//         INTERRUPTED.store(true, Ordering::Relaxed);
+//     }
+//
+//     signal::set_handler(signal::SIGINT, on_interrupt)?;
+
+use core::arch::global_asm;
+
+use bos_core::signal::{SigAction, SIG_BLOCK, SIG_DFL, SIG_IGN, SIG_SETMASK, SIG_UNBLOCK};
+
+pub use bos_core::signal::{
+    signal_name, SigSet, SIGABRT, SIGALRM, SIGBUS, SIGCHLD, SIGCONT, SIGFPE, SIGHUP, SIGILL, SIGINT, SIGKILL,
+    SIGPIPE, SIGQUIT, SIGSEGV, SIGSTOP, SIGTERM, SIGTRAP, SIGTSTP, SIGUSR1, SIGUSR2,
+};
+
+use crate::process;
+use crate::syscall::{self, SyscallError};
+
+/// Handler d'un signal : reçoit son numéro
+pub type Handler = extern "C" fn(u8);
+
+// Adresse de retour des handlers : à son entrée, RSP pointe juste
+// après le premier mot de la trame de signal (le `restorer` dépilé par RET)
+global_asm!(
+    ".global __libbos_restorer",
+    "__libbos_restorer:",
+    "    lea rdi, [rsp - 8]",
+    "    mov eax, {sigreturn}",
+    "    syscall",
+    "    ud2",
+    sigreturn = const syscall::SYS_SIGRETURN,
+);
+
+extern "C" {
+    fn __libbos_restorer();
+}
+
+/// Appelle `handler` à chaque réception de `signal` ; le signal est
+/// bloqué pendant le handler
+pub fn set_handler(signal: u8, handler: Handler) -> Result<(), SyscallError> {
+    let action = SigAction {
+        handler: handler as usize as u64,
+        mask: 0,
+        restorer: __libbos_restorer as *const () as u64,
+    };
+    syscall::sigaction(signal, Some(&action)).map(|_| ())
+}
+
+/// Ignore `signal`
+pub fn ignore(signal: u8) -> Result<(), SyscallError> {
+    let action = SigAction { handler: SIG_IGN, ..SigAction::default() };
+    syscall::sigaction(signal, Some(&action)).map(|_| ())
+}
+
+/// Rétablit l'action par défaut de `signal`
+pub fn reset(signal: u8) -> Result<(), SyscallError> {
+    let action = SigAction { handler: SIG_DFL, ..SigAction::default() };
+    syscall::sigaction(signal, Some(&action)).map(|_| ())
+}
+
+/// Bloque les signaux de `set` (ils attendent d'être débloqués),
+/// retourne l'ancien masque
+pub fn block(set: SigSet) -> Result<SigSet, SyscallError> {
+    syscall::sigprocmask(SIG_BLOCK, Some(set.0)).map(SigSet)
+}
+
+/// Débloque les signaux de `set`, retourne l'ancien masque
+pub fn unblock(set: SigSet) -> Result<SigSet, SyscallError> {
+    syscall::sigprocmask(SIG_UNBLOCK, Some(set.0)).map(SigSet)
+}
+
+/// Remplace le masque des signaux bloqués, retourne l'ancien
+pub fn set_mask(set: SigSet) -> Result<SigSet, SyscallError> {
+    syscall::sigprocmask(SIG_SETMASK, Some(set.0)).map(SigSet)
+}
+
+/// Envoie `signal` au processus lui-même
+pub fn raise(signal: u8) -> Result<(), SyscallError> {
+    process::kill(process::id(), signal)
+}
+
+/// Envoie SIGALRM au processus dans `ms` millisecondes (0 : annule),
+/// retourne le temps restant de l'alarme précédente
+pub fn alarm(ms: u64) -> u64 {
+    syscall::alarm(ms)
+}
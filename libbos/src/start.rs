@@ -0,0 +1,78 @@
+// ===================================================================
+// POINT D'ENTRÉE ET PANIC HANDLER
+// ===================================================================
+//
+// Le kernel saute à `_start` avec RSP sur argc. `_start` passe ce
+// pointeur à `start`, qui enregistre les arguments puis appelle la
+// fonction `main` du programme, déclarée avec `entry!`. Son résultat
+// devient le code de sortie.
+
+use core::arch::global_asm;
+use core::panic::PanicInfo;
+
+use crate::{env, process};
+
+/// Code de sortie d'un programme qui panique (comme Rust sur Linux)
+const PANIC_EXIT_CODE: i32 = 101;
+
+// La pile est déjà alignée sur 16 octets, mais on ne le suppose pas :
+// `call` doit laisser RSP à 16n + 8 à l'entrée de `start`
+global_asm!(
+    ".global _start",
+    "_start:",
+    "    mov rdi, rsp",
+    "    and rsp, -16",
+    "    xor ebp, ebp",
+    "    call {start}",
+    "    ud2",
+    start = sym start,
+);
+
+extern "Rust" {
+    // Définie par `entry!` dans le programme
+    fn __libbos_main() -> i32;
+}
+
+/// Appelée par `_start` avec le RSP de départ
+unsafe extern "C" fn start(stack: *const u64) -> ! {
+    env::init(stack);
+    process::exit(__libbos_main())
+}
+
+/// Valeur de retour possible de `main`
+pub trait Termination {
+    /// Code de sortie du programme
+    fn code(self) -> i32;
+}
+
+impl Termination for () {
+    fn code(self) -> i32 {
+        0
+    }
+}
+
+impl Termination for i32 {
+    fn code(self) -> i32 {
+        self
+    }
+}
+
+/// Déclare la fonction principale du programme
+///
+/// Elle ne prend pas d'argument (voir `env::args`) et retourne `()` ou
+/// un code de sortie `i32`.
+#[macro_export]
+macro_rules! entry {
+    ($main:path) => {
+        #[no_mangle]
+        fn __libbos_main() -> i32 {
+            $crate::Termination::code($main())
+        }
+    };
+}
+
+#[panic_handler]
+fn panic(info: &PanicInfo) -> ! {
+    crate::eprintln!("panic: {}", info);
+    process::exit(PANIC_EXIT_CODE)
+}
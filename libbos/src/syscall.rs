@@ -0,0 +1,167 @@
+// ===================================================================
+// APPELS SYSTÈME
+// ===================================================================
+//
+// Convention (voir bos_core::syscall) : numéro dans RAX, arguments dans
+// RDI, RSI, RDX, R10, R8 et R9, résultat dans RAX (-code si erreur).
+// SYSCALL écrase RCX et R11.
+
+use alloc::vec::Vec;
+use core::arch::asm;
+
+use bos_core::signal::SigAction;
+
+pub use bos_core::syscall::*;
+
+/// Fait l'appel système `number` avec ses 6 arguments, retourne RAX
+///
+/// # Safety
+/// Les arguments doivent être ceux attendus par l'appel système : un
+/// pointeur invalide donne une erreur, mais un pointeur valide vers la
+/// mauvaise donnée peut l'écraser.
+pub unsafe fn syscall(number: u64, args: [u64; 6]) -> u64 {
+    let result;
+    asm!(
+        "syscall",
+        inlateout("rax") number => result,
+        in("rdi") args[0],
+        in("rsi") args[1],
+        in("rdx") args[2],
+        in("r10") args[3],
+        in("r8") args[4],
+        in("r9") args[5],
+        lateout("rcx") _,
+        lateout("r11") _,
+        options(nostack)
+    );
+    result
+}
+
+/// Résultat d'un appel système d'après RAX
+fn check(rax: u64) -> Result<u64, SyscallError> {
+    // Un code inconnu viendrait d'un kernel plus récent que libbos
+    decode_result(rax).map_err(|code| SyscallError::from_code(code).unwrap_or(SyscallError::InvalidArgument))
+}
+
+/// Écrit `buffer` sur le descripteur `fd`, retourne le nombre d'octets écrits
+pub fn write(fd: u64, buffer: &[u8]) -> Result<usize, SyscallError> {
+    let rax = unsafe { syscall(SYS_WRITE, [fd, buffer.as_ptr() as u64, buffer.len() as u64, 0, 0, 0]) };
+    check(rax).map(|count| count as usize)
+}
+
+/// Lit au plus `buffer.len()` octets sur le descripteur `fd` (attend au
+/// moins un octet), retourne le nombre d'octets lus
+pub fn read(fd: u64, buffer: &mut [u8]) -> Result<usize, SyscallError> {
+    let rax = unsafe { syscall(SYS_READ, [fd, buffer.as_mut_ptr() as u64, buffer.len() as u64, 0, 0, 0]) };
+    check(rax).map(|count| count as usize)
+}
+
+/// Termine le processus
+pub fn exit(code: i32) -> ! {
+    unsafe {
+        syscall(SYS_EXIT, [code as u64, 0, 0, 0, 0, 0]);
+    }
+    unreachable!("exit est revenu");
+}
+
+/// Cède le CPU
+pub fn sched_yield() {
+    unsafe {
+        syscall(SYS_YIELD, [0; 6]);
+    }
+}
+
+/// Dort au moins `ms` millisecondes
+pub fn sleep(ms: u64) {
+    unsafe {
+        syscall(SYS_SLEEP, [ms, 0, 0, 0, 0, 0]);
+    }
+}
+
+/// Identifiant du processus
+pub fn getpid() -> u64 {
+    unsafe { syscall(SYS_GETPID, [0; 6]) }
+}
+
+/// Déplace la fin du tas à `end` (0 : la donne seulement), retourne la
+/// fin obtenue
+pub fn brk(end: u64) -> u64 {
+    unsafe { syscall(SYS_BRK, [end, 0, 0, 0, 0, 0]) }
+}
+
+/// Lance le programme `name` dans un processus enfant avec les
+/// arguments `args` (le premier est le nom du programme), retourne
+/// son pid
+pub fn spawn(name: &str, args: &[&str]) -> Result<u64, SyscallError> {
+    let argv: Vec<[u64; 2]> = args.iter().map(|arg| [arg.as_ptr() as u64, arg.len() as u64]).collect();
+    let rax = unsafe {
+        syscall(
+            SYS_SPAWN,
+            [name.as_ptr() as u64, name.len() as u64, argv.as_ptr() as u64, argv.len() as u64, 0, 0],
+        )
+    };
+    check(rax)
+}
+
+/// Attend la fin d'un enfant (`pid` ou `WAIT_ANY`), retourne son pid
+/// et son statut ; avec `WNOHANG`, le pid vaut 0 si aucun n'est terminé
+pub fn waitpid(pid: u64, options: u64) -> Result<(u64, u32), SyscallError> {
+    let mut status = 0u32;
+    let rax = unsafe { syscall(SYS_WAITPID, [pid, &mut status as *mut u32 as u64, options, 0, 0, 0]) };
+    check(rax).map(|pid| (pid, status))
+}
+
+/// Envoie le signal `signal` au processus `pid`
+pub fn kill(pid: u64, signal: u8) -> Result<(), SyscallError> {
+    let rax = unsafe { syscall(SYS_KILL, [pid, signal as u64, 0, 0, 0, 0]) };
+    check(rax).map(|_| ())
+}
+
+/// Identifiant du processus parent (0 : le kernel)
+pub fn getppid() -> u64 {
+    unsafe { syscall(SYS_GETPPID, [0; 6]) }
+}
+
+/// Change l'action du signal `signal` (si `action` est donnée),
+/// retourne l'ancienne
+pub fn sigaction(signal: u8, action: Option<&SigAction>) -> Result<SigAction, SyscallError> {
+    let mut old = SigAction::default();
+    let new = action.map_or(0, |action| action as *const SigAction as u64);
+    let rax = unsafe { syscall(SYS_SIGACTION, [signal as u64, new, &mut old as *mut SigAction as u64, 0, 0, 0]) };
+    check(rax).map(|_| old)
+}
+
+/// Change le masque des signaux bloqués (`how` : SIG_BLOCK, SIG_UNBLOCK
+/// ou SIG_SETMASK) si `set` est donné, retourne l'ancien
+pub fn sigprocmask(how: u64, set: Option<u64>) -> Result<u64, SyscallError> {
+    let mut old = 0u64;
+    let new = set.as_ref().map_or(0, |set| set as *const u64 as u64);
+    let rax = unsafe { syscall(SYS_SIGPROCMASK, [how, new, &mut old as *mut u64 as u64, 0, 0, 0]) };
+    check(rax).map(|_| old)
+}
+
+/// Envoie SIGALRM au processus dans `ms` millisecondes (0 : annule),
+/// retourne le temps restant de l'alarme précédente
+pub fn alarm(ms: u64) -> u64 {
+    unsafe { syscall(SYS_ALARM, [ms, 0, 0, 0, 0, 0]) }
+}
+
+/// Ouvre le fichier `path` (relatif à la racine) avec les options
+/// `flags` (O_RDONLY, O_CREAT...), retourne son descripteur
+pub fn open(path: &str, flags: u64) -> Result<u64, SyscallError> {
+    let rax = unsafe { syscall(SYS_OPEN, [path.as_ptr() as u64, path.len() as u64, flags, 0, 0, 0]) };
+    check(rax)
+}
+
+/// Ferme le descripteur `fd`
+pub fn close(fd: u64) -> Result<(), SyscallError> {
+    let rax = unsafe { syscall(SYS_CLOSE, [fd, 0, 0, 0, 0, 0]) };
+    check(rax).map(|_| ())
+}
+
+/// Déplace la position du fichier `fd` (`whence` : SEEK_SET, SEEK_CUR
+/// ou SEEK_END), retourne la nouvelle position
+pub fn lseek(fd: u64, offset: i64, whence: u64) -> Result<u64, SyscallError> {
+    let rax = unsafe { syscall(SYS_LSEEK, [fd, offset as u64, whence, 0, 0, 0]) };
+    check(rax)
+}
@@ -0,0 +1,146 @@
+/* =========================================================
+   BACKTRACE - REMONTÉE DE LA PILE D'APPELS
+
+   Le kernel est compilé avec `-C force-frame-pointers=yes`
+   (voir .cargo/config.toml) : chaque fonction commence par
+
+       push rbp
+       mov rbp, rsp
+
+   RBP pointe donc toujours sur une "frame" de la forme :
+
+       [rbp]     → RBP de la fonction appelante
+       [rbp + 8] → adresse de retour dans la fonction appelante
+
+   En suivant cette chaîne, on retrouve toutes les fonctions
+   appelantes. Chaque adresse est ensuite résolue en nom de
+   fonction grâce à la table des symboles (symbols.rs) :
+
+       #0 0x20a3f1 bos::panic::kernel_panic+0x51
+       #1 0x2041c2 bos::shell::Shell::execute+0x1b2
+========================================================= */
+
+use core::arch::asm;
+use core::fmt::{self, Write};
+
+use crate::symbols;
+
+/// Nombre maximum de frames affichées (protège contre une chaîne en boucle)
+pub const MAX_FRAMES: usize = 32;
+
+/// Écart maximum entre deux frames consécutives : au-delà, RBP ne
+/// pointe probablement plus dans la même pile
+const MAX_FRAME_SIZE: u64 = 1024 * 1024;
+
+/// Lit le registre RBP de la fonction appelante
+#[inline(always)]
+pub fn frame_pointer() -> u64 {
+    let rbp: u64;
+    unsafe {
+        asm!("mov {}, rbp", out(reg) rbp, options(nomem, nostack, preserves_flags));
+    }
+    rbp
+}
+
+/// Dans un handler d'interruption, lit le RBP du code interrompu
+///
+/// Le prologue du handler a sauvegardé ce RBP en haut de sa propre frame.
+#[inline(always)]
+pub fn interrupted_frame_pointer() -> u64 {
+    let rbp = frame_pointer();
+    unsafe { *(rbp as *const u64) }
+}
+
+/// Itérateur sur les adresses de retour de la chaîne de frames
+pub struct Frames {
+    rbp: u64,
+    count: usize,
+}
+
+impl Frames {
+    /// Commence la remontée à partir de la frame pointée par `rbp`
+    pub fn new(rbp: u64) -> Frames {
+        Frames { rbp, count: 0 }
+    }
+}
+
+impl Iterator for Frames {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<u64> {
+        if self.rbp == 0 || !self.rbp.is_multiple_of(8) || self.count >= MAX_FRAMES {
+            return None;
+        }
+        let (caller_rbp, return_address) = unsafe {
+            let frame = self.rbp as *const u64;
+            (*frame, *frame.add(1))
+        };
+        if !symbols::is_code_address(return_address) {
+            return None;
+        }
+        // La pile grandit vers le bas : les appelants sont plus haut
+        self.rbp = if caller_rbp > self.rbp && caller_rbp - self.rbp <= MAX_FRAME_SIZE {
+            caller_rbp
+        } else {
+            0
+        };
+        self.count += 1;
+        Some(return_address)
+    }
+}
+
+/// Écrit une ligne "#n 0xADDR symbole+0xdécalage"
+fn write_frame(out: &mut dyn Write, index: usize, addr: u64, is_return_address: bool) -> fmt::Result {
+    // Une adresse de retour pointe juste APRÈS l'appel, qui peut être la
+    // dernière instruction de la fonction : on résout l'octet précédent
+    let lookup = if is_return_address { addr - 1 } else { addr };
+    match symbols::resolve(lookup) {
+        Some((name, offset)) => {
+            let offset = offset + (addr - lookup);
+            writeln!(out, "#{} {:#x} {}+{:#x}", index, addr, name, offset)
+        }
+        None => writeln!(out, "#{} {:#x} ???", index, addr),
+    }
+}
+
+/// Affiche la pile d'appels à partir de la frame pointée par `rbp`
+pub fn write_backtrace(out: &mut dyn Write, rbp: u64) -> fmt::Result {
+    for (index, addr) in Frames::new(rbp).enumerate() {
+        write_frame(out, index, addr, true)?;
+    }
+    Ok(())
+}
+
+/// Affiche la pile d'appels d'un code interrompu par une exception
+///
+/// `rip` est l'instruction fautive (frame #0), `rbp` la valeur de RBP au
+/// moment de l'exception (voir `interrupted_frame_pointer`).
+pub fn write_exception_backtrace(out: &mut dyn Write, rip: u64, rbp: u64) -> fmt::Result {
+    write_frame(out, 0, rip, false)?;
+    for (index, addr) in Frames::new(rbp).enumerate() {
+        write_frame(out, index + 1, addr, true)?;
+    }
+    Ok(())
+}
+
+// ===================================================================
+// TESTS
+// ===================================================================
+
+#[cfg(test)]
+#[inline(never)]
+fn nested_frames() -> usize {
+    Frames::new(frame_pointer()).count()
+}
+
+#[test_case]
+fn test_walk_finds_callers() {
+    // nested_frames → ce test → runner de tests → ... → _start
+    assert!(nested_frames() >= 3);
+}
+
+#[test_case]
+fn test_walk_rejects_bad_frame_pointer() {
+    assert_eq!(Frames::new(0).count(), 0);
+    assert_eq!(Frames::new(0x1003).count(), 0);
+}
@@ -0,0 +1,448 @@
+/* =========================================================
+   CANAUX DE MESSAGES
+
+   Un canal est une file bornée de messages entre des producteurs
+   et un consommateur (MPSC ; avec un seul `Sender`, c'est un canal
+   SPSC) :
+
+     let (tx, rx) = channel::channel(16);
+     thread::spawn("producteur", move || tx.send(42).unwrap());
+     assert_eq!(rx.recv(), Ok(42));
+
+   - Un thread qui reçoit sur un canal vide (ou envoie sur un canal
+     plein) est bloqué dans une WaitQueue et rend le CPU ;
+     `recv_timeout` abandonne au bout d'un délai.
+   - Une tâche asynchrone lit le canal comme un `Stream` : son waker
+     est appelé à l'arrivée d'un message.
+   - `try_send` n'attend jamais : c'est la seule façon d'envoyer
+     depuis un handler d'interruption (le clavier dépose ainsi ses
+     scancodes pour le shell).
+
+   Un canal peut aussi être un static (`Channel::new`), partagé par
+   référence. Avec `channel()`, il est fermé quand le `Receiver` ou
+   le dernier `Sender` disparaît : les messages en attente restent
+   lisibles, puis `recv` retourne une erreur.
+========================================================= */
+
+use alloc::collections::VecDeque;
+use alloc::sync::Arc;
+use core::fmt;
+use core::pin::Pin;
+use core::task::{Context, Poll, Waker};
+
+use crate::sync::{Spinlock, WaitQueue};
+use crate::task::Stream;
+
+/// État d'un canal, protégé par un spinlock
+struct State<T> {
+    messages: VecDeque<T>,
+    closed: bool,
+    /// Tâche asynchrone qui attend un message
+    waker: Option<Waker>,
+}
+
+/// File bornée de messages
+pub struct Channel<T> {
+    state: Spinlock<State<T>>,
+    capacity: usize,
+    /// Threads qui attendent un message
+    not_empty: WaitQueue,
+    /// Threads qui attendent une place libre
+    not_full: WaitQueue,
+}
+
+impl<T> Channel<T> {
+    /// Crée un canal de `capacity` messages au plus (au moins 1)
+    pub const fn new(capacity: usize) -> Channel<T> {
+        Channel {
+            state: Spinlock::new(State {
+                messages: VecDeque::new(),
+                closed: false,
+                waker: None,
+            }),
+            capacity: if capacity == 0 { 1 } else { capacity },
+            not_empty: WaitQueue::new(),
+            not_full: WaitQueue::new(),
+        }
+    }
+
+    /// Nombre maximum de messages en attente
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Nombre de messages en attente
+    pub fn len(&self) -> usize {
+        self.state.lock().messages.len()
+    }
+
+    /// Indique si aucun message n'attend
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Indique si le canal est fermé
+    pub fn is_closed(&self) -> bool {
+        self.state.lock().closed
+    }
+
+    /// Ferme le canal et réveille tous ceux qui l'attendent
+    pub fn close(&self) {
+        let waker = {
+            let mut state = self.state.lock();
+            state.closed = true;
+            state.waker.take()
+        };
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+        self.not_empty.wake_all();
+        self.not_full.wake_all();
+    }
+
+    /// Envoie un message sans attendre (utilisable dans un handler
+    /// d'interruption)
+    pub fn try_send(&self, message: T) -> Result<(), TrySendError<T>> {
+        let waker = {
+            let mut state = self.state.lock();
+            if state.closed {
+                return Err(TrySendError::Closed(message));
+            }
+            if state.messages.len() >= self.capacity {
+                return Err(TrySendError::Full(message));
+            }
+            state.messages.push_back(message);
+            state.waker.take()
+        };
+        // Réveils faits verrou rendu
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+        self.not_empty.wake_one();
+        Ok(())
+    }
+
+    /// Envoie un message, en dormant tant que le canal est plein
+    pub fn send(&self, message: T) -> Result<(), SendError<T>> {
+        let mut pending = Some(message);
+        let mut result = Ok(());
+        self.not_full.wait_until(|| {
+            let Some(message) = pending.take() else {
+                return true;
+            };
+            match self.try_send(message) {
+                Ok(()) => true,
+                Err(TrySendError::Full(message)) => {
+                    pending = Some(message);
+                    false
+                }
+                Err(TrySendError::Closed(message)) => {
+                    result = Err(SendError(message));
+                    true
+                }
+            }
+        });
+        result
+    }
+
+    /// Reçoit un message s'il y en a un
+    pub fn try_recv(&self) -> Result<T, TryRecvError> {
+        let message = {
+            let mut state = self.state.lock();
+            match state.messages.pop_front() {
+                Some(message) => message,
+                None if state.closed => return Err(TryRecvError::Closed),
+                None => return Err(TryRecvError::Empty),
+            }
+        };
+        self.not_full.wake_one();
+        Ok(message)
+    }
+
+    /// Reçoit un message, en dormant tant que le canal est vide
+    pub fn recv(&self) -> Result<T, RecvError> {
+        let mut result = Err(RecvError);
+        self.not_empty.wait_until(|| self.poll_try_recv(&mut result));
+        result
+    }
+
+    /// Comme `recv`, en abandonnant au bout de `timeout_ms` millisecondes
+    pub fn recv_timeout(&self, timeout_ms: u64) -> Result<T, RecvTimeoutError> {
+        let mut result = Err(RecvError);
+        if self.not_empty.wait_until_timeout(timeout_ms, || self.poll_try_recv(&mut result)) {
+            result.map_err(|_| RecvTimeoutError::Closed)
+        } else {
+            Err(RecvTimeoutError::Timeout)
+        }
+    }
+
+    /// Condition d'attente de `recv` : vraie quand `result` est définitif
+    fn poll_try_recv(&self, result: &mut Result<T, RecvError>) -> bool {
+        match self.try_recv() {
+            Ok(message) => {
+                *result = Ok(message);
+                true
+            }
+            Err(TryRecvError::Closed) => true,
+            Err(TryRecvError::Empty) => false,
+        }
+    }
+
+    /// Version asynchrone de `recv` : `Ready(None)` quand le canal est
+    /// fermé et vide, `Pending` sinon (le waker sera appelé)
+    pub fn poll_recv(&self, context: &mut Context) -> Poll<Option<T>> {
+        let message = {
+            // Vérifier la file et enregistrer le waker sous le même verrou :
+            // un message ne peut pas arriver sans nous réveiller
+            let mut state = self.state.lock();
+            match state.messages.pop_front() {
+                Some(message) => message,
+                None if state.closed => return Poll::Ready(None),
+                None => {
+                    state.waker = Some(context.waker().clone());
+                    return Poll::Pending;
+                }
+            }
+        };
+        self.not_full.wake_one();
+        Poll::Ready(Some(message))
+    }
+}
+
+/* =========================================================
+   EXTRÉMITÉS D'UN CANAL
+========================================================= */
+
+/// Crée un canal de `capacity` messages, retourne ses deux extrémités
+pub fn channel<T>(capacity: usize) -> (Sender<T>, Receiver<T>) {
+    let shared = Arc::new(Shared {
+        channel: Channel::new(capacity),
+        senders: Spinlock::new(1),
+    });
+    // Place réservée d'avance : pas d'allocation à l'envoi
+    shared.channel.state.lock().messages.reserve_exact(shared.channel.capacity);
+    (Sender { shared: shared.clone() }, Receiver { shared })
+}
+
+struct Shared<T> {
+    channel: Channel<T>,
+    /// Nombre de `Sender` vivants
+    senders: Spinlock<usize>,
+}
+
+/// Extrémité d'envoi, clonable (plusieurs producteurs)
+pub struct Sender<T> {
+    shared: Arc<Shared<T>>,
+}
+
+impl<T> Sender<T> {
+    /// Voir `Channel::send`
+    pub fn send(&self, message: T) -> Result<(), SendError<T>> {
+        self.shared.channel.send(message)
+    }
+
+    /// Voir `Channel::try_send`
+    pub fn try_send(&self, message: T) -> Result<(), TrySendError<T>> {
+        self.shared.channel.try_send(message)
+    }
+
+    /// Indique si le `Receiver` a disparu
+    pub fn is_closed(&self) -> bool {
+        self.shared.channel.is_closed()
+    }
+}
+
+impl<T> Clone for Sender<T> {
+    fn clone(&self) -> Self {
+        *self.shared.senders.lock() += 1;
+        Sender { shared: self.shared.clone() }
+    }
+}
+
+impl<T> Drop for Sender<T> {
+    fn drop(&mut self) {
+        let last = {
+            let mut senders = self.shared.senders.lock();
+            *senders -= 1;
+            *senders == 0
+        };
+        if last {
+            self.shared.channel.close();
+        }
+    }
+}
+
+/// Extrémité de réception (un seul consommateur)
+pub struct Receiver<T> {
+    shared: Arc<Shared<T>>,
+}
+
+impl<T> Receiver<T> {
+    /// Voir `Channel::recv`
+    pub fn recv(&self) -> Result<T, RecvError> {
+        self.shared.channel.recv()
+    }
+
+    /// Voir `Channel::try_recv`
+    pub fn try_recv(&self) -> Result<T, TryRecvError> {
+        self.shared.channel.try_recv()
+    }
+
+    /// Voir `Channel::recv_timeout`
+    pub fn recv_timeout(&self, timeout_ms: u64) -> Result<T, RecvTimeoutError> {
+        self.shared.channel.recv_timeout(timeout_ms)
+    }
+
+    /// Nombre de messages en attente
+    pub fn len(&self) -> usize {
+        self.shared.channel.len()
+    }
+
+    /// Indique si aucun message n'attend
+    pub fn is_empty(&self) -> bool {
+        self.shared.channel.is_empty()
+    }
+}
+
+impl<T> Drop for Receiver<T> {
+    fn drop(&mut self) {
+        self.shared.channel.close();
+    }
+}
+
+impl<T> Stream for Receiver<T> {
+    type Item = T;
+
+    fn poll_next(self: Pin<&mut Self>, context: &mut Context) -> Poll<Option<T>> {
+        self.shared.channel.poll_recv(context)
+    }
+}
+
+/* =========================================================
+   ERREURS
+========================================================= */
+
+/// Échec de `try_send` : le message est rendu
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TrySendError<T> {
+    /// Le canal est plein
+    Full(T),
+    /// Le canal est fermé
+    Closed(T),
+}
+
+/// Échec de `send` : le canal est fermé, le message est rendu
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SendError<T>(pub T);
+
+/// Échec de `try_recv`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TryRecvError {
+    /// Aucun message pour l'instant
+    Empty,
+    /// Le canal est fermé et vide
+    Closed,
+}
+
+/// Échec de `recv` : le canal est fermé et vide
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RecvError;
+
+/// Échec de `recv_timeout`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecvTimeoutError {
+    /// Aucun message avant la fin du délai
+    Timeout,
+    /// Le canal est fermé et vide
+    Closed,
+}
+
+impl<T> fmt::Display for TrySendError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TrySendError::Full(_) => write!(f, "canal plein"),
+            TrySendError::Closed(_) => write!(f, "canal ferme"),
+        }
+    }
+}
+
+impl<T> fmt::Display for SendError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "canal ferme")
+    }
+}
+
+impl fmt::Display for TryRecvError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TryRecvError::Empty => write!(f, "canal vide"),
+            TryRecvError::Closed => write!(f, "canal ferme"),
+        }
+    }
+}
+
+impl fmt::Display for RecvError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "canal ferme")
+    }
+}
+
+impl fmt::Display for RecvTimeoutError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RecvTimeoutError::Timeout => write!(f, "delai depasse"),
+            RecvTimeoutError::Closed => write!(f, "canal ferme"),
+        }
+    }
+}
+
+// ===================================================================
+// TESTS
+// ===================================================================
+
+#[test_case]
+fn test_channel_between_threads() {
+    use crate::thread;
+
+    let (tx, rx) = channel(2);
+    let tx2 = tx.clone();
+    thread::spawn("producteur", move || {
+        for i in 0..10 {
+            tx.send(i).unwrap();
+        }
+    })
+    .unwrap();
+    // Canal de 2 places : le producteur attend le consommateur
+    let mut sum = 0;
+    for _ in 0..10 {
+        sum += rx.recv().unwrap();
+    }
+    assert_eq!(sum, 45);
+    assert_eq!(tx2.try_send(1), Ok(()));
+    assert_eq!(tx2.try_send(2), Ok(()));
+    assert_eq!(tx2.try_send(3), Err(TrySendError::Full(3)));
+    drop(tx2);
+    // Fermé par le dernier Sender : les messages restent lisibles
+    assert_eq!(rx.recv(), Ok(1));
+    assert_eq!(rx.try_recv(), Ok(2));
+    assert_eq!(rx.recv(), Err(RecvError));
+}
+
+#[test_case]
+fn test_recv_timeout() {
+    use crate::{pit, thread};
+
+    let (tx, rx) = channel::<u32>(4);
+    let start = pit::ticks();
+    assert_eq!(rx.recv_timeout(20), Err(RecvTimeoutError::Timeout));
+    assert!(pit::ticks() - start >= 20);
+
+    thread::spawn("retarde", move || {
+        thread::sleep_ms(10);
+        tx.send(7).unwrap();
+    })
+    .unwrap();
+    assert_eq!(rx.recv_timeout(1000), Ok(7));
+    // Le producteur a terminé : plus de Sender
+    assert_eq!(rx.recv_timeout(1000), Err(RecvTimeoutError::Closed));
+}
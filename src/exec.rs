@@ -0,0 +1,137 @@
+/* =========================================================
+   CHARGEMENT DES PROGRAMMES (ELF64)
+
+   `spawn` démarre un programme à partir de son fichier ELF :
+   1. validation (bos_core::exec::check_image) : exécutable
+      x86-64, segments dans la zone utilisateur
+   2. nouvel espace d'adressage (`UserSpace`)
+   3. chaque segment PT_LOAD est projeté avec ses droits (R, W, X),
+      rempli depuis le fichier puis de zéros (.bss) ; le tas
+      commence après le dernier
+   4. pile utilisateur, avec en haut argc, argv, envp et l'auxv
+   5. processus dont le thread saute au point d'entrée en ring 3
+
+   L'espace d'adressage n'est jamais actif pendant le chargement :
+   on y écrit via la projection de la mémoire physique.
+========================================================= */
+
+use alloc::vec;
+use core::fmt;
+
+use bos_core::elf::{ElfFile, PT_LOAD};
+use bos_core::exec::{self, ImageError};
+use bos_core::memory::PAGE_SIZE;
+
+use crate::paging::MapError;
+use crate::process;
+use crate::thread::SpawnError;
+use crate::usermode::{UserSpace, USER_HEAP_END, USER_START};
+
+/// Place en haut de la pile pour argc, argv, envp, l'auxv et les chaînes
+const ARGS_SIZE: usize = PAGE_SIZE as usize;
+
+/// Erreur au lancement d'un programme
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExecError {
+    /// Le fichier n'est pas un exécutable chargeable
+    Image(ImageError),
+    /// Projection de la mémoire du processus impossible
+    Map(MapError),
+    /// Création du thread impossible
+    Spawn(SpawnError),
+}
+
+impl From<ImageError> for ExecError {
+    fn from(error: ImageError) -> ExecError {
+        ExecError::Image(error)
+    }
+}
+
+impl From<MapError> for ExecError {
+    fn from(error: MapError) -> ExecError {
+        ExecError::Map(error)
+    }
+}
+
+impl From<SpawnError> for ExecError {
+    fn from(error: SpawnError) -> ExecError {
+        ExecError::Spawn(error)
+    }
+}
+
+impl fmt::Display for ExecError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ExecError::Image(error) => write!(f, "{}", error),
+            ExecError::Map(error) => write!(f, "memoire du processus : {}", error),
+            ExecError::Spawn(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+/// Programme chargé, prêt à démarrer
+pub struct Image {
+    pub space: UserSpace,
+    /// Point d'entrée
+    pub entry: u64,
+    /// RSP au démarrage (pointe sur argc)
+    pub stack: u64,
+}
+
+/// Charge l'exécutable `data` dans un nouvel espace d'adressage
+pub fn load(data: &[u8], args: &[&str], env: &[&str]) -> Result<Image, ExecError> {
+    let file = ElfFile::parse(data).map_err(ImageError::from)?;
+    exec::check_image(&file, USER_START..USER_HEAP_END)?;
+
+    let mut space = UserSpace::new()?;
+    let mut image_end = USER_START;
+    for segment in file.program_headers().filter(|segment| segment.kind == PT_LOAD) {
+        let pages = exec::segment_pages(&segment);
+        space.map_zeroed(pages.start, pages.end - pages.start, exec::segment_flags(segment.flags))?;
+        // Vérifié par check_image
+        let content = file.segment_data(&segment).ok_or(ImageError::BadSegment)?;
+        space.write(segment.vaddr, content)?;
+        image_end = image_end.max(pages.end);
+    }
+    space.set_heap_start(image_end);
+
+    let top = space.map_stack()?;
+    let mut stack = vec![0u8; ARGS_SIZE];
+    let rsp = exec::build_stack(&mut stack, top, args, env, &exec::auxiliary_vector(&file))?;
+    let used = (top - rsp) as usize;
+    space.write(rsp, &stack[ARGS_SIZE - used..])?;
+
+    Ok(Image {
+        space,
+        entry: file.header().entry,
+        stack: rsp,
+    })
+}
+
+/// Démarre le programme `data` dans un nouveau processus
+///
+/// `args[0]` est le nom du programme, par convention. Retourne le pid.
+pub fn spawn(name: &str, data: &[u8], args: &[&str], env: &[&str]) -> Result<u64, ExecError> {
+    let image = load(data, args, env)?;
+    Ok(process::spawn(name, image.space, image.entry, image.stack)?)
+}
+
+// ===================================================================
+// TESTS
+// ===================================================================
+
+#[test_case]
+fn test_load_rejects_invalid_files() {
+    assert_eq!(
+        load(b"pas un ELF", &[], &[]).err(),
+        Some(ExecError::Image(ImageError::Elf(bos_core::elf::ElfError::TooShort)))
+    );
+}
+
+#[test_case]
+fn test_spawn_program() {
+    let program = crate::programs::find("hello", "/").unwrap();
+    let pid = spawn(&program.name, &program.data, &["hello", "-v"], &["TERM=bos"]).unwrap();
+    let status = process::wait(Some(pid), false).unwrap().map(|(_, status)| status);
+    assert_eq!(status, Some(bos_core::process::ExitStatus::Exited(0)));
+}
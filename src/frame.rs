@@ -0,0 +1,124 @@
+/* =========================================================
+   ALLOCATEUR DE FRAMES PHYSIQUES
+
+   Distribue la mémoire physique par frames de 4 Kio. Seules les
+   régions "utilisables" de la carte mémoire sont proposées :
+   l'image du kernel, sa pile, les tables de pages et le BootInfo
+   du bootloader sont marqués autrement et ne sont jamais alloués.
+
+   Le bitmap (un bit par frame, voir bos-core/src/frame.rs) est
+   placé au début de la première région utilisable assez grande,
+   et lu via la projection de la mémoire physique. Pour 4 Gio de
+   RAM, il occupe 128 Kio.
+========================================================= */
+
+use bos_core::frame::{self, FrameBitmap, FrameError, FrameStats};
+use bos_core::memory::{RegionKind, PAGE_SIZE};
+
+use crate::memory;
+use crate::sync::Spinlock;
+
+// L'allocateur, créé par `init`
+static FRAMES: Spinlock<Option<FrameBitmap<'static>>> = Spinlock::new(None);
+
+/// Construit l'allocateur à partir de la carte mémoire
+///
+/// À appeler après `memory::init`.
+pub fn init() {
+    let usable = || memory::regions().filter(|region| region.kind == RegionKind::Usable);
+
+    // Le bitmap couvre les frames 0 jusqu'à la fin de la plus haute région utilisable
+    let frame_count = usable().map(|region| frame::frame_containing(region.end)).max().unwrap_or(0);
+    let words = frame::bitmap_words(frame_count);
+    let bitmap_frames = (words as u64 * 8).div_ceil(PAGE_SIZE);
+
+    // Le ranger dans la première région utilisable assez grande
+    let Some(home) = usable().find(|region| region.frame_count() >= bitmap_frames) else {
+        warn!("Pas de place pour le bitmap des frames : allocateur desactive");
+        return;
+    };
+    let bitmap_start = home.start.div_ceil(PAGE_SIZE);
+    let storage = unsafe {
+        core::slice::from_raw_parts_mut(
+            memory::phys_to_virt(frame::frame_address(bitmap_start)) as *mut u64,
+            words,
+        )
+    };
+
+    let mut bitmap = FrameBitmap::new(storage, frame_count);
+    for region in usable() {
+        bitmap.add_free_range(region.start.div_ceil(PAGE_SIZE), region.end / PAGE_SIZE);
+    }
+    bitmap.reserve_range(bitmap_start, bitmap_start + bitmap_frames);
+
+    *FRAMES.lock() = Some(bitmap);
+}
+
+/// Exécute `f` sur l'allocateur (None s'il n'est pas initialisé)
+fn with_frames<R>(f: impl FnOnce(&mut FrameBitmap<'static>) -> R) -> Option<R> {
+    // L'allocateur pourra être utilisé depuis les handlers d'interruption
+    FRAMES.lock().as_mut().map(f)
+}
+
+/// Alloue un frame de 4 Kio, retourne son adresse physique
+pub fn allocate_frame() -> Option<u64> {
+    with_frames(|frames| frames.allocate())?.map(frame::frame_address)
+}
+
+/// Alloue `count` frames physiquement contigus (tampons DMA)
+///
+/// L'adresse retournée est alignée sur `align` octets (puissance de 2,
+/// au moins 4 Kio).
+pub fn allocate_contiguous(count: u64, align: u64) -> Option<u64> {
+    let align_frames = (align / PAGE_SIZE).max(1);
+    with_frames(|frames| frames.allocate_contiguous(count, align_frames))?.map(frame::frame_address)
+}
+
+/// Libère un frame alloué par `allocate_frame`
+pub fn free_frame(address: u64) -> Result<(), FrameError> {
+    free_contiguous(address, 1)
+}
+
+/// Libère `count` frames alloués par `allocate_contiguous`
+pub fn free_contiguous(address: u64, count: u64) -> Result<(), FrameError> {
+    let start = frame::frame_containing(address);
+    with_frames(|frames| frames.free_contiguous(start, count)).unwrap_or(Err(FrameError::OutOfRange(start)))
+}
+
+/// Statistiques de l'allocateur (toutes à zéro s'il n'est pas initialisé)
+pub fn stats() -> FrameStats {
+    with_frames(|frames| frames.stats()).unwrap_or_default()
+}
+
+// ===================================================================
+// TESTS
+// ===================================================================
+
+#[test_case]
+fn test_allocate_and_free_frame() {
+    let before = stats();
+    let address = allocate_frame().expect("aucun frame libre");
+    assert!(address.is_multiple_of(PAGE_SIZE));
+    assert_eq!(stats().free, before.free - 1);
+
+    // Le frame est utilisable via la projection de la mémoire physique
+    let page = memory::phys_to_virt(address) as *mut u64;
+    unsafe {
+        page.write_volatile(0x1234_5678);
+        assert_eq!(page.read_volatile(), 0x1234_5678);
+    }
+
+    assert_eq!(free_frame(address), Ok(()));
+    assert_eq!(free_frame(address), Err(FrameError::NotAllocated(address / PAGE_SIZE)));
+    assert_eq!(stats().free, before.free);
+}
+
+#[test_case]
+fn test_contiguous_frames_are_outside_kernel() {
+    let address = allocate_contiguous(16, 64 * 1024).expect("pas de 64 Kio contigus");
+    assert!(address.is_multiple_of(64 * 1024));
+    for region in memory::regions().filter(|region| region.kind != RegionKind::Usable) {
+        assert!(address + 16 * PAGE_SIZE <= region.start || address >= region.end);
+    }
+    assert_eq!(free_contiguous(address, 16), Ok(()));
+}
@@ -0,0 +1,554 @@
+/* =========================================================
+   STUB GDB SUR LE PORT SÉRIE COM2
+
+   Permet de déboguer BOS avec GDB, y compris sur une vraie
+   machine (câble série), et pas seulement via l'option `-s` de
+   QEMU. GDB parle le protocole "Remote Serial Protocol" (voir
+   bos_core::gdb pour le format des paquets).
+
+   Le stub prend la main sur deux exceptions :
+   - Breakpoint (INT3, vecteur 3) : instruction 0xCC posée par GDB
+     à la place du premier octet d'une instruction
+   - Debug (vecteur 1) : levée après chaque instruction quand le
+     Trap Flag (bit 8 de RFLAGS) est actif → exécution pas à pas
+
+   Le handler habituel (x86-interrupt) ne donne pas accès aux
+   registres généraux du code interrompu. On installe donc des
+   points d'entrée en assembleur qui empilent TOUS les registres
+   dans une `TrapFrame` (voir interrupts.rs), que GDB peut lire et
+   modifier, puis les restaurent avant IRETQ.
+
+   Côté hôte, avec QEMU :
+     qemu-system-x86_64 ... -serial stdio -serial tcp::1234,server,nowait
+     gdb target/x86_64-bos/debug/bos -ex "target remote :1234"
+========================================================= */
+
+use core::arch::{asm, global_asm};
+use core::sync::atomic::{AtomicBool, Ordering};
+
+use bos_core::gdb::{
+    decode_hex, parse_command, register_size, to_hex_digit, Command, Decoded, PacketDecoder,
+    PACKET_SIZE, REGISTER_COUNT,
+};
+
+use crate::interrupts::{self, TrapFrame, BREAKPOINT_VECTOR, DEBUG_VECTOR};
+use crate::memory::{phys_to_virt, translate};
+use crate::serial::COM2;
+use crate::sync::Spinlock;
+
+/// Attendre GDB dès le démarrage (`BOS_GDB=1 cargo bootimage`)
+pub const WAIT_AT_BOOT: bool = option_env!("BOS_GDB").is_some();
+
+/// Nombre maximum de breakpoints logiciels simultanés
+const MAX_BREAKPOINTS: usize = 32;
+
+/// Trap Flag : exception debug après chaque instruction
+const TRAP_FLAG: u64 = 1 << 8;
+
+/// Opcode de l'instruction INT3
+const INT3: u8 = 0xCC;
+
+// Points d'entrée des exceptions debug et breakpoint. La pile fait
+// 5 + 2 + 15 = 22 mots de 8 octets : le CPU l'ayant alignée sur 16
+// octets avant d'empiler sa trame, elle reste alignée pour le `call`.
+global_asm!(
+    ".global gdb_debug_entry",
+    "gdb_debug_entry:",
+    "    push 0",
+    "    push 1",
+    "    jmp gdb_trap_common",
+    ".global gdb_breakpoint_entry",
+    "gdb_breakpoint_entry:",
+    "    push 0",
+    "    push 3",
+    "    jmp gdb_trap_common",
+    "gdb_trap_common:",
+    "    push rax",
+    "    push rbx",
+    "    push rcx",
+    "    push rdx",
+    "    push rsi",
+    "    push rdi",
+    "    push rbp",
+    "    push r8",
+    "    push r9",
+    "    push r10",
+    "    push r11",
+    "    push r12",
+    "    push r13",
+    "    push r14",
+    "    push r15",
+    "    mov rdi, rsp",
+    "    cld",
+    "    call {handler}",
+    "    pop r15",
+    "    pop r14",
+    "    pop r13",
+    "    pop r12",
+    "    pop r11",
+    "    pop r10",
+    "    pop r9",
+    "    pop r8",
+    "    pop rbp",
+    "    pop rdi",
+    "    pop rsi",
+    "    pop rdx",
+    "    pop rcx",
+    "    pop rbx",
+    "    pop rax",
+    // Retirer le vecteur et le code d'erreur
+    "    add rsp, 16",
+    "    iretq",
+    handler = sym gdb_trap,
+);
+
+extern "C" {
+    fn gdb_debug_entry();
+    fn gdb_breakpoint_entry();
+}
+
+/// Un breakpoint logiciel : adresse et octet d'origine remplacé par INT3
+#[derive(Clone, Copy)]
+struct Breakpoint {
+    addr: u64,
+    saved: u8,
+}
+
+static ACTIVE: AtomicBool = AtomicBool::new(false);
+static BREAKPOINTS: Spinlock<[Option<Breakpoint>; MAX_BREAKPOINTS]> =
+    Spinlock::new([None; MAX_BREAKPOINTS]);
+static SESSION: Spinlock<(PacketDecoder, Response)> =
+    Spinlock::new((PacketDecoder::new(), Response::new()));
+
+/// Installe le stub : initialise COM2 et détourne les exceptions
+/// debug et breakpoint vers GDB
+pub fn init() {
+    COM2.init();
+    unsafe {
+        interrupts::set_raw_handler(DEBUG_VECTOR, gdb_debug_entry as *const () as u64);
+        interrupts::set_raw_handler(BREAKPOINT_VECTOR, gdb_breakpoint_entry as *const () as u64);
+    }
+    ACTIVE.store(true, Ordering::SeqCst);
+}
+
+/// Indique si le stub est installé
+pub fn is_active() -> bool {
+    ACTIVE.load(Ordering::SeqCst)
+}
+
+/// Rend la main à GDB (instruction INT3)
+pub fn breakpoint() {
+    unsafe {
+        asm!("int3", options(nomem, nostack));
+    }
+}
+
+/* =========================================================
+   ACCÈS À LA MÉMOIRE
+
+   GDB peut demander n'importe quelle adresse : chaque octet est
+   d'abord traduit via les tables de pages, pour répondre par une
+   erreur plutôt que de provoquer un page fault. L'accès se fait
+   ensuite via la projection de la mémoire physique, qui est
+   toujours inscriptible : c'est ce qui permet de poser des INT3
+   dans le code du kernel, projeté en lecture seule.
+========================================================= */
+
+/// Lit un octet de mémoire virtuelle
+fn read_byte(addr: u64) -> Option<u8> {
+    let phys = translate(addr)?;
+    Some(unsafe { *(phys_to_virt(phys) as *const u8) })
+}
+
+/// Écrit un octet de mémoire virtuelle
+fn write_byte(addr: u64, value: u8) -> Option<()> {
+    let phys = translate(addr)?;
+    unsafe {
+        *(phys_to_virt(phys) as *mut u8) = value;
+    }
+    Some(())
+}
+
+/// Pose un breakpoint logiciel à `addr`
+fn insert_breakpoint(addr: u64) -> Option<()> {
+    let mut breakpoints = BREAKPOINTS.lock();
+    if breakpoints.iter().flatten().any(|b| b.addr == addr) {
+        return Some(());
+    }
+    let slot = breakpoints.iter_mut().find(|b| b.is_none())?;
+    let saved = read_byte(addr)?;
+    write_byte(addr, INT3)?;
+    *slot = Some(Breakpoint { addr, saved });
+    Some(())
+}
+
+/// Retire le breakpoint logiciel posé à `addr`
+fn remove_breakpoint(addr: u64) -> Option<()> {
+    let mut breakpoints = BREAKPOINTS.lock();
+    let slot = breakpoints.iter_mut().find(|b| b.is_some_and(|b| b.addr == addr))?;
+    let breakpoint = slot.take()?;
+    write_byte(breakpoint.addr, breakpoint.saved)
+}
+
+/// Indique si un breakpoint logiciel est posé à `addr`
+fn is_breakpoint(addr: u64) -> bool {
+    BREAKPOINTS.lock().iter().flatten().any(|b| b.addr == addr)
+}
+
+/* =========================================================
+   REGISTRES
+
+   Numérotation de GDB pour x86-64 : rax, rbx, rcx, rdx, rsi,
+   rdi, rbp, rsp, r8..r15, rip, puis eflags, cs, ss, ds, es,
+   fs, gs. Les registres absents de la réponse (x87, SSE) sont
+   considérés comme indisponibles par GDB.
+========================================================= */
+
+/// Lit un registre de segment qui n'est pas dans la trame
+fn segment_register(index: usize) -> u64 {
+    let value: u16;
+    unsafe {
+        match index {
+            20 => asm!("mov {0:x}, ds", out(reg) value, options(nomem, nostack, preserves_flags)),
+            21 => asm!("mov {0:x}, es", out(reg) value, options(nomem, nostack, preserves_flags)),
+            22 => asm!("mov {0:x}, fs", out(reg) value, options(nomem, nostack, preserves_flags)),
+            _ => asm!("mov {0:x}, gs", out(reg) value, options(nomem, nostack, preserves_flags)),
+        }
+    }
+    value as u64
+}
+
+/// Valeur du registre GDB numéro `index`
+fn register(frame: &TrapFrame, index: usize) -> u64 {
+    match index {
+        0 => frame.rax,
+        1 => frame.rbx,
+        2 => frame.rcx,
+        3 => frame.rdx,
+        4 => frame.rsi,
+        5 => frame.rdi,
+        6 => frame.rbp,
+        7 => frame.rsp,
+        8 => frame.r8,
+        9 => frame.r9,
+        10 => frame.r10,
+        11 => frame.r11,
+        12 => frame.r12,
+        13 => frame.r13,
+        14 => frame.r14,
+        15 => frame.r15,
+        16 => frame.rip,
+        17 => frame.rflags,
+        18 => frame.cs,
+        19 => frame.ss,
+        _ => segment_register(index),
+    }
+}
+
+/// Modifie le registre GDB numéro `index`
+///
+/// Les registres de segment sont ignorés : les changer casserait le kernel.
+fn set_register(frame: &mut TrapFrame, index: usize, value: u64) {
+    let register = match index {
+        0 => &mut frame.rax,
+        1 => &mut frame.rbx,
+        2 => &mut frame.rcx,
+        3 => &mut frame.rdx,
+        4 => &mut frame.rsi,
+        5 => &mut frame.rdi,
+        6 => &mut frame.rbp,
+        7 => &mut frame.rsp,
+        8 => &mut frame.r8,
+        9 => &mut frame.r9,
+        10 => &mut frame.r10,
+        11 => &mut frame.r11,
+        12 => &mut frame.r12,
+        13 => &mut frame.r13,
+        14 => &mut frame.r14,
+        15 => &mut frame.r15,
+        16 => &mut frame.rip,
+        17 => &mut frame.rflags,
+        _ => return,
+    };
+    *register = value;
+}
+
+/// Décode la valeur d'un registre (octets en little-endian)
+fn decode_register(hex: &[u8], size: usize) -> Option<u64> {
+    let mut bytes = [0u8; 8];
+    if decode_hex(hex, &mut bytes[..size])? != size {
+        return None;
+    }
+    Some(u64::from_le_bytes(bytes))
+}
+
+/* =========================================================
+   RÉPONSES
+========================================================= */
+
+/// Réponse en cours de construction (conservée pour la renvoyer si
+/// GDB répond '-')
+struct Response {
+    buffer: [u8; PACKET_SIZE],
+    len: usize,
+}
+
+impl Response {
+    const fn new() -> Response {
+        Response {
+            buffer: [0; PACKET_SIZE],
+            len: 0,
+        }
+    }
+
+    fn clear(&mut self) {
+        self.len = 0;
+    }
+
+    fn push(&mut self, byte: u8) {
+        if self.len < PACKET_SIZE {
+            self.buffer[self.len] = byte;
+            self.len += 1;
+        }
+    }
+
+    fn push_str(&mut self, s: &str) {
+        for byte in s.bytes() {
+            self.push(byte);
+        }
+    }
+
+    /// Ajoute un octet en deux chiffres hexadécimaux
+    fn push_hex(&mut self, byte: u8) {
+        self.push(to_hex_digit(byte >> 4));
+        self.push(to_hex_digit(byte));
+    }
+
+    /// Ajoute un registre de `size` octets, en little-endian
+    fn push_register(&mut self, value: u64, size: usize) {
+        for byte in &value.to_le_bytes()[..size] {
+            self.push_hex(*byte);
+        }
+    }
+
+    /// Envoie la réponse jusqu'à ce que GDB l'acquitte
+    fn send(&self) {
+        let data = &self.buffer[..self.len];
+        let checksum = bos_core::gdb::checksum(data);
+        loop {
+            COM2.write_byte(b'$');
+            for &byte in data {
+                COM2.write_byte(byte);
+            }
+            COM2.write_byte(b'#');
+            COM2.write_byte(to_hex_digit(checksum >> 4));
+            COM2.write_byte(to_hex_digit(checksum));
+            // '+' : reçu, '-' : à renvoyer (les autres octets sont ignorés)
+            loop {
+                match COM2.read_byte() {
+                    b'+' => return,
+                    b'-' => break,
+                    _ => {}
+                }
+            }
+        }
+    }
+}
+
+/// Ce que fait le stub après une commande
+enum Action {
+    /// Attendre la commande suivante
+    Reply,
+    /// Reprendre l'exécution du kernel
+    Resume,
+}
+
+/// Exécute une commande de GDB et prépare la réponse
+fn handle_command(frame: &mut TrapFrame, command: Command, response: &mut Response) -> Action {
+    match command {
+        Command::HaltReason => response.push_str("S05"),
+        Command::ReadRegisters => {
+            for index in 0..REGISTER_COUNT {
+                let size = register_size(index).unwrap_or(8);
+                response.push_register(register(frame, index), size);
+            }
+        }
+        Command::WriteRegisters(hex) => {
+            let mut rest = hex;
+            for index in 0..REGISTER_COUNT {
+                let size = register_size(index).unwrap_or(8);
+                if rest.len() < size * 2 {
+                    break;
+                }
+                if let Some(value) = decode_register(&rest[..size * 2], size) {
+                    set_register(frame, index, value);
+                }
+                rest = &rest[size * 2..];
+            }
+            response.push_str("OK");
+        }
+        Command::ReadRegister(index) => match register_size(index) {
+            Some(size) => response.push_register(register(frame, index), size),
+            None => response.push_str("E01"),
+        },
+        Command::WriteRegister(index, hex) => {
+            match register_size(index).and_then(|size| decode_register(hex, size)) {
+                Some(value) => {
+                    set_register(frame, index, value);
+                    response.push_str("OK");
+                }
+                None => response.push_str("E01"),
+            }
+        }
+        Command::ReadMemory { addr, len } => {
+            // Chaque octet prend 2 caractères dans la réponse
+            let len = len.min(PACKET_SIZE / 2) as u64;
+            let mut read = 0;
+            while read < len {
+                match read_byte(addr.wrapping_add(read)) {
+                    Some(byte) => response.push_hex(byte),
+                    None => break,
+                }
+                read += 1;
+            }
+            if read == 0 && len > 0 {
+                // EFAULT : adresse non projetée
+                response.push_str("E14");
+            }
+        }
+        Command::WriteMemory { addr, data } => {
+            let mut ok = true;
+            for (i, pair) in data.chunks(2).enumerate() {
+                let mut byte = [0u8; 1];
+                ok = decode_hex(pair, &mut byte).is_some()
+                    && write_byte(addr.wrapping_add(i as u64), byte[0]).is_some();
+                if !ok {
+                    break;
+                }
+            }
+            response.push_str(if ok { "OK" } else { "E14" });
+        }
+        Command::InsertBreakpoint(addr) => {
+            response.push_str(if insert_breakpoint(addr).is_some() { "OK" } else { "E14" });
+        }
+        Command::RemoveBreakpoint(addr) => {
+            response.push_str(if remove_breakpoint(addr).is_some() { "OK" } else { "E14" });
+        }
+        Command::Continue(addr) => {
+            if let Some(addr) = addr {
+                frame.rip = addr;
+            }
+            frame.rflags &= !TRAP_FLAG;
+            return Action::Resume;
+        }
+        Command::Step(addr) => {
+            if let Some(addr) = addr {
+                frame.rip = addr;
+            }
+            frame.rflags |= TRAP_FLAG;
+            return Action::Resume;
+        }
+        Command::Detach => {
+            frame.rflags &= !TRAP_FLAG;
+            response.push_str("OK");
+            response.send();
+            return Action::Resume;
+        }
+        Command::Kill => {
+            // Pas de réponse au paquet `k` : on relance simplement le kernel
+            frame.rflags &= !TRAP_FLAG;
+            return Action::Resume;
+        }
+        Command::Query(query) => {
+            if query.starts_with(b"Supported") {
+                response.push_str("PacketSize=");
+                for shift in [12, 8, 4, 0] {
+                    response.push(to_hex_digit((PACKET_SIZE >> shift) as u8));
+                }
+            } else if query == b"Attached" {
+                // Le kernel existait avant GDB : "detach" plutôt que "kill"
+                response.push_str("1");
+            }
+        }
+        Command::SetThread => response.push_str("OK"),
+        Command::Unsupported => {}
+        Command::Malformed => response.push_str("E01"),
+    }
+    Action::Reply
+}
+
+/// Point d'entrée Rust du stub, appelé par l'assembleur
+///
+/// Dialogue avec GDB jusqu'à une commande `c` ou `s` ; les registres
+/// éventuellement modifiés dans `frame` sont restaurés au retour.
+extern "C" fn gdb_trap(frame: &mut TrapFrame) {
+    // Après un INT3, RIP pointe sur l'octet suivant : si l'INT3 est un
+    // breakpoint de GDB, on revient sur l'instruction d'origine
+    if frame.vector == BREAKPOINT_VECTOR as u64 && is_breakpoint(frame.rip.wrapping_sub(1)) {
+        frame.rip -= 1;
+    }
+    frame.rflags &= !TRAP_FLAG;
+
+    let mut session = SESSION.lock();
+    let (decoder, response) = &mut *session;
+
+    // Signaler l'arrêt (signal 5 = SIGTRAP)
+    response.clear();
+    response.push_str("S05");
+    response.send();
+
+    loop {
+        match decoder.push(COM2.read_byte()) {
+            Decoded::Packet(_) => {
+                COM2.write_byte(b'+');
+                response.clear();
+                let command = parse_command(decoder.packet());
+                match handle_command(frame, command, response) {
+                    Action::Reply => response.send(),
+                    Action::Resume => return,
+                }
+            }
+            Decoded::BadChecksum => COM2.write_byte(b'-'),
+            // Déjà arrêté : Ctrl+C n'a rien à faire
+            Decoded::Interrupt | Decoded::Pending => {}
+        }
+    }
+}
+
+// ===================================================================
+// TESTS
+// ===================================================================
+
+#[test_case]
+fn test_registers_round_trip() {
+    let mut frame = TrapFrame::default();
+    for index in 0..=17 {
+        set_register(&mut frame, index, 0x1000 + index as u64);
+    }
+    for index in 0..=17 {
+        assert_eq!(register(&frame, index), 0x1000 + index as u64);
+    }
+    // Les segments ne sont pas modifiables
+    set_register(&mut frame, 18, 0x1234);
+    assert_eq!(frame.cs, 0);
+}
+
+#[test_case]
+fn test_breakpoint_insert_and_remove() {
+    use core::ptr::addr_of;
+
+    static mut CODE: [u8; 4] = [0x90, 0x90, 0xC3, 0x00];
+    let addr = addr_of!(CODE) as u64 + 1;
+    insert_breakpoint(addr).unwrap();
+    assert!(is_breakpoint(addr));
+    assert_eq!(unsafe { *addr_of!(CODE) }, [0x90, INT3, 0xC3, 0x00]);
+    remove_breakpoint(addr).unwrap();
+    assert!(!is_breakpoint(addr));
+    assert_eq!(unsafe { *addr_of!(CODE) }, [0x90, 0x90, 0xC3, 0x00]);
+}
+
+#[test_case]
+fn test_read_unmapped_memory_fails() {
+    assert_eq!(read_byte(0x0000_7fff_ffff_f000), None);
+    assert!(read_byte(&BREAKPOINTS as *const _ as u64).is_some());
+}
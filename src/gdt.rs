@@ -0,0 +1,214 @@
+/* =========================================================
+   GDT - GLOBAL DESCRIPTOR TABLE ET TSS
+
+   En mode 64 bits, la segmentation est presque inutilisée, mais
+   la GDT reste obligatoire pour deux choses :
+   - Décrire les segments de code/données (niveau de privilège, mode 64 bits)
+     du kernel (ring 0) et des programmes utilisateur (ring 3)
+   - Référencer la TSS (Task State Segment)
+
+   La TSS contient l'Interrupt Stack Table (IST) : 7 piles de secours
+   que le CPU peut utiliser pour certaines interruptions. C'est
+   indispensable pour le double fault : si la pile du kernel déborde,
+   le CPU ne peut plus empiler la trame d'interruption sur cette pile,
+   il faut donc basculer sur une pile propre et connue.
+
+   Elle contient aussi RSP0 : la pile du kernel chargée par le CPU
+   quand une interruption arrive en ring 3. Elle change à chaque
+   changement de thread (`set_kernel_stack`, voir usermode.rs).
+
+   Le bootloader fournit déjà une GDT, mais sans TSS. On installe
+   donc la nôtre, en gardant le segment de code kernel à 0x08
+   (le sélecteur utilisé par les entrées de l'IDT).
+
+   Organisation de notre GDT :
+   - 0x00 : Descripteur nul (obligatoire)
+   - 0x08 : Code kernel (64 bits, DPL 0)
+   - 0x10 : Données kernel (DPL 0)
+   - 0x18 : Données utilisateur (DPL 3)
+   - 0x20 : Code utilisateur (64 bits, DPL 3)
+   - 0x28 : TSS (descripteur système de 16 octets = 2 entrées)
+
+   L'ordre des segments utilisateur est imposé par SYSRET, qui
+   les déduit d'une seule base (voir syscall.rs) : données à
+   base + 8, code à base + 16.
+========================================================= */
+
+use core::arch::asm;
+use core::cell::UnsafeCell;
+
+use crate::sync::{Once, Spinlock};
+
+/// Sélecteur du segment de code kernel
+pub const KERNEL_CODE_SELECTOR: u16 = 0x08;
+/// Sélecteur du segment de données kernel
+pub const KERNEL_DATA_SELECTOR: u16 = 0x10;
+/// Sélecteur du segment de données utilisateur (RPL 3)
+pub const USER_DATA_SELECTOR: u16 = 0x18 | 3;
+/// Sélecteur du segment de code utilisateur (RPL 3)
+pub const USER_CODE_SELECTOR: u16 = 0x20 | 3;
+/// Sélecteur de la TSS
+pub const TSS_SELECTOR: u16 = 0x28;
+
+/// Index IST (1 à 7, 0 = pas d'IST) utilisé par le handler de double fault
+pub const DOUBLE_FAULT_IST_INDEX: u8 = 1;
+
+/// Taille de la pile dédiée au double fault (5 pages)
+const DOUBLE_FAULT_STACK_SIZE: usize = 4096 * 5;
+
+// Structure de la TSS 64 bits (104 octets), définie par le CPU
+#[repr(C, packed)]
+pub struct TaskStateSegment {
+    reserved_1: u32,
+    /// Piles utilisées lors d'un passage vers le niveau de privilège 0-2
+    pub privilege_stack_table: [u64; 3],
+    reserved_2: u64,
+    /// Les 7 piles de l'Interrupt Stack Table (IST1 à IST7)
+    pub interrupt_stack_table: [u64; 7],
+    reserved_3: u64,
+    reserved_4: u16,
+    /// Offset de la bitmap des permissions d'I/O (non utilisée)
+    pub iomap_base: u16,
+}
+
+impl TaskStateSegment {
+    /// Crée une TSS vide
+    const fn new() -> TaskStateSegment {
+        TaskStateSegment {
+            reserved_1: 0,
+            privilege_stack_table: [0; 3],
+            reserved_2: 0,
+            interrupt_stack_table: [0; 7],
+            reserved_3: 0,
+            reserved_4: 0,
+            // Pas de bitmap d'I/O : on la place après la fin de la TSS
+            iomap_base: core::mem::size_of::<TaskStateSegment>() as u16,
+        }
+    }
+}
+
+/// Pile utilisée seulement par le CPU : le code Rust ne fait qu'en
+/// prendre l'adresse
+struct Stack(UnsafeCell<[u8; DOUBLE_FAULT_STACK_SIZE]>);
+
+unsafe impl Sync for Stack {}
+
+// Pile de secours pour le double fault
+// (le CPU aligne lui-même RSP sur 16 octets en basculant sur une pile IST)
+static DOUBLE_FAULT_STACK: Stack = Stack(UnsafeCell::new([0; DOUBLE_FAULT_STACK_SIZE]));
+
+// La TSS globale : la pile IST est remplie par `init_gdt`, RSP0 à
+// chaque changement de thread. Le CPU la lit sans prendre le verrou.
+static TSS: Spinlock<TaskStateSegment> = Spinlock::new(TaskStateSegment::new());
+
+// Descripteurs de segments 64 bits (valeurs standard) :
+// - Code : Present, DPL 0, exécutable/lisible, L = 1 (mode long)
+// - Données : Present, DPL 0, lecture/écriture
+// - Les mêmes avec DPL 3 pour le mode utilisateur
+const KERNEL_CODE_DESCRIPTOR: u64 = 0x00AF_9A00_0000_FFFF;
+const KERNEL_DATA_DESCRIPTOR: u64 = 0x00CF_9200_0000_FFFF;
+const USER_DATA_DESCRIPTOR: u64 = 0x00CF_F200_0000_FFFF;
+const USER_CODE_DESCRIPTOR: u64 = 0x00AF_FA00_0000_FFFF;
+
+/// Nombre d'entrées de 8 octets de la GDT
+const GDT_ENTRIES: usize = 7;
+
+// La GDT : nul, code et données kernel, données et code utilisateur,
+// TSS sur 2 entrées
+static GDT: Once<[u64; GDT_ENTRIES]> = Once::new();
+
+// Structure pour décrire la GDT au CPU (utilisée par l'instruction LGDT)
+#[repr(C, packed)]
+struct GdtDescriptor {
+    size: u16,  // Taille de la GDT - 1 (en octets)
+    offset: u64, // Adresse mémoire de la GDT
+}
+
+/// Construit le descripteur système (16 octets) qui pointe vers la TSS
+///
+/// Le format éclate la base 64 bits et la limite en plusieurs morceaux :
+/// - Entrée basse : limite 0-15, base 0-23, type (0x89 = TSS 64 bits présente), base 24-31
+/// - Entrée haute : base 32-63
+fn tss_descriptor(base: u64, limit: u64) -> (u64, u64) {
+    let mut low = 0u64;
+    low |= limit & 0xFFFF;
+    low |= (base & 0xFF_FFFF) << 16;
+    low |= 0x89 << 40;
+    low |= ((limit >> 16) & 0xF) << 48;
+    low |= ((base >> 24) & 0xFF) << 56;
+    let high = base >> 32;
+    (low, high)
+}
+
+/// Initialise la TSS, charge notre GDT et recharge les registres de segment
+pub fn init_gdt() {
+    // ===== Pile IST pour le double fault =====
+    let tss_base = {
+        let mut tss = TSS.lock();
+        // Les piles grandissent vers le bas : on donne l'adresse de FIN
+        let stack_start = DOUBLE_FAULT_STACK.0.get() as u64;
+        let stack_end = stack_start + DOUBLE_FAULT_STACK_SIZE as u64;
+        tss.interrupt_stack_table[(DOUBLE_FAULT_IST_INDEX - 1) as usize] = stack_end;
+        // La TSS est dans un static : l'adresse reste valable
+        &*tss as *const TaskStateSegment as u64
+    };
+
+    // ===== Descripteur de TSS dans la GDT =====
+    let gdt = GDT.call_once(|| {
+        let tss_limit = (core::mem::size_of::<TaskStateSegment>() - 1) as u64;
+        let (low, high) = tss_descriptor(tss_base, tss_limit);
+        let mut gdt = [
+            0,
+            KERNEL_CODE_DESCRIPTOR,
+            KERNEL_DATA_DESCRIPTOR,
+            USER_DATA_DESCRIPTOR,
+            USER_CODE_DESCRIPTOR,
+            0,
+            0,
+        ];
+        gdt[(TSS_SELECTOR / 8) as usize] = low;
+        gdt[(TSS_SELECTOR / 8) as usize + 1] = high;
+        gdt
+    });
+
+    unsafe {
+        // ===== Charger la GDT avec LGDT =====
+        let gdt_desc = GdtDescriptor {
+            size: (core::mem::size_of::<[u64; GDT_ENTRIES]>() - 1) as u16,
+            offset: gdt.as_ptr() as u64,
+        };
+        asm!("lgdt [{}]", in(reg) &gdt_desc, options(readonly, nostack, preserves_flags));
+
+        // ===== Recharger CS =====
+        // On ne peut pas faire "mov cs, ..." : on empile le sélecteur et
+        // l'adresse de retour, puis un "far return" (retfq) recharge CS.
+        asm!(
+            "push {sel}",
+            "lea {tmp}, [rip + 2f]",
+            "push {tmp}",
+            "retfq",
+            "2:",
+            sel = in(reg) KERNEL_CODE_SELECTOR as u64,
+            tmp = lateout(reg) _,
+            options(preserves_flags)
+        );
+
+        // ===== Recharger les segments de données =====
+        asm!(
+            "mov ds, {0:x}",
+            "mov es, {0:x}",
+            "mov ss, {0:x}",
+            in(reg) KERNEL_DATA_SELECTOR,
+            options(nostack, preserves_flags)
+        );
+
+        // ===== Charger la TSS avec LTR =====
+        asm!("ltr {0:x}", in(reg) TSS_SELECTOR, options(nostack, preserves_flags));
+    }
+}
+
+/// Change RSP0 : la pile chargée par le CPU quand une interruption ou
+/// une exception arrive pendant l'exécution de code utilisateur
+pub fn set_kernel_stack(stack_top: u64) {
+    TSS.lock().privilege_stack_table[0] = stack_top;
+}
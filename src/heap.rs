@@ -0,0 +1,158 @@
+/* =========================================================
+   TAS DU KERNEL
+
+   Une zone de mémoire virtuelle fixe (HEAP_START, HEAP_SIZE) est
+   projetée sur des frames de l'allocateur physique, puis confiée
+   à l'allocateur à liste chaînée de bos-core (heap.rs).
+
+   Il est déclaré comme `#[global_allocator]` : la crate `alloc`
+   (Box, Vec, String, BTreeMap...) est utilisable dans tout le
+   kernel une fois `init` appelé. Les allocations de 1 Kio ou
+   moins vont dans les caches slab "kmalloc-N" (slab.rs), plus
+   rapides et sans fragmentation ; les autres vont dans le tas.
+========================================================= */
+
+use core::alloc::{GlobalAlloc, Layout};
+
+use bos_core::heap::{FreeListInfo, HeapStats, LinkedListHeap};
+use bos_core::memory::{Size, PAGE_SIZE};
+use bos_core::paging::PageFlags;
+
+use crate::paging::{AddressSpace, MapError};
+use crate::sync::Spinlock;
+use crate::{frame, heap_debug, slab};
+
+/// Début du tas dans l'espace virtuel (adresse facile à reconnaître)
+pub const HEAP_START: u64 = 0x0000_4444_4444_0000;
+
+/// Taille du tas
+pub const HEAP_SIZE: u64 = 1024 * 1024;
+
+/// L'allocateur global du kernel
+pub struct KernelHeap {
+    heap: Spinlock<LinkedListHeap>,
+}
+
+impl KernelHeap {
+    const fn new() -> KernelHeap {
+        KernelHeap { heap: Spinlock::new(LinkedListHeap::empty()) }
+    }
+
+    /// Exécute `f` sur le tas, à l'abri des handlers d'interruption
+    fn with_heap<R>(&self, f: impl FnOnce(&mut LinkedListHeap) -> R) -> R {
+        f(&mut self.heap.lock())
+    }
+
+    /// Alloue un bloc dans un cache kmalloc ou dans le tas
+    fn alloc_block(&self, layout: Layout) -> *mut u8 {
+        match slab::kmalloc_cache(layout) {
+            Some(cache) => cache.alloc(),
+            None => self.with_heap(|heap| heap.allocate(layout)),
+        }
+    }
+
+    /// Libère un bloc alloué par `alloc_block`
+    unsafe fn dealloc_block(&self, ptr: *mut u8, layout: Layout) {
+        // Même layout qu'à l'allocation : même choix de cache
+        match slab::kmalloc_cache(layout) {
+            Some(cache) => cache.free(ptr),
+            None => self.with_heap(|heap| heap.deallocate(ptr, layout)),
+        }
+    }
+}
+
+unsafe impl GlobalAlloc for KernelHeap {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        // Mode débogage : zones rouges et suivi autour du bloc réel
+        if heap_debug::ENABLED {
+            heap_debug::alloc(layout, |outer| self.alloc_block(outer))
+        } else {
+            self.alloc_block(layout)
+        }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        if heap_debug::ENABLED {
+            heap_debug::dealloc(ptr, layout, |base, outer| self.dealloc_block(base, outer))
+        } else {
+            self.dealloc_block(ptr, layout)
+        }
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: KernelHeap = KernelHeap::new();
+
+/// Projette la zone du tas et initialise l'allocateur
+///
+/// À appeler après `frame::init`.
+pub fn init() -> Result<(), MapError> {
+    let mut space = AddressSpace::current();
+    let flags = PageFlags::WRITABLE | PageFlags::NO_EXECUTE;
+    for page in (HEAP_START..HEAP_START + HEAP_SIZE).step_by(PAGE_SIZE as usize) {
+        let frame = frame::allocate_frame().ok_or(MapError::OutOfFrames)?;
+        space.map(page, frame, flags)?;
+    }
+    ALLOCATOR.with_heap(|heap| unsafe { heap.init(HEAP_START as usize, HEAP_SIZE as usize) });
+    Ok(())
+}
+
+/// Statistiques du tas
+pub fn stats() -> HeapStats {
+    ALLOCATOR.with_heap(|heap| heap.stats())
+}
+
+/// État de la liste des blocs libres (fragmentation)
+pub fn free_list_info() -> FreeListInfo {
+    ALLOCATOR.with_heap(|heap| heap.free_list_info())
+}
+
+/// Appelé quand une allocation échoue (tas plein ou trop fragmenté)
+#[alloc_error_handler]
+fn alloc_error(layout: Layout) -> ! {
+    let stats = stats();
+    let free_list = free_list_info();
+    panic!(
+        "Allocation impossible : {} octets (alignement {})\n\
+         Tas : {} utilises / {}, {} blocs libres, plus grand {}",
+        layout.size(),
+        layout.align(),
+        Size(stats.used as u64),
+        Size(stats.size as u64),
+        free_list.blocks,
+        Size(free_list.largest as u64)
+    );
+}
+
+// ===================================================================
+// TESTS
+// ===================================================================
+
+#[test_case]
+fn test_box_and_vec() {
+    use alloc::boxed::Box;
+    use alloc::vec::Vec;
+
+    let boxed = Box::new(41u64);
+    assert_eq!(*boxed + 1, 42);
+
+    // 8000 octets : trop grand pour les caches slab, alloué dans le tas
+    let values: Vec<u64> = (0..1000).collect();
+    assert_eq!(values.iter().sum::<u64>(), 999 * 1000 / 2);
+    let address = values.as_ptr() as u64;
+    assert!((HEAP_START..HEAP_START + HEAP_SIZE).contains(&address));
+}
+
+#[test_case]
+fn test_freed_memory_is_reused() {
+    use alloc::boxed::Box;
+
+    let before = stats();
+    // Bien plus que la taille du tas au total : possible seulement si
+    // la mémoire libérée est réutilisée
+    for i in 0..(HEAP_SIZE / 4096) * 4 {
+        let block = Box::new([i as u8; 4096]);
+        assert_eq!(block[4095], i as u8);
+    }
+    assert_eq!(stats().used, before.used);
+}
@@ -0,0 +1,187 @@
+/* =========================================================
+   MODE DE DÉBOGAGE DU TAS (feature `heap-debug`)
+
+   Compilé avec `cargo build --features heap-debug`, l'allocateur
+   global entoure chaque allocation de zones rouges et la suit
+   jusqu'à sa libération (voir bos-core/src/heap_debug.rs) :
+
+   - une écriture hors limites ou une double libération provoque
+     un panic qui indique l'allocation et l'endroit du code qui
+     l'a faite ;
+   - la commande `heapcheck` vérifie toutes les allocations
+     vivantes et liste celles qui n'ont pas été libérées.
+
+   L'endroit de l'allocation est retrouvé en remontant la chaîne
+   des frames (backtrace.rs) au moment de l'appel.
+========================================================= */
+
+use core::alloc::Layout;
+use core::fmt::{self, Write};
+use core::ptr;
+
+use bos_core::heap_debug::{AllocationInfo, HeapDebugError, Tracker, SITE_FRAMES};
+
+use crate::backtrace::{self, Frames};
+use crate::symbols;
+use crate::sync::Spinlock;
+
+/// Mode de débogage actif (feature `heap-debug`)
+pub const ENABLED: bool = cfg!(feature = "heap-debug");
+
+/// Nombre maximum d'allocations listées par `heapcheck`
+const MAX_LISTED: usize = 16;
+
+// Allocations vivantes
+static TRACKER: Spinlock<Tracker> = Spinlock::new(Tracker::new());
+
+/// Alloue `layout` avec zones rouges, en demandant le bloc réel à `inner`
+pub fn alloc(layout: Layout, inner: impl FnOnce(Layout) -> *mut u8) -> *mut u8 {
+    let Some(outer) = Tracker::outer_layout(layout) else {
+        return ptr::null_mut();
+    };
+    let base = inner(outer);
+    if base.is_null() {
+        return base;
+    }
+    let mut site = [0; SITE_FRAMES];
+    for (slot, address) in site.iter_mut().zip(Frames::new(backtrace::frame_pointer())) {
+        *slot = address;
+    }
+    unsafe { TRACKER.lock().track(base, layout, site) }
+}
+
+/// Vérifie puis libère une allocation faite par `alloc`
+///
+/// # Safety
+/// Mêmes conditions que `GlobalAlloc::dealloc`.
+pub unsafe fn dealloc(data: *mut u8, layout: Layout, inner: impl FnOnce(*mut u8, Layout)) {
+    // Le verrou est relâché avant `report_error`, qui panique
+    let result = TRACKER.lock().untrack(data, layout);
+    match result {
+        Ok(base) => inner(base, Tracker::outer_layout(layout).unwrap_or(layout)),
+        Err(error) => report_error(error),
+    }
+}
+
+/// Panic avec la description d'une erreur et l'endroit de l'allocation
+fn report_error(error: HeapDebugError) -> ! {
+    struct Site(Option<AllocationInfo>);
+    impl fmt::Display for Site {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            match self.0 {
+                Some(info) => write_site(f, &info.site),
+                None => f.write_str("?"),
+            }
+        }
+    }
+    panic!("heap-debug: {}\nAllouee par : {}", error, Site(error.info()));
+}
+
+/// Adresse de retour qui n'est pas dans l'allocateur lui-même
+fn caller(site: &[u64; SITE_FRAMES]) -> Option<u64> {
+    site.iter().copied().take_while(|&address| address != 0).find(|&address| {
+        match symbols::resolve(address - 1) {
+            Some((name, _)) => {
+                let mut text = NameBuffer { bytes: [0; 64], len: 0 };
+                let _ = write!(text, "{}", name);
+                let name = text.as_str();
+                !(name.contains("alloc::") || name.starts_with("core::") || name.starts_with("bos::heap"))
+            }
+            None => true,
+        }
+    })
+}
+
+/// Écrit l'endroit d'une allocation : "fonction+0xdécalage"
+pub fn write_site(out: &mut dyn Write, site: &[u64; SITE_FRAMES]) -> fmt::Result {
+    match caller(site) {
+        Some(address) => match symbols::resolve(address - 1) {
+            Some((name, offset)) => write!(out, "{}+{:#x}", name, offset + 1),
+            None => write!(out, "{:#x}", address),
+        },
+        None => out.write_str("?"),
+    }
+}
+
+/// Début d'un nom de symbole (suffisant pour reconnaître l'allocateur)
+struct NameBuffer {
+    bytes: [u8; 64],
+    len: usize,
+}
+
+impl NameBuffer {
+    fn as_str(&self) -> &str {
+        core::str::from_utf8(&self.bytes[..self.len]).unwrap_or("")
+    }
+}
+
+impl Write for NameBuffer {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        // Les noms démanglés sont en ASCII : pas de caractère coupé
+        let count = s.len().min(self.bytes.len() - self.len);
+        self.bytes[self.len..self.len + count].copy_from_slice(&s.as_bytes()[..count]);
+        self.len += count;
+        Ok(())
+    }
+}
+
+/// Nombre d'allocations vivantes et leur taille totale
+pub fn live() -> (usize, usize) {
+    TRACKER.lock().live()
+}
+
+/// Vérifie toutes les allocations vivantes et liste les plus récentes
+///
+/// Retourne le nombre d'allocations dont les zones rouges sont abîmées.
+pub fn write_report(out: &mut dyn Write) -> Result<usize, fmt::Error> {
+    // `out` ne doit pas allouer : le tas attendrait le verrou du suivi
+    let tracker = TRACKER.lock();
+    let mut result = Ok(());
+    let mut corrupted = 0;
+    tracker.check_all(|error| {
+        corrupted += 1;
+        result = result.and_then(|_| {
+            write!(out, "CORROMPU {} par ", error)?;
+            if let Some(info) = error.info() {
+                write_site(out, &info.site)?;
+            }
+            writeln!(out)
+        });
+    });
+
+    let (count, bytes) = tracker.live();
+    result = result.and_then(|_| writeln!(out, "{} allocations vivantes ({} octets)", count, bytes));
+    let mut listed = 0;
+    tracker.for_each(|info| {
+        listed += 1;
+        if listed <= MAX_LISTED {
+            result = result.and_then(|_| {
+                write!(out, "  #{:<5} {:#x} {:>6} o  ", info.sequence, info.address, info.size)?;
+                write_site(out, &info.site)?;
+                writeln!(out)
+            });
+        }
+    });
+    if count > MAX_LISTED {
+        result = result.and_then(|_| writeln!(out, "  ... et {} autres", count - MAX_LISTED));
+    }
+    result.map(|_| corrupted)
+}
+
+// ===================================================================
+// TESTS
+// ===================================================================
+
+#[test_case]
+fn test_tracks_allocations() {
+    use alloc::boxed::Box;
+
+    if !ENABLED {
+        return;
+    }
+    let (count, bytes) = live();
+    let boxed = Box::new([7u8; 100]);
+    assert_eq!(live(), (count + 1, bytes + 100));
+    drop(boxed);
+    assert_eq!(live(), (count, bytes));
+}
@@ -0,0 +1,228 @@
+/* =========================================================
+   INITRD : SYSTÈME DE FICHIERS INITIAL EN LECTURE SEULE
+
+   En attendant les drivers de disque, les fichiers livrés avec
+   BOS sont dans une archive tar (bos_core::tar) construite par
+   build.rs : le contenu du répertoire initrd/ du dépôt, les
+   programmes de libbos dans bin/ et les points de montage. L'archive
+   est incluse dans le kernel ; `init` la lit au démarrage et en
+   fait une table de noeuds (fichiers, répertoires, liens
+   symboliques), présentée au VFS par le système de fichiers
+   `Initrd`, monté sur "/".
+
+   Les données des fichiers ne sont pas copiées : elles restent
+   dans l'archive, en mémoire jusqu'à l'arrêt. Les répertoires
+   absents de l'archive mais contenant des fichiers sont créés.
+========================================================= */
+
+use alloc::string::String;
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+
+use bos_core::tar::{self, EntryKind, TarError};
+use bos_core::vfs::{FileType, FsError, Metadata};
+
+use crate::sync::Once;
+use crate::vfs::{DirEntry, Directory, FileSystem, Inode};
+
+/// Archive construite par build.rs
+static ARCHIVE: &[u8] = include_bytes!(concat!(env!("OUT_DIR"), "/initrd.tar"));
+
+/// Un fichier, répertoire ou lien symbolique de l'initrd
+#[derive(Debug)]
+struct Node {
+    /// Chemin depuis la racine, sans '/' au début ("" pour la racine)
+    path: String,
+    kind: FileType,
+    /// Droits (0o755...)
+    mode: u32,
+    /// Date de modification (secondes depuis 1970)
+    mtime: u64,
+    /// Contenu d'un fichier
+    data: &'static [u8],
+    /// Cible d'un lien symbolique
+    link: &'static str,
+}
+
+impl Node {
+    fn directory(path: String) -> Node {
+        Node { path, kind: FileType::Directory, mode: 0o755, mtime: 0, data: &[], link: "" }
+    }
+
+    /// Nom du noeud (dernier composant du chemin)
+    fn name(&self) -> &str {
+        self.path.rsplit('/').next().unwrap_or("")
+    }
+
+    /// Chemin du répertoire parent
+    fn parent(&self) -> Option<&str> {
+        match self.path.rfind('/') {
+            Some(index) => Some(&self.path[..index]),
+            None if self.path.is_empty() => None,
+            None => Some(""),
+        }
+    }
+}
+
+// Noeuds de l'initrd, la racine en premier
+static NODES: Once<Vec<Node>> = Once::new();
+
+/// Lit l'archive de l'initrd, retourne le nombre de noeuds
+///
+/// Une archive corrompue donne une erreur ; les fichiers lus avant
+/// restent disponibles.
+pub fn init() -> Result<usize, TarError> {
+    let mut result = Ok(());
+    let nodes = NODES.call_once(|| {
+        let mut nodes = Vec::from([Node::directory(String::new())]);
+        for entry in tar::entries(ARCHIVE) {
+            match entry {
+                Ok(entry) => add(&mut nodes, &entry),
+                Err(error) => result = Err(error),
+            }
+        }
+        nodes
+    });
+    result.map(|()| nodes.len())
+}
+
+/// Ajoute une entrée de l'archive (et ses répertoires parents absents)
+fn add(nodes: &mut Vec<Node>, entry: &tar::Entry<'static>) {
+    let kind = match entry.kind {
+        EntryKind::File => FileType::File,
+        EntryKind::Directory => FileType::Directory,
+        EntryKind::Symlink => FileType::Symlink,
+        EntryKind::Other(_) => return,
+    };
+    let mut path = String::new();
+    for component in entry.components() {
+        if !path.is_empty() {
+            if !nodes.iter().any(|node| node.path == path) {
+                nodes.push(Node::directory(path.clone()));
+            }
+            path.push('/');
+        }
+        path.push_str(component);
+    }
+    let node = Node { path, kind, mode: entry.mode, mtime: entry.mtime, data: entry.data, link: entry.link };
+    // Une entrée déjà vue (répertoire créé pour ses fichiers) est remplacée
+    match nodes.iter_mut().find(|existing| existing.path == node.path) {
+        Some(existing) => *existing = node,
+        None => nodes.push(node),
+    }
+}
+
+fn nodes() -> &'static [Node] {
+    NODES.get().map_or(&[], |nodes| nodes.as_slice())
+}
+
+/// Taille de l'archive
+pub fn size() -> usize {
+    ARCHIVE.len()
+}
+
+/// Le système de fichiers de l'initrd (toutes ses instances partagent
+/// les mêmes noeuds)
+pub struct Initrd;
+
+impl FileSystem for Initrd {
+    fn name(&self) -> &'static str {
+        "initrd"
+    }
+
+    fn root(&self) -> Arc<dyn Inode> {
+        Arc::new(InitrdInode(0))
+    }
+
+    fn is_read_only(&self) -> bool {
+        true
+    }
+}
+
+/// Inode de l'initrd : l'index de son noeud
+struct InitrdInode(usize);
+
+impl InitrdInode {
+    fn node(&self) -> &'static Node {
+        &nodes()[self.0]
+    }
+
+    /// Index des noeuds contenus dans ce répertoire
+    fn children(&self) -> impl Iterator<Item = usize> + '_ {
+        let path = self.node().path.as_str();
+        nodes().iter().enumerate().filter(move |(_, node)| node.parent() == Some(path)).map(|(index, _)| index)
+    }
+}
+
+impl Inode for InitrdInode {
+    fn metadata(&self) -> Metadata {
+        let node = self.node();
+        let size = match node.kind {
+            FileType::Symlink => node.link.len(),
+            _ => node.data.len(),
+        };
+        Metadata { kind: node.kind, size: size as u64, mode: node.mode, mtime: node.mtime }
+    }
+
+    fn read_at(&self, offset: u64, buffer: &mut [u8]) -> Result<usize, FsError> {
+        let node = self.node();
+        if node.kind == FileType::Directory {
+            return Err(FsError::IsADirectory);
+        }
+        let start = (offset as usize).min(node.data.len());
+        let count = buffer.len().min(node.data.len() - start);
+        buffer[..count].copy_from_slice(&node.data[start..start + count]);
+        Ok(count)
+    }
+
+    fn readlink(&self) -> Result<String, FsError> {
+        match self.node().kind {
+            FileType::Symlink => Ok(String::from(self.node().link)),
+            _ => Err(FsError::InvalidArgument),
+        }
+    }
+
+    fn as_directory(&self) -> Option<&dyn Directory> {
+        match self.node().kind {
+            FileType::Directory => Some(self),
+            _ => None,
+        }
+    }
+}
+
+impl Directory for InitrdInode {
+    fn lookup(&self, name: &str) -> Result<Arc<dyn Inode>, FsError> {
+        let index = self.children().find(|&index| nodes()[index].name() == name).ok_or(FsError::NotFound)?;
+        Ok(Arc::new(InitrdInode(index)))
+    }
+
+    fn entries(&self) -> Result<Vec<DirEntry>, FsError> {
+        let entries = self.children().map(|index| {
+            let inode: Arc<dyn Inode> = Arc::new(InitrdInode(index));
+            DirEntry { name: String::from(nodes()[index].name()), inode }
+        });
+        Ok(entries.collect())
+    }
+}
+
+// ===================================================================
+// TESTS
+// ===================================================================
+
+#[test_case]
+fn test_initrd_contents() {
+    let root = Initrd.root();
+    let directory = root.as_directory().unwrap();
+    let names: Vec<String> = directory.entries().unwrap().into_iter().map(|entry| entry.name).collect();
+    assert!(names.iter().any(|name| name == "bin") && names.iter().any(|name| name == "etc"));
+    let bin = directory.lookup("bin").unwrap();
+    let hello = bin.as_directory().unwrap().lookup("hello").unwrap();
+    assert_eq!(hello.metadata().kind, FileType::File);
+    assert!(hello.metadata().size > 4);
+    let mut magic = [0u8; 4];
+    assert_eq!(hello.read_at(0, &mut magic), Ok(4));
+    assert_eq!(&magic, b"\x7fELF");
+    assert_eq!(hello.read_at(hello.metadata().size, &mut magic), Ok(0));
+    assert_eq!(directory.lookup("nulle").err(), Some(FsError::NotFound));
+    assert_eq!(directory.create("x", FileType::File).err(), Some(FsError::ReadOnly));
+}
@@ -0,0 +1,657 @@
+// ===================================================================
+// INTERRUPTIONS POUR BOS
+// ===================================================================
+//
+// Ce module regroupe :
+// - L'IDT et les handlers d'exceptions CPU
+// - Les points d'entrée en assembleur qui sauvegardent tous les
+//   registres (`TrapFrame`) : fautes d'un programme et timer, après
+//   lesquels le kernel peut remettre un signal
+// - La configuration du PIC (remapping des IRQs)
+// - Le handler du clavier, qui remplit la file des scancodes
+
+use core::arch::{asm, global_asm};
+
+use bos_core::paging::PageFaultError;
+use bos_core::signal::{Registers, SIGFPE, SIGILL, SIGSEGV};
+
+use crate::backtrace;
+use crate::sync::Spinlock;
+use crate::gdt::{DOUBLE_FAULT_IST_INDEX, KERNEL_CODE_SELECTOR};
+use crate::vga::VgaWriter;
+use crate::{inb, outb};
+
+/// Vecteur de l'exception division par zéro (#DE)
+pub const DIVIDE_ERROR_VECTOR: usize = 0;
+/// Vecteur de l'exception debug (pas à pas, breakpoints matériels)
+pub const DEBUG_VECTOR: usize = 1;
+/// Vecteur de l'exception breakpoint (INT3)
+pub const BREAKPOINT_VECTOR: usize = 3;
+/// Vecteur de l'exception instruction invalide (#UD)
+pub const INVALID_OPCODE_VECTOR: usize = 6;
+/// Vecteur de l'exception double fault
+pub const DOUBLE_FAULT_VECTOR: usize = 8;
+/// Vecteur de l'exception general protection fault (#GP)
+pub const GENERAL_PROTECTION_VECTOR: usize = 13;
+/// Vecteur de l'exception page fault
+pub const PAGE_FAULT_VECTOR: usize = 14;
+/// Vecteur du timer (IRQ 0 après remapping du PIC)
+pub const TIMER_VECTOR: usize = 32;
+/// Vecteur du clavier (IRQ 1 après remapping du PIC)
+pub const KEYBOARD_VECTOR: usize = 33;
+
+// Structure représentant l'état de la pile quand une interruption se produit
+// Le CPU sauvegarde automatiquement ces informations sur la pile
+#[repr(C)]
+#[derive(Debug)]
+pub struct InterruptStackFrame {
+    pub instruction_pointer: u64,  // Adresse de l'instruction interrompue (RIP)
+    pub code_segment: u64,         // Segment de code (CS)
+    pub cpu_flags: u64,            // Flags du CPU (RFLAGS)
+    pub stack_pointer: u64,        // Pointeur de pile (RSP)
+    pub stack_segment: u64,        // Segment de pile (SS)
+}
+
+impl InterruptStackFrame {
+    /// Indique si l'interruption a eu lieu en ring 3 (programme utilisateur)
+    pub fn from_user(&self) -> bool {
+        self.code_segment & 3 == 3
+    }
+}
+
+/// Handler d'interruption simple (sans code d'erreur)
+pub type HandlerFunc = unsafe extern "x86-interrupt" fn(InterruptStackFrame);
+/// Handler d'exception avec code d'erreur empilé par le CPU
+pub type HandlerFuncWithErrCode = unsafe extern "x86-interrupt" fn(InterruptStackFrame, u64);
+/// Handler d'exception avec code d'erreur qui ne retourne jamais
+pub type DivergingHandlerFuncWithErrCode = unsafe extern "x86-interrupt" fn(InterruptStackFrame, u64) -> !;
+
+/* =========================================================
+   IDT - INTERRUPT DESCRIPTOR TABLE
+   
+   L'IDT est une table qui associe chaque numéro d'interruption
+   (0-255) à une fonction qui sera appelée quand cette
+   interruption se produit.
+   
+   Structure : 256 entrées de 16 octets chacune
+   Adresse : configurée via l'instruction assembleur LIDT
+   
+   Interruptions importantes :
+   - 0-31  : Exceptions CPU (division par zéro, page fault, etc.)
+   - 3     : Breakpoint (instruction INT3)
+   - 8     : Double fault (une exception survenue pendant une autre)
+   - 32-47 : IRQs matérielles (après remapping du PIC)
+   - 33    : IRQ 1 = Interruption clavier
+========================================================= */
+
+// Structure d'une entrée dans l'IDT (16 octets)
+// #[repr(C, packed)] garantit que le compilateur n'ajoute pas de padding
+#[repr(C, packed)]
+#[derive(Clone, Copy)]
+pub struct IdtEntry {
+    offset_low: u16,    // Bits 0-15 de l'adresse du handler
+    selector: u16,      // Sélecteur de segment de code (GDT)
+    ist: u8,            // Bits 0-2 : index de pile IST (0 = pile courante), reste réservé
+    type_attr: u8,      // Type et attributs (présent, DPL, type de gate)
+    offset_mid: u16,    // Bits 16-31 de l'adresse du handler
+    offset_high: u32,   // Bits 32-63 de l'adresse du handler (mode 64-bit)
+    reserved: u32,      // Réservé (doit être 0)
+}
+
+impl IdtEntry {
+    /// Crée une entrée IDT vide (initialisée à zéro)
+    pub const fn new() -> IdtEntry {
+        IdtEntry {
+            offset_low: 0,
+            selector: 0,
+            ist: 0,
+            type_attr: 0,
+            offset_mid: 0,
+            offset_high: 0,
+            reserved: 0,
+        }
+    }
+
+    /// Configure cette entrée IDT pour pointer vers un handler d'interruption
+    pub fn set_handler(&mut self, handler: HandlerFunc) {
+        self.set_handler_addr(handler as usize as u64);
+    }
+
+    /// Configure un handler d'exception qui reçoit un code d'erreur
+    /// (page fault, general protection fault, etc.)
+    pub fn set_handler_with_err_code(&mut self, handler: HandlerFuncWithErrCode) {
+        self.set_handler_addr(handler as usize as u64);
+    }
+
+    /// Configure un handler qui ne retourne jamais (double fault, machine check)
+    pub fn set_diverging_handler_with_err_code(&mut self, handler: DivergingHandlerFuncWithErrCode) {
+        self.set_handler_addr(handler as usize as u64);
+    }
+
+    /// Configure un handler écrit en assembleur (voir gdb.rs)
+    ///
+    /// # Safety
+    /// `addr` doit être le point d'entrée d'une routine qui sauvegarde et
+    /// restaure elle-même les registres et se termine par IRETQ.
+    pub unsafe fn set_raw_handler(&mut self, addr: u64) {
+        self.set_handler_addr(addr);
+    }
+
+    /// Fait pointer cette entrée IDT vers l'adresse d'un handler
+    /// 
+    /// L'adresse 64-bit du handler est divisée en 3 parties (low, mid, high)
+    /// car l'architecture x86-64 utilise ce format pour la compatibilité
+    fn set_handler_addr(&mut self, addr: u64) {
+        // Diviser l'adresse 64-bit en 3 parties
+        self.offset_low = addr as u16;              // Bits 0-15
+        self.offset_mid = (addr >> 16) as u16;      // Bits 16-31
+        self.offset_high = (addr >> 32) as u32;     // Bits 32-63
+        
+        // Sélecteur de segment de code (notre GDT, voir gdt.rs)
+        // 0x08 = entrée 1 de la GDT (Global Descriptor Table) = segment de code kernel
+        self.selector = KERNEL_CODE_SELECTOR;
+        
+        self.ist = 0;
+        
+        // Attributs de l'entrée : 0x8E
+        // 0x80 = Present (l'entrée est valide)
+        // 0x0E = Type = 64-bit interrupt gate, DPL=0 (niveau privilège 0)
+        self.type_attr = 0x8E;
+        
+        self.reserved = 0;
+    }
+
+    /// Demande au CPU de basculer sur la pile IST `index` (1-7) avant
+    /// d'appeler le handler (voir gdt.rs)
+    pub fn set_stack_index(&mut self, index: u8) {
+        self.ist = index & 0x7;
+    }
+
+    /// Retourne l'index de pile IST configuré (0 = pile courante)
+    pub fn stack_index(&self) -> u8 {
+        self.ist & 0x7
+    }
+
+    /// Retourne l'adresse du handler configuré dans cette entrée
+    pub fn handler_addr(&self) -> u64 {
+        (self.offset_low as u64) | ((self.offset_mid as u64) << 16) | ((self.offset_high as u64) << 32)
+    }
+
+    /// Indique si l'entrée est marquée présente
+    pub fn is_present(&self) -> bool {
+        self.type_attr & 0x80 != 0
+    }
+}
+
+impl Default for IdtEntry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// Structure pour décrire l'IDT au CPU (utilisée par l'instruction LIDT)
+#[repr(C, packed)]
+pub struct IdtDescriptor {
+    pub size: u16,      // Taille de l'IDT - 1 (en octets)
+    pub offset: u64,    // Adresse mémoire de l'IDT
+}
+
+// Table globale contenant les 256 entrées d'interruption
+// Initialisée avec des entrées vides. Le CPU la lit sans prendre le
+// verrou : il ne protège que nos modifications.
+static IDT: Spinlock<[IdtEntry; 256]> = Spinlock::new([IdtEntry::new(); 256]);
+
+/// Initialise l'IDT et la charge dans le CPU
+pub fn init_idt() {
+    let mut idt = IDT.lock();
+    // Exceptions CPU
+    idt[BREAKPOINT_VECTOR].set_handler(breakpoint_handler);
+    // Le double fault utilise sa propre pile (IST) : si la pile du kernel
+    // a débordé, le CPU ne pourrait pas y empiler la trame d'interruption
+    idt[DOUBLE_FAULT_VECTOR].set_diverging_handler_with_err_code(double_fault_handler);
+    idt[DOUBLE_FAULT_VECTOR].set_stack_index(DOUBLE_FAULT_IST_INDEX);
+
+    // Fautes qu'un programme peut provoquer et tick du timer (IRQ 0,
+    // PIT canal 0) : tous les registres sont sauvegardés, pour pouvoir
+    // remettre un signal au programme interrompu
+    unsafe {
+        idt[DIVIDE_ERROR_VECTOR].set_raw_handler(trap_divide_error_entry as *const () as u64);
+        idt[INVALID_OPCODE_VECTOR].set_raw_handler(trap_invalid_opcode_entry as *const () as u64);
+        idt[GENERAL_PROTECTION_VECTOR].set_raw_handler(trap_general_protection_entry as *const () as u64);
+        idt[PAGE_FAULT_VECTOR].set_raw_handler(trap_page_fault_entry as *const () as u64);
+        idt[TIMER_VECTOR].set_raw_handler(trap_timer_entry as *const () as u64);
+    }
+
+    // Configurer l'entrée 33 (IRQ 1 après remapping) pour le clavier
+    // IRQ 1 correspond à l'interruption matérielle du clavier PS/2
+    idt[KEYBOARD_VECTOR].set_handler(keyboard_interrupt_handler);
+
+    // La table est dans un static : elle reste valide une fois le verrou rendu
+    unsafe { load_idt(&*idt) };
+}
+
+/// Remplace le handler d'un vecteur de l'IDT du kernel
+///
+/// Le CPU relit l'IDT à chaque interruption : le changement est immédiat.
+///
+/// # Safety
+/// Mêmes conditions que `IdtEntry::set_raw_handler`.
+pub unsafe fn set_raw_handler(vector: usize, addr: u64) {
+    IDT.lock()[vector].set_raw_handler(addr);
+}
+
+/// Charge une IDT dans le CPU avec l'instruction LIDT
+///
+/// # Safety
+/// La table doit rester valide (donc être `static`) tant qu'elle est chargée,
+/// et toutes ses entrées présentes doivent pointer vers des handlers valides.
+pub unsafe fn load_idt(idt: *const [IdtEntry; 256]) {
+    // Créer le descripteur qui pointe vers notre IDT
+    let idt_desc = IdtDescriptor {
+        // Taille en octets - 1 (256 entrées × 16 octets = 4096)
+        size: (core::mem::size_of::<[IdtEntry; 256]>() - 1) as u16,
+        offset: idt as u64,
+    };
+    
+    // Charger l'IDT dans le CPU avec l'instruction LIDT
+    // À partir de maintenant, le CPU utilisera cette table pour
+    // gérer les interruptions
+    asm!(
+        "lidt [{}]",
+        in(reg) &idt_desc,
+        options(nostack, preserves_flags)
+    );
+}
+
+/// Lit le descripteur de l'IDT actuellement chargée (instruction SIDT)
+pub fn current_idt() -> IdtDescriptor {
+    let mut desc = IdtDescriptor { size: 0, offset: 0 };
+    unsafe {
+        asm!(
+            "sidt [{}]",
+            in(reg) &mut desc,
+            options(nostack, preserves_flags)
+        );
+    }
+    desc
+}
+
+/* =========================================================
+   EXCEPTIONS CPU
+   
+   Les exceptions sont déclenchées par le CPU lui-même quand
+   une instruction pose problème. Certaines empilent un code
+   d'erreur en plus de la trame d'interruption.
+
+   Celles qu'un programme peut provoquer passent par les
+   trappes plus bas, qui lui envoient un signal.
+========================================================= */
+
+/// Handler du breakpoint (INT3) : affiche l'adresse, la pile d'appels
+/// et reprend l'exécution
+extern "x86-interrupt" fn breakpoint_handler(stack_frame: InterruptStackFrame) {
+    let rbp = backtrace::interrupted_frame_pointer();
+    println!("EXCEPTION: BREAKPOINT a {:#x}", stack_frame.instruction_pointer);
+    let _ = backtrace::write_exception_backtrace(&mut VgaWriter, stack_frame.instruction_pointer, rbp);
+}
+
+/// Handler du double fault : l'état du kernel est irrécupérable
+extern "x86-interrupt" fn double_fault_handler(stack_frame: InterruptStackFrame, _error_code: u64) -> ! {
+    // Le panic affichera la pile d'appels du code fautif, pas celle du handler
+    crate::panic::record_exception(stack_frame.instruction_pointer, backtrace::interrupted_frame_pointer());
+    panic!("EXCEPTION: DOUBLE FAULT\n{:#x?}", stack_frame);
+}
+
+/* =========================================================
+   TRAPPES AVEC TOUS LES REGISTRES
+
+   Le handler x86-interrupt ne donne que la trame empilée par le
+   CPU (RIP, CS, RFLAGS, RSP, SS). Pour remettre un signal à un
+   programme, le kernel doit pouvoir sauvegarder tous ses registres
+   dans la trame de signal, et les remplacer pour appeler le
+   handler du programme.
+
+   Les fautes qu'un programme peut provoquer (#DE, #UD, #GP, #PF)
+   et le timer passent donc par des points d'entrée en assembleur
+   qui empilent tous les registres dans une `TrapFrame` (comme le
+   stub GDB, voir gdb.rs) et appellent `trap_dispatch`. Au retour,
+   les registres de la trame, éventuellement modifiés, sont
+   restaurés avant IRETQ.
+
+   Une faute en ring 3 envoie un signal au programme fautif
+   (SIGFPE, SIGILL, SIGSEGV) ; en ring 0, c'est un bug du kernel.
+========================================================= */
+
+/// Registres du code interrompu, dans l'ordre où l'assembleur les empile
+/// (le dernier empilé est à l'adresse la plus basse)
+#[repr(C)]
+#[derive(Debug, Default)]
+pub struct TrapFrame {
+    pub r15: u64,
+    pub r14: u64,
+    pub r13: u64,
+    pub r12: u64,
+    pub r11: u64,
+    pub r10: u64,
+    pub r9: u64,
+    pub r8: u64,
+    pub rbp: u64,
+    pub rdi: u64,
+    pub rsi: u64,
+    pub rdx: u64,
+    pub rcx: u64,
+    pub rbx: u64,
+    pub rax: u64,
+    /// Vecteur de l'interruption
+    pub vector: u64,
+    /// Code d'erreur (0 si le CPU n'en empile pas, pour garder une
+    /// trame uniforme)
+    pub error_code: u64,
+    // Trame d'interruption empilée par le CPU
+    pub rip: u64,
+    pub cs: u64,
+    pub rflags: u64,
+    pub rsp: u64,
+    pub ss: u64,
+}
+
+impl TrapFrame {
+    /// Indique si l'interruption a eu lieu en ring 3 (programme utilisateur)
+    pub fn from_user(&self) -> bool {
+        self.cs & 3 == 3
+    }
+
+    /// Registres du programme interrompu
+    pub fn registers(&self) -> Registers {
+        Registers {
+            rax: self.rax,
+            rbx: self.rbx,
+            rcx: self.rcx,
+            rdx: self.rdx,
+            rsi: self.rsi,
+            rdi: self.rdi,
+            rbp: self.rbp,
+            rsp: self.rsp,
+            r8: self.r8,
+            r9: self.r9,
+            r10: self.r10,
+            r11: self.r11,
+            r12: self.r12,
+            r13: self.r13,
+            r14: self.r14,
+            r15: self.r15,
+            rip: self.rip,
+            rflags: self.rflags,
+        }
+    }
+
+    /// Remplace les registres du programme interrompu (les sélecteurs
+    /// CS et SS ne changent pas)
+    pub fn set_registers(&mut self, registers: &Registers) {
+        self.rax = registers.rax;
+        self.rbx = registers.rbx;
+        self.rcx = registers.rcx;
+        self.rdx = registers.rdx;
+        self.rsi = registers.rsi;
+        self.rdi = registers.rdi;
+        self.rbp = registers.rbp;
+        self.rsp = registers.rsp;
+        self.r8 = registers.r8;
+        self.r9 = registers.r9;
+        self.r10 = registers.r10;
+        self.r11 = registers.r11;
+        self.r12 = registers.r12;
+        self.r13 = registers.r13;
+        self.r14 = registers.r14;
+        self.r15 = registers.r15;
+        self.rip = registers.rip;
+        self.rflags = registers.rflags;
+    }
+}
+
+// Points d'entrée : chacun empile un code d'erreur (sauf si le CPU
+// l'a fait) et son vecteur. La pile fait 5 + 2 + 15 = 22 mots de 8
+// octets : le CPU l'ayant alignée sur 16 octets avant d'empiler sa
+// trame, elle reste alignée pour le `call`.
+global_asm!(
+    ".global trap_divide_error_entry",
+    "trap_divide_error_entry:",
+    "    push 0",
+    "    push {divide_error}",
+    "    jmp trap_common",
+    ".global trap_invalid_opcode_entry",
+    "trap_invalid_opcode_entry:",
+    "    push 0",
+    "    push {invalid_opcode}",
+    "    jmp trap_common",
+    ".global trap_general_protection_entry",
+    "trap_general_protection_entry:",
+    "    push {general_protection}",
+    "    jmp trap_common",
+    ".global trap_page_fault_entry",
+    "trap_page_fault_entry:",
+    "    push {page_fault}",
+    "    jmp trap_common",
+    ".global trap_timer_entry",
+    "trap_timer_entry:",
+    "    push 0",
+    "    push {timer}",
+    "    jmp trap_common",
+    "trap_common:",
+    "    push rax",
+    "    push rbx",
+    "    push rcx",
+    "    push rdx",
+    "    push rsi",
+    "    push rdi",
+    "    push rbp",
+    "    push r8",
+    "    push r9",
+    "    push r10",
+    "    push r11",
+    "    push r12",
+    "    push r13",
+    "    push r14",
+    "    push r15",
+    "    mov rdi, rsp",
+    "    cld",
+    "    call {dispatch}",
+    "    pop r15",
+    "    pop r14",
+    "    pop r13",
+    "    pop r12",
+    "    pop r11",
+    "    pop r10",
+    "    pop r9",
+    "    pop r8",
+    "    pop rbp",
+    "    pop rdi",
+    "    pop rsi",
+    "    pop rdx",
+    "    pop rcx",
+    "    pop rbx",
+    "    pop rax",
+    // Retirer le vecteur et le code d'erreur
+    "    add rsp, 16",
+    "    iretq",
+    divide_error = const DIVIDE_ERROR_VECTOR,
+    invalid_opcode = const INVALID_OPCODE_VECTOR,
+    general_protection = const GENERAL_PROTECTION_VECTOR,
+    page_fault = const PAGE_FAULT_VECTOR,
+    timer = const TIMER_VECTOR,
+    dispatch = sym trap_dispatch,
+);
+
+extern "C" {
+    fn trap_divide_error_entry();
+    fn trap_invalid_opcode_entry();
+    fn trap_general_protection_entry();
+    fn trap_page_fault_entry();
+    fn trap_timer_entry();
+}
+
+/// Appelée par les points d'entrée, interruptions masquées
+extern "C" fn trap_dispatch(frame: &mut TrapFrame) {
+    match frame.vector as usize {
+        TIMER_VECTOR => timer_interrupt(),
+        DIVIDE_ERROR_VECTOR => exception(frame, SIGFPE, "division par zero", frame.rip),
+        INVALID_OPCODE_VECTOR => exception(frame, SIGILL, "instruction invalide", frame.rip),
+        GENERAL_PROTECTION_VECTOR => exception(frame, SIGSEGV, "general protection fault", frame.rip),
+        PAGE_FAULT_VECTOR => exception(frame, SIGSEGV, "page fault", crate::paging::fault_address()),
+        vector => panic!("trappe inattendue : vecteur {}", vector),
+    }
+    // Signaux en attente (faute, kill, Ctrl+C...) : remis avant de
+    // revenir au programme
+    if frame.from_user() {
+        crate::signal::deliver_trap(frame);
+    }
+}
+
+/// Faute `description` à l'adresse `address` : signal `signal` pour un
+/// programme, panic pour le kernel
+fn exception(frame: &TrapFrame, signal: u8, description: &'static str, address: u64) {
+    if frame.from_user() {
+        crate::process::fault(signal, description, address);
+        return;
+    }
+    // Le panic affichera la pile d'appels du code fautif
+    crate::panic::record_exception(frame.rip, frame.rbp);
+    match frame.vector as usize {
+        DIVIDE_ERROR_VECTOR => panic!("EXCEPTION: DIVIDE ERROR\n{:#x?}", frame),
+        INVALID_OPCODE_VECTOR => panic!("EXCEPTION: INVALID OPCODE\n{:#x?}", frame),
+        GENERAL_PROTECTION_VECTOR => {
+            panic!("EXCEPTION: GENERAL PROTECTION FAULT (code {:#x})\n{:#x?}", frame.error_code, frame)
+        }
+        // Le kernel ne sait pas encore charger une page à la demande :
+        // tout page fault est une erreur
+        _ => panic!(
+            "EXCEPTION: PAGE FAULT\nAdresse : {:#x}\nCause : {} (code {:#x})\n{:#x?}",
+            address,
+            PageFaultError(frame.error_code),
+            frame.error_code,
+            frame
+        ),
+    }
+}
+
+/* =========================================================
+   PIC - PROGRAMMABLE INTERRUPT CONTROLLER
+   
+   Le PIC (8259) gère les interruptions matérielles (IRQs).
+   Sur PC, il y a 2 PICs en cascade : PIC1 (maître) et PIC2 (esclave)
+   
+   PIC1 gère IRQ 0-7  : Timer, Clavier, Cascade, COM2, COM1, etc.
+   PIC2 gère IRQ 8-15 : RTC, Souris, Disques, etc.
+   
+   Par défaut, les IRQs sont mappées sur INT 8-15, ce qui entre
+   en conflit avec les exceptions CPU. On les remappera sur INT 32-47.
+   
+   Après remapping :
+   - IRQ 0 (timer)   → INT 32
+   - IRQ 1 (clavier) → INT 33  ← Nous utilisons celle-ci !
+   - IRQ 2-7         → INT 34-39
+   - IRQ 8-15        → INT 40-47
+========================================================= */
+
+// Ports I/O pour communiquer avec les PICs
+pub const PIC1_COMMAND: u16 = 0x20;  // Port de commande du PIC maître
+pub const PIC1_DATA: u16 = 0x21;     // Port de données du PIC maître
+pub const PIC2_COMMAND: u16 = 0xA0;  // Port de commande du PIC esclave
+pub const PIC2_DATA: u16 = 0xA1;     // Port de données du PIC esclave
+
+/// Initialise et configure les PICs (remapping des IRQs)
+pub fn init_pic() {
+    unsafe {
+        // ===== ICW1 : Commencer l'initialisation =====
+        // 0x11 = 00010001 :
+        //   - Bit 4 = 1 : Mode d'initialisation
+        //   - Bit 0 = 1 : ICW4 sera envoyé
+        outb(PIC1_COMMAND, 0x11);
+        outb(PIC2_COMMAND, 0x11);
+        
+        // ===== ICW2 : Remapper les vecteurs d'interruption =====
+        // Par défaut, IRQ 0-7 sont mappées sur INT 8-15 (conflit avec CPU exceptions)
+        // On les remapppe sur INT 32-47 pour éviter les conflits
+        outb(PIC1_DATA, 0x20);  // PIC1 : IRQ 0-7  → INT 32-39 (0x20 = 32)
+        outb(PIC2_DATA, 0x28);  // PIC2 : IRQ 8-15 → INT 40-47 (0x28 = 40)
+        
+        // ===== ICW3 : Configurer le mode cascade =====
+        // Le PIC2 est connecté au PIC1 via IRQ2
+        outb(PIC1_DATA, 0x04);  // 0x04 = 00000100 : IRQ2 a un PIC esclave
+        outb(PIC2_DATA, 0x02);  // 0x02 : Le PIC2 est l'esclave sur IRQ2
+        
+        // ===== ICW4 : Mode de fonctionnement =====
+        // 0x01 = Mode 8086/88 (vs mode MCS-80/85)
+        outb(PIC1_DATA, 0x01);
+        outb(PIC2_DATA, 0x01);
+        
+        // ===== Masquer/démasquer les IRQs =====
+        // Chaque bit contrôle une IRQ (0=activée, 1=masquée)
+        // 0xFC = 11111100 : Toutes les IRQs masquées sauf le timer et le clavier
+        //   Bit 0 = 0 : IRQ 0 (timer) ACTIF (horodatage du journal)
+        //   Bit 1 = 0 : IRQ 1 (clavier) ACTIF
+        //   Bit 2-7 = 1 : IRQ 2-7 masqués
+        outb(PIC1_DATA, 0xFC);
+        // 0xFF : Masquer toutes les IRQs du PIC2 (on n'en a pas besoin pour l'instant)
+        outb(PIC2_DATA, 0xFF);
+    }
+}
+
+/// Tick du timer (IRQ 0 = INT 32) : compte les ticks, envoie les
+/// alarmes échues et préempte le thread en cours à la fin de sa
+/// tranche de temps
+fn timer_interrupt() {
+    crate::pit::tick();
+    unsafe {
+        outb(PIC1_COMMAND, 0x20);
+    }
+    crate::process::fire_alarms();
+    // Après l'EOI : le thread suivant peut garder la main longtemps
+    crate::thread::preempt();
+}
+
+/* =========================================================
+   KEYBOARD HANDLER
+   
+   Le clavier PS/2 communique via le port 0x60 (données).
+   
+   Quand une touche est pressée :
+   1. Le contrôleur clavier envoie un "scancode" (code de la touche)
+   2. Le PIC déclenche l'IRQ 1
+   3. Le CPU appelle notre handler (INT 33 après remapping)
+   4. On lit le scancode du port 0x60
+   5. On le dépose dans la file des scancodes, ce qui réveille
+      la tâche du shell (voir keyboard.rs)
+   6. On envoie EOI (End Of Interrupt) au PIC
+
+   Le décodage (touches pressées ou relâchées, layout AZERTY) et
+   l'exécution des commandes se font dans la tâche du shell, hors
+   du handler.
+========================================================= */
+
+// Port I/O pour lire les données du clavier
+const KEYBOARD_DATA_PORT: u16 = 0x60;
+
+/// Handler d'interruption pour le clavier (IRQ 1 = INT 33)
+/// 
+/// Cette fonction est appelée automatiquement par le CPU chaque fois
+/// qu'une touche est pressée ou relâchée sur le clavier.
+/// 
+/// Convention d'appel x86-interrupt :
+/// - Le CPU sauvegarde automatiquement l'état
+/// - Le CPU désactive les interruptions (CLI)
+/// - À la fin, le CPU restaure l'état et réactive les interruptions (IRET)
+unsafe extern "x86-interrupt" fn keyboard_interrupt_handler(_stack_frame: InterruptStackFrame) {
+    // Lire le scancode depuis le port 0x60
+    // Ce port contient le code de la touche qui vient d'être pressée/relâchée
+    let scancode = inb(KEYBOARD_DATA_PORT);
+    
+    // Le confier à la tâche qui lit le clavier
+    crate::keyboard::add_scancode(scancode);
+    
+    // ===== IMPORTANT : Envoyer EOI (End Of Interrupt) au PIC =====
+    // On doit signaler au PIC que l'interruption a été traitée.
+    // Sans cela, le PIC ne déclenchera plus d'interruptions !
+    // 0x20 sur le port de commande du PIC1 = commande EOI
+    outb(PIC1_COMMAND, 0x20);
+}
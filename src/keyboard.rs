@@ -0,0 +1,135 @@
+/* =========================================================
+   FLUX DES SCANCODES DU CLAVIER
+
+   Le handler de l'IRQ 1 ne fait plus que lire le scancode et
+   l'envoyer dans un canal (`add_scancode`), ce qui réveille la
+   tâche qui attend le clavier. Le décodage et l'exécution des
+   commandes se font dans cette tâche (le shell), interruptions
+   activées :
+
+     IRQ 1 → add_scancode → canal → ScancodeStream → shell::run
+
+   Si le canal est plein (tâche bloquée trop longtemps), les
+   scancodes suivants sont perdus et comptés dans `dropped()`.
+========================================================= */
+
+use core::pin::Pin;
+use core::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use core::task::{Context, Poll};
+
+use crate::channel::Channel;
+use crate::task::Stream;
+
+/// Nombre de scancodes en attente avant d'en perdre
+const QUEUE_SIZE: usize = 128;
+
+// Scancodes reçus et pas encore lus
+static SCANCODES: Channel<u8> = Channel::new(QUEUE_SIZE);
+
+/// Nombre de caractères en attente pour les programmes utilisateur
+const INPUT_SIZE: usize = 256;
+
+// Caractères destinés aux programmes utilisateur
+static INPUT: Channel<u8> = Channel::new(INPUT_SIZE);
+
+// Scancodes perdus faute de place
+static DROPPED: AtomicU64 = AtomicU64::new(0);
+
+// Un seul lecteur à la fois
+static STREAM_TAKEN: AtomicBool = AtomicBool::new(false);
+
+/// Dépose un scancode dans le canal (appelée par le handler du clavier)
+pub fn add_scancode(scancode: u8) {
+    if SCANCODES.try_send(scancode).is_err() {
+        DROPPED.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+/// Caractères tapés destinés aux programmes utilisateur, lus par
+/// l'appel système `read` sur l'entrée standard (voir syscall.rs)
+pub fn input() -> &'static Channel<u8> {
+    &INPUT
+}
+
+/// Nombre de scancodes perdus depuis le démarrage
+pub fn dropped() -> u64 {
+    DROPPED.load(Ordering::Relaxed)
+}
+
+/// Flux asynchrone des scancodes reçus
+pub struct ScancodeStream {
+    _private: (),
+}
+
+impl ScancodeStream {
+    /// Crée le flux
+    ///
+    /// Panic si un autre `ScancodeStream` existe déjà : chaque scancode
+    /// ne doit être lu qu'une fois.
+    pub fn new() -> ScancodeStream {
+        if STREAM_TAKEN.swap(true, Ordering::AcqRel) {
+            panic!("ScancodeStream deja utilise");
+        }
+        ScancodeStream { _private: () }
+    }
+
+    /// Attend un scancode au plus `timeout_ms` millisecondes, en
+    /// bloquant le thread (hors d'une tâche asynchrone)
+    pub fn next_timeout(&mut self, timeout_ms: u64) -> Option<u8> {
+        SCANCODES.recv_timeout(timeout_ms).ok()
+    }
+}
+
+impl Default for ScancodeStream {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for ScancodeStream {
+    fn drop(&mut self) {
+        STREAM_TAKEN.store(false, Ordering::Release);
+    }
+}
+
+impl Stream for ScancodeStream {
+    type Item = u8;
+
+    fn poll_next(self: Pin<&mut Self>, context: &mut Context) -> Poll<Option<u8>> {
+        SCANCODES.poll_recv(context)
+    }
+}
+
+// ===================================================================
+// TESTS
+// ===================================================================
+
+#[test_case]
+fn test_stream_wakes_on_scancode() {
+    use crate::task::{Executor, Task};
+    use alloc::rc::Rc;
+    use core::cell::RefCell;
+
+    let received = Rc::new(RefCell::new(alloc::vec::Vec::new()));
+    let mut executor = Executor::new();
+    let sink = received.clone();
+    executor.spawn(Task::new("test", async move {
+        let mut stream = ScancodeStream::new();
+        while let Some(scancode) = stream.next().await {
+            sink.borrow_mut().push(scancode);
+            if scancode == 0x1C {
+                break;
+            }
+        }
+    }));
+
+    // Rien à lire : la tâche attend
+    executor.run_ready_tasks();
+    assert_eq!(executor.task_count(), 1);
+
+    add_scancode(0x10);
+    add_scancode(0x1C);
+    executor.run_ready_tasks();
+    assert_eq!(*received.borrow(), [0x10, 0x1C]);
+    assert_eq!(executor.task_count(), 0);
+}
@@ -0,0 +1,328 @@
+// ===================================================================
+// BIBLIOTHÈQUE DU KERNEL BOS
+// ===================================================================
+//
+// Tout le code du kernel vit dans cette bibliothèque, le binaire
+// (main.rs) ne contient que le point d'entrée et le panic handler.
+// Cette séparation permet aux tests d'intégration (dossier tests/)
+// de réutiliser le kernel : chaque fichier de tests/ est un petit
+// kernel indépendant qui appelle les fonctions de `bos`.
+
+#![no_std]
+
+// En mode test, la bibliothèque est compilée comme un kernel autonome
+// avec son propre point d'entrée _start
+#![cfg_attr(test, no_main)]
+
+// Active l'ABI x86-interrupt pour gérer les interruptions matérielles
+#![feature(abi_x86_interrupt)]
+
+// Permet de définir le handler appelé quand une allocation échoue (heap.rs)
+#![feature(alloc_error_handler)]
+
+// Framework de tests personnalisé : `cargo test` ne peut pas utiliser le
+// harness standard (il dépend de std). On fournit notre propre runner
+// qui exécute les fonctions marquées #[test_case] à l'intérieur de QEMU.
+// `test_main` est la fonction générée qui appelle ce runner.
+#![feature(custom_test_frameworks)]
+#![test_runner(crate::test_runner)]
+#![reexport_test_harness_main = "test_main"]
+
+use core::arch::asm; // Pour l'assembleur inline
+use core::panic::PanicInfo;
+
+use bootloader::BootInfo;
+
+// Box, Vec, String... alloués sur le tas du kernel (voir heap.rs)
+extern crate alloc;
+
+// Déclarer les modules (serial, vga et log en premier pour que leurs macros soient visibles partout)
+#[macro_use]
+pub mod serial;
+#[macro_use]
+pub mod vga;
+#[macro_use]
+pub mod log;
+pub mod backtrace;
+pub mod channel;
+pub mod exec;
+pub mod frame;
+pub mod gdb;
+pub mod gdt;
+pub mod heap;
+pub mod heap_debug;
+pub mod initrd;
+pub mod interrupts;
+pub mod keyboard;
+pub mod memory;
+pub mod paging;
+pub mod panic;
+pub mod pit;
+pub mod process;
+pub mod programs;
+pub mod rtc;
+pub mod shell;
+pub mod signal;
+pub mod slab;
+pub mod symbols;
+pub mod sync;
+pub mod syscall;
+pub mod task;
+pub mod thread;
+pub mod tmpfs;
+pub mod usermode;
+pub mod vfs;
+
+// Réexporter les fonctions d'affichage utilisées partout dans le kernel
+pub use vga::{update_hardware_cursor, vga_backspace, vga_print, vga_print_char};
+
+/* =========================================================
+   PORTS I/O
+========================================================= */
+
+/// Écrit un octet sur un port I/O (OUT instruction)
+/// 
+/// Les ports I/O permettent de communiquer avec les périphériques matériels.
+/// Exemples : 0x60 = clavier, 0x20/0xA0 = PICs, 0x3F8 = COM1, etc.
+///
+/// # Safety
+/// Écrire sur un port peut reconfigurer le matériel de façon arbitraire.
+pub unsafe fn outb(port: u16, value: u8) {
+    asm!(
+        "out dx, al",           // Instruction assembleur OUT
+        in("dx") port,          // Port dans le registre DX
+        in("al") value,         // Valeur dans le registre AL
+        options(nostack, preserves_flags)
+    );
+}
+
+/// Lit un octet depuis un port I/O (IN instruction)
+/// 
+/// Utilisé pour lire l'état des périphériques matériels.
+/// Par exemple : lire le scancode du clavier depuis le port 0x60
+///
+/// # Safety
+/// Certaines lectures ont des effets de bord (acquittement, vidage de FIFO).
+pub unsafe fn inb(port: u16) -> u8 {
+    let value: u8;
+    asm!(
+        "in al, dx",            // Instruction assembleur IN
+        out("al") value,        // Lire le résultat depuis AL
+        in("dx") port,          // Port dans le registre DX
+        options(nostack, preserves_flags)
+    );
+    value
+}
+
+/// Écrit un mot de 32 bits sur un port I/O (OUT instruction, variante 32 bits)
+///
+/// Utilisé par exemple pour le périphérique `isa-debug-exit` de QEMU.
+///
+/// # Safety
+/// Mêmes précautions que pour `outb`.
+pub unsafe fn outl(port: u16, value: u32) {
+    asm!(
+        "out dx, eax",
+        in("dx") port,
+        in("eax") value,
+        options(nostack, preserves_flags)
+    );
+}
+
+/* =========================================================
+   INITIALISATION
+========================================================= */
+
+/// Initialise tout le matériel de base du kernel (sans rien afficher)
+///
+/// Utilisé par les tests ; `kernel_main` dans main.rs fait la même chose
+/// étape par étape en affichant la progression du boot.
+pub fn init(boot_info: &'static BootInfo) {
+    memory::init(boot_info);
+    serial::init_serial();
+    frame::init();
+    paging::init();
+    heap::init().expect("initialisation du tas");
+    initrd::init().expect("lecture de l'initrd");
+    rtc::init();
+    vfs::init();
+    symbols::init_symbols();
+    gdt::init_gdt();
+    syscall::init();
+    interrupts::init_idt();
+    interrupts::init_pic();
+    pit::init_timer();
+    thread::init();
+    enable_interrupts();
+}
+
+/// Active les interruptions matérielles (instruction STI)
+pub fn enable_interrupts() {
+    unsafe {
+        asm!("sti", options(nostack, preserves_flags));
+    }
+}
+
+/// Désactive les interruptions matérielles (instruction CLI)
+pub fn disable_interrupts() {
+    unsafe {
+        asm!("cli", options(nomem, nostack));
+    }
+}
+
+/// Active les interruptions et met le CPU en veille, sans fenêtre entre
+/// les deux
+///
+/// STI ne prend effet qu'après l'instruction suivante : une interruption
+/// en attente réveille HLT au lieu d'être traitée juste avant. Appelée
+/// interruptions désactivées, après avoir vérifié qu'il n'y a rien à faire.
+pub fn enable_and_hlt() {
+    unsafe {
+        asm!("sti", "hlt", options(nomem, nostack));
+    }
+}
+
+/// Indique si les interruptions sont activées (bit IF de RFLAGS)
+pub fn interrupts_enabled() -> bool {
+    let rflags: u64;
+    unsafe {
+        asm!("pushfq", "pop {}", out(reg) rflags, options(nomem, preserves_flags));
+    }
+    rflags & (1 << 9) != 0
+}
+
+/// Exécute `f` avec les interruptions désactivées, puis les réactive
+/// si elles l'étaient avant
+///
+/// Protège les données partagées avec les handlers d'interruption.
+pub fn without_interrupts<F: FnOnce() -> R, R>(f: F) -> R {
+    let enabled = interrupts_enabled();
+    if enabled {
+        disable_interrupts();
+    }
+    let result = f();
+    if enabled {
+        enable_interrupts();
+    }
+    result
+}
+
+/// Redémarre la machine
+///
+/// On demande d'abord au contrôleur clavier 8042 d'activer la ligne RESET
+/// du CPU (commande 0xFE). Si cela ne marche pas, on provoque un triple
+/// fault : avec une IDT vide, la moindre exception redémarre le CPU.
+pub fn reboot() -> ! {
+    disable_interrupts();
+    unsafe {
+        // Attendre que le tampon d'entrée du 8042 soit vide (bit 1 du port 0x64)
+        while inb(0x64) & 0x02 != 0 {
+            core::hint::spin_loop();
+        }
+        outb(0x64, 0xFE);
+
+        // Plan B : triple fault
+        let empty_idt = [0u8; 10];
+        asm!("lidt [{}]", "int3", in(reg) &empty_idt, options(nostack));
+    }
+    hlt_loop();
+}
+
+/// Boucle infinie qui met le CPU en veille entre deux interruptions
+pub fn hlt_loop() -> ! {
+    loop {
+        unsafe {
+            asm!("hlt", options(nomem, nostack, preserves_flags));
+        }
+    }
+}
+
+/* =========================================================
+   TESTS DANS QEMU
+
+   Les tests s'exécutent dans le kernel lui-même, démarré par QEMU.
+   Les résultats sont envoyés sur le port série (redirigé vers le
+   terminal de l'hôte), puis on quitte QEMU via le périphérique
+   `isa-debug-exit` (voir [package.metadata.bootimage] dans Cargo.toml).
+
+   Écrire la valeur V sur le port 0xf4 termine QEMU avec le code
+   de sortie (V << 1) | 1 :
+   - Success (0x10) → code 33 (déclaré comme `test-success-exit-code`)
+   - Failed  (0x11) → code 35
+========================================================= */
+
+/// Port I/O du périphérique `isa-debug-exit` de QEMU
+const QEMU_EXIT_PORT: u16 = 0xf4;
+
+/// Codes de sortie envoyés à QEMU
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u32)]
+pub enum QemuExitCode {
+    Success = 0x10,
+    Failed = 0x11,
+}
+
+/// Quitte QEMU avec le code donné (ne fait rien sur du vrai matériel,
+/// d'où la boucle HLT finale)
+pub fn exit_qemu(exit_code: QemuExitCode) -> ! {
+    unsafe {
+        outl(QEMU_EXIT_PORT, exit_code as u32);
+    }
+    hlt_loop();
+}
+
+/// Un test exécutable : affiche son nom, s'exécute, puis affiche [ok]
+///
+/// Implémenté automatiquement pour toutes les fonctions `fn()`, ce qui
+/// permet d'écrire simplement `#[test_case] fn mon_test() { ... }`.
+pub trait Testable {
+    fn run(&self);
+}
+
+impl<T: Fn()> Testable for T {
+    fn run(&self) {
+        serial_print!("{}...\t", core::any::type_name::<T>());
+        self();
+        serial_println!("[ok]");
+    }
+}
+
+/// Runner appelé par `test_main()` avec la liste de tous les #[test_case]
+///
+/// Public pour être réutilisé par le binaire et les tests d'intégration (tests/*.rs).
+pub fn test_runner(tests: &[&dyn Testable]) {
+    serial_println!("Lancement de {} tests", tests.len());
+    for test in tests {
+        test.run();
+    }
+    exit_qemu(QemuExitCode::Success);
+}
+
+/// Panic handler commun à tous les tests : un panic signifie qu'un test a
+/// échoué, on l'affiche sur le port série et on quitte QEMU avec un code d'erreur.
+pub fn test_panic_handler(info: &PanicInfo) -> ! {
+    serial_println!("[failed]\n");
+    serial_println!("Erreur: {}\n", info);
+    exit_qemu(QemuExitCode::Failed);
+}
+
+// ===================================================================
+// POINT D'ENTRÉE EN MODE TEST
+// ===================================================================
+// Quand on exécute `cargo test --lib`, la bibliothèque est le kernel.
+
+#[cfg(test)]
+bootloader::entry_point!(test_kernel_main);
+
+#[cfg(test)]
+fn test_kernel_main(boot_info: &'static BootInfo) -> ! {
+    init(boot_info);
+    test_main();
+    hlt_loop();
+}
+
+#[cfg(test)]
+#[panic_handler]
+fn panic(info: &PanicInfo) -> ! {
+    test_panic_handler(info)
+}
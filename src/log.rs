@@ -0,0 +1,247 @@
+// ===================================================================
+// JOURNAL DU KERNEL
+// ===================================================================
+//
+// Façade de journalisation dans l'esprit de la crate `log` :
+//
+//   info!("GDT OK");
+//   warn!("{} octets perdus", n);
+//
+// Chaque message reçoit un niveau (error, warn, info, debug, trace),
+// le module d'où il vient et l'heure depuis le démarrage (timer PIT) :
+//
+//   [    0.012] INFO  bos::gdt: GDT OK
+//
+// Toutes les lignes sont conservées dans un tampon circulaire en
+// mémoire (commande `dmesg`), puis recopiées sur chaque sortie
+// ("sink") dont le niveau le permet : l'écran VGA et le port série
+// COM1. Le niveau de chaque sortie se règle avec `loglevel`.
+
+use core::fmt::{self, Write};
+use core::sync::atomic::{AtomicU8, Ordering};
+
+use bos_core::log::LogRing;
+pub use bos_core::log::Level;
+
+use crate::vga::{vga_print_color, COLOR_DEBUG, COLOR_DEFAULT, COLOR_ERROR, COLOR_WARNING};
+use crate::sync::Spinlock;
+use crate::{pit, serial};
+
+/// Taille du tampon circulaire du journal
+const LOG_BUFFER_SIZE: usize = 16 * 1024;
+
+/// Longueur maximale d'une ligne (au-delà, elle est tronquée)
+const LINE_SIZE: usize = 256;
+
+// Dernières lignes du journal, lues par `dmesg`
+static LOG_BUFFER: Spinlock<LogRing<LOG_BUFFER_SIZE>> = Spinlock::new(LogRing::new());
+
+/// Une sortie du journal et le niveau maximum qu'elle affiche
+pub struct Sink {
+    pub name: &'static str,
+    level: AtomicU8,
+    write: fn(&str, Level),
+}
+
+impl Sink {
+    /// Niveau maximum affiché par cette sortie
+    pub fn level(&self) -> Level {
+        Level::from_u8(self.level.load(Ordering::Relaxed)).unwrap_or(Level::Info)
+    }
+
+    /// Change le niveau maximum affiché par cette sortie
+    pub fn set_level(&self, level: Level) {
+        self.level.store(level as u8, Ordering::Relaxed);
+    }
+}
+
+/// Les sorties du journal : l'écran (console) et le port série
+pub static SINKS: [Sink; 2] = [
+    Sink {
+        name: "console",
+        level: AtomicU8::new(Level::Info as u8),
+        write: write_console,
+    },
+    Sink {
+        name: "serial",
+        level: AtomicU8::new(Level::Debug as u8),
+        write: write_serial,
+    },
+];
+
+/// Trouve une sortie par son nom
+pub fn sink(name: &str) -> Option<&'static Sink> {
+    SINKS.iter().find(|sink| sink.name == name)
+}
+
+/// Sortie écran : une couleur par niveau
+fn write_console(line: &str, level: Level) {
+    let color = match level {
+        Level::Error => COLOR_ERROR,
+        Level::Warn => COLOR_WARNING,
+        Level::Info => COLOR_DEFAULT,
+        Level::Debug | Level::Trace => COLOR_DEBUG,
+    };
+    vga_print_color(line, color);
+}
+
+/// Sortie série (COM1)
+fn write_serial(line: &str, _level: Level) {
+    serial::serial_print(line);
+}
+
+/// Tampon d'une ligne en cours de formatage (tronque si trop long)
+struct LineBuffer {
+    bytes: [u8; LINE_SIZE],
+    len: usize,
+}
+
+impl LineBuffer {
+    fn as_str(&self) -> &str {
+        // La troncature peut couper un caractère multi-octets en deux
+        match core::str::from_utf8(&self.bytes[..self.len]) {
+            Ok(s) => s,
+            Err(e) => core::str::from_utf8(&self.bytes[..e.valid_up_to()]).unwrap_or(""),
+        }
+    }
+}
+
+impl Write for LineBuffer {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let count = s.len().min(LINE_SIZE - self.len);
+        self.bytes[self.len..self.len + count].copy_from_slice(&s.as_bytes()[..count]);
+        self.len += count;
+        Ok(())
+    }
+}
+
+/// Enregistre un message : utilisé par les macros `error!` à `trace!`
+#[doc(hidden)]
+pub fn _log(level: Level, target: &str, args: fmt::Arguments) {
+    let ms = pit::uptime_ms();
+    let mut line = LineBuffer { bytes: [0; LINE_SIZE], len: 0 };
+    // Un seul '\n' final, ajouté par le tampon et les sorties
+    let _ = write!(line, "[{:5}.{:03}] {:<5} {}: {}", ms / 1000, ms % 1000, level, target, args);
+    let text = line.as_str().trim_end_matches('\n');
+
+    // Le tampon est partagé avec les handlers d'interruption. Le garder
+    // verrouillé pendant l'écriture sur les sorties évite d'y mélanger
+    // deux lignes.
+    let mut buffer = LOG_BUFFER.lock();
+    buffer.push_line(text.as_bytes());
+    for sink in SINKS.iter() {
+        if level <= sink.level() {
+            (sink.write)(text, level);
+            (sink.write)("\n", level);
+        }
+    }
+}
+
+/// Écrit tout le contenu du tampon du journal, du plus ancien au plus récent
+pub fn write_buffer(out: &mut dyn Write) -> fmt::Result {
+    let buffer = LOG_BUFFER.lock();
+    let (first, second) = buffer.as_slices();
+    for part in [first, second] {
+        // Une ligne à cheval sur le bord du tampon peut couper un caractère
+        for chunk in part.utf8_chunks() {
+            out.write_str(chunk.valid())?;
+        }
+    }
+    Ok(())
+}
+
+/// Vide le tampon du journal
+pub fn clear_buffer() {
+    LOG_BUFFER.lock().clear();
+}
+
+/// Enregistre un message avec le niveau donné
+#[macro_export]
+macro_rules! log {
+    ($level:expr, $($arg:tt)+) => {
+        $crate::log::_log($level, module_path!(), format_args!($($arg)+))
+    };
+}
+
+/// Message d'erreur
+#[macro_export]
+macro_rules! error {
+    ($($arg:tt)+) => ($crate::log!($crate::log::Level::Error, $($arg)+));
+}
+
+/// Avertissement
+#[macro_export]
+macro_rules! warn {
+    ($($arg:tt)+) => ($crate::log!($crate::log::Level::Warn, $($arg)+));
+}
+
+/// Information
+#[macro_export]
+macro_rules! info {
+    ($($arg:tt)+) => ($crate::log!($crate::log::Level::Info, $($arg)+));
+}
+
+/// Message de débogage
+#[macro_export]
+macro_rules! debug {
+    ($($arg:tt)+) => ($crate::log!($crate::log::Level::Debug, $($arg)+));
+}
+
+/// Trace très détaillée
+#[macro_export]
+macro_rules! trace {
+    ($($arg:tt)+) => ($crate::log!($crate::log::Level::Trace, $($arg)+));
+}
+
+// ===================================================================
+// TESTS
+// ===================================================================
+
+#[test_case]
+fn test_messages_go_to_buffer() {
+    // Cherche un motif dans ce qui est écrit
+    struct Finder {
+        pattern: &'static str,
+        matched: usize,
+        found: bool,
+    }
+    impl Write for Finder {
+        fn write_str(&mut self, s: &str) -> fmt::Result {
+            for byte in s.bytes() {
+                if byte == self.pattern.as_bytes()[self.matched] {
+                    self.matched += 1;
+                    if self.matched == self.pattern.len() {
+                        self.found = true;
+                        self.matched = 0;
+                    }
+                } else {
+                    self.matched = (byte == self.pattern.as_bytes()[0]) as usize;
+                }
+            }
+            Ok(())
+        }
+    }
+
+    sink("console").unwrap().set_level(Level::Error);
+    debug!("message de test {}", 42);
+    sink("console").unwrap().set_level(Level::Info);
+
+    let mut finder = Finder { pattern: "DEBUG bos::log: message de test 42\n", matched: 0, found: false };
+    write_buffer(&mut finder).unwrap();
+    assert!(finder.found);
+
+    clear_buffer();
+    let mut finder = Finder { pattern: "message de test", matched: 0, found: false };
+    write_buffer(&mut finder).unwrap();
+    assert!(!finder.found);
+}
+
+#[test_case]
+fn test_sink_levels() {
+    let serial = sink("serial").unwrap();
+    let previous = serial.level();
+    serial.set_level(Level::Trace);
+    assert_eq!(serial.level(), Level::Trace);
+    serial.set_level(previous);
+    assert!(sink("inconnu").is_none());
+}
@@ -0,0 +1,123 @@
+/* =========================================================
+   MÉMOIRE PHYSIQUE
+
+   Le bootloader projette toute la mémoire physique dans l'espace
+   virtuel du kernel, à partir d'une adresse fixe (configurée dans
+   Cargo.toml, section [package.metadata.bootloader]).
+
+   Pour lire l'octet à l'adresse physique P, on lit donc l'adresse
+   virtuelle PHYSICAL_MEMORY_OFFSET + P.
+
+   Au démarrage, le bootloader transmet aussi (structure BootInfo)
+   l'adresse réellement utilisée et la carte de la mémoire
+   physique : régions libres, réservées, occupées par le kernel...
+========================================================= */
+
+use core::sync::atomic::{AtomicU64, Ordering};
+
+use bootloader::bootinfo::{BootInfo, MemoryMap, MemoryRegion, MemoryRegionType};
+use bos_core::memory::{MemorySummary, Region, RegionKind};
+
+use crate::sync::Once;
+
+/// Début de la projection de la mémoire physique dans l'espace virtuel
+///
+/// Doit être identique à `physical-memory-offset` dans Cargo.toml.
+/// Sert de valeur par défaut avant `init`.
+pub const PHYSICAL_MEMORY_OFFSET: u64 = 0xFFFF_8000_0000_0000;
+
+// Adresse de la projection transmise par le bootloader
+static PHYSICAL_OFFSET: AtomicU64 = AtomicU64::new(PHYSICAL_MEMORY_OFFSET);
+
+// Carte de la mémoire physique transmise par le bootloader
+static MEMORY_MAP: Once<&'static MemoryMap> = Once::new();
+
+/// Enregistre les informations mémoire transmises par le bootloader
+///
+/// À appeler en tout premier : `phys_to_virt` en dépend.
+pub fn init(boot_info: &'static BootInfo) {
+    PHYSICAL_OFFSET.store(boot_info.physical_memory_offset, Ordering::Relaxed);
+    MEMORY_MAP.call_once(|| &boot_info.memory_map);
+}
+
+/// Début de la projection de la mémoire physique
+pub fn physical_memory_offset() -> u64 {
+    PHYSICAL_OFFSET.load(Ordering::Relaxed)
+}
+
+/// Convertit une adresse physique en adresse virtuelle accessible
+pub fn phys_to_virt(phys: u64) -> u64 {
+    physical_memory_offset() + phys
+}
+
+/// Catégorie d'une région du bootloader (None pour une entrée vide)
+fn region_kind(region_type: MemoryRegionType) -> Option<RegionKind> {
+    use MemoryRegionType::*;
+    match region_type {
+        Usable => Some(RegionKind::Usable),
+        Reserved | BadMemory | FrameZero => Some(RegionKind::Reserved),
+        AcpiReclaimable | AcpiNvs => Some(RegionKind::Acpi),
+        Kernel | KernelStack => Some(RegionKind::Kernel),
+        InUse | PageTable | Bootloader | BootInfo | Package => Some(RegionKind::Bootloader),
+        Empty | NonExhaustive => None,
+    }
+}
+
+fn to_region(region: &MemoryRegion) -> Option<Region> {
+    Some(Region {
+        start: region.range.start_addr(),
+        end: region.range.end_addr(),
+        kind: region_kind(region.region_type)?,
+    })
+}
+
+/// Régions de la mémoire physique, par adresse croissante
+///
+/// Vide si `init` n'a pas été appelé.
+pub fn regions() -> impl Iterator<Item = Region> {
+    let map: &'static [MemoryRegion] = match MEMORY_MAP.get() {
+        Some(map) => map,
+        None => &[],
+    };
+    map.iter().filter_map(to_region)
+}
+
+/// Totaux de la mémoire physique par catégorie
+pub fn summary() -> MemorySummary {
+    MemorySummary::from_regions(regions())
+}
+
+/// Traduit une adresse virtuelle de l'espace d'adressage actif en
+/// adresse physique (voir paging.rs)
+///
+/// Retourne None si l'adresse n'est pas projetée (y accéder
+/// provoquerait un page fault).
+pub fn translate(virt: u64) -> Option<u64> {
+    crate::paging::AddressSpace::current().translate(virt)
+}
+
+// ===================================================================
+// TESTS
+// ===================================================================
+
+#[test_case]
+fn test_memory_map() {
+    let summary = summary();
+    assert!(summary.usable > 0);
+    assert!(summary.kernel > 0);
+    assert_eq!(physical_memory_offset(), PHYSICAL_MEMORY_OFFSET);
+}
+
+#[test_case]
+fn test_translate_vga_buffer() {
+    // Le bootloader projette le buffer VGA à son adresse physique
+    assert_eq!(translate(0xb8000), Some(0xb8000));
+}
+
+#[test_case]
+fn test_translate_physical_memory_mapping() {
+    assert_eq!(translate(phys_to_virt(0x1234)), Some(0x1234));
+    // Adresse non projetée, puis adresse non canonique
+    assert_eq!(translate(0x0000_7fff_ffff_f000), None);
+    assert_eq!(translate(0x0000_8000_0000_0000), None);
+}
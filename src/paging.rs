@@ -0,0 +1,407 @@
+/* =========================================================
+   TABLES DE PAGES (MÉMOIRE VIRTUELLE)
+
+   Un espace d'adressage est décrit par 4 niveaux de tables
+   (PML4 → PDPT → PD → PT), la PML4 étant désignée par CR3.
+   On lit et modifie les tables via la projection de la mémoire
+   physique mise en place par le bootloader (voir memory.rs) :
+   une table à l'adresse physique P se lit à phys_to_virt(P).
+
+   `map` crée au besoin les tables intermédiaires avec des frames
+   de l'allocateur (frame.rs). Les tables intermédiaires sont
+   permissives (écriture, et utilisateur si la page l'est) : ce
+   sont les droits de la page finale qui comptent.
+
+   Après chaque modification d'une entrée de l'espace actif, il
+   faut invalider l'ancienne traduction gardée par le TLB du CPU.
+
+   Chaque processus utilisateur a sa propre PML4 (voir usermode.rs) :
+   elle reprend les entrées de celle du kernel, enregistrée au
+   démarrage par `init`, et y ajoute la zone utilisateur.
+========================================================= */
+
+use core::arch::asm;
+use core::fmt;
+use core::ops::Range;
+
+use bos_core::memory::PAGE_SIZE;
+use bos_core::paging::{is_canonical, table_index, PageFlags, ADDRESS_MASK, ENTRY_COUNT};
+
+use crate::sync::Once;
+use crate::{frame, memory};
+
+/// Erreur lors d'une modification des tables de pages
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MapError {
+    /// Adresse non alignée sur 4 Kio
+    NotAligned(u64),
+    /// Adresse virtuelle non canonique
+    NonCanonical(u64),
+    /// La page est déjà projetée (vers l'adresse physique indiquée)
+    AlreadyMapped(u64),
+    /// La page n'est pas projetée
+    NotMapped(u64),
+    /// L'adresse est couverte par une huge page (non gérée)
+    HugePage(u64),
+    /// Plus de frame libre pour créer une table
+    OutOfFrames,
+    /// Adresse hors de la zone utilisateur (voir usermode.rs)
+    NotUser(u64),
+}
+
+impl fmt::Display for MapError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            MapError::NotAligned(addr) => write!(f, "adresse {:#x} non alignee sur 4 Kio", addr),
+            MapError::NonCanonical(addr) => write!(f, "adresse {:#x} non canonique", addr),
+            MapError::AlreadyMapped(phys) => write!(f, "page deja projetee vers {:#x}", phys),
+            MapError::NotMapped(addr) => write!(f, "page {:#x} non projetee", addr),
+            MapError::HugePage(addr) => write!(f, "adresse {:#x} dans une huge page", addr),
+            MapError::OutOfFrames => f.write_str("plus de frame libre"),
+            MapError::NotUser(addr) => write!(f, "adresse {:#x} hors de la zone utilisateur", addr),
+        }
+    }
+}
+
+/// Une table de pages, vue via la projection de la mémoire physique
+fn table(phys: u64) -> *mut [u64; ENTRY_COUNT] {
+    memory::phys_to_virt(phys) as *mut [u64; ENTRY_COUNT]
+}
+
+// PML4 du démarrage, utilisée par le kernel et ses threads
+static KERNEL_LEVEL_4: Once<u64> = Once::new();
+
+/// Enregistre l'espace d'adressage actif comme celui du kernel
+///
+/// À appeler au démarrage, avant de créer des threads ou des processus.
+pub fn init() {
+    KERNEL_LEVEL_4.call_once(active_level_4_table);
+}
+
+/// Adresse physique de la PML4 du kernel
+pub fn kernel_level_4_table() -> u64 {
+    *KERNEL_LEVEL_4.get().expect("paging::init non appele")
+}
+
+/// Active un autre espace d'adressage (écriture de CR3, vide le TLB)
+///
+/// # Safety
+/// `level_4` doit être une PML4 valide qui projette le kernel (code,
+/// pile en cours, tas) aux mêmes adresses que l'espace actif.
+pub unsafe fn load_level_4_table(level_4: u64) {
+    asm!("mov cr3, {}", in(reg) level_4, options(nostack, preserves_flags));
+}
+
+/// Adresse physique de la table de niveau 4 active (registre CR3)
+pub fn active_level_4_table() -> u64 {
+    let cr3: u64;
+    unsafe {
+        asm!("mov {}, cr3", out(reg) cr3, options(nomem, nostack, preserves_flags));
+    }
+    cr3 & ADDRESS_MASK
+}
+
+/// Adresse dont l'accès a provoqué le dernier page fault (registre CR2)
+pub fn fault_address() -> u64 {
+    let cr2: u64;
+    unsafe {
+        asm!("mov {}, cr2", out(reg) cr2, options(nomem, nostack, preserves_flags));
+    }
+    cr2
+}
+
+/// Un espace d'adressage : une hiérarchie de tables de pages
+pub struct AddressSpace {
+    /// Adresse physique de la PML4
+    level_4: u64,
+}
+
+impl AddressSpace {
+    /// L'espace d'adressage actif (celui de CR3)
+    pub fn current() -> AddressSpace {
+        AddressSpace { level_4: active_level_4_table() }
+    }
+
+    /// Espace d'adressage décrit par la PML4 à l'adresse physique donnée
+    ///
+    /// # Safety
+    /// `level_4` doit être une table de pages valide, qu'aucun autre
+    /// `AddressSpace` ne modifie en même temps.
+    pub unsafe fn from_level_4(level_4: u64) -> AddressSpace {
+        AddressSpace { level_4 }
+    }
+
+    /// Adresse physique de la PML4 (valeur à charger dans CR3)
+    pub fn level_4_table(&self) -> u64 {
+        self.level_4
+    }
+
+    /// Indique si cet espace est celui du CPU (modifications visibles tout de suite)
+    pub fn is_active(&self) -> bool {
+        self.level_4 == active_level_4_table()
+    }
+
+    /// Dernière entrée du parcours de `virt` et son niveau
+    /// (1 pour une page de 4 Kio, 2 ou 3 pour une huge page)
+    fn walk(&self, virt: u64) -> Option<(u64, usize)> {
+        if !is_canonical(virt) {
+            return None;
+        }
+        let mut table_phys = self.level_4;
+        for level in (1..=4).rev() {
+            let entry = unsafe { (*table(table_phys))[table_index(virt, level)] };
+            let flags = PageFlags::from_entry(entry);
+            if !flags.contains(PageFlags::PRESENT) {
+                return None;
+            }
+            // Huge page au niveau PDPT (1 Gio) ou PD (2 Mio)
+            if level == 1 || ((level == 2 || level == 3) && flags.contains(PageFlags::HUGE)) {
+                return Some((entry, level));
+            }
+            table_phys = entry & ADDRESS_MASK;
+        }
+        None
+    }
+
+    /// Entrée de la PT décrivant la page `virt`
+    ///
+    /// Avec `create`, les tables intermédiaires manquantes sont allouées
+    /// pour une page qui aura ces droits.
+    fn leaf_entry(&mut self, virt: u64, create: Option<PageFlags>) -> Result<*mut u64, MapError> {
+        if !is_canonical(virt) {
+            return Err(MapError::NonCanonical(virt));
+        }
+        if !virt.is_multiple_of(PAGE_SIZE) {
+            return Err(MapError::NotAligned(virt));
+        }
+        let mut table_phys = self.level_4;
+        for level in (2..=4).rev() {
+            let entry = unsafe { &mut (*table(table_phys))[table_index(virt, level)] };
+            let flags = PageFlags::from_entry(*entry);
+            if !flags.contains(PageFlags::PRESENT) {
+                let Some(page_flags) = create else {
+                    return Err(MapError::NotMapped(virt));
+                };
+                let new_table = frame::allocate_frame().ok_or(MapError::OutOfFrames)?;
+                unsafe {
+                    (*table(new_table)).fill(0);
+                }
+                *entry = new_table | page_flags.for_parent_table().bits();
+            } else if flags.contains(PageFlags::HUGE) {
+                return Err(MapError::HugePage(virt));
+            } else if let Some(page_flags) = create {
+                // Une page utilisateur doit être accessible à tous les niveaux
+                *entry |= page_flags.for_parent_table().bits() & PageFlags::USER.bits();
+            }
+            table_phys = *entry & ADDRESS_MASK;
+        }
+        Ok(unsafe { &mut (*table(table_phys))[table_index(virt, 1)] as *mut u64 })
+    }
+
+    /// Traduit une adresse virtuelle en adresse physique
+    pub fn translate(&self, virt: u64) -> Option<u64> {
+        let (entry, level) = self.walk(virt)?;
+        let page_mask = (PAGE_SIZE << (9 * (level - 1))) - 1;
+        Some((entry & ADDRESS_MASK & !page_mask) + (virt & page_mask))
+    }
+
+    /// Droits de la page contenant `virt`
+    pub fn flags(&self, virt: u64) -> Option<PageFlags> {
+        self.walk(virt).map(|(entry, _)| PageFlags::from_entry(entry))
+    }
+
+    /// Projette la page virtuelle `virt` sur le frame `phys`
+    pub fn map(&mut self, virt: u64, phys: u64, flags: PageFlags) -> Result<(), MapError> {
+        if !phys.is_multiple_of(PAGE_SIZE) {
+            return Err(MapError::NotAligned(phys));
+        }
+        let flags = flags | PageFlags::PRESENT;
+        let entry = self.leaf_entry(virt, Some(flags))?;
+        unsafe {
+            if PageFlags::from_entry(*entry).contains(PageFlags::PRESENT) {
+                return Err(MapError::AlreadyMapped(*entry & ADDRESS_MASK));
+            }
+            *entry = phys | flags.bits();
+        }
+        // Une entrée absente peut quand même être dans le TLB (échec mis en cache)
+        self.invalidate(virt);
+        Ok(())
+    }
+
+    /// Projette `size` octets contigus (MMIO, framebuffer...)
+    ///
+    /// En cas d'erreur, les pages déjà projetées sont retirées.
+    pub fn map_range(&mut self, virt: u64, phys: u64, size: u64, flags: PageFlags) -> Result<(), MapError> {
+        let pages = size.div_ceil(PAGE_SIZE);
+        for page in 0..pages {
+            let offset = page * PAGE_SIZE;
+            if let Err(error) = self.map(virt + offset, phys + offset, flags) {
+                for done in 0..page {
+                    let _ = self.unmap(virt + done * PAGE_SIZE);
+                }
+                return Err(error);
+            }
+        }
+        Ok(())
+    }
+
+    /// Retire la projection de la page `virt`, retourne le frame qu'elle
+    /// désignait (à libérer par l'appelant s'il lui appartient)
+    pub fn unmap(&mut self, virt: u64) -> Result<u64, MapError> {
+        let entry = self.leaf_entry(virt, None)?;
+        let phys = unsafe {
+            if !PageFlags::from_entry(*entry).contains(PageFlags::PRESENT) {
+                return Err(MapError::NotMapped(virt));
+            }
+            let phys = *entry & ADDRESS_MASK;
+            *entry = 0;
+            phys
+        };
+        self.invalidate(virt);
+        Ok(phys)
+    }
+
+    /// Change les droits de la page `virt` (R/W/X, user, global, cache...)
+    pub fn protect(&mut self, virt: u64, flags: PageFlags) -> Result<(), MapError> {
+        let entry = self.leaf_entry(virt, None)?;
+        unsafe {
+            if !PageFlags::from_entry(*entry).contains(PageFlags::PRESENT) {
+                return Err(MapError::NotMapped(virt));
+            }
+            *entry = (*entry & ADDRESS_MASK) | (flags | PageFlags::PRESENT).bits();
+            // Le droit utilisateur doit aussi figurer dans les tables intermédiaires
+            if flags.contains(PageFlags::USER) {
+                self.leaf_entry(virt, Some(flags))?;
+            }
+        }
+        self.invalidate(virt);
+        Ok(())
+    }
+
+    /// Libère les pages et les tables sous les entrées `entries` de la
+    /// PML4, et vide ces entrées
+    ///
+    /// # Safety
+    /// L'espace ne doit pas être actif, et les frames des pages doivent
+    /// lui appartenir (aucun autre espace ne les projette).
+    pub unsafe fn free_entries(&mut self, entries: Range<usize>) {
+        let level_4 = &mut *table(self.level_4);
+        for entry in &mut level_4[entries] {
+            if PageFlags::from_entry(*entry).contains(PageFlags::PRESENT) {
+                free_table(*entry & ADDRESS_MASK, 3);
+            }
+            *entry = 0;
+        }
+    }
+
+    /// Invalide la traduction de `virt` si cet espace est actif
+    fn invalidate(&self, virt: u64) {
+        if self.is_active() {
+            shootdown(virt);
+        }
+    }
+}
+
+/// Libère une table de niveau `level`, ses sous-tables et ses pages
+unsafe fn free_table(phys: u64, level: usize) {
+    for &entry in (*table(phys)).iter() {
+        let flags = PageFlags::from_entry(entry);
+        if !flags.contains(PageFlags::PRESENT) {
+            continue;
+        }
+        // Les espaces utilisateur ne contiennent pas de huge pages
+        if level == 1 || flags.contains(PageFlags::HUGE) {
+            let _ = frame::free_frame(entry & ADDRESS_MASK);
+        } else {
+            free_table(entry & ADDRESS_MASK, level - 1);
+        }
+    }
+    let _ = frame::free_frame(phys);
+}
+
+/* =========================================================
+   TLB (TRANSLATION LOOKASIDE BUFFER)
+
+   Le CPU garde en cache les traductions récentes. Après une
+   modification, INVLPG retire une page du cache ; recharger
+   CR3 vide tout le cache sauf les pages globales.
+========================================================= */
+
+/// Retire la traduction d'une page du TLB du CPU courant
+pub fn flush(virt: u64) {
+    unsafe {
+        asm!("invlpg [{}]", in(reg) virt, options(nostack, preserves_flags));
+    }
+}
+
+/// Vide le TLB du CPU courant (sauf les pages globales)
+pub fn flush_all() {
+    unsafe {
+        asm!("mov {tmp}, cr3", "mov cr3, {tmp}", tmp = out(reg) _, options(nostack, preserves_flags));
+    }
+}
+
+/// Invalide une page dans le TLB de tous les CPU
+///
+/// BOS n'utilise qu'un seul CPU : l'invalidation locale suffit. Avec
+/// plusieurs CPU, il faudra ici envoyer une IPI aux autres et attendre
+/// qu'ils aient exécuté INVLPG à leur tour.
+pub fn shootdown(virt: u64) {
+    flush(virt);
+}
+
+// ===================================================================
+// TESTS
+// ===================================================================
+
+/// Adresse virtuelle libre pour les tests (loin du kernel et de la
+/// projection de la mémoire physique)
+#[cfg(test)]
+const TEST_PAGE: u64 = 0x0000_5555_0000_0000;
+
+#[test_case]
+fn test_map_translate_unmap() {
+    let mut space = AddressSpace::current();
+    let frame = frame::allocate_frame().unwrap();
+    let flags = PageFlags::WRITABLE | PageFlags::NO_EXECUTE;
+
+    assert_eq!(space.translate(TEST_PAGE), None);
+    space.map(TEST_PAGE, frame, flags).unwrap();
+    assert_eq!(space.translate(TEST_PAGE + 0x10), Some(frame + 0x10));
+    assert_eq!(space.map(TEST_PAGE, frame, flags), Err(MapError::AlreadyMapped(frame)));
+
+    // La page virtuelle et la projection physique désignent la même mémoire
+    unsafe {
+        (TEST_PAGE as *mut u64).write_volatile(0xB05);
+        assert_eq!((memory::phys_to_virt(frame) as *const u64).read_volatile(), 0xB05);
+    }
+
+    assert_eq!(space.unmap(TEST_PAGE), Ok(frame));
+    assert_eq!(space.translate(TEST_PAGE), None);
+    assert_eq!(space.unmap(TEST_PAGE), Err(MapError::NotMapped(TEST_PAGE)));
+    frame::free_frame(frame).unwrap();
+}
+
+#[test_case]
+fn test_protect_changes_flags() {
+    let mut space = AddressSpace::current();
+    let frame = frame::allocate_frame().unwrap();
+    space.map(TEST_PAGE, frame, PageFlags::WRITABLE).unwrap();
+    assert!(space.flags(TEST_PAGE).unwrap().contains(PageFlags::WRITABLE));
+
+    space.protect(TEST_PAGE, PageFlags::NO_EXECUTE | PageFlags::NO_CACHE).unwrap();
+    let flags = space.flags(TEST_PAGE).unwrap();
+    assert!(!flags.contains(PageFlags::WRITABLE));
+    assert!(flags.contains(PageFlags::NO_EXECUTE | PageFlags::NO_CACHE));
+
+    space.unmap(TEST_PAGE).unwrap();
+    frame::free_frame(frame).unwrap();
+}
+
+#[test_case]
+fn test_map_errors() {
+    let mut space = AddressSpace::current();
+    assert_eq!(space.map(TEST_PAGE + 1, 0, PageFlags::empty()), Err(MapError::NotAligned(TEST_PAGE + 1)));
+    assert_eq!(space.map(0x0000_8000_0000_0000, 0, PageFlags::empty()), Err(MapError::NonCanonical(0x0000_8000_0000_0000)));
+    assert_eq!(space.protect(TEST_PAGE, PageFlags::empty()), Err(MapError::NotMapped(TEST_PAGE)));
+}
@@ -0,0 +1,256 @@
+// ===================================================================
+// PANIC DU KERNEL
+// ===================================================================
+//
+// Quand le kernel panique, on veut savoir pourquoi et où, pas seulement
+// constater que tout est figé. Ce module :
+// 1. Désactive les interruptions (plus rien ne doit modifier l'état)
+// 2. Capture les registres généraux et de contrôle
+// 3. Affiche un bandeau rouge "KERNEL PANIC" avec le message, le
+//    fichier:ligne, le contenu des registres et la pile d'appels
+//    (backtrace.rs), sur VGA et sur COM1
+// 4. Arrête le CPU avec HLT (au lieu de boucler à 100% du CPU), ou
+//    redémarre après un délai si BOS a été compilé avec la variable
+//    d'environnement BOS_PANIC_REBOOT=<secondes>
+
+use core::arch::asm;
+use core::fmt::{self, Write};
+use core::panic::PanicInfo;
+use core::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+
+use crate::vga::{vga_print_color, COLOR_BANNER, COLOR_ERROR};
+use crate::{backtrace, disable_interrupts, hlt_loop, pit, reboot, serial};
+
+/// Délai avant redémarrage automatique, fixé à la compilation
+/// (ex : `BOS_PANIC_REBOOT=10 cargo bootimage`). Absent = pas de redémarrage.
+const PANIC_REBOOT_SECS: Option<&str> = option_env!("BOS_PANIC_REBOOT");
+
+// Passe à true au premier panic : un panic pendant l'affichage d'un
+// panic ne doit pas boucler indéfiniment
+static PANICKING: AtomicBool = AtomicBool::new(false);
+
+// RIP et RBP du code interrompu, quand le panic vient d'une exception
+// (0 = panic ordinaire : on remonte la pile depuis le panic lui-même)
+static EXCEPTION_RIP: AtomicU64 = AtomicU64::new(0);
+static EXCEPTION_RBP: AtomicU64 = AtomicU64::new(0);
+
+/// Mémorise le contexte d'une exception avant d'appeler `panic!`
+///
+/// La pile d'appels affichée sera alors celle du code fautif : depuis
+/// le handler, la chaîne des frames s'arrêterait à la trame d'interruption.
+pub fn record_exception(rip: u64, rbp: u64) {
+    EXCEPTION_RBP.store(rbp, Ordering::SeqCst);
+    EXCEPTION_RIP.store(rip, Ordering::SeqCst);
+}
+
+/// Instantané des registres du CPU
+#[repr(C)]
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Registers {
+    pub rax: u64,
+    pub rbx: u64,
+    pub rcx: u64,
+    pub rdx: u64,
+    pub rsi: u64,
+    pub rdi: u64,
+    pub rbp: u64,
+    pub rsp: u64,
+    pub r8: u64,
+    pub r9: u64,
+    pub r10: u64,
+    pub r11: u64,
+    pub r12: u64,
+    pub r13: u64,
+    pub r14: u64,
+    pub r15: u64,
+    pub rip: u64,
+    pub rflags: u64,
+    pub cr0: u64,
+    pub cr2: u64,
+    pub cr3: u64,
+    pub cr4: u64,
+}
+
+impl Registers {
+    /// Capture les registres au point d'appel
+    ///
+    /// Le registre qui contient l'adresse de la structure de destination
+    /// est lui-même écrasé : sa valeur affichée est donc cette adresse.
+    #[inline(always)]
+    pub fn capture() -> Registers {
+        let mut regs = Registers::default();
+        unsafe {
+            asm!(
+                // Registres généraux (offsets dans l'ordre de la structure)
+                "mov [{r} + 0x00], rax",
+                "mov [{r} + 0x08], rbx",
+                "mov [{r} + 0x10], rcx",
+                "mov [{r} + 0x18], rdx",
+                "mov [{r} + 0x20], rsi",
+                "mov [{r} + 0x28], rdi",
+                "mov [{r} + 0x30], rbp",
+                "mov [{r} + 0x38], rsp",
+                "mov [{r} + 0x40], r8",
+                "mov [{r} + 0x48], r9",
+                "mov [{r} + 0x50], r10",
+                "mov [{r} + 0x58], r11",
+                "mov [{r} + 0x60], r12",
+                "mov [{r} + 0x68], r13",
+                "mov [{r} + 0x70], r14",
+                "mov [{r} + 0x78], r15",
+                // RIP : adresse de l'instruction suivante
+                "lea {tmp}, [rip]",
+                "mov [{r} + 0x80], {tmp}",
+                // RFLAGS : seulement accessible via la pile
+                "pushfq",
+                "pop qword ptr [{r} + 0x88]",
+                // Registres de contrôle
+                "mov {tmp}, cr0",
+                "mov [{r} + 0x90], {tmp}",
+                "mov {tmp}, cr2",
+                "mov [{r} + 0x98], {tmp}",
+                "mov {tmp}, cr3",
+                "mov [{r} + 0xa0], {tmp}",
+                "mov {tmp}, cr4",
+                "mov [{r} + 0xa8], {tmp}",
+                r = in(reg) &mut regs,
+                tmp = out(reg) _,
+            );
+        }
+        regs
+    }
+}
+
+impl fmt::Display for Registers {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // 3 registres par ligne pour tenir dans les 80 colonnes de l'écran
+        let general = [
+            ("RAX", self.rax), ("RBX", self.rbx), ("RCX", self.rcx),
+            ("RDX", self.rdx), ("RSI", self.rsi), ("RDI", self.rdi),
+            ("RBP", self.rbp), ("RSP", self.rsp), ("R8 ", self.r8),
+            ("R9 ", self.r9), ("R10", self.r10), ("R11", self.r11),
+            ("R12", self.r12), ("R13", self.r13), ("R14", self.r14),
+            ("R15", self.r15), ("RIP", self.rip), ("RFL", self.rflags),
+            ("CR0", self.cr0), ("CR2", self.cr2), ("CR3", self.cr3),
+            ("CR4", self.cr4),
+        ];
+        for (i, (name, value)) in general.iter().enumerate() {
+            write!(f, "{}={:016x}", name, value)?;
+            if i % 3 == 2 || i == general.len() - 1 {
+                writeln!(f)?;
+            } else {
+                write!(f, "  ")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Sortie du panic : écrit à la fois à l'écran (en couleur) et sur COM1
+struct PanicWriter {
+    color: u8,
+}
+
+impl Write for PanicWriter {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        vga_print_color(s, self.color);
+        serial::serial_print(s);
+        Ok(())
+    }
+}
+
+/// Panic handler du kernel : affiche le diagnostic puis arrête le CPU
+pub fn kernel_panic(info: &PanicInfo) -> ! {
+    disable_interrupts();
+    let regs = Registers::capture();
+
+    if PANICKING.swap(true, Ordering::SeqCst) {
+        // Panic pendant le panic : on n'essaie même plus d'afficher
+        hlt_loop();
+    }
+    // Le code interrompu ne reprendra pas : l'écran peut être verrouillé
+    unsafe { crate::vga::force_unlock() };
+
+    let mut out = PanicWriter { color: COLOR_BANNER };
+    let _ = writeln!(out);
+    let _ = writeln!(out, "{:^79}", "*** KERNEL PANIC ***");
+
+    out.color = COLOR_ERROR;
+    let _ = writeln!(out, "{}", info.message());
+    match info.location() {
+        Some(location) => {
+            let _ = writeln!(out, "  a {}:{}:{}", location.file(), location.line(), location.column());
+        }
+        None => {
+            let _ = writeln!(out, "  (emplacement inconnu)");
+        }
+    }
+    let _ = writeln!(out);
+    let _ = write!(out, "{}", regs);
+    let _ = writeln!(out, "Pile d'appels :");
+    match EXCEPTION_RIP.load(Ordering::SeqCst) {
+        0 => {
+            let _ = backtrace::write_backtrace(&mut out, regs.rbp);
+        }
+        rip => {
+            let rbp = EXCEPTION_RBP.load(Ordering::SeqCst);
+            let _ = backtrace::write_exception_backtrace(&mut out, rip, rbp);
+        }
+    }
+
+    match PANIC_REBOOT_SECS.and_then(|secs| secs.parse::<u32>().ok()) {
+        Some(secs) => {
+            let _ = writeln!(out, "Redemarrage dans {} secondes...", secs);
+            pit::busy_wait_ms(secs.saturating_mul(1000));
+            reboot();
+        }
+        None => {
+            let _ = writeln!(out, "Systeme arrete.");
+            hlt_loop();
+        }
+    }
+}
+
+// ===================================================================
+// TESTS
+// ===================================================================
+
+#[test_case]
+fn test_capture_control_registers() {
+    let regs = Registers::capture();
+    // Mode protégé (PE, bit 0) et pagination (PG, bit 31) actifs
+    assert!(regs.cr0 & 1 != 0);
+    assert!(regs.cr0 & (1 << 31) != 0);
+    // PAE (bit 5 de CR4) obligatoire en mode long
+    assert!(regs.cr4 & (1 << 5) != 0);
+    assert_ne!(regs.cr3, 0);
+    assert_ne!(regs.rsp, 0);
+}
+
+#[test_case]
+fn test_registers_display_fits_screen() {
+    use core::fmt::Write;
+
+    // Compte la longueur de la plus longue ligne produite
+    struct LineWidth {
+        current: usize,
+        max: usize,
+    }
+    impl Write for LineWidth {
+        fn write_str(&mut self, s: &str) -> fmt::Result {
+            for c in s.chars() {
+                if c == '\n' {
+                    self.current = 0;
+                } else {
+                    self.current += 1;
+                    self.max = self.max.max(self.current);
+                }
+            }
+            Ok(())
+        }
+    }
+
+    let mut width = LineWidth { current: 0, max: 0 };
+    write!(width, "{}", Registers::default()).unwrap();
+    assert!(width.max < 80);
+}
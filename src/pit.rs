@@ -0,0 +1,133 @@
+/* =========================================================
+   PIT - PROGRAMMABLE INTERVAL TIMER (8253/8254)
+
+   Le PIT est un compteur cadencé à 1 193 182 Hz. Il possède
+   3 canaux :
+   - Canal 0 : relié à l'IRQ 0 (timer système)
+   - Canal 1 : obsolète (rafraîchissement mémoire)
+   - Canal 2 : relié au haut-parleur, mais sa sortie peut être
+               lue sur le port 0x61 (bit 5)
+
+   Le canal 0 génère l'IRQ 0 TIMER_FREQUENCY fois par seconde :
+   chaque tick incrémente un compteur, qui donne le temps écoulé
+   depuis le démarrage (horodatage du journal).
+
+   Le canal 2 permet d'attendre un délai précis SANS
+   interruptions, par scrutation : indispensable dans le panic
+   handler, qui s'exécute avec les interruptions désactivées.
+========================================================= */
+
+use core::sync::atomic::{AtomicU64, Ordering};
+
+use crate::{inb, outb};
+
+/// Fréquence de l'horloge d'entrée du PIT (Hz)
+pub const PIT_FREQUENCY: u32 = 1_193_182;
+
+/// Fréquence des ticks du timer système (Hz) : un tick par milliseconde
+pub const TIMER_FREQUENCY: u32 = 1000;
+
+// Ports I/O du PIT
+const PIT_CHANNEL0_DATA: u16 = 0x40; // Compteur du canal 0
+const PIT_CHANNEL2_DATA: u16 = 0x42; // Compteur du canal 2
+const PIT_COMMAND: u16 = 0x43;       // Registre de commande
+const PORT_B: u16 = 0x61;            // Contrôle du haut-parleur / porte du canal 2
+
+// Nombre de ticks depuis `init_timer`
+static TICKS: AtomicU64 = AtomicU64::new(0);
+
+/// Programme le canal 0 pour générer l'IRQ 0 à TIMER_FREQUENCY Hz
+pub fn init_timer() {
+    let divisor = (PIT_FREQUENCY / TIMER_FREQUENCY) as u16;
+    unsafe {
+        // Canal 0, accès octet bas puis octet haut, mode 3 (onde carrée), binaire
+        outb(PIT_COMMAND, 0b0011_0110);
+        outb(PIT_CHANNEL0_DATA, (divisor & 0xFF) as u8);
+        outb(PIT_CHANNEL0_DATA, (divisor >> 8) as u8);
+    }
+}
+
+/// Appelé par le handler de l'IRQ 0 à chaque tick
+pub fn tick() {
+    TICKS.fetch_add(1, Ordering::Relaxed);
+}
+
+/// Nombre de ticks depuis le démarrage du timer
+pub fn ticks() -> u64 {
+    TICKS.load(Ordering::Relaxed)
+}
+
+/// Temps écoulé depuis le démarrage du timer, en millisecondes
+pub fn uptime_ms() -> u64 {
+    ticks() * 1000 / TIMER_FREQUENCY as u64
+}
+
+/// Nombre de ticks couvrant au moins `ms` millisecondes
+///
+/// `ms` vient parfois d'un programme : un délai trop grand sature au
+/// lieu de déborder.
+pub fn ms_to_ticks(ms: u64) -> u64 {
+    ms.saturating_mul(TIMER_FREQUENCY as u64).div_ceil(1000)
+}
+
+/// Attend `ms` millisecondes en scrutant le canal 2 du PIT
+///
+/// Fonctionne avec les interruptions désactivées. Le compteur est sur
+/// 16 bits (≈ 54 ms maximum), on attend donc par tranches de 10 ms.
+pub fn busy_wait_ms(ms: u32) {
+    for _ in 0..(ms / 10) {
+        wait_ticks(PIT_FREQUENCY / 100);
+    }
+    let rest = ms % 10;
+    if rest > 0 {
+        wait_ticks(PIT_FREQUENCY / 1000 * rest);
+    }
+}
+
+/// Programme le canal 2 en mode 0 (one-shot) et attend que sa sortie passe à 1
+fn wait_ticks(ticks: u32) {
+    let ticks = ticks.min(0xFFFF) as u16;
+    unsafe {
+        // Activer la porte du canal 2 (bit 0) et couper le haut-parleur (bit 1)
+        let port_b = inb(PORT_B);
+        outb(PORT_B, (port_b & !0x02) | 0x01);
+
+        // Canal 2, accès octet bas puis octet haut, mode 0, binaire
+        outb(PIT_COMMAND, 0b1011_0000);
+        outb(PIT_CHANNEL2_DATA, (ticks & 0xFF) as u8);
+        outb(PIT_CHANNEL2_DATA, (ticks >> 8) as u8);
+
+        // Redémarrer le comptage en rabaissant puis relevant la porte
+        let port_b = inb(PORT_B);
+        outb(PORT_B, port_b & !0x01);
+        outb(PORT_B, port_b | 0x01);
+
+        // Le bit 5 du port 0x61 reflète la sortie du canal 2 :
+        // elle passe à 1 quand le compteur atteint 0
+        while inb(PORT_B) & 0x20 == 0 {
+            core::hint::spin_loop();
+        }
+    }
+}
+
+// ===================================================================
+// TESTS
+// ===================================================================
+
+#[test_case]
+fn test_busy_wait_returns() {
+    busy_wait_ms(25);
+}
+
+#[test_case]
+fn test_ms_to_ticks_saturates() {
+    assert_eq!(ms_to_ticks(1), 1);
+    assert_eq!(ms_to_ticks(u64::MAX), u64::MAX.div_ceil(1000));
+}
+
+#[test_case]
+fn test_timer_ticks() {
+    let start = ticks();
+    busy_wait_ms(20);
+    assert!(ticks() > start);
+}
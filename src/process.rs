@@ -0,0 +1,647 @@
+/* =========================================================
+   PROCESSUS UTILISATEUR
+
+   Un processus est un programme en ring 3 : son espace
+   d'adressage (`UserSpace`), ses fichiers ouverts (`FdTable`)
+   et le thread qui l'exécute. Le
+   thread démarre en ring 0 dans l'espace du processus, puis
+   passe en ring 3 avec `usermode::enter` ; il ne revient dans
+   le kernel que pour un appel système ou une interruption.
+
+   Chaque processus a un parent : le processus qui l'a lancé
+   (appel système spawn), ou le kernel (le shell). Il reçoit des
+   signaux (voir bos_core::signal) : kill, Ctrl+C, Ctrl+Z, ses
+   fautes (page fault, #GP, instruction invalide, division par
+   zéro) et ses alarmes. Ils sont notés ici (`kill`, `fault`) et
+   remis quand il va revenir en ring 3 (voir signal.rs).
+
+   Il se termine par l'appel système exit, ou tué par un signal.
+   Il passe par ces états :
+   - En cours : son thread s'exécute
+   - Arrêté : son thread attend SIGCONT (Ctrl+Z, SIGSTOP)
+   - En sortie : son thread est terminé mais peut encore être
+     actif sur le CPU (son espace d'adressage est en place)
+   - Zombie : son thread est retiré de la table des threads
+     (`thread_reaped`), ses ressources sont libérées ; il ne reste
+     que son statut, que le parent récupère avec `wait`
+
+   Un processus dont le parent n'est plus en vie (orphelin)
+   ne devient pas zombie : il est retiré dès sa fin.
+========================================================= */
+
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::sync::atomic::{AtomicU64, Ordering};
+
+use bos_core::process::ExitStatus;
+use bos_core::signal::{signal_name, Delivery, Registers, Signals, SIGALRM, SIGCHLD, SIGCONT, SIGKILL};
+
+use crate::sync::{Spinlock, WaitQueue};
+use crate::thread::{self, SpawnError};
+use crate::usermode::{self, UserSpace};
+use crate::vfs::FdTable;
+use crate::{pit, without_interrupts};
+
+/// Où en est un processus
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum State {
+    /// Son thread s'exécute
+    Running,
+    /// Arrêté par un signal, jusqu'à SIGCONT
+    Stopped(u8),
+    /// Son thread est terminé, mais pas encore retiré
+    Exiting(ExitStatus),
+    /// Terminé, en attente de son parent
+    Zombie(ExitStatus),
+}
+
+impl State {
+    /// Nom affiché par `ps`
+    pub fn name(self) -> &'static str {
+        match self {
+            State::Running => "en cours",
+            State::Stopped(_) => "arrete",
+            State::Exiting(_) => "sortie",
+            State::Zombie(_) => "zombie",
+        }
+    }
+
+    /// Indique si le thread du processus existe encore (en cours ou
+    /// arrêté)
+    pub fn is_alive(self) -> bool {
+        matches!(self, State::Running | State::Stopped(_))
+    }
+}
+
+/// Un processus et ses ressources
+struct Process {
+    name: String,
+    /// Processus qui l'a lancé (None : le kernel)
+    parent: Option<u64>,
+    state: State,
+    /// Thread qui exécute le programme
+    thread: u64,
+    /// Signaux en attente, bloqués, et leurs actions
+    signals: Signals,
+    /// Date (en ms depuis le démarrage) de son alarme (SIGALRM)
+    alarm: Option<u64>,
+    /// Dernière faute du programme : description et adresse
+    fault: Option<(&'static str, u64)>,
+    /// Libéré quand son thread est retiré (None ensuite)
+    space: Option<UserSpace>,
+    /// Descripteurs de fichiers, fermés avec l'espace d'adressage
+    files: Option<FdTable>,
+}
+
+impl Process {
+    /// Met `signal` en attente, retourne true s'il faut réveiller son
+    /// thread (il attend peut-être dans un appel système)
+    ///
+    /// SIGCONT et SIGKILL reprennent un processus arrêté.
+    fn send(&mut self, signal: u8) -> bool {
+        let raised = self.signals.raise(signal);
+        if let State::Stopped(_) = self.state {
+            if signal == SIGCONT || signal == SIGKILL {
+                self.state = State::Running;
+                return true;
+            }
+            return false;
+        }
+        raised && self.signals.has_deliverable()
+    }
+}
+
+/// Description d'un processus (voir `for_each`)
+#[derive(Debug, Clone, Copy)]
+pub struct ProcessInfo<'a> {
+    pub pid: u64,
+    /// 0 : lancé par le kernel
+    pub parent: u64,
+    pub name: &'a str,
+    pub state: State,
+    pub thread: u64,
+}
+
+/// Erreur de `wait`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WaitError {
+    /// Pas d'enfant (ou pas cet enfant) à attendre
+    NoChild,
+    /// Le processus qui attend a reçu un signal
+    Interrupted,
+}
+
+// Processus par pid
+static PROCESSES: Spinlock<BTreeMap<u64, Process>> = Spinlock::new(BTreeMap::new());
+
+// Prochain pid (jamais réutilisé)
+static NEXT_PID: AtomicU64 = AtomicU64::new(1);
+
+// Réveillée quand un processus devient zombie ou s'arrête (voir `wait`)
+static CHILD_EXITED: WaitQueue = WaitQueue::new();
+
+// Réveillée quand un processus arrêté reprend (voir `stop`)
+static CONTINUED: WaitQueue = WaitQueue::new();
+
+// Nom des threads des processus (le nom du programme, choisi par
+// l'utilisateur, est gardé par le processus)
+const THREAD_NAME: &str = "user";
+
+/// Démarre un processus : son thread exécute `entry` en ring 3 avec
+/// la pile `stack`, dans l'espace `space`
+///
+/// Son parent est le processus en cours (le kernel pour un thread du
+/// kernel). Retourne le pid du processus.
+pub fn spawn(name: &str, space: UserSpace, entry: u64, stack: u64) -> Result<u64, SpawnError> {
+    let pid = NEXT_PID.fetch_add(1, Ordering::Relaxed);
+    let parent = current_pid();
+    let level_4 = space.level_4_table();
+    // Interruptions désactivées : le processus est enregistré avant
+    // que son thread ne puisse s'exécuter
+    without_interrupts(|| {
+        let thread = thread::spawn_in(THREAD_NAME, level_4, move || unsafe { usermode::enter(entry, stack) })?;
+        let process = Process {
+            name: String::from(name),
+            parent,
+            state: State::Running,
+            thread,
+            signals: Signals::new(),
+            alarm: None,
+            fault: None,
+            space: Some(space),
+            files: Some(FdTable::new()),
+        };
+        PROCESSES.lock().insert(pid, process);
+        debug!("Processus {} ({}) demarre", pid, name);
+        Ok(pid)
+    })
+}
+
+/// Exécute `f` sur le processus en vie du thread en cours
+fn with_current<R>(f: impl FnOnce(u64, &mut Process) -> R) -> Option<R> {
+    let thread = thread::current_id();
+    let mut processes = PROCESSES.lock();
+    processes
+        .iter_mut()
+        .find(|(_, process)| process.thread == thread && process.state.is_alive())
+        .map(|(&pid, process)| f(pid, process))
+}
+
+/// Pid du processus en cours (None pour un thread du kernel)
+pub fn current_pid() -> Option<u64> {
+    with_current(|pid, _| pid)
+}
+
+/// Pid du parent du processus en cours (None : le kernel)
+pub fn parent_pid() -> Option<u64> {
+    with_current(|_, process| process.parent).flatten()
+}
+
+/// Exécute `f` sur l'espace d'adressage du processus en cours
+///
+/// Retourne None pour un thread du kernel.
+pub fn with_current_space<R>(f: impl FnOnce(&mut UserSpace) -> R) -> Option<R> {
+    let thread = thread::current_id();
+    let mut processes = PROCESSES.lock();
+    let process = processes.values_mut().find(|process| process.thread == thread)?;
+    process.space.as_mut().map(f)
+}
+
+/// Exécute `f` sur les signaux du processus en cours (sigaction,
+/// sigprocmask, sigreturn)
+///
+/// Retourne None pour un thread du kernel.
+pub fn with_current_signals<R>(f: impl FnOnce(&mut Signals) -> R) -> Option<R> {
+    with_current(|_, process| f(&mut process.signals))
+}
+
+/// Exécute `f` sur la table des descripteurs du processus en cours
+///
+/// Retourne None pour un thread du kernel.
+pub fn with_current_files<R>(f: impl FnOnce(&mut FdTable) -> R) -> Option<R> {
+    with_current(|_, process| process.files.as_mut().map(f)).flatten()
+}
+
+/// Indique si le processus `pid` existe encore (zombie compris)
+pub fn exists(pid: u64) -> bool {
+    PROCESSES.lock().contains_key(&pid)
+}
+
+/// État du processus `pid`
+pub fn state(pid: u64) -> Option<State> {
+    PROCESSES.lock().get(&pid).map(|process| process.state)
+}
+
+/// Nombre de processus
+pub fn count() -> usize {
+    PROCESSES.lock().len()
+}
+
+/// Appelle `f` pour chaque processus
+pub fn for_each(mut f: impl FnMut(&ProcessInfo)) {
+    for (&pid, process) in PROCESSES.lock().iter() {
+        f(&ProcessInfo {
+            pid,
+            parent: process.parent.unwrap_or(0),
+            name: &process.name,
+            state: process.state,
+            thread: process.thread,
+        });
+    }
+}
+
+/// Termine le processus en cours avec le code `code`
+pub fn exit(code: i32) -> ! {
+    terminate(ExitStatus::Exited(code))
+}
+
+/// Termine le processus en cours avec le statut `status`
+pub fn terminate(status: ExitStatus) -> ! {
+    let thread = thread::current_id();
+    {
+        let mut processes = PROCESSES.lock();
+        let current = processes.iter_mut().find(|(_, process)| process.thread == thread);
+        if let Some((pid, process)) = current {
+            process.state = State::Exiting(status);
+            process.alarm = None;
+            debug!("Processus {} ({}) termine : {}", pid, process.name, status);
+        }
+    }
+    thread::exit();
+}
+
+/// Termine le processus en cours, tué par le signal de faute `signal`,
+/// en affichant un rapport : sa dernière faute et ses registres
+pub fn terminate_with_report(signal: u8, registers: &Registers) -> ! {
+    let (pid, name, fault) = with_current(|pid, process| (pid, process.name.clone(), process.fault.take()))
+        .unwrap_or((0, String::from("?"), None));
+    let signal_name = signal_name(signal).unwrap_or("?");
+    match fault {
+        Some((description, address)) => {
+            warn!("Processus {} ({}) arrete : {} ({} a {:#x})", pid, name, signal_name, description, address);
+        }
+        None => {
+            warn!("Processus {} ({}) arrete : {}", pid, name, signal_name);
+        }
+    }
+    let r = registers;
+    println!("  rip={:#018x} rsp={:#018x} rflags={:#x}", r.rip, r.rsp, r.rflags);
+    println!("  rax={:#018x} rbx={:#018x} rcx={:#018x}", r.rax, r.rbx, r.rcx);
+    println!("  rdx={:#018x} rsi={:#018x} rdi={:#018x}", r.rdx, r.rsi, r.rdi);
+    println!("  rbp={:#018x} r8={:#018x}  r9={:#018x}", r.rbp, r.r8, r.r9);
+    terminate(ExitStatus::Killed(signal));
+}
+
+/// Note une faute du processus en cours en ring 3 : `description` à
+/// l'adresse `address`, qui lui envoie le signal `signal` (SIGSEGV,
+/// SIGILL, SIGFPE)
+///
+/// Appelée par les handlers d'exceptions, sur la pile du kernel du
+/// thread du processus. Le signal est remis au retour de l'exception :
+/// s'il est bloqué ou ignoré, il reprend son action par défaut.
+pub fn fault(signal: u8, description: &'static str, address: u64) {
+    with_current(|pid, process| {
+        debug!("Processus {} ({}) : {} a {:#x}", pid, process.name, description, address);
+        process.fault = Some((description, address));
+        process.signals.force(signal);
+    });
+}
+
+/// Envoie le signal `signal` au processus `pid`
+///
+/// Il est remis quand le processus va revenir en ring 3 ; s'il attend
+/// dans un appel système (sleep, read, waitpid), il est réveillé.
+/// Sans effet sur un processus déjà terminé. Retourne false si `pid`
+/// n'existe pas.
+pub fn kill(pid: u64, signal: u8) -> bool {
+    let (thread, continued) = {
+        let mut processes = PROCESSES.lock();
+        let Some(process) = processes.get_mut(&pid) else {
+            return false;
+        };
+        if !process.state.is_alive() {
+            return true;
+        }
+        let stopped = matches!(process.state, State::Stopped(_));
+        let wake = process.send(signal);
+        (wake.then_some(process.thread), stopped && process.state == State::Running)
+    };
+    if continued {
+        debug!("Processus {} reprend", pid);
+        CONTINUED.wake_all();
+    } else if let Some(thread) = thread {
+        thread::unblock(thread);
+    }
+    true
+}
+
+/// Indique si le processus en cours a un signal à recevoir : un appel
+/// système qui attend doit alors s'interrompre
+pub fn signal_pending() -> bool {
+    with_current(|_, process| process.signals.has_deliverable()).unwrap_or(false)
+}
+
+/// Retire le prochain signal à remettre au processus en cours (voir
+/// `Signals::take_next`)
+pub fn next_signal() -> Option<Delivery> {
+    with_current(|_, process| process.signals.take_next()).flatten()
+}
+
+/// Arrête le processus en cours par le signal `signal` jusqu'à ce qu'il
+/// reçoive SIGCONT (ou SIGKILL)
+pub fn stop(signal: u8) {
+    let Some((pid, parent)) = with_current(|pid, process| {
+        process.state = State::Stopped(signal);
+        (pid, process.parent)
+    }) else {
+        return;
+    };
+    debug!("Processus {} arrete par {}", pid, signal_name(signal).unwrap_or("?"));
+    if let Some(parent) = parent {
+        kill(parent, SIGCHLD);
+    }
+    CHILD_EXITED.wake_all();
+    CONTINUED.wait_until(|| state(pid) != Some(State::Stopped(signal)));
+}
+
+/// Programme l'envoi de SIGALRM au processus en cours dans `ms`
+/// millisecondes (0 : annule), retourne le temps restant de l'alarme
+/// précédente (0 s'il n'y en avait pas)
+pub fn set_alarm(ms: u64) -> u64 {
+    let now = pit::uptime_ms();
+    let deadline = (ms > 0).then(|| now.saturating_add(ms));
+    with_current(|_, process| core::mem::replace(&mut process.alarm, deadline))
+        .flatten()
+        .map_or(0, |old| old.saturating_sub(now).max(1))
+}
+
+/// Envoie SIGALRM aux processus dont l'alarme est échue
+///
+/// Appelée à chaque tick du timer.
+pub fn fire_alarms() {
+    let now = pit::uptime_ms();
+    let expired: Vec<u64> = {
+        let mut processes = PROCESSES.lock();
+        processes
+            .iter_mut()
+            .filter(|(_, process)| process.alarm.is_some_and(|deadline| deadline <= now))
+            .map(|(&pid, process)| {
+                process.alarm = None;
+                pid
+            })
+            .collect()
+    };
+    for pid in expired {
+        kill(pid, SIGALRM);
+    }
+}
+
+/// Récupère le statut d'un enfant terminé du processus en cours :
+/// l'enfant `pid`, ou n'importe lequel si `pid` vaut None
+///
+/// Sans `nohang`, attend qu'un enfant se termine ; avec, retourne
+/// `Ok(None)` si aucun n'est terminé. L'enfant zombie est retiré.
+pub fn wait(pid: Option<u64>, nohang: bool) -> Result<Option<(u64, ExitStatus)>, WaitError> {
+    let parent = current_pid();
+    if nohang {
+        return try_wait(parent, pid);
+    }
+    let mut result = Ok(None);
+    CHILD_EXITED.wait_until(|| {
+        result = try_wait(parent, pid);
+        if matches!(result, Ok(None)) && parent.is_some() && signal_pending() {
+            result = Err(WaitError::Interrupted);
+        }
+        !matches!(result, Ok(None))
+    });
+    result
+}
+
+/// Retire un enfant zombie de `parent` (voir `wait`)
+fn try_wait(parent: Option<u64>, pid: Option<u64>) -> Result<Option<(u64, ExitStatus)>, WaitError> {
+    let mut processes = PROCESSES.lock();
+    let mut children = processes
+        .iter()
+        .filter(|(&child, process)| process.parent == parent && pid.is_none_or(|pid| pid == child))
+        .peekable();
+    if children.peek().is_none() {
+        return Err(WaitError::NoChild);
+    }
+    let zombie = children.find_map(|(&child, process)| match process.state {
+        State::Zombie(status) => Some((child, status)),
+        _ => None,
+    });
+    if let Some((child, _)) = zombie {
+        processes.remove(&child);
+    }
+    Ok(zombie)
+}
+
+/// Appelée quand le thread `thread` est retiré de la table des threads :
+/// libère les ressources du processus qu'il exécutait, qui devient
+/// zombie (ou disparaît s'il est orphelin)
+pub(crate) fn thread_reaped(thread: u64) {
+    // L'espace d'adressage et les fichiers sont libérés hors du verrou
+    let (space, files, parent) = {
+        let mut processes = PROCESSES.lock();
+        let Some((&pid, process)) = processes.iter_mut().find(|(_, process)| process.thread == thread) else {
+            return;
+        };
+        let status = match process.state {
+            State::Exiting(status) => status,
+            // Thread terminé sans passer par `terminate`
+            _ => ExitStatus::Exited(0),
+        };
+        process.state = State::Zombie(status);
+        let space = process.space.take();
+        let files = process.files.take();
+        let parent = process.parent;
+        // Ses enfants zombies n'ont plus personne pour les attendre
+        processes.retain(|_, child| child.parent != Some(pid) || !matches!(child.state, State::Zombie(_)));
+        let orphan = parent.is_some_and(|parent| {
+            processes.get(&parent).is_none_or(|parent| !parent.state.is_alive())
+        });
+        if orphan {
+            processes.remove(&pid);
+        }
+        (space, files, parent.filter(|_| !orphan))
+    };
+    drop(space);
+    drop(files);
+    if let Some(parent) = parent {
+        kill(parent, SIGCHLD);
+    }
+    CHILD_EXITED.wake_all();
+}
+
+// ===================================================================
+// TESTS
+// ===================================================================
+
+/// Crée un processus qui exécute `code`
+#[cfg(test)]
+pub(crate) fn start_code(code: &[u8]) -> u64 {
+    use bos_core::memory::PAGE_SIZE;
+    use bos_core::paging::PageFlags;
+
+    let mut space = UserSpace::new().unwrap();
+    space.map_zeroed(usermode::USER_START, PAGE_SIZE, PageFlags::empty()).unwrap();
+    space.write(usermode::USER_START, code).unwrap();
+    let stack = space.map_stack().unwrap();
+    spawn("test", space, usermode::USER_START, stack).unwrap()
+}
+
+/// Code machine d'un programme de test, assemblé instruction par
+/// instruction (les numéros d'appels système ne sont pas à des
+/// décalages fixes)
+#[cfg(test)]
+#[derive(Default)]
+pub(crate) struct UserCode(Vec<u8>);
+
+#[cfg(test)]
+impl UserCode {
+    pub fn new() -> UserCode {
+        UserCode::default()
+    }
+
+    /// Octets bruts
+    pub fn bytes(mut self, bytes: &[u8]) -> UserCode {
+        self.0.extend_from_slice(bytes);
+        self
+    }
+
+    fn with_imm32(self, opcode: &[u8], value: u32) -> UserCode {
+        self.bytes(opcode).bytes(&value.to_le_bytes())
+    }
+
+    /// mov edi, value
+    pub fn mov_edi(self, value: u32) -> UserCode {
+        self.with_imm32(&[0xBF], value)
+    }
+
+    /// mov rdi, value (étendu à 64 bits)
+    pub fn mov_rdi(self, value: i32) -> UserCode {
+        self.with_imm32(&[0x48, 0xC7, 0xC7], value as u32)
+    }
+
+    /// mov edx, value
+    pub fn mov_edx(self, value: u32) -> UserCode {
+        self.with_imm32(&[0xBA], value)
+    }
+
+    /// xor edx, edx
+    pub fn zero_edx(self) -> UserCode {
+        self.bytes(&[0x31, 0xD2])
+    }
+
+    /// mov rdi, rax : le résultat du dernier appel système
+    pub fn mov_rdi_rax(self) -> UserCode {
+        self.bytes(&[0x48, 0x89, 0xC7])
+    }
+
+    /// push value (étendu à 64 bits)
+    pub fn push(self, value: i8) -> UserCode {
+        self.bytes(&[0x6A, value as u8])
+    }
+
+    /// mov rsi, rsp
+    pub fn mov_rsi_rsp(self) -> UserCode {
+        self.bytes(&[0x48, 0x89, 0xE6])
+    }
+
+    /// rsi pointe sur `data`, placé dans le code et sauté
+    pub fn lea_rsi_data(self, data: &[u8]) -> UserCode {
+        let length = i8::try_from(data.len()).unwrap() as u8;
+        // lea rsi, [rip + 2] ; jmp $ + 2 + length
+        self.bytes(&[0x48, 0x8D, 0x35, 0x02, 0x00, 0x00, 0x00, 0xEB, length]).bytes(data)
+    }
+
+    /// mov eax, number ; syscall
+    pub fn syscall(self, number: u64) -> UserCode {
+        self.with_imm32(&[0xB8], u32::try_from(number).unwrap()).bytes(&[0x0F, 0x05])
+    }
+
+    /// jmp $ : boucle infinie, seul le timer reprend la main
+    pub fn spin(self) -> UserCode {
+        self.bytes(&[0xEB, 0xFE])
+    }
+
+    /// Crée un processus qui exécute ce code (voir `start_code`)
+    pub fn start(self) -> u64 {
+        start_code(&self.0)
+    }
+}
+
+/// Crée un processus qui exécute `code`, attend sa fin et retourne
+/// son statut
+#[cfg(test)]
+fn run_code(code: &[u8]) -> ExitStatus {
+    let pid = start_code(code);
+    let (waited, status) = wait(Some(pid), false).unwrap().unwrap();
+    assert_eq!(waited, pid);
+    // Le zombie est retiré par `wait`
+    assert!(!exists(pid));
+    status
+}
+
+#[test_case]
+fn test_user_write_and_exit() {
+    use bos_core::syscall::{SYS_EXIT, SYS_WRITE};
+
+    let code = UserCode::new()
+        .lea_rsi_data(b"ring3\n")
+        .mov_edi(1)
+        .mov_edx(6)
+        .syscall(SYS_WRITE)
+        .mov_edi(7)
+        .syscall(SYS_EXIT);
+    assert_eq!(run_code(&code.0), ExitStatus::Exited(7));
+}
+
+#[test_case]
+fn test_user_fault_kills_process() {
+    use bos_core::signal::{SIGFPE, SIGILL, SIGSEGV};
+
+    // mov byte ptr [0xb8000], 'A' : page du kernel (VGA)
+    assert_eq!(run_code(&[0xC6, 0x04, 0x25, 0x00, 0x80, 0x0B, 0x00, 0x41]), ExitStatus::Killed(SIGSEGV));
+    // cli : instruction privilégiée
+    assert_eq!(run_code(&[0xFA]), ExitStatus::Killed(SIGSEGV));
+    // ud2
+    assert_eq!(run_code(&[0x0F, 0x0B]), ExitStatus::Killed(SIGILL));
+    // xor ecx, ecx ; div ecx
+    assert_eq!(run_code(&[0x31, 0xC9, 0xF7, 0xF1]), ExitStatus::Killed(SIGFPE));
+}
+
+#[test_case]
+fn test_kill_and_wait() {
+    use bos_core::signal::SIGKILL;
+
+    let pid = UserCode::new().spin().start();
+    assert_eq!(wait(Some(pid), true), Ok(None));
+    assert!(kill(pid, SIGKILL));
+    assert_eq!(wait(None, false), Ok(Some((pid, ExitStatus::Killed(SIGKILL)))));
+    assert!(!kill(pid, SIGKILL));
+    assert_eq!(wait(Some(pid), true), Err(WaitError::NoChild));
+}
+
+#[test_case]
+fn test_stop_and_continue() {
+    use bos_core::signal::{SIGKILL, SIGSTOP};
+
+    let pid = UserCode::new().spin().start();
+    assert!(kill(pid, SIGSTOP));
+    // Le signal est remis au prochain tick du timer
+    while state(pid) != Some(State::Stopped(SIGSTOP)) {
+        thread::yield_now();
+    }
+    assert!(kill(pid, SIGCONT));
+    assert_eq!(state(pid), Some(State::Running));
+    assert!(kill(pid, SIGSTOP));
+    while state(pid) != Some(State::Stopped(SIGSTOP)) {
+        thread::yield_now();
+    }
+    // SIGKILL termine aussi un processus arrêté
+    assert!(kill(pid, SIGKILL));
+    assert_eq!(wait(Some(pid), false), Ok(Some((pid, ExitStatus::Killed(SIGKILL)))));
+}
@@ -0,0 +1,83 @@
+/* =========================================================
+   PROGRAMMES UTILISATEUR
+
+   Les programmes lancés par le shell (`run <programme>`) ou par
+   l'appel système spawn sont des fichiers ELF lus par le VFS :
+   un nom seul est cherché dans /bin, un chemin (`/bin/hello`,
+   `../bin/hello`) désigne directement le fichier.
+
+   Ceux de l'initrd sont écrits en Rust avec libbos
+   (libbos/examples) et compilés par build.rs pour la cible
+   x86_64-bos-user.json.
+========================================================= */
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use bos_core::vfs::{self, FileType, FsError};
+
+/// Répertoire des programmes
+pub const BIN: &str = "/bin";
+
+/// Un programme : son nom et son fichier ELF
+#[derive(Debug, Clone)]
+pub struct Program {
+    pub name: String,
+    pub data: Vec<u8>,
+}
+
+/// Chemin du fichier du programme `name` ("/bin/hello" pour "hello")
+fn program_path(name: &str) -> String {
+    match name.contains('/') {
+        true => String::from(name),
+        false => alloc::format!("{}/{}", BIN, name),
+    }
+}
+
+/// Le programme `name` existe-t-il (fichier ordinaire) ?
+pub fn exists(name: &str, cwd: &str) -> bool {
+    matches!(crate::vfs::stat(&program_path(name), cwd), Ok(metadata) if metadata.kind == FileType::File)
+}
+
+/// Lit le programme `name` : son nom dans /bin, ou son chemin (relatif à
+/// `cwd`) s'il contient un '/'
+pub fn find(name: &str, cwd: &str) -> Result<Program, FsError> {
+    let path = program_path(name);
+    let data = crate::vfs::read_file(&path, cwd)?;
+    let (_, file_name) = vfs::split_last(&path).ok_or(FsError::InvalidArgument)?;
+    Ok(Program { name: String::from(file_name), data })
+}
+
+/// Appelle `f` pour chaque programme de /bin (nom, taille)
+pub fn for_each(mut f: impl FnMut(&str, u64)) {
+    let Ok(entries) = crate::vfs::read_dir(BIN, "/") else {
+        return;
+    };
+    for entry in entries {
+        let metadata = entry.inode.metadata();
+        if metadata.kind == FileType::File {
+            f(&entry.name, metadata.size);
+        }
+    }
+}
+
+// ===================================================================
+// TESTS
+// ===================================================================
+
+#[test_case]
+fn test_programs_are_valid_executables() {
+    use bos_core::elf::ElfFile;
+    use bos_core::exec::check_image;
+
+    use crate::usermode::{USER_HEAP_END, USER_START};
+
+    for name in ["hello", "signals", "wc"] {
+        let program = find(name, "/").unwrap();
+        let file = ElfFile::parse(&program.data).unwrap();
+        assert_eq!(check_image(&file, USER_START..USER_HEAP_END), Ok(()));
+    }
+    assert_eq!(find("../bin/hello", "/etc").map(|program| program.name).as_deref(), Ok("hello"));
+    assert!(exists("hello", "/") && !exists("/bin", "/") && !exists("absent", "/"));
+    assert_eq!(find("/bin", "/").err(), Some(FsError::IsADirectory));
+}
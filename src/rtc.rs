@@ -0,0 +1,90 @@
+/* =========================================================
+   HORLOGE TEMPS RÉEL (RTC)
+
+   La date est lue dans la CMOS par les ports 0x70 (numéro du
+   registre) et 0x71 (valeur). Une lecture pendant la mise à jour
+   de l'horloge peut mélanger deux secondes : on attend la fin de
+   la mise à jour et on relit jusqu'à obtenir deux fois les mêmes
+   valeurs. Le décodage (BCD, 12 heures) est dans bos_core::rtc.
+
+   La CMOS n'est lue qu'une fois, par `init` : l'heure courante
+   (`now`) est cette date plus le temps écoulé depuis, compté par
+   le timer du PIT.
+========================================================= */
+
+use core::sync::atomic::{AtomicU64, Ordering};
+
+use bos_core::rtc::{DateTime, RawTime, REG_STATUS_A, REG_STATUS_B, RTC_REGISTERS, STATUS_A_UPDATING};
+
+use crate::{inb, outb, pit, without_interrupts};
+
+// Ports I/O de la CMOS
+const CMOS_ADDRESS: u16 = 0x70; // Numéro du registre (bit 7 : NMI masquées)
+const CMOS_DATA: u16 = 0x71;    // Valeur du registre
+
+/// Bit 7 du port d'adresse : masque les NMI pendant l'accès
+const NMI_DISABLE: u8 = 0x80;
+
+/// Nombre maximal de lectures pour obtenir deux dates identiques
+const MAX_READS: usize = 10;
+
+// Date du démarrage du timer (secondes depuis 1970), posée par `init`
+static BOOT_TIME: AtomicU64 = AtomicU64::new(0);
+
+/// Lit un registre de la CMOS
+fn read_register(register: u8) -> u8 {
+    unsafe {
+        outb(CMOS_ADDRESS, register | NMI_DISABLE);
+        inb(CMOS_DATA)
+    }
+}
+
+/// Lit les registres de la date, après la fin d'une mise à jour
+fn read_raw() -> RawTime {
+    while read_register(REG_STATUS_A) & STATUS_A_UPDATING != 0 {
+        core::hint::spin_loop();
+    }
+    RTC_REGISTERS.map(read_register)
+}
+
+/// Lit la date de la RTC (`None` si l'horloge donne une date invalide)
+pub fn read() -> Option<DateTime> {
+    without_interrupts(|| {
+        let mut previous = read_raw();
+        for _ in 0..MAX_READS {
+            let raw = read_raw();
+            if raw == previous {
+                return DateTime::decode(raw, read_register(REG_STATUS_B));
+            }
+            previous = raw;
+        }
+        None
+    })
+}
+
+/// Lit la RTC et retient la date du démarrage, retourne la date lue
+///
+/// Sans date valide, l'heure courante part du 1er janvier 1970.
+pub fn init() -> Option<DateTime> {
+    let time = read()?;
+    let boot = time.unix_time().saturating_sub(pit::uptime_ms() / 1000);
+    BOOT_TIME.store(boot, Ordering::Relaxed);
+    Some(time)
+}
+
+/// Heure courante en secondes depuis 1970 (dates des fichiers)
+pub fn now() -> u64 {
+    BOOT_TIME.load(Ordering::Relaxed) + pit::uptime_ms() / 1000
+}
+
+// ===================================================================
+// TESTS
+// ===================================================================
+
+#[test_case]
+fn test_rtc_date() {
+    let time = read().expect("date de la RTC");
+    assert!(time.year >= 2000);
+    assert!(now() >= BOOT_TIME.load(Ordering::Relaxed));
+    assert!(DateTime::from_unix(now()).year >= 2000);
+}
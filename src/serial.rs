@@ -0,0 +1,133 @@
+// ===================================================================
+// PORT SÉRIE (UART 16550) POUR BOS
+// ===================================================================
+//
+// Ce module pilote les ports série COM1 et COM2 (UART 16550).
+// Contrairement à l'écran VGA, le port série peut être redirigé par QEMU
+// vers la console de l'hôte (option `-serial stdio`), ce qui permet :
+// - D'afficher les résultats des tests sur le terminal de l'hôte
+// - De conserver une trace du boot même quand l'écran n'est pas visible
+//
+// Registres de l'UART (relatifs au port de base : 0x3F8 pour COM1, 0x2F8 pour COM2) :
+// - +0 : Données (ou diviseur bas si DLAB = 1)
+// - +1 : Activation des interruptions (ou diviseur haut si DLAB = 1)
+// - +2 : Contrôle FIFO
+// - +3 : Contrôle de ligne (bit 7 = DLAB)
+// - +4 : Contrôle modem
+// - +5 : État de ligne (bit 0 = octet reçu, bit 5 = registre d'émission vide)
+
+use core::fmt;
+
+use crate::{inb, outb};
+
+/// Un port série UART 16550, identifié par son port I/O de base
+pub struct SerialPort {
+    base: u16,
+}
+
+/// COM1 : traces du kernel et résultats des tests
+pub const COM1: SerialPort = SerialPort::new(0x3F8);
+/// COM2 : réservé au stub GDB (voir gdb.rs)
+pub const COM2: SerialPort = SerialPort::new(0x2F8);
+
+impl SerialPort {
+    /// Décrit le port dont les registres commencent à `base`
+    pub const fn new(base: u16) -> SerialPort {
+        SerialPort { base }
+    }
+
+    /// Initialise le port : 38400 bauds, 8 bits, pas de parité, 1 bit de stop
+    pub fn init(&self) {
+        unsafe {
+            // Désactiver toutes les interruptions de l'UART
+            outb(self.base + 1, 0x00);
+            // Activer DLAB pour programmer le diviseur de fréquence
+            outb(self.base + 3, 0x80);
+            // Diviseur = 3 → 115200 / 3 = 38400 bauds
+            outb(self.base, 0x03);
+            outb(self.base + 1, 0x00);
+            // 8 bits, pas de parité, 1 bit de stop (et DLAB désactivé)
+            outb(self.base + 3, 0x03);
+            // Activer la FIFO, la vider, seuil de 14 octets
+            outb(self.base + 2, 0xC7);
+            // DTR + RTS + OUT2
+            outb(self.base + 4, 0x0B);
+        }
+    }
+
+    /// Envoie un octet (attend que le registre d'émission soit vide)
+    pub fn write_byte(&self, byte: u8) {
+        unsafe {
+            while inb(self.base + 5) & 0x20 == 0 {
+                core::hint::spin_loop();
+            }
+            outb(self.base, byte);
+        }
+    }
+
+    /// Lit un octet s'il en est arrivé un (bit 0 de l'état de ligne)
+    pub fn try_read_byte(&self) -> Option<u8> {
+        unsafe {
+            if inb(self.base + 5) & 0x01 != 0 {
+                Some(inb(self.base))
+            } else {
+                None
+            }
+        }
+    }
+
+    /// Attend puis lit un octet
+    pub fn read_byte(&self) -> u8 {
+        loop {
+            if let Some(byte) = self.try_read_byte() {
+                return byte;
+            }
+            core::hint::spin_loop();
+        }
+    }
+}
+
+/// Initialise COM1
+pub fn init_serial() {
+    COM1.init();
+}
+
+/// Envoie une chaîne de caractères sur le port série
+pub fn serial_print(s: &str) {
+    for byte in s.bytes() {
+        COM1.write_byte(byte);
+    }
+}
+
+/// Écrivain pour `core::fmt` qui envoie le texte formaté sur COM1
+pub struct SerialWriter;
+
+impl fmt::Write for SerialWriter {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        serial_print(s);
+        Ok(())
+    }
+}
+
+/// Fonction utilisée par les macros `serial_print!` et `serial_println!`
+#[doc(hidden)]
+pub fn _print(args: fmt::Arguments) {
+    use core::fmt::Write;
+    let _ = SerialWriter.write_fmt(args);
+}
+
+/// Affiche du texte formaté sur le port série (comme `print!`)
+#[macro_export]
+macro_rules! serial_print {
+    ($($arg:tt)*) => {
+        $crate::serial::_print(format_args!($($arg)*))
+    };
+}
+
+/// Affiche du texte formaté sur le port série suivi d'un retour à la ligne
+#[macro_export]
+macro_rules! serial_println {
+    () => ($crate::serial_print!("\n"));
+    ($fmt:expr) => ($crate::serial_print!(concat!($fmt, "\n")));
+    ($fmt:expr, $($arg:tt)*) => ($crate::serial_print!(concat!($fmt, "\n"), $($arg)*));
+}
@@ -7,26 +7,108 @@
 // - L'affichage d'un prompt
 // - L'exécution de commandes de base
 // - Gestion du backspace
+//
+// Le shell est une tâche asynchrone (`run`) qui attend les scancodes
+// du clavier : les commandes s'exécutent hors des handlers d'interruption.
+//
+// Les fichiers se parcourent à travers le VFS (`ls`, `cd`, `cat`...)
+// depuis le répertoire courant du shell, et s'écrivent dans /tmp
+// (`touch`, `write`, `cp`, `mv`) ; la sortie de `echo` et de `cat` se
+// redirige vers un fichier avec `>` (ou `>>` pour ajouter à la fin).
+// Les programmes se lancent par leur nom (ceux de /bin) ou leur chemin.
+//
+// Un programme lancé par le shell (`run`, ou son nom seul) a le
+// clavier jusqu'à sa fin : le shell lui passe les caractères tapés
+// (appel système read) au lieu de les traiter, sauf Ctrl+C qui lui
+// envoie SIGINT et Ctrl+Z qui l'arrête (SIGTSTP). Suivi d'un `&`, ou
+// une fois arrêté, c'est un job, que le shell attend pour annoncer sa
+// fin avant le prompt suivant ; `fg` et `bg` le font reprendre.
 
-use crate::{vga_print, vga_print_char, vga_backspace, update_hardware_cursor};
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::Write;
+
+use bos_core::command::{parse_command, split_args, split_background, split_redirect, Redirect};
+use bos_core::keyboard::{Key, KeyDecoder};
+use bos_core::log::Level;
+use bos_core::memory::Size;
+use bos_core::rtc::DateTime;
+use bos_core::signal::{is_valid, parse_signal, SIGCONT, SIGINT, SIGTERM, SIGTSTP};
+use bos_core::vfs::{FileType, FsError};
+
+use crate::keyboard::{self, ScancodeStream};
+use crate::process::State;
+use crate::task::Stream;
+use crate::vfs::{self, Inode};
+use crate::vga::VgaWriter;
+use crate::{vga_print, vga_print_char, vga_backspace};
 
 /// Taille maximale d'une commande
 const CMD_BUFFER_SIZE: usize = 256;
 
+/// Nombre maximal d'arguments d'un programme (nom compris)
+const MAX_ARGS: usize = 16;
+
+/// Environnement des programmes lancés par le shell
+const PROGRAM_ENV: &[&str] = &["TERM=bos"];
+
+/// Délai entre deux vérifications de la fin du programme au premier plan
+const FOREGROUND_POLL_MS: u64 = 10;
+
+/// Tâche du shell : lit le clavier et exécute les commandes
+pub async fn run() {
+    let mut shell = Shell::new();
+    shell.init();
+    let mut scancodes = ScancodeStream::new();
+    while let Some(scancode) = scancodes.next().await {
+        // Les touches relâchées et les touches spéciales (Shift, Ctrl, etc.)
+        // sont ignorées (voir bos_core::keyboard)
+        if let Some(key) = shell.keys.feed(scancode) {
+            shell.handle_key(key);
+        }
+        if let Some((pid, name)) = shell.foreground.take() {
+            shell.wait_foreground(pid, name, &mut scancodes);
+            shell.notify_jobs();
+            shell.print_prompt();
+        }
+    }
+}
+
+/// Programme lancé en arrière-plan, ou arrêté
+struct Job {
+    /// Numéro du job (`%n`)
+    id: usize,
+    pid: u64,
+    name: String,
+}
+
 /// Structure représentant le shell
 pub struct Shell {
     /// Buffer contenant la commande en cours de saisie
     cmd_buffer: [u8; CMD_BUFFER_SIZE],
     /// Position actuelle dans le buffer (nombre de caractères)
     cmd_position: usize,
+    /// Processus lancé (ou repris) par la dernière commande, qui prend
+    /// le clavier : pid et nom
+    foreground: Option<(u64, String)>,
+    /// Programmes en arrière-plan ou arrêtés, pas encore attendus
+    jobs: Vec<Job>,
+    /// Répertoire courant (chemin absolu)
+    cwd: String,
+    /// État de la touche Ctrl
+    keys: KeyDecoder,
 }
 
 impl Shell {
     /// Crée une nouvelle instance du shell
-    pub const fn new() -> Shell {
+    pub fn new() -> Shell {
         Shell {
             cmd_buffer: [0; CMD_BUFFER_SIZE],
             cmd_position: 0,
+            foreground: None,
+            jobs: Vec::new(),
+            cwd: String::from("/"),
+            keys: KeyDecoder::new(),
         }
     }
 
@@ -46,6 +128,23 @@ impl Shell {
         vga_print("bos> ");
     }
 
+    /// Traite une touche pressée
+    ///
+    /// Ctrl+C abandonne la ligne en cours ; les autres combinaisons
+    /// avec Ctrl sont ignorées.
+    pub fn handle_key(&mut self, key: Key) {
+        match key {
+            Key::Char(c) => self.handle_char(c),
+            Key::Ctrl('c') => {
+                vga_print("^C\n");
+                self.clear_buffer();
+                self.notify_jobs();
+                self.print_prompt();
+            }
+            Key::Ctrl(_) => {}
+        }
+    }
+
     /// Traite un caractère reçu du clavier
     /// 
     /// Retourne true si le caractère a été traité, false sinon
@@ -56,7 +155,11 @@ impl Shell {
                 vga_print_char('\n');
                 self.execute_command();
                 self.clear_buffer();
-                self.print_prompt();
+                // Sinon le prompt revient à la fin du programme
+                if self.foreground.is_none() {
+                    self.notify_jobs();
+                    self.print_prompt();
+                }
             }
             
             // Backspace : effacer le dernier caractère
@@ -96,20 +199,25 @@ impl Shell {
 
     /// Exécute la commande contenue dans le buffer
     fn execute_command(&mut self) {
-        let cmd = self.get_command().trim();
+        // Un `&` final lance le programme en arrière-plan
+        let (line, background) = split_background(self.get_command());
+        // `> fichier` ou `>> fichier` redirige la sortie
+        let (line, redirect) = split_redirect(line);
+        // Parser la commande (séparer la commande des arguments)
+        let (command, args) = parse_command(line);
 
-        if cmd.is_empty() {
+        if command.is_empty() {
+            return;
+        }
+        if let Some(redirect) = redirect {
+            self.redirect_output(command, args, redirect);
             return;
         }
 
-        // Parser la commande (séparer la commande des arguments)
-        let parts: (&str, &str) = match cmd.find(' ') {
-            Some(pos) => (&cmd[..pos], &cmd[pos+1..]),
-            None => (cmd, ""),
-        };
-
-        let command = parts.0;
-        let args = parts.1;
+        // Programme lancé par la commande
+        let mut launched = None;
+        // Nouveau répertoire courant (cd)
+        let mut cwd = None;
 
         // Dispatcher vers la bonne commande
         match command {
@@ -118,13 +226,73 @@ impl Shell {
             "echo" => self.cmd_echo(args),
             "about" => self.cmd_about(),
             "uptime" => self.cmd_uptime(),
+            "gdb" => self.cmd_gdb(),
+            "dmesg" => self.cmd_dmesg(args),
+            "loglevel" => self.cmd_loglevel(args),
+            "meminfo" => self.cmd_meminfo(),
+            "slabinfo" => self.cmd_slabinfo(),
+            "heapcheck" => self.cmd_heapcheck(),
+            "ps" => self.cmd_ps(),
+            "pwd" => self.cmd_pwd(),
+            "cd" => cwd = self.cmd_cd(args),
+            "ls" => self.cmd_ls(args),
+            "cat" => self.cmd_cat(args),
+            "touch" => self.cmd_touch(args),
+            "write" => self.cmd_write(args),
+            "cp" => self.cmd_cp(args),
+            "mv" => self.cmd_mv(args),
+            "date" => self.cmd_date(),
+            "mkdir" => self.cmd_mkdir(args),
+            "rm" => self.cmd_rm(args),
+            "mount" => self.cmd_mount(args),
+            "umount" => self.cmd_umount(args),
+            "jobs" => self.cmd_jobs(),
+            "kill" => self.cmd_kill(args),
+            "fg" => launched = self.cmd_fg(args),
+            "bg" => self.cmd_bg(args),
+            "run" => launched = self.cmd_run(args),
             "" => {},
+            // Un nom de programme seul le lance
+            _ if crate::programs::exists(command, &self.cwd) => launched = self.cmd_run(line),
             _ => {
                 vga_print("Commande inconnue: ");
                 vga_print(command);
                 vga_print("\nTapez 'help' pour voir les commandes disponibles.\n");
             }
         }
+        match launched {
+            Some((pid, name)) if background => {
+                let id = self.add_job(pid, name);
+                let _ = writeln!(VgaWriter, "[{}] {}", id, pid);
+            }
+            Some((pid, name)) => {
+                // Un job repris par fg n'est plus un job
+                self.jobs.retain(|job| job.pid != pid);
+                self.foreground = Some((pid, name));
+            }
+            None => {}
+        }
+        if let Some(cwd) = cwd {
+            self.cwd = cwd;
+        }
+    }
+
+    /// Ajoute un job, retourne son numéro
+    fn add_job(&mut self, pid: u64, name: String) -> usize {
+        let id = self.jobs.iter().map(|job| job.id).max().unwrap_or(0) + 1;
+        self.jobs.push(Job { id, pid, name });
+        id
+    }
+
+    /// Index du job désigné par `%n` (le dernier si `spec` est absent)
+    fn find_job(&self, spec: Option<&str>) -> Option<usize> {
+        match spec {
+            None => self.jobs.len().checked_sub(1),
+            Some(spec) => {
+                let id = spec.strip_prefix('%').unwrap_or(spec).parse().ok()?;
+                self.jobs.iter().position(|job| job.id == id)
+            }
+        }
     }
 
     /// Commande: help - Affiche la liste des commandes
@@ -134,7 +302,33 @@ impl Shell {
         vga_print("  clear  - Efface l'ecran\n");
         vga_print("  echo   - Affiche un message\n");
         vga_print("  about  - Informations sur BOS\n");
-        vga_print("  uptime - (Pas encore implemente)\n");
+        vga_print("  uptime - Temps depuis le demarrage\n");
+        vga_print("  gdb    - Rend la main a GDB (port COM2)\n");
+        vga_print("  dmesg  - Affiche le journal du kernel (-c : le vide)\n");
+        vga_print("  loglevel [sortie] [niveau] - Filtre du journal\n");
+        vga_print("  meminfo - Carte de la memoire physique\n");
+        vga_print("  slabinfo - Utilisation des caches slab\n");
+        vga_print("  heapcheck - Verifie le tas, liste les allocations\n");
+        vga_print("  ps     - Liste les threads du kernel et les processus\n");
+        vga_print("  pwd    - Affiche le repertoire courant\n");
+        vga_print("  cd [chemin] - Change de repertoire courant\n");
+        vga_print("  ls [chemin] - Liste un repertoire\n");
+        vga_print("  cat <fichier>... - Affiche des fichiers\n");
+        vga_print("  touch <fichier>... - Cree des fichiers vides ou les date\n");
+        vga_print("  write <fichier> [texte] - Remplace le contenu d'un fichier\n");
+        vga_print("  echo|cat ... > <fichier> - Redirige la sortie (>> : ajoute)\n");
+        vga_print("  cp <source> <destination> - Copie un fichier\n");
+        vga_print("  mv <source> <destination> - Renomme ou deplace\n");
+        vga_print("  date   - Date et heure (RTC)\n");
+        vga_print("  mkdir <chemin>... - Cree des repertoires\n");
+        vga_print("  rm <chemin>... - Supprime des fichiers ou repertoires vides\n");
+        vga_print("  mount [<type> <repertoire>] - Liste les montages ou monte\n");
+        vga_print("  umount <repertoire> - Demonte un systeme de fichiers\n");
+        vga_print("  run <programme> [args] [&] - Lance un programme (sans argument : la liste)\n");
+        vga_print("  jobs   - Liste les programmes en arriere-plan ou arretes\n");
+        vga_print("  kill [-signal] <pid|%job> - Envoie un signal (defaut : TERM)\n");
+        vga_print("  fg [%job] - Reprend un job au premier plan\n");
+        vga_print("  bg [%job] - Reprend un job arrete en arriere-plan\n");
     }
 
     /// Commande: clear - Efface l'écran
@@ -149,11 +343,9 @@ impl Shell {
                     *vga_buffer.add(i) = 0x0f;
                 }
             }
-            // Réinitialiser le curseur
-            crate::VGA_CURSOR = 0;
-            // Mettre à jour le curseur matériel
-            update_hardware_cursor();
         }
+        // Réinitialiser le curseur (et le curseur matériel)
+        crate::vga::set_cursor(0);
     }
 
     /// Commande: echo - Affiche un message
@@ -179,7 +371,723 @@ impl Shell {
 
     /// Commande: uptime - Affiche le temps depuis le démarrage
     fn cmd_uptime(&self) {
-        vga_print("Uptime: (non implemente)\n");
-        vga_print("TODO: Integrer un timer PIT pour compter le temps\n");
+        let ms = crate::pit::uptime_ms();
+        let seconds = ms / 1000;
+        let _ = writeln!(
+            VgaWriter,
+            "Uptime: {}h {:02}m {:02}.{:03}s",
+            seconds / 3600,
+            seconds / 60 % 60,
+            seconds % 60,
+            ms % 1000
+        );
+    }
+
+    /// Commande: gdb - Installe le stub GDB si besoin et lui rend la main
+    fn cmd_gdb(&self) {
+        if !crate::gdb::is_active() {
+            crate::gdb::init();
+        }
+        vga_print("En attente de GDB sur COM2...\n");
+        crate::gdb::breakpoint();
+        vga_print("GDB: reprise de l'execution\n");
+    }
+
+    /// Commande: dmesg - Affiche le journal du kernel
+    fn cmd_dmesg(&self, args: &str) {
+        let _ = crate::log::write_buffer(&mut VgaWriter);
+        if args.trim() == "-c" {
+            crate::log::clear_buffer();
+        }
+    }
+
+    /// Commande: loglevel - Affiche ou change le niveau des sorties du journal
+    ///
+    /// `loglevel` liste les sorties, `loglevel <niveau>` règle l'écran,
+    /// `loglevel <sortie> <niveau>` règle une sortie donnée.
+    fn cmd_loglevel(&self, args: &str) {
+        let mut words = split_args(args);
+        let (sink, level) = match (words.next(), words.next()) {
+            (None, _) => {
+                for sink in crate::log::SINKS.iter() {
+                    let _ = writeln!(VgaWriter, "  {:<8} {}", sink.name, sink.level().name());
+                }
+                return;
+            }
+            (Some(level), None) => ("console", level),
+            (Some(sink), Some(level)) => (sink, level),
+        };
+        let sink = match crate::log::sink(sink) {
+            Some(sink) => sink,
+            None => {
+                let _ = writeln!(VgaWriter, "Sortie inconnue: {} (console, serial)", sink);
+                return;
+            }
+        };
+        match Level::parse(level) {
+            Some(level) => {
+                sink.set_level(level);
+                let _ = writeln!(VgaWriter, "{}: {}", sink.name, level.name());
+            }
+            None => vga_print("Niveaux: error, warn, info, debug, trace (ou 1 a 5)\n"),
+        }
+    }
+
+    /// Commande: meminfo - Affiche la carte de la mémoire physique
+    fn cmd_meminfo(&self) {
+        for region in crate::memory::regions() {
+            let _ = writeln!(
+                VgaWriter,
+                "  {:#012x}-{:#012x} {:>9} {}",
+                region.start,
+                region.end,
+                Size(region.size()),
+                region.kind.name()
+            );
+        }
+        let summary = crate::memory::summary();
+        let _ = writeln!(VgaWriter, "Utilisable : {}", Size(summary.usable));
+        let _ = writeln!(VgaWriter, "Reservee   : {}", Size(summary.reserved + summary.acpi));
+        let _ = writeln!(VgaWriter, "Kernel     : {}", Size(summary.kernel));
+        let _ = writeln!(VgaWriter, "Bootloader : {}", Size(summary.bootloader));
+        let frames = crate::frame::stats();
+        let _ = writeln!(
+            VgaWriter,
+            "Frames     : {} libres, {} utilises ({} allocations, {} liberations)",
+            frames.free,
+            frames.used(),
+            frames.allocations,
+            frames.frees
+        );
+        let heap = crate::heap::stats();
+        let free_list = crate::heap::free_list_info();
+        let _ = writeln!(
+            VgaWriter,
+            "Tas        : {} utilises / {}, {} blocs libres (plus grand {})",
+            Size(heap.used as u64),
+            Size(heap.size as u64),
+            free_list.blocks,
+            Size(free_list.largest as u64)
+        );
+    }
+
+    /// Commande: slabinfo - Affiche l'utilisation des caches slab
+    fn cmd_slabinfo(&self) {
+        let _ = writeln!(VgaWriter, "{:<14} {:>6} {:>6} {:>6} {:>6} {:>9}", "cache", "actifs", "total", "taille", "slabs", "allocs");
+        crate::slab::for_each_cache(|cache| {
+            let stats = cache.stats();
+            let _ = writeln!(
+                VgaWriter,
+                "{:<14} {:>6} {:>6} {:>6} {:>6} {:>9}",
+                cache.name(),
+                stats.active_objects,
+                stats.total_objects,
+                cache.object_size(),
+                stats.slabs,
+                stats.allocations
+            );
+        });
+        if crate::slab::SLAB_POISON {
+            vga_print("Empoisonnement des objets liberes actif\n");
+        }
+    }
+
+    /// Commande: heapcheck - Vérifie les zones rouges et liste les allocations vivantes
+    fn cmd_heapcheck(&self) {
+        if !crate::heap_debug::ENABLED {
+            vga_print("Compiler BOS avec --features heap-debug\n");
+            return;
+        }
+        match crate::heap_debug::write_report(&mut VgaWriter) {
+            Ok(0) => vga_print("Zones rouges intactes\n"),
+            Ok(corrupted) => {
+                let _ = writeln!(VgaWriter, "{} allocations corrompues", corrupted);
+            }
+            Err(_) => {}
+        }
+    }
+
+    /// Commande: ps - Liste les threads et leur état, puis les processus
+    fn cmd_ps(&self) {
+        let _ = writeln!(VgaWriter, "{:>4} {:<8} {:>8} {:>7}  nom", "tid", "etat", "ticks", "commut");
+        crate::thread::for_each(|info| {
+            let _ = writeln!(
+                VgaWriter,
+                "{:>4} {:<8} {:>8} {:>7}  {}",
+                info.id, info.state, info.ticks, info.switches, info.name
+            );
+        });
+        if crate::process::count() == 0 {
+            return;
+        }
+        let _ = writeln!(VgaWriter, "{:>4} {:>4} {:<8} {:>4}  nom", "pid", "ppid", "etat", "tid");
+        crate::process::for_each(|info| {
+            let _ = writeln!(
+                VgaWriter,
+                "{:>4} {:>4} {:<8} {:>4}  {}",
+                info.pid,
+                info.parent,
+                info.state.name(),
+                info.thread,
+                info.name
+            );
+        });
+    }
+
+    /// Commande: pwd - Affiche le répertoire courant
+    fn cmd_pwd(&self) {
+        let _ = writeln!(VgaWriter, "{}", self.cwd);
+    }
+
+    /// Commande: cd - Change de répertoire courant (la racine par
+    /// défaut)
+    ///
+    /// Retourne le nouveau répertoire courant, absolu et sans lien.
+    fn cmd_cd(&self, args: &str) -> Option<String> {
+        let path = split_args(args).next().unwrap_or("/");
+        let result = vfs::lookup(path, &self.cwd).and_then(|resolved| match resolved.metadata().kind {
+            FileType::Directory => Ok(resolved.path),
+            _ => Err(FsError::NotADirectory),
+        });
+        match result {
+            Ok(cwd) => Some(cwd),
+            Err(error) => {
+                let _ = writeln!(VgaWriter, "cd: {} : {}", path, error);
+                None
+            }
+        }
+    }
+
+    /// Commande: ls - Liste un répertoire (le répertoire courant par
+    /// défaut)
+    fn cmd_ls(&self, args: &str) {
+        let path = split_args(args).next().unwrap_or(".");
+        let result = vfs::lookup(path, &self.cwd).and_then(|resolved| match resolved.inode.as_directory() {
+            Some(directory) => {
+                let mut entries = directory.entries()?;
+                entries.sort_by(|a, b| a.name.cmp(&b.name));
+                entries.iter().for_each(|entry| print_entry(&entry.name, entry.inode.as_ref()));
+                Ok(())
+            }
+            None => {
+                print_entry(path, resolved.inode.as_ref());
+                Ok(())
+            }
+        });
+        if let Err(error) = result {
+            let _ = writeln!(VgaWriter, "ls: {} : {}", path, error);
+        }
+    }
+
+    /// Commande: cat - Affiche des fichiers
+    fn cmd_cat(&self, args: &str) {
+        for path in split_args(args) {
+            let data = match vfs::read_file(path, &self.cwd) {
+                Ok(data) => data,
+                Err(error) => {
+                    let _ = writeln!(VgaWriter, "cat: {} : {}", path, error);
+                    continue;
+                }
+            };
+            match core::str::from_utf8(&data) {
+                Ok(text) => {
+                    vga_print(text);
+                    if !text.is_empty() && !text.ends_with('\n') {
+                        vga_print("\n");
+                    }
+                }
+                Err(_) => {
+                    let _ = writeln!(VgaWriter, "cat: {} : fichier binaire ({})", path, Size(data.len() as u64));
+                }
+            }
+        }
+    }
+
+    /// Exécute `echo` ou `cat` en écrivant leur sortie dans un fichier
+    fn redirect_output(&self, command: &str, args: &str, redirect: Redirect) {
+        if redirect.path.is_empty() {
+            vga_print("Redirection sans fichier\n");
+            return;
+        }
+        let data = match command {
+            "echo" => {
+                let mut text = String::from(args);
+                text.push('\n');
+                text.into_bytes()
+            }
+            "cat" => {
+                let mut data = Vec::new();
+                for path in split_args(args) {
+                    match vfs::read_file(path, &self.cwd) {
+                        Ok(content) => data.extend_from_slice(&content),
+                        Err(error) => {
+                            let _ = writeln!(VgaWriter, "cat: {} : {}", path, error);
+                        }
+                    }
+                }
+                data
+            }
+            _ => {
+                let _ = writeln!(VgaWriter, "Redirection impossible pour {} (echo, cat)", command);
+                return;
+            }
+        };
+        if let Err(error) = vfs::write_file(redirect.path, &self.cwd, &data, redirect.append) {
+            let _ = writeln!(VgaWriter, "{} : {}", redirect.path, error);
+        }
+    }
+
+    /// Commande: touch - Crée des fichiers vides, ou met leur date à
+    /// l'heure courante
+    fn cmd_touch(&self, args: &str) {
+        for path in split_args(args) {
+            if let Err(error) = vfs::touch(path, &self.cwd) {
+                let _ = writeln!(VgaWriter, "touch: {} : {}", path, error);
+            }
+        }
+    }
+
+    /// Commande: write - Remplace le contenu d'un fichier par le texte
+    /// (suivi d'un retour à la ligne, rien sans texte), le crée s'il
+    /// n'existe pas
+    fn cmd_write(&self, args: &str) {
+        let (path, text) = parse_command(args);
+        if path.is_empty() {
+            vga_print("Usage: write <fichier> [texte]\n");
+            return;
+        }
+        let mut data = String::from(text);
+        if !data.is_empty() {
+            data.push('\n');
+        }
+        if let Err(error) = vfs::write_file(path, &self.cwd, data.as_bytes(), false) {
+            let _ = writeln!(VgaWriter, "write: {} : {}", path, error);
+        }
+    }
+
+    /// Source et destination de `cp` et `mv` ; une destination qui est
+    /// un répertoire reçoit le fichier sous son nom
+    fn source_and_target(&self, command: &str, args: &str) -> Option<(String, String)> {
+        let mut words = split_args(args);
+        let (Some(source), Some(target), None) = (words.next(), words.next(), words.next()) else {
+            let _ = writeln!(VgaWriter, "Usage: {} <source> <destination>", command);
+            return None;
+        };
+        let target = match vfs::stat(target, &self.cwd) {
+            Ok(metadata) if metadata.kind == FileType::Directory => {
+                let name = bos_core::vfs::split_last(source).map_or(source, |(_, name)| name);
+                alloc::format!("{}/{}", target.trim_end_matches('/'), name)
+            }
+            _ => String::from(target),
+        };
+        Some((String::from(source), target))
+    }
+
+    /// Commande: cp - Copie un fichier
+    fn cmd_cp(&self, args: &str) {
+        let Some((source, target)) = self.source_and_target("cp", args) else {
+            return;
+        };
+        let result = vfs::read_file(&source, &self.cwd).map_err(|error| (&source, error)).and_then(|data| {
+            vfs::write_file(&target, &self.cwd, &data, false).map_err(|error| (&target, error))
+        });
+        if let Err((path, error)) = result {
+            let _ = writeln!(VgaWriter, "cp: {} : {}", path, error);
+        }
+    }
+
+    /// Commande: mv - Renomme ou déplace un fichier ou un répertoire
+    fn cmd_mv(&self, args: &str) {
+        let Some((source, target)) = self.source_and_target("mv", args) else {
+            return;
+        };
+        if let Err(error) = vfs::rename(&source, &target, &self.cwd) {
+            let _ = writeln!(VgaWriter, "mv: {} -> {} : {}", source, target, error);
+        }
+    }
+
+    /// Commande: date - Affiche la date et l'heure (UTC)
+    fn cmd_date(&self) {
+        let _ = writeln!(VgaWriter, "{}", DateTime::from_unix(crate::rtc::now()));
+    }
+
+    /// Commande: mkdir - Crée des répertoires
+    fn cmd_mkdir(&self, args: &str) {
+        for path in split_args(args) {
+            if let Err(error) = vfs::mkdir(path, &self.cwd) {
+                let _ = writeln!(VgaWriter, "mkdir: {} : {}", path, error);
+            }
+        }
+    }
+
+    /// Commande: rm - Supprime des fichiers, liens ou répertoires vides
+    fn cmd_rm(&self, args: &str) {
+        for path in split_args(args) {
+            if let Err(error) = vfs::remove(path, &self.cwd) {
+                let _ = writeln!(VgaWriter, "rm: {} : {}", path, error);
+            }
+        }
+    }
+
+    /// Commande: mount - Liste les montages, ou monte un système de
+    /// fichiers (`mount <type> <repertoire>`)
+    fn cmd_mount(&self, args: &str) {
+        let mut words = split_args(args);
+        let (kind, path) = match (words.next(), words.next()) {
+            (None, _) => {
+                vfs::for_each_mount(|path, kind| {
+                    let _ = writeln!(VgaWriter, "  {:<8} sur {}", kind, path);
+                });
+                return;
+            }
+            (Some(kind), Some(path)) => (kind, path),
+            (Some(_), None) => {
+                vga_print("Usage: mount [<type> <repertoire>]\n");
+                return;
+            }
+        };
+        let Some(fs) = vfs::new_filesystem(kind) else {
+            let _ = writeln!(VgaWriter, "mount: type inconnu: {}", kind);
+            return;
+        };
+        if let Err(error) = vfs::mount(path, &self.cwd, fs) {
+            let _ = writeln!(VgaWriter, "mount: {} : {}", path, error);
+        }
+    }
+
+    /// Commande: umount - Démonte un système de fichiers
+    fn cmd_umount(&self, args: &str) {
+        let Some(path) = split_args(args).next() else {
+            vga_print("Usage: umount <repertoire>\n");
+            return;
+        };
+        match vfs::umount(path, &self.cwd) {
+            Ok(()) => {}
+            Err(FsError::InvalidArgument) => {
+                let _ = writeln!(VgaWriter, "umount: {} : pas un point de montage", path);
+            }
+            Err(error) => {
+                let _ = writeln!(VgaWriter, "umount: {} : {}", path, error);
+            }
+        }
+    }
+
+    /// Commande: jobs - Liste les programmes en arrière-plan ou arrêtés
+    fn cmd_jobs(&self) {
+        for job in &self.jobs {
+            let state = match crate::process::state(job.pid) {
+                Some(State::Zombie(_)) | None => "Termine",
+                Some(State::Stopped(_)) => "Arrete",
+                Some(_) => "En cours",
+            };
+            let _ = writeln!(VgaWriter, "[{}] {:>4} {:<9} {}", job.id, job.pid, state, job.name);
+        }
+    }
+
+    /// Commande: kill - Envoie un signal à un processus (par son pid ou
+    /// `%job`)
+    ///
+    /// Le signal par défaut est SIGTERM ; `-9`, `-KILL` ou `-SIGSTOP` en
+    /// choisissent un autre.
+    fn cmd_kill(&self, args: &str) {
+        let mut words = split_args(args).peekable();
+        let mut signal = SIGTERM;
+        if let Some(option) = words.next_if(|word| word.starts_with('-')) {
+            match parse_signal(&option[1..]) {
+                Some(number) if is_valid(number) => signal = number,
+                _ => {
+                    let _ = writeln!(VgaWriter, "Signal invalide: {}", option);
+                    return;
+                }
+            }
+        }
+        let Some(target) = words.next() else {
+            vga_print("Usage: kill [-signal] <pid|%job>\n");
+            return;
+        };
+        let pid = match target.starts_with('%') {
+            true => self.find_job(Some(target)).map(|index| self.jobs[index].pid),
+            false => target.parse().ok(),
+        };
+        match pid {
+            Some(pid) if crate::process::kill(pid, signal) => {}
+            _ => {
+                let _ = writeln!(VgaWriter, "Processus introuvable: {}", target);
+            }
+        }
+    }
+
+    /// Commande: fg - Reprend un job au premier plan : il reprend le
+    /// clavier
+    fn cmd_fg(&self, args: &str) -> Option<(u64, String)> {
+        let Some(index) = self.find_job(split_args(args).next()) else {
+            vga_print("Job introuvable\n");
+            return None;
+        };
+        let job = &self.jobs[index];
+        let _ = writeln!(VgaWriter, "{}", job.name);
+        crate::process::kill(job.pid, SIGCONT);
+        Some((job.pid, job.name.clone()))
+    }
+
+    /// Commande: bg - Reprend un job arrêté, en arrière-plan
+    fn cmd_bg(&self, args: &str) {
+        let Some(index) = self.find_job(split_args(args).next()) else {
+            vga_print("Job introuvable\n");
+            return;
+        };
+        let job = &self.jobs[index];
+        let _ = writeln!(VgaWriter, "[{}] {} &", job.id, job.name);
+        crate::process::kill(job.pid, SIGCONT);
+    }
+
+    /// Annonce la fin des jobs terminés et les retire
+    fn notify_jobs(&mut self) {
+        self.jobs.retain(|job| match crate::process::wait(Some(job.pid), true) {
+            Ok(None) => true,
+            Ok(Some((_, status))) => {
+                let _ = writeln!(VgaWriter, "[{}] Termine ({})  {}", job.id, status, job.name);
+                false
+            }
+            // Déjà attendu ailleurs
+            Err(_) => false,
+        });
+    }
+
+    /// Commande: run - Lance un programme utilisateur
+    ///
+    /// Retourne le pid du processus lancé et le nom du programme.
+    fn cmd_run(&self, args: &str) -> Option<(u64, String)> {
+        let mut argv = [""; MAX_ARGS];
+        let mut argc = 0;
+        for arg in split_args(args) {
+            if argc == MAX_ARGS {
+                let _ = writeln!(VgaWriter, "Trop d'arguments (maximum {})", MAX_ARGS);
+                return None;
+            }
+            argv[argc] = arg;
+            argc += 1;
+        }
+        if argc == 0 {
+            vga_print("Programmes:\n");
+            crate::programs::for_each(|name, size| {
+                let _ = writeln!(VgaWriter, "  {:<12} {}", name, Size(size));
+            });
+            return None;
+        }
+        let program = match crate::programs::find(argv[0], &self.cwd) {
+            Ok(program) => program,
+            Err(FsError::NotFound) => {
+                let _ = writeln!(VgaWriter, "Programme introuvable: {}", argv[0]);
+                return None;
+            }
+            Err(error) => {
+                let _ = writeln!(VgaWriter, "{}: {}", argv[0], error);
+                return None;
+            }
+        };
+        match crate::exec::spawn(&program.name, &program.data, &argv[..argc], PROGRAM_ENV) {
+            Ok(pid) => Some((pid, program.name)),
+            Err(error) => {
+                let _ = writeln!(VgaWriter, "{}: {}", program.name, error);
+                None
+            }
+        }
+    }
+
+    /// Passe le clavier au processus `pid` jusqu'à sa fin ou son arrêt,
+    /// puis l'attend
+    ///
+    /// Bloque le thread du shell : les caractères tapés sont affichés
+    /// et déposés dans `keyboard::input()`, lue par l'appel système read.
+    /// Ctrl+C lui envoie SIGINT ; Ctrl+Z l'arrête (SIGTSTP) et en fait
+    /// un job.
+    fn wait_foreground(&mut self, pid: u64, name: String, scancodes: &mut ScancodeStream) {
+        let input = keyboard::input();
+        // Ce qui a été tapé pour un programme précédent est perdu
+        while input.try_recv().is_ok() {}
+        loop {
+            match crate::process::wait(Some(pid), true) {
+                Ok(None) => {}
+                Ok(Some(_)) | Err(_) => return,
+            }
+            if let Some(State::Stopped(_)) = crate::process::state(pid) {
+                let id = self.add_job(pid, name.clone());
+                let _ = writeln!(VgaWriter, "[{}] Arrete  {}", id, name);
+                return;
+            }
+            let Some(key) = scancodes.next_timeout(FOREGROUND_POLL_MS).and_then(|scancode| self.keys.feed(scancode)) else {
+                continue;
+            };
+            match key {
+                Key::Ctrl('c') => {
+                    vga_print("^C\n");
+                    crate::process::kill(pid, SIGINT);
+                    continue;
+                }
+                Key::Ctrl('z') => {
+                    vga_print("^Z\n");
+                    crate::process::kill(pid, SIGTSTP);
+                    continue;
+                }
+                _ => {}
+            }
+            let Some(c) = key.to_char() else {
+                continue;
+            };
+            if c == '\x08' {
+                vga_backspace();
+            } else if !c.is_ascii_control() || c == '\n' {
+                vga_print_char(c);
+            }
+            let mut bytes = [0u8; 4];
+            for &byte in c.encode_utf8(&mut bytes).as_bytes() {
+                // Programme qui ne lit pas : le surplus est perdu
+                let _ = input.try_send(byte);
+            }
+        }
+    }
+}
+
+impl Default for Shell {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Une ligne de `ls` : répertoires suivis de '/', liens de leur cible,
+/// fichiers de leur taille
+fn print_entry(name: &str, inode: &dyn Inode) {
+    let metadata = inode.metadata();
+    let _ = match metadata.kind {
+        FileType::Directory => writeln!(VgaWriter, "  {}/", name),
+        FileType::Symlink => writeln!(VgaWriter, "  {} -> {}", name, inode.readlink().unwrap_or_default()),
+        FileType::File => writeln!(VgaWriter, "  {:<20} {:>9}", name, Size(metadata.size)),
+    };
+}
+
+// ===================================================================
+// TESTS
+// ===================================================================
+
+#[test_case]
+fn test_shell_buffer_and_backspace() {
+    let mut shell = Shell::new();
+    for c in "echi".chars() {
+        shell.handle_char(c);
+    }
+    shell.handle_char('\x08');
+    shell.handle_char('o');
+    assert_eq!(shell.get_command(), "echo");
+}
+
+#[test_case]
+fn test_shell_buffer_cleared_after_enter() {
+    let mut shell = Shell::new();
+    for c in "echo bos\n".chars() {
+        shell.handle_char(c);
+    }
+    assert_eq!(shell.cmd_position, 0);
+    assert_eq!(shell.get_command(), "");
+}
+
+#[test_case]
+fn test_shell_runs_program_by_name() {
+    let mut shell = Shell::new();
+    for c in "hello\n".chars() {
+        shell.handle_char(c);
+    }
+    let (pid, _) = shell.foreground.expect("hello lance au premier plan");
+    assert!(crate::process::wait(Some(pid), false).unwrap().is_some());
+}
+
+#[test_case]
+fn test_shell_runs_program_by_path() {
+    let mut shell = Shell::new();
+    for c in "/bin/hello\n".chars() {
+        shell.handle_char(c);
+    }
+    let (pid, name) = shell.foreground.expect("/bin/hello lance au premier plan");
+    assert_eq!(name, "hello");
+    assert!(crate::process::wait(Some(pid), false).unwrap().is_some());
+}
+
+#[test_case]
+fn test_shell_background_job() {
+    let mut shell = Shell::new();
+    for c in "run hello &\n".chars() {
+        shell.handle_char(c);
+    }
+    assert!(shell.foreground.is_none());
+    assert_eq!(shell.jobs.len(), 1);
+    let pid = shell.jobs[0].pid;
+    while crate::process::state(pid) != Some(State::Zombie(bos_core::process::ExitStatus::Exited(0))) {
+        crate::thread::yield_now();
+    }
+    shell.notify_jobs();
+    assert!(shell.jobs.is_empty());
+    assert!(!crate::process::exists(pid));
+}
+
+#[test_case]
+fn test_shell_ctrl_c_clears_line() {
+    let mut shell = Shell::new();
+    for c in "echo".chars() {
+        shell.handle_char(c);
+    }
+    shell.handle_key(Key::Ctrl('c'));
+    assert_eq!(shell.get_command(), "");
+}
+
+#[test_case]
+fn test_shell_buffer_overflow_is_ignored() {
+    let mut shell = Shell::new();
+    for _ in 0..(CMD_BUFFER_SIZE * 2) {
+        shell.handle_char('x');
+    }
+    assert_eq!(shell.cmd_position, CMD_BUFFER_SIZE - 1);
+}
+
+#[test_case]
+fn test_shell_cd_and_relative_programs() {
+    let mut shell = Shell::new();
+    for c in "cd /etc/../bin\n".chars() {
+        shell.handle_char(c);
+    }
+    assert_eq!(shell.cwd, "/bin");
+    for c in "cd motd\ncd\n".chars() {
+        shell.handle_char(c);
+    }
+    assert_eq!(shell.cwd, "/");
+    for c in "cd bin\n./hello\n".chars() {
+        shell.handle_char(c);
+    }
+    let (pid, name) = shell.foreground.expect("./hello lance au premier plan");
+    assert_eq!(name, "hello");
+    assert!(crate::process::wait(Some(pid), false).unwrap().is_some());
+}
+
+#[test_case]
+fn test_shell_writes_files() {
+    let mut shell = Shell::new();
+    for c in "cd /tmp\necho bonjour > shell\necho le monde >> shell\n".chars() {
+        shell.handle_char(c);
+    }
+    assert_eq!(crate::vfs::read_file("/tmp/shell", "/").unwrap(), b"bonjour\nle monde\n");
+    for c in "write shell a b\ncp shell copie\nmkdir rep\nmv copie rep\ntouch vide\n".chars() {
+        shell.handle_char(c);
+    }
+    assert_eq!(crate::vfs::read_file("/tmp/rep/copie", "/").unwrap(), b"a b\n");
+    assert_eq!(crate::vfs::stat("/tmp/vide", "/").unwrap().size, 0);
+    // Un programme copié dans /tmp se lance par son chemin
+    for c in "cp /bin/hello .\n./hello\n".chars() {
+        shell.handle_char(c);
+    }
+    let (pid, name) = shell.foreground.take().expect("./hello lance au premier plan");
+    assert_eq!(name, "hello");
+    assert!(crate::process::wait(Some(pid), false).unwrap().is_some());
+    for c in "rm shell rep/copie rep vide hello\n".chars() {
+        shell.handle_char(c);
     }
+    assert!(crate::vfs::read_dir("/tmp", "/").unwrap().is_empty());
 }
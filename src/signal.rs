@@ -0,0 +1,147 @@
+/* =========================================================
+   REMISE DES SIGNAUX AUX PROGRAMMES
+
+   Les signaux d'un processus sont notés par `process::kill`
+   (kill, Ctrl+C, Ctrl+Z, alarmes) et `process::fault` (fautes du
+   programme). Ils lui sont remis quand il va revenir en ring 3 :
+   - à la fin d'un appel système (`deliver_syscall`)
+   - à la fin d'une trappe venue du ring 3 : faute ou tick du
+     timer (`deliver_trap`, voir interrupts.rs)
+
+   Selon l'action du signal (voir bos_core::signal), le processus
+   est terminé (avec un rapport pour les fautes), arrêté jusqu'à
+   SIGCONT, ou son handler est appelé :
+   1. le kernel copie une `SignalFrame` sous la pile du programme :
+      ses registres et le masque des signaux bloqués à rétablir
+   2. il remplace ses registres : RIP = handler, RSP = trame (dont
+      le premier mot est l'adresse du restorer), RDI = signal
+   3. le handler retourne vers le restorer, qui fait l'appel
+      système sigreturn avec l'adresse de la trame (`sigreturn`)
+   4. le kernel rétablit le masque et reprend le programme avec
+      tous ses registres (`usermode::resume`)
+========================================================= */
+
+use bos_core::process::ExitStatus;
+use bos_core::signal::{Delivery, Registers, SigSet, SignalFrame, SIGSEGV};
+use bos_core::syscall::SyscallError;
+
+use crate::interrupts::TrapFrame;
+use crate::process;
+use crate::syscall::SyscallFrame;
+use crate::usermode::{self, copy_from_user, copy_to_user};
+
+/// Bit DF de RFLAGS : la convention d'appel veut DF = 0 à l'entrée
+/// d'une fonction
+const DIRECTION_FLAG: u64 = 1 << 10;
+
+/// Remet les signaux en attente avant le retour d'une trappe en ring 3
+pub fn deliver_trap(frame: &mut TrapFrame) {
+    let mut registers = frame.registers();
+    if deliver(&mut registers) {
+        frame.set_registers(&registers);
+    }
+}
+
+/// Remet les signaux en attente avant le retour d'un appel système
+pub fn deliver_syscall(frame: &mut SyscallFrame) {
+    let mut registers = frame.registers();
+    if deliver(&mut registers) {
+        frame.set_registers(&registers);
+    }
+}
+
+/// Remet les signaux en attente du processus en cours, interrompu avec
+/// les registres `registers`
+///
+/// Retourne true si `registers` ont été changés pour appeler un
+/// handler (un seul par retour en ring 3 : les signaux suivants
+/// attendent le prochain). Ne retourne pas si le processus est tué.
+fn deliver(registers: &mut Registers) -> bool {
+    while let Some(delivery) = process::next_signal() {
+        match delivery {
+            Delivery::Terminate(signal) => process::terminate(ExitStatus::Killed(signal)),
+            Delivery::Core(signal) => process::terminate_with_report(signal, registers),
+            Delivery::Stop(signal) => process::stop(signal),
+            Delivery::Handler { signal, handler, restorer, blocked } => {
+                match setup_frame(registers, signal, handler, restorer, blocked) {
+                    Ok(()) => return true,
+                    // Pile du programme inutilisable : SIGSEGV, forcé à
+                    // son action par défaut s'il est en cours de handler
+                    Err(_) => process::fault(SIGSEGV, "pile invalide pour un signal", registers.rsp),
+                }
+            }
+        }
+    }
+    false
+}
+
+/// Copie la trame de signal sur la pile du programme et prépare ses
+/// registres pour appeler `handler`
+fn setup_frame(
+    registers: &mut Registers,
+    signal: u8,
+    handler: u64,
+    restorer: u64,
+    blocked: SigSet,
+) -> Result<(), SyscallError> {
+    let frame = SignalFrame { restorer, signal: signal as u64, blocked: blocked.0, registers: *registers };
+    let address = SignalFrame::address(registers.rsp);
+    copy_to_user(address, &frame.to_bytes())?;
+    registers.rip = handler;
+    registers.rsp = address;
+    registers.rdi = signal as u64;
+    registers.rflags &= !DIRECTION_FLAG;
+    Ok(())
+}
+
+/// Fin d'un handler : rétablit le masque et les registres sauvegardés
+/// dans la trame à `address`, et reprend le programme
+///
+/// Ne retourne qu'en cas d'erreur (trame illisible ou invalide) : le
+/// programme reçoit alors SIGSEGV.
+pub fn sigreturn(address: u64) -> SyscallError {
+    let mut bytes = [0u8; SignalFrame::SIZE];
+    if let Err(error) = copy_from_user(&mut bytes, address) {
+        process::fault(SIGSEGV, "trame de signal illisible", address);
+        return error;
+    }
+    let frame = SignalFrame::from_bytes(&bytes);
+    let Some(mut trap) = usermode::user_trap_frame(&frame.registers) else {
+        process::fault(SIGSEGV, "trame de signal invalide", address);
+        return SyscallError::BadAddress;
+    };
+    process::with_current_signals(|signals| signals.restore_blocked(SigSet(frame.blocked)));
+    // Des signaux débloqués par le masque rétabli sont peut-être en attente
+    deliver_trap(&mut trap);
+    unsafe { usermode::resume(&trap) }
+}
+
+// ===================================================================
+// TESTS
+// ===================================================================
+
+#[test_case]
+fn test_ignored_signal_does_not_kill() {
+    use bos_core::signal::{SIGKILL, SIGTERM};
+    use bos_core::syscall::SYS_SIGACTION;
+
+    use crate::process::UserCode;
+
+    let code = UserCode::new()
+        .push(0) // restorer
+        .push(0) // mask
+        .push(1) // handler : SIG_IGN
+        .mov_rsi_rsp()
+        .mov_edi(u32::from(SIGTERM))
+        .zero_edx()
+        .syscall(SYS_SIGACTION)
+        .spin();
+    let pid = code.start();
+    // Laisser le programme installer son action
+    crate::thread::sleep_ms(50);
+    assert!(process::kill(pid, SIGTERM));
+    crate::thread::sleep_ms(20);
+    assert_eq!(process::state(pid), Some(process::State::Running));
+    assert!(process::kill(pid, SIGKILL));
+    assert_eq!(process::wait(Some(pid), false), Ok(Some((pid, ExitStatus::Killed(SIGKILL)))));
+}
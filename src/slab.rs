@@ -0,0 +1,193 @@
+/* =========================================================
+   CACHES SLAB DU KERNEL
+
+   Les objets du kernel de taille fixe (tâches, timers, fichiers
+   ouverts...) sont alloués dans des caches slab (voir
+   bos-core/src/slab.rs). Chaque slab est un frame de 4 Kio pris
+   directement à l'allocateur physique et lu via la projection de
+   la mémoire physique : aucune table de pages à modifier.
+
+   Les caches "kmalloc-N" servent les petites allocations de
+   l'allocateur global (heap.rs) : une demande de 40 octets va
+   dans kmalloc-64. Un sous-système peut aussi déclarer son
+   propre cache :
+
+     static TASKS: KmemCache = KmemCache::new("task", size_of::<Task>());
+     slab::register(&TASKS);
+
+   Avec BOS_SLAB_POISON=1 à la compilation, les objets libérés
+   sont empoisonnés pour détecter les use-after-free.
+========================================================= */
+
+use core::alloc::Layout;
+use core::ptr;
+
+use bos_core::slab::{PageSource, SlabCache, SlabError, SlabStats};
+
+use crate::sync::Spinlock;
+use crate::{frame, memory};
+
+/// Empoisonnement des objets libérés (compilation avec BOS_SLAB_POISON=1)
+pub const SLAB_POISON: bool = option_env!("BOS_SLAB_POISON").is_some();
+
+/// Nombre maximum de caches nommés
+const MAX_CACHES: usize = 32;
+
+/// Pages des slabs : des frames de l'allocateur physique
+struct FramePages;
+
+impl PageSource for FramePages {
+    fn allocate_page(&mut self) -> Option<usize> {
+        frame::allocate_frame().map(|phys| memory::phys_to_virt(phys) as usize)
+    }
+
+    fn free_page(&mut self, page: usize) {
+        let phys = page as u64 - memory::physical_memory_offset();
+        if let Err(error) = frame::free_frame(phys) {
+            panic!("slab: frame {:#x} impossible a liberer ({:?})", phys, error);
+        }
+    }
+}
+
+/// Un cache slab partagé par tout le kernel
+pub struct KmemCache {
+    cache: Spinlock<SlabCache>,
+}
+
+impl KmemCache {
+    /// Crée un cache d'objets de `object_size` octets (1024 au plus
+    /// pour en loger plusieurs par slab)
+    pub const fn new(name: &'static str, object_size: usize) -> KmemCache {
+        KmemCache { cache: Spinlock::new(SlabCache::new(name, object_size, SLAB_POISON)) }
+    }
+
+    fn with_cache<R>(&self, f: impl FnOnce(&mut SlabCache) -> R) -> R {
+        f(&mut self.cache.lock())
+    }
+
+    /// Nom du cache
+    pub fn name(&self) -> &'static str {
+        self.with_cache(|cache| cache.name())
+    }
+
+    /// Taille réelle d'un objet
+    pub fn object_size(&self) -> usize {
+        self.with_cache(|cache| cache.object_size())
+    }
+
+    /// Nombre d'objets par slab
+    pub fn objects_per_slab(&self) -> usize {
+        self.with_cache(|cache| cache.objects_per_slab())
+    }
+
+    /// Statistiques du cache
+    pub fn stats(&self) -> SlabStats {
+        self.with_cache(|cache| cache.stats())
+    }
+
+    /// Alloue un objet, retourne un pointeur nul s'il n'y a plus de mémoire
+    ///
+    /// Un use-after-free détecté par l'empoisonnement provoque un panic.
+    pub fn alloc(&self) -> *mut u8 {
+        match self.with_cache(|cache| cache.allocate(&mut FramePages)) {
+            Ok(object) => object,
+            Err(SlabError::OutOfMemory) => ptr::null_mut(),
+            Err(error) => panic!("slab {}: {}", self.name(), error),
+        }
+    }
+
+    /// Libère un objet alloué par ce cache
+    ///
+    /// Une libération invalide (double libération, objet d'un autre
+    /// cache) provoque un panic.
+    ///
+    /// # Safety
+    /// `object` ne doit plus être utilisé après l'appel.
+    pub unsafe fn free(&self, object: *mut u8) {
+        if let Err(error) = self.with_cache(|cache| cache.free(object, &mut FramePages)) {
+            panic!("slab {}: {}", self.name(), error);
+        }
+    }
+}
+
+/// Caches des petites allocations de l'allocateur global
+pub static KMALLOC_CACHES: [KmemCache; 7] = [
+    KmemCache::new("kmalloc-16", 16),
+    KmemCache::new("kmalloc-32", 32),
+    KmemCache::new("kmalloc-64", 64),
+    KmemCache::new("kmalloc-128", 128),
+    KmemCache::new("kmalloc-256", 256),
+    KmemCache::new("kmalloc-512", 512),
+    KmemCache::new("kmalloc-1024", 1024),
+];
+
+/// Cache kmalloc adapté à une allocation (None si elle est trop grande)
+///
+/// Les tailles sont des puissances de 2 : un objet de taille N est
+/// aligné sur N, ce qui couvre tout alignement inférieur ou égal.
+pub fn kmalloc_cache(layout: Layout) -> Option<&'static KmemCache> {
+    let needed = layout.size().max(layout.align());
+    KMALLOC_CACHES.iter().find(|cache| cache.object_size() >= needed)
+}
+
+// Caches nommés déclarés par les sous-systèmes
+static CACHES: Spinlock<[Option<&'static KmemCache>; MAX_CACHES]> = Spinlock::new([None; MAX_CACHES]);
+
+/// Ajoute un cache à la liste affichée par `slabinfo`
+pub fn register(cache: &'static KmemCache) {
+    let mut caches = CACHES.lock();
+    if caches.iter().flatten().any(|known| ptr::eq(*known, cache)) {
+        return;
+    }
+    match caches.iter_mut().find(|slot| slot.is_none()) {
+        Some(slot) => *slot = Some(cache),
+        None => {
+            drop(caches);
+            warn!("slab: trop de caches, {} non enregistre", cache.name());
+        }
+    }
+}
+
+/// Appelle `f` pour chaque cache : kmalloc puis caches nommés
+pub fn for_each_cache(mut f: impl FnMut(&'static KmemCache)) {
+    for cache in KMALLOC_CACHES.iter() {
+        f(cache);
+    }
+    let caches = *CACHES.lock();
+    for cache in caches.iter().flatten() {
+        f(cache);
+    }
+}
+
+// ===================================================================
+// TESTS
+// ===================================================================
+
+#[test_case]
+fn test_named_cache() {
+    static TEST_CACHE: KmemCache = KmemCache::new("test", 48);
+    register(&TEST_CACHE);
+    register(&TEST_CACHE);
+    let mut found = 0;
+    for_each_cache(|cache| found += ptr::eq(cache, &TEST_CACHE) as usize);
+    assert_eq!(found, 1);
+
+    let a = TEST_CACHE.alloc();
+    let b = TEST_CACHE.alloc();
+    assert!(!a.is_null() && !b.is_null() && a != b);
+    assert_eq!(TEST_CACHE.stats().active_objects, 2);
+    unsafe {
+        TEST_CACHE.free(a);
+        TEST_CACHE.free(b);
+    }
+    assert_eq!(TEST_CACHE.stats().active_objects, 0);
+}
+
+#[test_case]
+fn test_kmalloc_size_classes() {
+    let cache = kmalloc_cache(Layout::from_size_align(40, 8).unwrap()).unwrap();
+    assert_eq!(cache.name(), "kmalloc-64");
+    let cache = kmalloc_cache(Layout::from_size_align(8, 256).unwrap()).unwrap();
+    assert_eq!(cache.name(), "kmalloc-256");
+    assert!(kmalloc_cache(Layout::from_size_align(1025, 8).unwrap()).is_none());
+}
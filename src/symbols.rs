@@ -0,0 +1,110 @@
+/* =========================================================
+   TABLE DES SYMBOLES DU KERNEL
+
+   Pour afficher "bos::shell::Shell::execute+0x42" plutôt qu'une
+   adresse brute, il faut la table des symboles du kernel.
+
+   Le bootloader charge le fichier ELF complet du kernel à l'adresse
+   physique 0x400000, puis copie ses segments aux bonnes adresses.
+   `bootimage` ne retire que les informations de debug : les sections
+   .symtab et .strtab sont toujours là. On relit donc le fichier ELF
+   d'origine via la projection de la mémoire physique (memory.rs).
+========================================================= */
+
+use bos_core::demangle::Demangle;
+use bos_core::elf::{ElfFile, Header, SymbolTable, HEADER_SIZE};
+
+use crate::memory::phys_to_virt;
+use crate::sync::Once;
+
+/// Adresse physique où le bootloader a chargé le fichier ELF du kernel
+const KERNEL_ELF_PHYS: u64 = 0x40_0000;
+
+// Fichier ELF du kernel (vide si introuvable ou invalide)
+static KERNEL_ELF: Once<ElfFile<'static>> = Once::new();
+
+/// Retrouve le fichier ELF du kernel en mémoire
+///
+/// À appeler une fois au démarrage. Sans table des symboles, les
+/// backtraces affichent seulement les adresses.
+pub fn init_symbols() {
+    let start = phys_to_virt(KERNEL_ELF_PHYS) as *const u8;
+    // Lire d'abord l'en-tête seul pour connaître la taille du fichier
+    // (la table des sections est à la fin)
+    let header = unsafe { core::slice::from_raw_parts(start, HEADER_SIZE) };
+    let size = match Header::parse(header) {
+        Ok(header) => header.section_table_end() as usize,
+        Err(_) => return,
+    };
+    let data = unsafe { core::slice::from_raw_parts(start, size) };
+    if let Ok(elf) = ElfFile::parse(data) {
+        KERNEL_ELF.call_once(|| elf);
+    }
+}
+
+/// Le fichier ELF du kernel, si `init_symbols` l'a trouvé
+pub fn kernel_elf() -> Option<ElfFile<'static>> {
+    KERNEL_ELF.get().copied()
+}
+
+/// Table des symboles du kernel
+pub fn symbol_table() -> Option<SymbolTable<'static>> {
+    kernel_elf()?.symbol_table()
+}
+
+/// Indique si `addr` est dans le code du kernel
+///
+/// Sans fichier ELF, on accepte toute adresse non nulle.
+pub fn is_code_address(addr: u64) -> bool {
+    match kernel_elf() {
+        Some(elf) => elf.is_code_address(addr),
+        None => addr != 0,
+    }
+}
+
+/// Trouve la fonction qui contient `addr`
+///
+/// Retourne le nom démanglé (affichable avec `{}`) et le décalage depuis
+/// le début de la fonction.
+pub fn resolve(addr: u64) -> Option<(Demangle<'static>, u64)> {
+    let (symbol, offset) = symbol_table()?.lookup(addr)?;
+    Some((Demangle(symbol.name), offset))
+}
+
+// ===================================================================
+// TESTS
+// ===================================================================
+
+#[test_case]
+fn test_kernel_elf_found() {
+    let table = symbol_table().expect("table des symboles introuvable");
+    assert!(!table.is_empty());
+}
+
+#[test_case]
+fn test_resolve_own_function() {
+    use core::fmt::Write;
+
+    // Écrit le nom résolu dans un petit tampon pour le comparer
+    struct Buffer {
+        bytes: [u8; 128],
+        len: usize,
+    }
+    impl Write for Buffer {
+        fn write_str(&mut self, s: &str) -> core::fmt::Result {
+            let end = (self.len + s.len()).min(self.bytes.len());
+            self.bytes[self.len..end].copy_from_slice(&s.as_bytes()[..end - self.len]);
+            self.len = end;
+            Ok(())
+        }
+    }
+
+    let addr = init_symbols as *const () as u64;
+    assert!(is_code_address(addr));
+    let (name, offset) = resolve(addr).expect("symbole introuvable");
+    assert_eq!(offset, 0);
+    let mut buffer = Buffer { bytes: [0; 128], len: 0 };
+    write!(buffer, "{}", name).unwrap();
+    let name = core::str::from_utf8(&buffer.bytes[..buffer.len]).unwrap();
+    assert_eq!(name, "bos::symbols::init_symbols");
+}
@@ -0,0 +1,640 @@
+/* =========================================================
+   APPELS SYSTÈME (SYSCALL / SYSRET)
+
+   Un programme utilisateur appelle le kernel avec l'instruction
+   SYSCALL (convention dans bos_core::syscall). Le CPU passe en
+   ring 0 et saute à `syscall_entry`, avec :
+   - RCX = RIP de retour, R11 = RFLAGS du programme
+   - les interruptions masquées (registre FMASK)
+   - RSP inchangé : c'est encore la pile du programme !
+
+   `syscall_entry` charge donc la pile du kernel du thread en cours
+   (usermode::KERNEL_STACK), y sauvegarde tous les registres du
+   programme (`SyscallFrame`) puis appelle `syscall_dispatch`, qui
+   choisit le handler dans la table des appels système. Les
+   signaux en attente sont remis (voir signal.rs), puis SYSRET
+   repart en ring 3 avec le résultat dans RAX.
+
+   Les handlers s'exécutent interruptions activées : un appel long
+   (sleep, read) est préempté comme n'importe quel thread.
+
+   Registres MSR utilisés :
+   - EFER.SCE : active SYSCALL/SYSRET
+   - STAR : sélecteurs du kernel (SYSCALL) et base des sélecteurs
+     utilisateur (SYSRET : données à base + 8, code à base + 16)
+   - LSTAR : adresse de `syscall_entry`
+   - FMASK : bits de RFLAGS effacés à l'entrée (IF, TF, DF, AC)
+========================================================= */
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::arch::{asm, global_asm};
+use core::mem::size_of;
+use core::sync::atomic::AtomicU64;
+
+use bos_core::exec::ImageError;
+use bos_core::process::{WAIT_ANY, WNOHANG};
+use bos_core::signal::{Action, Registers, SigAction, SigSet, MAX_SIGNAL};
+use bos_core::syscall::{self, SyscallError, SYSCALL_COUNT};
+use bos_core::vfs::{OpenFlags, MAX_PATH};
+
+use crate::channel::RecvTimeoutError;
+use crate::exec::ExecError;
+use crate::gdt::{KERNEL_CODE_SELECTOR, USER_DATA_SELECTOR};
+use crate::process::WaitError;
+use crate::thread::SpawnError;
+use crate::usermode::{self, copy_from_user, copy_to_user, is_user_range};
+use crate::vfs::{self, Descriptor, OpenFile};
+use crate::vga::VgaWriter;
+use crate::{disable_interrupts, enable_interrupts, keyboard, pit, process, programs, signal, thread};
+
+// Registres MSR
+const IA32_EFER: u32 = 0xC000_0080;
+const IA32_STAR: u32 = 0xC000_0081;
+const IA32_LSTAR: u32 = 0xC000_0082;
+const IA32_FMASK: u32 = 0xC000_0084;
+
+/// Bit SCE (System Call Extensions) de EFER
+const EFER_SCE: u64 = 1 << 0;
+
+/// RFLAGS effacés à l'entrée : TF (8), IF (9), DF (10), AC (18)
+const SYSCALL_FLAG_MASK: u64 = (1 << 8) | (1 << 9) | (1 << 10) | (1 << 18);
+
+/// Taille des morceaux copiés depuis ou vers le programme
+const CHUNK_SIZE: usize = 256;
+
+/// Nombre maximal d'arguments passés à spawn (nom compris)
+const MAX_SPAWN_ARGS: usize = 32;
+
+/// Longueur maximale d'un argument de spawn
+const MAX_ARG_LEN: u64 = 256;
+
+/// Environnement des programmes lancés par spawn
+const SPAWN_ENV: &[&str] = &["TERM=bos"];
+
+/// Délai entre deux vérifications des signaux en attente pendant read
+const READ_POLL_MS: u64 = 10;
+
+/// Registres du programme, sauvegardés sur la pile du kernel par
+/// `syscall_entry` (dans l'ordre inverse des PUSH)
+#[repr(C)]
+#[derive(Debug, Default)]
+pub struct SyscallFrame {
+    pub r15: u64,
+    pub r14: u64,
+    pub r13: u64,
+    pub r12: u64,
+    pub rbp: u64,
+    pub rbx: u64,
+    pub r9: u64,
+    pub r8: u64,
+    pub r10: u64,
+    pub rdx: u64,
+    pub rsi: u64,
+    pub rdi: u64,
+    /// Numéro de l'appel système, puis résultat
+    pub rax: u64,
+    /// RFLAGS du programme (R11)
+    pub rflags: u64,
+    /// RIP de retour (RCX)
+    pub rip: u64,
+    pub rsp: u64,
+}
+
+impl SyscallFrame {
+    /// Arguments 1 à 6 (RDI, RSI, RDX, R10, R8, R9)
+    pub fn args(&self) -> [u64; 6] {
+        [self.rdi, self.rsi, self.rdx, self.r10, self.r8, self.r9]
+    }
+
+    /// Registres du programme (RCX et R11 valent RIP et RFLAGS, comme
+    /// après SYSCALL)
+    pub fn registers(&self) -> Registers {
+        Registers {
+            rax: self.rax,
+            rbx: self.rbx,
+            rcx: self.rip,
+            rdx: self.rdx,
+            rsi: self.rsi,
+            rdi: self.rdi,
+            rbp: self.rbp,
+            rsp: self.rsp,
+            r8: self.r8,
+            r9: self.r9,
+            r10: self.r10,
+            r11: self.rflags,
+            r12: self.r12,
+            r13: self.r13,
+            r14: self.r14,
+            r15: self.r15,
+            rip: self.rip,
+            rflags: self.rflags,
+        }
+    }
+
+    /// Remplace les registres du programme (RCX et R11 sont ignorés :
+    /// SYSRET les remplace par RIP et RFLAGS)
+    pub fn set_registers(&mut self, registers: &Registers) {
+        self.rax = registers.rax;
+        self.rbx = registers.rbx;
+        self.rdx = registers.rdx;
+        self.rsi = registers.rsi;
+        self.rdi = registers.rdi;
+        self.rbp = registers.rbp;
+        self.rsp = registers.rsp;
+        self.r8 = registers.r8;
+        self.r9 = registers.r9;
+        self.r10 = registers.r10;
+        self.r12 = registers.r12;
+        self.r13 = registers.r13;
+        self.r14 = registers.r14;
+        self.r15 = registers.r15;
+        self.rip = registers.rip;
+        self.rflags = registers.rflags;
+    }
+}
+
+// Point d'entrée de SYSCALL
+//
+// Aucun registre n'est libre pour garder RSP le temps de charger la
+// pile du kernel : il passe par un emplacement fixe (USER_RSP), sans
+// risque puisque les interruptions sont masquées et qu'il n'y a qu'un
+// CPU.
+global_asm!(
+    ".global syscall_entry",
+    "syscall_entry:",
+    "    mov [rip + {user_rsp}], rsp",
+    "    mov rsp, [rip + {kernel_stack}]",
+    "    push qword ptr [rip + {user_rsp}]",
+    "    push rcx",
+    "    push r11",
+    "    push rax",
+    "    push rdi",
+    "    push rsi",
+    "    push rdx",
+    "    push r10",
+    "    push r8",
+    "    push r9",
+    "    push rbx",
+    "    push rbp",
+    "    push r12",
+    "    push r13",
+    "    push r14",
+    "    push r15",
+    // 16 registres empilés : RSP reste aligné sur 16 octets
+    "    mov rdi, rsp",
+    "    call {dispatch}",
+    "    pop r15",
+    "    pop r14",
+    "    pop r13",
+    "    pop r12",
+    "    pop rbp",
+    "    pop rbx",
+    "    pop r9",
+    "    pop r8",
+    "    pop r10",
+    "    pop rdx",
+    "    pop rsi",
+    "    pop rdi",
+    "    pop rax",
+    "    pop r11",
+    "    pop rcx",
+    "    pop rsp",
+    "    sysretq",
+    user_rsp = sym USER_RSP,
+    kernel_stack = sym usermode::KERNEL_STACK,
+    dispatch = sym syscall_dispatch,
+);
+
+extern "C" {
+    fn syscall_entry();
+}
+
+// RSP du programme pendant les premières instructions de `syscall_entry`
+static USER_RSP: AtomicU64 = AtomicU64::new(0);
+
+/// Lit un registre MSR
+unsafe fn rdmsr(msr: u32) -> u64 {
+    let (low, high): (u32, u32);
+    asm!("rdmsr", in("ecx") msr, out("eax") low, out("edx") high, options(nomem, nostack, preserves_flags));
+    ((high as u64) << 32) | low as u64
+}
+
+/// Écrit un registre MSR
+unsafe fn wrmsr(msr: u32, value: u64) {
+    let (low, high) = (value as u32, (value >> 32) as u32);
+    asm!("wrmsr", in("ecx") msr, in("eax") low, in("edx") high, options(nostack, preserves_flags));
+}
+
+/// Active SYSCALL/SYSRET et installe le point d'entrée
+///
+/// À appeler après `gdt::init_gdt` : STAR désigne les segments de notre GDT.
+pub fn init() {
+    // SYSRET ajoute 8 (données) et 16 (code) à la base utilisateur
+    let user_base = (USER_DATA_SELECTOR & !3) as u64 - 8;
+    let star = (user_base << 48) | ((KERNEL_CODE_SELECTOR as u64) << 32);
+    unsafe {
+        wrmsr(IA32_EFER, rdmsr(IA32_EFER) | EFER_SCE);
+        wrmsr(IA32_STAR, star);
+        wrmsr(IA32_LSTAR, syscall_entry as *const () as u64);
+        wrmsr(IA32_FMASK, SYSCALL_FLAG_MASK);
+    }
+}
+
+/* =========================================================
+   TABLE DES APPELS SYSTÈME
+========================================================= */
+
+/// Handler d'un appel système : reçoit les 6 arguments
+type SyscallHandler = fn([u64; 6]) -> Result<u64, SyscallError>;
+
+/// Table des appels système, indexée par leur numéro
+static SYSCALLS: [SyscallHandler; SYSCALL_COUNT] = [
+    sys_write,       // SYS_WRITE
+    sys_read,        // SYS_READ
+    sys_exit,        // SYS_EXIT
+    sys_yield,       // SYS_YIELD
+    sys_sleep,       // SYS_SLEEP
+    sys_getpid,      // SYS_GETPID
+    sys_brk,         // SYS_BRK
+    sys_spawn,       // SYS_SPAWN
+    sys_waitpid,     // SYS_WAITPID
+    sys_kill,        // SYS_KILL
+    sys_getppid,     // SYS_GETPPID
+    sys_sigaction,   // SYS_SIGACTION
+    sys_sigprocmask, // SYS_SIGPROCMASK
+    sys_sigreturn,   // SYS_SIGRETURN
+    sys_alarm,       // SYS_ALARM
+    sys_open,        // SYS_OPEN
+    sys_close,       // SYS_CLOSE
+    sys_lseek,       // SYS_LSEEK
+];
+
+/// Appelée par `syscall_entry`, interruptions masquées
+extern "C" fn syscall_dispatch(frame: &mut SyscallFrame) {
+    enable_interrupts();
+    let number = frame.rax;
+    let result = match SYSCALLS.get(number as usize) {
+        Some(handler) => handler(frame.args()),
+        None => Err(SyscallError::NoSuchSyscall),
+    };
+    if let Err(error) = result {
+        trace!("{} : {}", syscall::name(number).unwrap_or("?"), error);
+    }
+    frame.rax = syscall::encode_result(result);
+    // Signaux en attente : le programme peut être tué ou repartir
+    // dans un handler
+    signal::deliver_syscall(frame);
+    // SYSRET restaure RFLAGS : les interruptions reviennent en ring 3
+    disable_interrupts();
+}
+
+/// Écrit sur la console ou dans un fichier ouvert
+fn sys_write([fd, buffer, len, ..]: [u64; 6]) -> Result<u64, SyscallError> {
+    use core::fmt::Write;
+
+    let descriptor = descriptor(fd)?;
+    let mut chunk = [0u8; CHUNK_SIZE];
+    let mut done = 0;
+    while done < len {
+        let size = (len - done).min(CHUNK_SIZE as u64) as usize;
+        copy_from_user(&mut chunk[..size], buffer.wrapping_add(done))?;
+        match &descriptor {
+            Descriptor::Console => {
+                // Un caractère UTF-8 coupé entre deux morceaux est ignoré
+                for part in chunk[..size].utf8_chunks() {
+                    let _ = VgaWriter.write_str(part.valid());
+                }
+            }
+            Descriptor::File(file) => match file.write(&chunk[..size]) {
+                Ok(written) if written < size => return Ok(done + written as u64),
+                Ok(_) => {}
+                // Ce qui est déjà écrit compte
+                Err(_) if done > 0 => return Ok(done),
+                Err(error) => return Err(error.into()),
+            },
+        }
+        done += size as u64;
+    }
+    Ok(len)
+}
+
+/// Lit le clavier (console) ou un fichier ouvert
+fn sys_read([fd, buffer, len, ..]: [u64; 6]) -> Result<u64, SyscallError> {
+    match descriptor(fd)? {
+        Descriptor::Console => read_console(buffer, len),
+        Descriptor::File(file) => read_file(&file, buffer, len),
+    }
+}
+
+/// Lit les caractères tapés au clavier
+fn read_console(buffer: u64, len: u64) -> Result<u64, SyscallError> {
+    let mut chunk = [0u8; CHUNK_SIZE];
+    let size = len.min(CHUNK_SIZE as u64) as usize;
+    if size == 0 {
+        return Ok(0);
+    }
+    // Attendre le premier caractère, puis prendre ceux déjà là. Un
+    // signal pendant l'attente (Ctrl+C) interrompt la lecture
+    let input = keyboard::input();
+    let first = loop {
+        match input.recv_timeout(READ_POLL_MS) {
+            Ok(byte) => break byte,
+            Err(RecvTimeoutError::Timeout) if !process::signal_pending() => {}
+            Err(RecvTimeoutError::Timeout) => return Err(SyscallError::Interrupted),
+            Err(RecvTimeoutError::Closed) => return Ok(0),
+        }
+    };
+    chunk[0] = first;
+    let mut count = 1;
+    while count < size {
+        match input.try_recv() {
+            Ok(byte) => chunk[count] = byte,
+            Err(_) => break,
+        }
+        count += 1;
+    }
+    copy_to_user(buffer, &chunk[..count])?;
+    Ok(count as u64)
+}
+
+/// Lit un fichier ouvert jusqu'à `len` octets ou sa fin
+fn read_file(file: &OpenFile, buffer: u64, len: u64) -> Result<u64, SyscallError> {
+    let mut chunk = [0u8; CHUNK_SIZE];
+    let mut done = 0;
+    while done < len {
+        let size = (len - done).min(CHUNK_SIZE as u64) as usize;
+        let count = match file.read(&mut chunk[..size]) {
+            Ok(count) => count,
+            Err(_) if done > 0 => break,
+            Err(error) => return Err(error.into()),
+        };
+        copy_to_user(buffer.wrapping_add(done), &chunk[..count])?;
+        done += count as u64;
+        if count < size {
+            break;
+        }
+    }
+    Ok(done)
+}
+
+/// Termine le processus
+fn sys_exit([code, ..]: [u64; 6]) -> Result<u64, SyscallError> {
+    process::exit(code as i32);
+}
+
+/// Cède le CPU
+fn sys_yield(_: [u64; 6]) -> Result<u64, SyscallError> {
+    thread::yield_now();
+    Ok(0)
+}
+
+/// Dort `ms` millisecondes, sauf si un signal arrive
+fn sys_sleep([ms, ..]: [u64; 6]) -> Result<u64, SyscallError> {
+    let deadline = pit::uptime_ms().saturating_add(ms);
+    // Un signal réveille le thread (voir process::kill)
+    loop {
+        let now = pit::uptime_ms();
+        if now >= deadline {
+            return Ok(0);
+        }
+        if process::signal_pending() {
+            return Err(SyscallError::Interrupted);
+        }
+        thread::sleep_ms(deadline - now);
+    }
+}
+
+/// Identifiant du processus
+fn sys_getpid(_: [u64; 6]) -> Result<u64, SyscallError> {
+    process::current_pid().ok_or(SyscallError::InvalidArgument)
+}
+
+/// Déplace la fin du tas du processus (`brk(0)` la donne seulement)
+fn sys_brk([brk, ..]: [u64; 6]) -> Result<u64, SyscallError> {
+    process::with_current_space(|space| match brk {
+        0 => space.brk(),
+        _ => space.set_brk(brk),
+    })
+    .ok_or(SyscallError::InvalidArgument)
+}
+
+/// Lance le programme `name` dans un processus enfant
+fn sys_spawn([name, name_len, argv, argc, ..]: [u64; 6]) -> Result<u64, SyscallError> {
+    let name = user_string(name, name_len, MAX_ARG_LEN)?;
+    let program = programs::find(&name, "/")?;
+    if argc as usize > MAX_SPAWN_ARGS {
+        return Err(SyscallError::InvalidArgument);
+    }
+    let mut args = Vec::with_capacity(argc as usize);
+    for i in 0..argc {
+        let mut pair = [0u8; 16];
+        copy_from_user(&mut pair, argv.wrapping_add(16 * i))?;
+        let address = u64::from_le_bytes(pair[..8].try_into().unwrap());
+        let len = u64::from_le_bytes(pair[8..].try_into().unwrap());
+        args.push(user_string(address, len, MAX_ARG_LEN)?);
+    }
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+    crate::exec::spawn(&program.name, &program.data, &args, SPAWN_ENV).map_err(exec_error)
+}
+
+/// Attend la fin d'un processus enfant
+fn sys_waitpid([pid, status, options, ..]: [u64; 6]) -> Result<u64, SyscallError> {
+    if options & !WNOHANG != 0 {
+        return Err(SyscallError::InvalidArgument);
+    }
+    let pid = if pid == WAIT_ANY { None } else { Some(pid) };
+    match process::wait(pid, options & WNOHANG != 0) {
+        Ok(Some((child, exit_status))) => {
+            if status != 0 {
+                copy_to_user(status, &exit_status.encode().to_le_bytes())?;
+            }
+            Ok(child)
+        }
+        Ok(None) => Ok(0),
+        Err(WaitError::NoChild) => Err(SyscallError::NoChild),
+        Err(WaitError::Interrupted) => Err(SyscallError::Interrupted),
+    }
+}
+
+/// Envoie un signal à un processus
+fn sys_kill([pid, signal, ..]: [u64; 6]) -> Result<u64, SyscallError> {
+    if signal > MAX_SIGNAL as u64 {
+        return Err(SyscallError::InvalidArgument);
+    }
+    let found = match signal {
+        0 => process::exists(pid),
+        _ => process::kill(pid, signal as u8),
+    };
+    if found {
+        Ok(0)
+    } else {
+        Err(SyscallError::NoSuchProcess)
+    }
+}
+
+/// Identifiant du processus parent (0 : le kernel)
+fn sys_getppid(_: [u64; 6]) -> Result<u64, SyscallError> {
+    Ok(process::parent_pid().unwrap_or(0))
+}
+
+/// Change l'action d'un signal
+fn sys_sigaction([signal, action, old, ..]: [u64; 6]) -> Result<u64, SyscallError> {
+    if signal == 0 || signal > MAX_SIGNAL as u64 {
+        return Err(SyscallError::InvalidArgument);
+    }
+    let new = if action != 0 {
+        let raw = read_sigaction(action)?;
+        let action = Action::from_raw(raw).ok_or(SyscallError::InvalidArgument)?;
+        // SYSRET vers une adresse hors de la zone utilisateur fauterait
+        // dans le kernel
+        if let Action::Handler { handler, restorer, .. } = action {
+            if !is_user_range(handler, 1) || !is_user_range(restorer, 1) {
+                return Err(SyscallError::BadAddress);
+            }
+        }
+        Some(action)
+    } else {
+        None
+    };
+    let previous = process::with_current_signals(|signals| match new {
+        Some(action) => signals.set_action(signal as u8, action),
+        None => Ok(signals.action(signal as u8)),
+    })
+    .ok_or(SyscallError::InvalidArgument)?
+    .map_err(|_| SyscallError::InvalidArgument)?;
+    if old != 0 {
+        write_sigaction(old, previous.to_raw())?;
+    }
+    Ok(0)
+}
+
+/// Change le masque des signaux bloqués
+fn sys_sigprocmask([how, set, old, ..]: [u64; 6]) -> Result<u64, SyscallError> {
+    let new = if set != 0 {
+        let mut bytes = [0u8; 8];
+        copy_from_user(&mut bytes, set)?;
+        Some(SigSet(u64::from_le_bytes(bytes)))
+    } else {
+        None
+    };
+    let previous = process::with_current_signals(|signals| match new {
+        Some(set) => signals.set_blocked(how, set),
+        None => Ok(signals.blocked()),
+    })
+    .ok_or(SyscallError::InvalidArgument)?
+    .map_err(|_| SyscallError::InvalidArgument)?;
+    if old != 0 {
+        copy_to_user(old, &previous.0.to_le_bytes())?;
+    }
+    Ok(0)
+}
+
+/// Fin d'un handler de signal : ne revient que sur une trame invalide
+fn sys_sigreturn([frame, ..]: [u64; 6]) -> Result<u64, SyscallError> {
+    Err(signal::sigreturn(frame))
+}
+
+/// Programme l'envoi de SIGALRM
+fn sys_alarm([ms, ..]: [u64; 6]) -> Result<u64, SyscallError> {
+    Ok(process::set_alarm(ms))
+}
+
+/// Ouvre un fichier (chemin relatif à la racine), retourne son
+/// descripteur
+fn sys_open([path, path_len, flags, ..]: [u64; 6]) -> Result<u64, SyscallError> {
+    if path_len > MAX_PATH as u64 {
+        return Err(SyscallError::NameTooLong);
+    }
+    let path = user_string(path, path_len, MAX_PATH as u64)?;
+    let file = vfs::open(&path, "/", OpenFlags::from_raw(flags)?)?;
+    let fd = process::with_current_files(|files| files.insert(Descriptor::File(file)));
+    Ok(fd.ok_or(SyscallError::BadFileDescriptor)?? as u64)
+}
+
+/// Ferme un descripteur
+fn sys_close([fd, ..]: [u64; 6]) -> Result<u64, SyscallError> {
+    let fd = usize::try_from(fd).map_err(|_| SyscallError::BadFileDescriptor)?;
+    process::with_current_files(|files| files.close(fd)).ok_or(SyscallError::BadFileDescriptor)??;
+    Ok(0)
+}
+
+/// Déplace la position d'un fichier ouvert
+fn sys_lseek([fd, offset, whence, ..]: [u64; 6]) -> Result<u64, SyscallError> {
+    match descriptor(fd)? {
+        // La console n'a pas de position
+        Descriptor::Console => Err(SyscallError::InvalidArgument),
+        Descriptor::File(file) => Ok(file.seek(offset as i64, whence)?),
+    }
+}
+
+/// Ce que désigne le descripteur `fd` du processus en cours
+fn descriptor(fd: u64) -> Result<Descriptor, SyscallError> {
+    let fd = usize::try_from(fd).map_err(|_| SyscallError::BadFileDescriptor)?;
+    let descriptor = process::with_current_files(|files| files.get(fd));
+    Ok(descriptor.ok_or(SyscallError::BadFileDescriptor)??)
+}
+
+/// Lit une `SigAction` du programme
+fn read_sigaction(address: u64) -> Result<SigAction, SyscallError> {
+    let mut bytes = [0u8; size_of::<SigAction>()];
+    copy_from_user(&mut bytes, address)?;
+    let word = |i: usize| u64::from_le_bytes(bytes[8 * i..8 * i + 8].try_into().unwrap());
+    Ok(SigAction { handler: word(0), mask: word(1), restorer: word(2) })
+}
+
+/// Écrit une `SigAction` dans la mémoire du programme
+fn write_sigaction(address: u64, action: SigAction) -> Result<(), SyscallError> {
+    let mut bytes = [0u8; size_of::<SigAction>()];
+    for (chunk, word) in bytes.chunks_exact_mut(8).zip([action.handler, action.mask, action.restorer]) {
+        chunk.copy_from_slice(&word.to_le_bytes());
+    }
+    copy_to_user(address, &bytes)
+}
+
+/// Copie une chaîne UTF-8 du programme
+fn user_string(address: u64, len: u64, max: u64) -> Result<String, SyscallError> {
+    if len > max {
+        return Err(SyscallError::InvalidArgument);
+    }
+    let mut bytes = alloc::vec![0u8; len as usize];
+    copy_from_user(&mut bytes, address)?;
+    String::from_utf8(bytes).map_err(|_| SyscallError::InvalidArgument)
+}
+
+/// Erreur de spawn pour un échec du chargeur
+fn exec_error(error: ExecError) -> SyscallError {
+    match error {
+        ExecError::Image(ImageError::StackOverflow) => SyscallError::InvalidArgument,
+        ExecError::Image(_) => SyscallError::ExecFormat,
+        ExecError::Spawn(SpawnError::TooManyThreads) => SyscallError::TryAgain,
+        ExecError::Map(_) | ExecError::Spawn(SpawnError::Stack(_)) => SyscallError::OutOfMemory,
+    }
+}
+
+// ===================================================================
+// TESTS
+// ===================================================================
+
+#[test_case]
+fn test_sleep_forever_is_interrupted() {
+    use bos_core::process::ExitStatus;
+    use bos_core::signal::{SIGCONT, SIGSTOP};
+    use bos_core::syscall::{SYS_EXIT, SYS_SLEEP};
+
+    use crate::process::{State, UserCode};
+
+    // sleep(u64::MAX), puis exit(résultat de sleep)
+    let pid = UserCode::new().mov_rdi(-1).syscall(SYS_SLEEP).mov_rdi_rax().syscall(SYS_EXIT).start();
+    // Laisser le programme s'endormir : le délai ne déborde pas
+    thread::sleep_ms(50);
+    assert_eq!(process::state(pid), Some(State::Running));
+    // SIGSTOP interrompt sleep, SIGCONT laisse le programme finir
+    assert!(process::kill(pid, SIGSTOP));
+    while process::state(pid) != Some(State::Stopped(SIGSTOP)) {
+        thread::yield_now();
+    }
+    assert!(process::kill(pid, SIGCONT));
+    let code = -(SyscallError::Interrupted.code() as i32);
+    assert_eq!(process::wait(Some(pid), false), Ok(Some((pid, ExitStatus::Exited(code)))));
+}
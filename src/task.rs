@@ -0,0 +1,323 @@
+/* =========================================================
+   EXÉCUTEUR DE TÂCHES ASYNCHRONES
+
+   Les tâches du kernel sont des `Future` (fonctions `async`) que
+   l'exécuteur fait avancer à tour de rôle. Une tâche qui attend
+   (une touche, par exemple) rend la main en retournant `Pending` ;
+   son `Waker` la remet dans la file des tâches prêtes quand
+   l'événement arrive, le plus souvent depuis un handler
+   d'interruption :
+
+     IRQ 1 → keyboard::add_scancode → waker.wake()
+           → file des tâches prêtes → Executor::run → poll du shell
+
+   Le multitâche est coopératif : une tâche garde le CPU jusqu'à
+   son prochain `.await` qui n'aboutit pas.
+
+   Quand aucune tâche n'est prête, le CPU dort (HLT). La file est
+   vérifiée interruptions désactivées, puis `sti; hlt` : STI ne
+   prend effet qu'après l'instruction suivante, donc une
+   interruption arrivée entre la vérification et HLT réveille
+   forcément le CPU au lieu d'être perdue.
+========================================================= */
+
+use alloc::boxed::Box;
+use alloc::collections::BTreeMap;
+use alloc::sync::Arc;
+use alloc::task::Wake;
+use core::future::Future;
+use core::pin::Pin;
+use core::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use core::task::{Context, Poll, Waker};
+
+use bos_core::ring::RingBuffer;
+
+use crate::sync::Spinlock;
+use crate::{disable_interrupts, enable_and_hlt, enable_interrupts};
+
+/// Nombre maximum de tâches
+pub const MAX_TASKS: usize = 64;
+
+/// Identifiant unique d'une tâche
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct TaskId(u64);
+
+impl TaskId {
+    fn new() -> TaskId {
+        static NEXT_ID: AtomicU64 = AtomicU64::new(1);
+        TaskId(NEXT_ID.fetch_add(1, Ordering::Relaxed))
+    }
+
+    /// Valeur numérique de l'identifiant
+    pub fn as_u64(self) -> u64 {
+        self.0
+    }
+}
+
+/// Tâche du kernel : une future qui ne retourne rien
+pub struct Task {
+    id: TaskId,
+    name: &'static str,
+    future: Pin<Box<dyn Future<Output = ()>>>,
+}
+
+impl Task {
+    /// Crée une tâche à partir d'une future (par exemple `shell::run()`)
+    pub fn new(name: &'static str, future: impl Future<Output = ()> + 'static) -> Task {
+        Task {
+            id: TaskId::new(),
+            name,
+            future: Box::pin(future),
+        }
+    }
+
+    /// Identifiant de la tâche
+    pub fn id(&self) -> TaskId {
+        self.id
+    }
+
+    /// Nom de la tâche
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn poll(&mut self, context: &mut Context) -> Poll<()> {
+        self.future.as_mut().poll(context)
+    }
+}
+
+/* =========================================================
+   FILE DES TÂCHES PRÊTES ET WAKERS
+========================================================= */
+
+/// File des tâches prêtes, partagée avec les wakers
+///
+/// Les wakers peuvent être appelés depuis un handler d'interruption :
+/// la file est protégée par un spinlock (interruptions désactivées)
+/// et n'alloue rien.
+struct ReadyQueue {
+    ids: Spinlock<RingBuffer<TaskId, MAX_TASKS>>,
+}
+
+impl ReadyQueue {
+    fn new() -> ReadyQueue {
+        ReadyQueue {
+            ids: Spinlock::new(RingBuffer::new()),
+        }
+    }
+
+    fn push(&self, id: TaskId) {
+        // Chaque tâche y figure au plus une fois (voir TaskWaker::queued)
+        let result = self.ids.lock().push(id);
+        if result.is_err() {
+            panic!("file des taches pretes pleine");
+        }
+    }
+
+    fn pop(&self) -> Option<TaskId> {
+        self.ids.lock().pop()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.ids.lock().is_empty()
+    }
+}
+
+/// Waker d'une tâche : la remet dans la file des tâches prêtes
+struct TaskWaker {
+    id: TaskId,
+    /// La tâche est déjà dans la file (évite les doublons)
+    queued: AtomicBool,
+    ready: Arc<ReadyQueue>,
+}
+
+impl Wake for TaskWaker {
+    fn wake(self: Arc<Self>) {
+        self.wake_by_ref();
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        if !self.queued.swap(true, Ordering::AcqRel) {
+            self.ready.push(self.id);
+        }
+    }
+}
+
+/* =========================================================
+   EXÉCUTEUR
+========================================================= */
+
+/// Tâche en cours d'exécution et son waker
+struct Spawned {
+    task: Task,
+    waker: Arc<TaskWaker>,
+}
+
+/// Exécuteur coopératif de tâches asynchrones
+pub struct Executor {
+    tasks: BTreeMap<TaskId, Spawned>,
+    ready: Arc<ReadyQueue>,
+}
+
+impl Executor {
+    /// Crée un exécuteur sans tâche
+    pub fn new() -> Executor {
+        Executor {
+            tasks: BTreeMap::new(),
+            ready: Arc::new(ReadyQueue::new()),
+        }
+    }
+
+    /// Ajoute une tâche, qui sera exécutée au prochain tour
+    pub fn spawn(&mut self, task: Task) -> TaskId {
+        if self.tasks.len() >= MAX_TASKS {
+            panic!("trop de taches (maximum {})", MAX_TASKS);
+        }
+        let id = task.id;
+        let waker = Arc::new(TaskWaker {
+            id,
+            queued: AtomicBool::new(false),
+            ready: self.ready.clone(),
+        });
+        waker.wake_by_ref();
+        debug!("Tache {} ({}) creee", id.0, task.name);
+        self.tasks.insert(id, Spawned { task, waker });
+        id
+    }
+
+    /// Nombre de tâches non terminées
+    pub fn task_count(&self) -> usize {
+        self.tasks.len()
+    }
+
+    /// Fait avancer toutes les tâches prêtes, jusqu'à ce que la file soit vide
+    pub fn run_ready_tasks(&mut self) {
+        while let Some(id) = self.ready.pop() {
+            let Some(spawned) = self.tasks.get_mut(&id) else {
+                continue;
+            };
+            // Un réveil pendant le poll remettra la tâche dans la file
+            spawned.waker.queued.store(false, Ordering::Release);
+            let waker = Waker::from(spawned.waker.clone());
+            let mut context = Context::from_waker(&waker);
+            if spawned.task.poll(&mut context).is_ready() {
+                debug!("Tache {} ({}) terminee", id.0, spawned.task.name);
+                self.tasks.remove(&id);
+            }
+        }
+    }
+
+    /// Boucle principale du kernel : exécute les tâches, dort sinon
+    pub fn run(&mut self) -> ! {
+        loop {
+            self.run_ready_tasks();
+            self.sleep_if_idle();
+        }
+    }
+
+    /// Met le CPU en veille si aucune tâche n'est prête
+    fn sleep_if_idle(&self) {
+        // Interruptions désactivées : un réveil ne peut pas se glisser
+        // entre la vérification de la file et HLT
+        disable_interrupts();
+        if self.ready.is_empty() {
+            enable_and_hlt();
+        } else {
+            enable_interrupts();
+        }
+    }
+}
+
+impl Default for Executor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/* =========================================================
+   FLUX ASYNCHRONES
+========================================================= */
+
+/// Suite de valeurs produites au fil du temps (version asynchrone
+/// d'un itérateur)
+pub trait Stream {
+    type Item;
+
+    /// Valeur suivante : `Ready(None)` quand le flux est terminé,
+    /// `Pending` s'il faut attendre (le waker sera appelé)
+    fn poll_next(self: Pin<&mut Self>, context: &mut Context) -> Poll<Option<Self::Item>>;
+
+    /// Future qui attend la valeur suivante : `stream.next().await`
+    fn next(&mut self) -> Next<'_, Self>
+    where
+        Self: Unpin + Sized,
+    {
+        Next { stream: self }
+    }
+}
+
+/// Future retournée par `Stream::next`
+pub struct Next<'a, S> {
+    stream: &'a mut S,
+}
+
+impl<S: Stream + Unpin> Future for Next<'_, S> {
+    type Output = Option<S::Item>;
+
+    fn poll(mut self: Pin<&mut Self>, context: &mut Context) -> Poll<Self::Output> {
+        Pin::new(&mut *self.stream).poll_next(context)
+    }
+}
+
+// ===================================================================
+// TESTS
+// ===================================================================
+
+#[test_case]
+fn test_runs_spawned_tasks() {
+    use alloc::rc::Rc;
+    use core::cell::Cell;
+
+    let done = Rc::new(Cell::new(0));
+    let mut executor = Executor::new();
+    for _ in 0..3 {
+        let done = done.clone();
+        executor.spawn(Task::new("test", async move { done.set(done.get() + 1) }));
+    }
+    executor.run_ready_tasks();
+    assert_eq!(done.get(), 3);
+    assert_eq!(executor.task_count(), 0);
+}
+
+#[test_case]
+fn test_wakers_requeue_tasks() {
+    use alloc::rc::Rc;
+    use core::cell::Cell;
+
+    /// Future qui se réveille elle-même `remaining` fois avant de finir
+    struct YieldTimes {
+        remaining: usize,
+        polls: Rc<Cell<usize>>,
+    }
+
+    impl Future for YieldTimes {
+        type Output = ();
+
+        fn poll(mut self: Pin<&mut Self>, context: &mut Context) -> Poll<()> {
+            self.polls.set(self.polls.get() + 1);
+            if self.remaining == 0 {
+                return Poll::Ready(());
+            }
+            self.remaining -= 1;
+            context.waker().wake_by_ref();
+            Poll::Pending
+        }
+    }
+
+    let polls = Rc::new(Cell::new(0));
+    let mut executor = Executor::new();
+    executor.spawn(Task::new("test", YieldTimes { remaining: 5, polls: polls.clone() }));
+    executor.run_ready_tasks();
+    assert_eq!(polls.get(), 6);
+    assert_eq!(executor.task_count(), 0);
+}
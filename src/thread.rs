@@ -0,0 +1,466 @@
+/* =========================================================
+   THREADS DU KERNEL
+
+   Chaque thread a sa propre pile de 16 Kio, projetée dans une zone
+   dédiée et précédée d'une page de garde non projetée : un
+   débordement provoque un page fault au lieu d'écraser la pile
+   voisine.
+
+     0x666600000000 : [garde][pile emplacement 0][garde][pile emplacement 1]...
+
+   Changement de contexte (`thread_switch`, en assembleur) : on
+   empile les registres que l'appelé doit préserver (RBX, RBP,
+   R12-R15), on sauvegarde RSP, on charge le RSP de l'autre thread
+   et on dépile ses registres. Le `ret` final reprend l'autre thread
+   là où il avait lui-même appelé `thread_switch`. Les autres
+   registres sont déjà sauvegardés par l'appelant (convention
+   System V), ou par le handler d'interruption en cas de préemption.
+
+   Ordonnancement (bos_core::sched) : tourniquet avec des tranches
+   de 10 ticks. Le handler du timer appelle `preempt`, qui change
+   de thread à la fin de la tranche. Un thread peut aussi céder sa
+   place (`yield_now`), dormir (`sleep_ms`) ou se terminer (`exit`).
+
+   Le code du démarrage devient le thread "kernel" (celui du shell),
+   et le thread "idle" tourne quand aucun autre n'est prêt.
+
+   Le thread d'un processus utilisateur a son propre espace
+   d'adressage : `switch` recharge CR3 quand il change, et fait
+   de la pile du nouveau thread celle qu'utilise le CPU en
+   quittant le ring 3 (voir usermode.rs).
+========================================================= */
+
+use alloc::boxed::Box;
+use core::arch::global_asm;
+use core::fmt;
+use core::ptr::addr_of_mut;
+use core::sync::atomic::{AtomicBool, Ordering};
+
+use bos_core::memory::PAGE_SIZE;
+use bos_core::paging::PageFlags;
+use bos_core::sched::{Scheduler, ThreadInfo};
+
+use crate::paging::{self, AddressSpace, MapError};
+use crate::sync::Spinlock;
+use crate::{enable_interrupts, frame, hlt_loop, pit, process, usermode, without_interrupts};
+
+/// Nombre maximum de threads
+pub const MAX_THREADS: usize = 32;
+
+/// Durée d'une tranche de temps, en ticks du timer
+pub const TIME_SLICE_TICKS: u64 = 10;
+
+/// Taille de la pile d'un thread (en pages)
+const STACK_PAGES: u64 = 4;
+
+/// Début de la zone des piles (chaque emplacement : une page de garde + la pile)
+const STACKS_START: u64 = 0x0000_6666_0000_0000;
+const STACK_STRIDE: u64 = (STACK_PAGES + 1) * PAGE_SIZE;
+
+/// Erreur à la création d'un thread
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpawnError {
+    /// La table des threads est pleine
+    TooManyThreads,
+    /// La pile n'a pas pu être projetée
+    Stack(MapError),
+}
+
+impl fmt::Display for SpawnError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SpawnError::TooManyThreads => write!(f, "trop de threads (maximum {})", MAX_THREADS),
+            SpawnError::Stack(error) => write!(f, "pile impossible a projeter : {}", error),
+        }
+    }
+}
+
+/// Contexte d'un thread qui ne s'exécute pas
+#[derive(Clone, Copy)]
+struct Context {
+    /// RSP sauvegardé par `thread_switch`
+    rsp: u64,
+    /// Pile projetée dans la zone des piles (faux pour le thread kernel)
+    own_stack: bool,
+    /// PML4 de son espace d'adressage (CR3)
+    level_4: u64,
+}
+
+impl Context {
+    const EMPTY: Context = Context { rsp: 0, own_stack: false, level_4: 0 };
+}
+
+/// État des threads : l'ordonnanceur et le contexte de chaque emplacement
+struct Threads {
+    scheduler: Scheduler<MAX_THREADS>,
+    contexts: [Context; MAX_THREADS],
+}
+
+static THREADS: Spinlock<Threads> = Spinlock::new(Threads {
+    scheduler: Scheduler::new(),
+    contexts: [Context::EMPTY; MAX_THREADS],
+});
+static INITIALIZED: AtomicBool = AtomicBool::new(false);
+
+// Fonction exécutée par un nouveau thread
+type Entry = Box<dyn FnOnce() + Send + 'static>;
+
+// thread_switch(rdi = &mut ancien RSP, rsi = nouveau RSP)
+//
+// thread_trampoline : premier `ret` d'un nouveau thread, qui trouve
+// dans R12 la fonction à exécuter (voir `prepare_stack`).
+global_asm!(
+    ".global thread_switch",
+    "thread_switch:",
+    "    push rbp",
+    "    push rbx",
+    "    push r12",
+    "    push r13",
+    "    push r14",
+    "    push r15",
+    "    mov [rdi], rsp",
+    "    mov rsp, rsi",
+    "    pop r15",
+    "    pop r14",
+    "    pop r13",
+    "    pop r12",
+    "    pop rbx",
+    "    pop rbp",
+    "    ret",
+    ".global thread_trampoline",
+    "thread_trampoline:",
+    "    mov rdi, r12",
+    "    call {start}",
+    "    ud2",
+    start = sym thread_start,
+);
+
+extern "C" {
+    fn thread_switch(old_rsp: *mut u64, new_rsp: u64);
+    fn thread_trampoline();
+}
+
+/// Début de la pile (adresse la plus basse) d'un emplacement
+fn stack_bottom(slot: usize) -> u64 {
+    STACKS_START + slot as u64 * STACK_STRIDE + PAGE_SIZE
+}
+
+/// Fin de la pile (adresse la plus haute, exclue) d'un emplacement
+fn stack_top(slot: usize) -> u64 {
+    stack_bottom(slot) + STACK_PAGES * PAGE_SIZE
+}
+
+/// Projette la pile d'un emplacement
+fn map_stack(slot: usize) -> Result<(), MapError> {
+    let mut space = AddressSpace::current();
+    let flags = PageFlags::WRITABLE | PageFlags::NO_EXECUTE;
+    let bottom = stack_bottom(slot);
+    for page in (0..STACK_PAGES).map(|i| bottom + i * PAGE_SIZE) {
+        let mapped = match frame::allocate_frame() {
+            Some(frame) => space.map(page, frame, flags),
+            None => Err(MapError::OutOfFrames),
+        };
+        if let Err(error) = mapped {
+            unmap_stack(slot);
+            return Err(error);
+        }
+    }
+    Ok(())
+}
+
+/// Libère la pile d'un emplacement (les pages non projetées sont ignorées)
+fn unmap_stack(slot: usize) {
+    let mut space = AddressSpace::current();
+    let bottom = stack_bottom(slot);
+    for page in (0..STACK_PAGES).map(|i| bottom + i * PAGE_SIZE) {
+        if let Ok(frame) = space.unmap(page) {
+            let _ = frame::free_frame(frame);
+        }
+    }
+}
+
+/// Prépare la pile d'un nouveau thread comme si `thread_switch` l'avait
+/// quitté juste avant de retourner dans `thread_trampoline`
+///
+/// Retourne le RSP à sauvegarder dans son contexte.
+unsafe fn prepare_stack(slot: usize, entry: *mut Entry) -> u64 {
+    let top = stack_top(slot);
+    // Registres dépilés par thread_switch, puis l'adresse de retour.
+    // RBP = 0 termine les backtraces ; RSP est aligné sur 16 octets
+    // à l'entrée de thread_trampoline.
+    let frame: [u64; 7] = [
+        0,                                       // r15
+        0,                                       // r14
+        0,                                       // r13
+        entry as u64,                            // r12
+        0,                                       // rbx
+        0,                                       // rbp
+        thread_trampoline as *const () as u64,   // adresse de retour
+    ];
+    let rsp = top - 8 * frame.len() as u64;
+    (rsp as *mut [u64; 7]).write(frame);
+    rsp
+}
+
+/// Point d'entrée Rust d'un nouveau thread
+extern "C" fn thread_start(entry: *mut Entry) -> ! {
+    // On arrive ici depuis `switch`, interruptions désactivées
+    reap_exited();
+    enable_interrupts();
+    let entry = unsafe { Box::from_raw(entry) };
+    entry();
+    exit();
+}
+
+/// Thread idle : dort jusqu'à la prochaine interruption
+fn idle() {
+    hlt_loop();
+}
+
+/// Fait du code en cours le thread "kernel" et crée le thread idle
+///
+/// À appeler après `heap::init` et avant d'activer les interruptions.
+pub fn init() {
+    let entry: *mut Entry = Box::into_raw(Box::new(Box::new(idle)));
+    let level_4 = paging::kernel_level_4_table();
+    {
+        let mut threads = THREADS.lock();
+        let slot = threads.scheduler.add_running("kernel").expect("table des threads vide");
+        threads.contexts[slot].level_4 = level_4;
+        let slot = threads.scheduler.add_idle("idle").expect("table des threads vide");
+        map_stack(slot).expect("pile du thread idle");
+        threads.contexts[slot] = Context {
+            rsp: unsafe { prepare_stack(slot, entry) },
+            own_stack: true,
+            level_4,
+        };
+    }
+    INITIALIZED.store(true, Ordering::SeqCst);
+}
+
+/// Indique si les threads sont initialisés (on peut bloquer et dormir)
+pub fn is_running() -> bool {
+    INITIALIZED.load(Ordering::Relaxed)
+}
+
+/// Crée un thread qui exécute `f`, retourne son identifiant
+pub fn spawn<F>(name: &'static str, f: F) -> Result<u64, SpawnError>
+where
+    F: FnOnce() + Send + 'static,
+{
+    spawn_in(name, paging::kernel_level_4_table(), f)
+}
+
+/// Crée un thread qui exécute `f` dans l'espace d'adressage de PML4
+/// `level_4` (celui d'un processus, voir process.rs)
+pub(crate) fn spawn_in<F>(name: &'static str, level_4: u64, f: F) -> Result<u64, SpawnError>
+where
+    F: FnOnce() + Send + 'static,
+{
+    let entry: *mut Entry = Box::into_raw(Box::new(Box::new(f)));
+    let result = insert_thread(name, level_4, entry);
+    if result.is_err() {
+        // Le thread n'existera pas : rendre la fonction (et ses captures)
+        drop(unsafe { Box::from_raw(entry) });
+    }
+    result
+}
+
+/// Prépare la pile d'un nouveau thread et l'inscrit dans l'ordonnanceur
+fn insert_thread(name: &'static str, level_4: u64, entry: *mut Entry) -> Result<u64, SpawnError> {
+    let mut threads = THREADS.lock();
+    // Projeter la pile avant d'inscrire le thread : il pourrait
+    // être choisi dès le prochain tick
+    let slot = (0..MAX_THREADS)
+        .find(|&slot| threads.scheduler.get(slot).is_none())
+        .ok_or(SpawnError::TooManyThreads)?;
+    map_stack(slot).map_err(SpawnError::Stack)?;
+    threads.contexts[slot] = Context {
+        rsp: unsafe { prepare_stack(slot, entry) },
+        own_stack: true,
+        level_4,
+    };
+    let added = threads.scheduler.add(name);
+    debug_assert_eq!(added, Some(slot));
+    Ok(threads.scheduler.get(slot).map_or(0, |info| info.id))
+}
+
+/// Donne la main au prochain thread s'il faut en changer
+///
+/// # Safety
+/// Appelée interruptions désactivées, sans tenir `THREADS`.
+unsafe fn switch() {
+    let (old_rsp, new) = {
+        let mut threads = THREADS.lock();
+        let Some((old, new)) = threads.scheduler.schedule() else {
+            return;
+        };
+        // Les contextes sont dans un static : l'adresse reste valable
+        // une fois le verrou rendu
+        (addr_of_mut!(threads.contexts[old].rsp), (new, threads.contexts[new]))
+    };
+    let (slot, context) = new;
+    if context.own_stack {
+        usermode::set_kernel_stack(stack_top(slot));
+    }
+    // Toutes les PML4 projettent le kernel (et donc la pile en cours)
+    // aux mêmes adresses
+    if context.level_4 != paging::active_level_4_table() {
+        paging::load_level_4_table(context.level_4);
+    }
+    // Le verrou ne doit pas rester pris pendant que l'autre thread tourne
+    thread_switch(old_rsp, context.rsp);
+    // De retour dans l'ancien thread, quand il reprend la main
+    reap_exited();
+}
+
+/// Libère les piles des threads terminés
+///
+/// Jamais appelée sur la pile d'un thread terminé.
+fn reap_exited() {
+    let mut reaped = [0; MAX_THREADS];
+    let mut count = 0;
+    {
+        let mut threads = THREADS.lock();
+        for slot in 0..MAX_THREADS {
+            if let Some(info) = threads.scheduler.remove(slot) {
+                if threads.contexts[slot].own_stack {
+                    unmap_stack(slot);
+                }
+                threads.contexts[slot] = Context::EMPTY;
+                reaped[count] = info.id;
+                count += 1;
+            }
+        }
+    }
+    // Espace d'adressage du processus libéré maintenant qu'il n'est
+    // plus actif
+    for &id in &reaped[..count] {
+        process::thread_reaped(id);
+    }
+}
+
+/// Appelée par le handler du timer, après l'EOI : change de thread à la
+/// fin de la tranche de temps
+pub fn preempt() {
+    if !is_running() {
+        return;
+    }
+    let expired = THREADS.lock().scheduler.tick(pit::ticks(), TIME_SLICE_TICKS);
+    if expired {
+        // Handler d'interruption : les interruptions sont désactivées
+        unsafe { switch() };
+    }
+}
+
+/// Cède le CPU aux autres threads prêts
+pub fn yield_now() {
+    without_interrupts(|| unsafe { switch() });
+}
+
+/// Endort le thread en cours pendant au moins `ms` millisecondes
+pub fn sleep_ms(ms: u64) {
+    let deadline = pit::ticks().saturating_add(pit::ms_to_ticks(ms));
+    without_interrupts(|| unsafe {
+        THREADS.lock().scheduler.sleep_current(deadline);
+        switch();
+    });
+}
+
+/// Bloque le thread en cours jusqu'à `unblock` (voir sync::WaitQueue)
+///
+/// Appelée interruptions désactivées, après s'être inscrit là où le
+/// réveil sera fait : sinon le réveil pourrait arriver avant le blocage.
+pub(crate) fn block_current() {
+    debug_assert!(!crate::interrupts_enabled());
+    THREADS.lock().scheduler.block_current();
+    unsafe { switch() };
+}
+
+/// Comme `block_current`, mais le thread se réveille de lui-même au
+/// tick `deadline` si personne ne l'a débloqué avant
+pub(crate) fn block_current_until(deadline: u64) {
+    debug_assert!(!crate::interrupts_enabled());
+    THREADS.lock().scheduler.sleep_current(deadline);
+    unsafe { switch() };
+}
+
+/// Réveille un thread bloqué ou endormi
+///
+/// Retourne false s'il n'existe pas ou n'attendait pas.
+pub fn unblock(id: u64) -> bool {
+    let mut threads = THREADS.lock();
+    match threads.scheduler.find(id) {
+        Some(slot) => threads.scheduler.unblock(slot),
+        None => false,
+    }
+}
+
+/// Termine le thread en cours
+pub fn exit() -> ! {
+    without_interrupts(|| unsafe {
+        THREADS.lock().scheduler.exit_current();
+        switch();
+    });
+    unreachable!("un thread termine a repris la main");
+}
+
+/// Identifiant du thread en cours
+pub fn current_id() -> u64 {
+    let threads = THREADS.lock();
+    let current = threads.scheduler.current();
+    threads.scheduler.get(current).map_or(0, |info| info.id)
+}
+
+/// Appelle `f` pour chaque thread
+pub fn for_each(mut f: impl FnMut(&ThreadInfo)) {
+    for (_, info) in THREADS.lock().scheduler.iter() {
+        f(info);
+    }
+}
+
+// ===================================================================
+// TESTS
+// ===================================================================
+
+#[test_case]
+fn test_spawn_and_yield() {
+    static DONE: AtomicBool = AtomicBool::new(false);
+
+    let id = spawn("test", || DONE.store(true, Ordering::SeqCst)).unwrap();
+    assert!(id != current_id());
+    for _ in 0..1000 {
+        if DONE.load(Ordering::SeqCst) {
+            break;
+        }
+        yield_now();
+    }
+    assert!(DONE.load(Ordering::SeqCst));
+}
+
+#[test_case]
+fn test_sleep_ms() {
+    let start = pit::ticks();
+    sleep_ms(20);
+    assert!(pit::ticks() - start >= 20);
+}
+
+#[test_case]
+fn test_timer_preempts() {
+    use core::sync::atomic::AtomicU64;
+
+    static SPINS: AtomicU64 = AtomicU64::new(0);
+    static STOP: AtomicBool = AtomicBool::new(false);
+
+    spawn("spin", || {
+        while !STOP.load(Ordering::SeqCst) {
+            SPINS.fetch_add(1, Ordering::SeqCst);
+        }
+    })
+    .unwrap();
+    // Sans jamais céder la main : seul le timer peut faire tourner l'autre thread
+    pit::busy_wait_ms(50);
+    assert!(SPINS.load(Ordering::SeqCst) > 0);
+    STOP.store(true, Ordering::SeqCst);
+    sleep_ms(2 * TIME_SLICE_TICKS);
+}
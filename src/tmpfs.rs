@@ -0,0 +1,431 @@
+/* =========================================================
+   TMPFS : SYSTÈME DE FICHIERS EN MÉMOIRE
+
+   Fichiers et répertoires créés à l'exécution, perdus à l'arrêt :
+   fichiers temporaires, sorties redirigées du shell, tests sans
+   disque. Monté sur /tmp au démarrage ; `mount tmpfs <rep>` en
+   monte d'autres, chacun avec son propre contenu.
+
+   Un répertoire est une table nom → inode. Le contenu d'un
+   fichier est rangé dans des frames physiques de 4 Kio (et non
+   dans le tas du kernel, trop petit), lus et écrits par la
+   projection de la mémoire physique. Les octets au-delà de la fin
+   d'un fichier sont toujours nuls : un fichier agrandi (truncate,
+   écriture après la fin) se complète de zéros. Les frames sont
+   rendus quand le fichier est supprimé et n'est plus ouvert.
+
+   Chaque instance est limitée à MAX_PAGES frames (NoSpace au-delà).
+   Les dates de modification viennent de la RTC.
+========================================================= */
+
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use core::any::Any;
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+use bos_core::memory::PAGE_SIZE;
+use bos_core::vfs::{FileType, FsError, Metadata};
+
+use crate::sync::Spinlock;
+use crate::vfs::{DirEntry, Directory, FileSystem, Inode};
+use crate::{frame, memory, rtc};
+
+/// Nombre maximal de frames d'un tmpfs (8 Mio)
+pub const MAX_PAGES: usize = 2048;
+
+/// Un tmpfs : sa racine et le nombre de frames utilisés
+pub struct TmpFs {
+    root: Arc<TmpInode>,
+}
+
+impl TmpFs {
+    /// Crée un tmpfs vide
+    pub fn new() -> TmpFs {
+        TmpFs { root: TmpInode::new(FileType::Directory, Arc::new(AtomicUsize::new(0))) }
+    }
+
+    /// Nombre de frames utilisés par les fichiers
+    pub fn used_pages(&self) -> usize {
+        self.root.usage.load(Ordering::Relaxed)
+    }
+}
+
+impl Default for TmpFs {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FileSystem for TmpFs {
+    fn name(&self) -> &'static str {
+        "tmpfs"
+    }
+
+    fn root(&self) -> Arc<dyn Inode> {
+        self.root.clone()
+    }
+}
+
+/// Contenu d'un fichier : ses frames et sa taille
+struct Pages {
+    frames: Vec<u64>,
+    size: u64,
+}
+
+/// Octets du frame `frame`, par la projection de la mémoire physique
+///
+/// # Safety
+/// Le frame doit appartenir à un fichier verrouillé par l'appelant.
+unsafe fn frame_bytes<'a>(frame: u64) -> &'a mut [u8] {
+    core::slice::from_raw_parts_mut(memory::phys_to_virt(frame) as *mut u8, PAGE_SIZE as usize)
+}
+
+impl Pages {
+    /// Appelle `f` pour chaque morceau de page couvrant `len` octets à
+    /// partir de `offset` : (octets de la page, position dans le tampon)
+    fn for_each_chunk(&self, offset: u64, len: usize, mut f: impl FnMut(&mut [u8], usize)) {
+        let mut done = 0;
+        while done < len {
+            let position = offset + done as u64;
+            let start = (position % PAGE_SIZE) as usize;
+            let count = (len - done).min(PAGE_SIZE as usize - start);
+            let page = unsafe { frame_bytes(self.frames[(position / PAGE_SIZE) as usize]) };
+            f(&mut page[start..start + count], done);
+            done += count;
+        }
+    }
+
+    fn read(&self, offset: u64, buffer: &mut [u8]) -> usize {
+        if offset >= self.size {
+            return 0;
+        }
+        let count = buffer.len().min((self.size - offset) as usize);
+        self.for_each_chunk(offset, count, |page, done| {
+            buffer[done..done + page.len()].copy_from_slice(page);
+        });
+        count
+    }
+
+    fn write(&mut self, offset: u64, buffer: &[u8], usage: &AtomicUsize) -> Result<usize, FsError> {
+        let end = offset.checked_add(buffer.len() as u64).ok_or(FsError::InvalidArgument)?;
+        if end > self.size {
+            self.resize(end, usage)?;
+        }
+        self.for_each_chunk(offset, buffer.len(), |page, done| {
+            page.copy_from_slice(&buffer[done..done + page.len()]);
+        });
+        Ok(buffer.len())
+    }
+
+    /// Change la taille : alloue ou rend des frames
+    ///
+    /// En cas d'échec, le fichier ne change pas : les frames alloués
+    /// par cet appel sont rendus.
+    fn resize(&mut self, size: u64, usage: &AtomicUsize) -> Result<(), FsError> {
+        let needed = size.div_ceil(PAGE_SIZE) as usize;
+        let tail = (size % PAGE_SIZE) as usize;
+        if size < self.size && tail != 0 {
+            // La fin de la dernière page gardée doit relire des zéros
+            unsafe { frame_bytes(self.frames[needed - 1])[tail..].fill(0) };
+        }
+        self.free_from(needed, usage);
+        let kept = self.frames.len();
+        while self.frames.len() < needed {
+            if let Err(error) = self.grow(usage) {
+                self.free_from(kept, usage);
+                return Err(error);
+            }
+        }
+        self.size = size;
+        Ok(())
+    }
+
+    /// Ajoute un frame nul à la fin
+    fn grow(&mut self, usage: &AtomicUsize) -> Result<(), FsError> {
+        if usage.fetch_add(1, Ordering::Relaxed) >= MAX_PAGES {
+            usage.fetch_sub(1, Ordering::Relaxed);
+            return Err(FsError::NoSpace);
+        }
+        let Some(frame) = frame::allocate_frame() else {
+            usage.fetch_sub(1, Ordering::Relaxed);
+            return Err(FsError::NoSpace);
+        };
+        unsafe { frame_bytes(frame).fill(0) };
+        self.frames.push(frame);
+        Ok(())
+    }
+
+    /// Rend les frames à partir du `len`-ième
+    fn free_from(&mut self, len: usize, usage: &AtomicUsize) {
+        while self.frames.len() > len {
+            let frame = self.frames.pop().unwrap();
+            let _ = frame::free_frame(frame);
+            usage.fetch_sub(1, Ordering::Relaxed);
+        }
+    }
+}
+
+/// Contenu d'un inode
+enum Content {
+    File(Pages),
+    Directory(BTreeMap<String, Arc<TmpInode>>),
+}
+
+/// État modifiable d'un inode
+struct State {
+    mtime: u64,
+    content: Content,
+}
+
+impl State {
+    fn entries(&mut self) -> Result<&mut BTreeMap<String, Arc<TmpInode>>, FsError> {
+        match &mut self.content {
+            Content::Directory(entries) => Ok(entries),
+            Content::File(_) => Err(FsError::NotADirectory),
+        }
+    }
+
+    fn pages(&mut self) -> Result<&mut Pages, FsError> {
+        match &mut self.content {
+            Content::File(pages) => Ok(pages),
+            Content::Directory(_) => Err(FsError::IsADirectory),
+        }
+    }
+}
+
+/// Un fichier ou un répertoire du tmpfs
+struct TmpInode {
+    kind: FileType,
+    /// Frames utilisés par le tmpfs entier
+    usage: Arc<AtomicUsize>,
+    state: Spinlock<State>,
+}
+
+impl TmpInode {
+    fn new(kind: FileType, usage: Arc<AtomicUsize>) -> Arc<TmpInode> {
+        let content = match kind {
+            FileType::Directory => Content::Directory(BTreeMap::new()),
+            _ => Content::File(Pages { frames: Vec::new(), size: 0 }),
+        };
+        Arc::new(TmpInode { kind, usage, state: Spinlock::new(State { mtime: rtc::now(), content }) })
+    }
+
+    /// Entrée `name` de ce répertoire
+    fn child(&self, name: &str) -> Result<Arc<TmpInode>, FsError> {
+        self.state.lock().entries()?.get(name).cloned().ok_or(FsError::NotFound)
+    }
+
+    /// Répertoire vide (un fichier ne l'est jamais)
+    fn is_empty_directory(&self) -> bool {
+        matches!(&self.state.lock().content, Content::Directory(entries) if entries.is_empty())
+    }
+}
+
+impl Drop for TmpInode {
+    fn drop(&mut self) {
+        if let Content::File(pages) = &mut self.state.get_mut().content {
+            let _ = pages.resize(0, &self.usage);
+        }
+    }
+}
+
+impl Inode for TmpInode {
+    fn metadata(&self) -> Metadata {
+        let state = self.state.lock();
+        let (size, mode) = match &state.content {
+            Content::File(pages) => (pages.size, 0o644),
+            Content::Directory(_) => (0, 0o755),
+        };
+        Metadata { kind: self.kind, size, mode, mtime: state.mtime }
+    }
+
+    fn read_at(&self, offset: u64, buffer: &mut [u8]) -> Result<usize, FsError> {
+        Ok(self.state.lock().pages()?.read(offset, buffer))
+    }
+
+    fn write_at(&self, offset: u64, buffer: &[u8]) -> Result<usize, FsError> {
+        let mut state = self.state.lock();
+        let count = state.pages()?.write(offset, buffer, &self.usage)?;
+        state.mtime = rtc::now();
+        Ok(count)
+    }
+
+    fn truncate(&self, size: u64) -> Result<(), FsError> {
+        let mut state = self.state.lock();
+        state.pages()?.resize(size, &self.usage)?;
+        state.mtime = rtc::now();
+        Ok(())
+    }
+
+    fn set_mtime(&self, mtime: u64) -> Result<(), FsError> {
+        self.state.lock().mtime = mtime;
+        Ok(())
+    }
+
+    fn as_directory(&self) -> Option<&dyn Directory> {
+        match self.kind {
+            FileType::Directory => Some(self),
+            _ => None,
+        }
+    }
+}
+
+impl Directory for TmpInode {
+    fn lookup(&self, name: &str) -> Result<Arc<dyn Inode>, FsError> {
+        Ok(self.child(name)?)
+    }
+
+    fn entries(&self) -> Result<Vec<DirEntry>, FsError> {
+        let mut state = self.state.lock();
+        let entries = state.entries()?.iter().map(|(name, inode)| {
+            let inode: Arc<dyn Inode> = inode.clone();
+            DirEntry { name: name.clone(), inode }
+        });
+        Ok(entries.collect())
+    }
+
+    fn create(&self, name: &str, kind: FileType) -> Result<Arc<dyn Inode>, FsError> {
+        if kind == FileType::Symlink {
+            return Err(FsError::InvalidArgument);
+        }
+        let mut state = self.state.lock();
+        let entries = state.entries()?;
+        if entries.contains_key(name) {
+            return Err(FsError::AlreadyExists);
+        }
+        let inode = TmpInode::new(kind, self.usage.clone());
+        entries.insert(String::from(name), inode.clone());
+        state.mtime = rtc::now();
+        Ok(inode)
+    }
+
+    fn remove(&self, name: &str) -> Result<(), FsError> {
+        let mut state = self.state.lock();
+        let entries = state.entries()?;
+        let inode = entries.get(name).ok_or(FsError::NotFound)?;
+        if inode.kind == FileType::Directory && !inode.is_empty_directory() {
+            return Err(FsError::NotEmpty);
+        }
+        // Les frames sont rendus avec le dernier fichier ouvert
+        entries.remove(name);
+        state.mtime = rtc::now();
+        Ok(())
+    }
+
+    fn rename(&self, name: &str, target: &dyn Inode, new_name: &str) -> Result<(), FsError> {
+        let target = (target as &dyn Any).downcast_ref::<TmpInode>().ok_or(FsError::CrossDevice)?;
+        if !Arc::ptr_eq(&self.usage, &target.usage) {
+            return Err(FsError::CrossDevice);
+        }
+        let source = self.child(name)?;
+        let now = rtc::now();
+        // Le nouveau nom d'abord, en remplaçant une entrée compatible.
+        // Les deux répertoires ne sont jamais verrouillés ensemble : un
+        // court instant, la source a ses deux noms
+        {
+            let mut state = target.state.lock();
+            let entries = state.entries()?;
+            if let Some(existing) = entries.get(new_name) {
+                if Arc::ptr_eq(existing, &source) {
+                    return Ok(());
+                }
+                match (source.kind, existing.kind) {
+                    (FileType::Directory, FileType::Directory) if !existing.is_empty_directory() => {
+                        return Err(FsError::NotEmpty)
+                    }
+                    (FileType::Directory, FileType::File) => return Err(FsError::NotADirectory),
+                    (FileType::File, FileType::Directory) => return Err(FsError::IsADirectory),
+                    _ => {}
+                }
+            }
+            entries.insert(String::from(new_name), source.clone());
+            state.mtime = now;
+        }
+        let mut state = self.state.lock();
+        let entries = state.entries()?;
+        if entries.get(name).is_some_and(|entry| Arc::ptr_eq(entry, &source)) {
+            entries.remove(name);
+        }
+        state.mtime = now;
+        Ok(())
+    }
+}
+
+// ===================================================================
+// TESTS
+// ===================================================================
+
+#[test_case]
+fn test_tmpfs_files() {
+    let fs = TmpFs::new();
+    let root = fs.root();
+    let directory = root.as_directory().unwrap();
+    let file = directory.create("notes", FileType::File).unwrap();
+    assert_eq!(directory.create("notes", FileType::File).err(), Some(FsError::AlreadyExists));
+    // Écriture à cheval sur deux pages, après un trou
+    let offset = PAGE_SIZE - 2;
+    assert_eq!(file.write_at(offset, b"bonjour"), Ok(7));
+    assert_eq!(file.metadata().size, offset + 7);
+    assert_eq!(fs.used_pages(), 2);
+    let mut buffer = [0xFFu8; 9];
+    assert_eq!(file.read_at(offset - 2, &mut buffer), Ok(9));
+    assert_eq!(&buffer, b"\0\0bonjour");
+    // Réduit puis agrandi : la fin relit des zéros
+    file.truncate(offset + 3).unwrap();
+    assert_eq!(fs.used_pages(), 2);
+    file.truncate(offset + 7).unwrap();
+    assert_eq!(file.read_at(offset, &mut buffer), Ok(7));
+    assert_eq!(&buffer[..7], b"bon\0\0\0\0");
+    file.truncate(0).unwrap();
+    assert_eq!(fs.used_pages(), 0);
+    assert_eq!(file.read_at(0, &mut buffer), Ok(0));
+    assert!(file.metadata().mtime >= rtc::now() - 1);
+    // Les frames sont rendus avec le dernier inode
+    file.write_at(0, b"x").unwrap();
+    directory.remove("notes").unwrap();
+    assert_eq!(fs.used_pages(), 1);
+    drop(file);
+    assert_eq!(fs.used_pages(), 0);
+}
+
+#[test_case]
+fn test_tmpfs_directories_and_rename() {
+    let fs = TmpFs::new();
+    let root = fs.root();
+    let directory = root.as_directory().unwrap();
+    let sub = directory.create("sub", FileType::Directory).unwrap();
+    sub.as_directory().unwrap().create("a", FileType::File).unwrap().write_at(0, b"A").unwrap();
+    assert_eq!(directory.remove("sub"), Err(FsError::NotEmpty));
+    // Déplacement dans un autre répertoire, puis renommage sur place
+    sub.as_directory().unwrap().rename("a", root.as_ref(), "b").unwrap();
+    assert_eq!(sub.as_directory().unwrap().lookup("a").err(), Some(FsError::NotFound));
+    directory.create("c", FileType::File).unwrap();
+    directory.rename("b", root.as_ref(), "c").unwrap();
+    let mut byte = [0u8];
+    assert_eq!(directory.lookup("c").unwrap().read_at(0, &mut byte), Ok(1));
+    assert_eq!(&byte, b"A");
+    assert_eq!(directory.rename("c", root.as_ref(), "sub"), Err(FsError::IsADirectory));
+    assert_eq!(directory.rename("sub", root.as_ref(), "c"), Err(FsError::NotADirectory));
+    let names: Vec<String> = directory.entries().unwrap().into_iter().map(|entry| entry.name).collect();
+    assert_eq!(names, ["c", "sub"]);
+    directory.remove("sub").unwrap();
+    // Pas de renommage vers un autre tmpfs
+    let other = TmpFs::new().root();
+    assert_eq!(directory.rename("c", other.as_ref(), "c"), Err(FsError::CrossDevice));
+}
+
+#[test_case]
+fn test_tmpfs_failed_grow_is_undone() {
+    let fs = TmpFs::new();
+    let root = fs.root();
+    let file = root.as_directory().unwrap().create("gros", FileType::File).unwrap();
+    file.write_at(0, b"x").unwrap();
+    // Au-delà de MAX_PAGES : les frames alloués avant l'échec sont rendus
+    let too_big = (MAX_PAGES as u64 + 1) * PAGE_SIZE;
+    assert_eq!(file.truncate(too_big), Err(FsError::NoSpace));
+    assert_eq!(file.write_at(too_big - 1, b"y"), Err(FsError::NoSpace));
+    assert_eq!(fs.used_pages(), 1);
+    assert_eq!(file.metadata().size, 1);
+}
@@ -0,0 +1,378 @@
+/* =========================================================
+   MODE UTILISATEUR (RING 3)
+
+   Un programme utilisateur s'exécute au niveau de privilège 3 :
+   il ne peut ni exécuter les instructions privilégiées (CLI, HLT,
+   IN/OUT, MOV CR3...) ni accéder aux pages du kernel, qui n'ont
+   pas le droit USER. Une faute envoie un signal au programme
+   (SIGSEGV...), elle n'arrête pas le kernel (voir interrupts.rs).
+
+   Chaque processus a son propre espace d'adressage (`UserSpace`) :
+   une PML4 qui reprend les entrées de celle du kernel et ajoute la
+   zone utilisateur, seule partie qui lui est propre :
+
+     0x100000000000 - 0x200000000000 : zone utilisateur
+       (code et données en bas, puis le tas, pile en haut)
+
+   Le tas commence après le dernier segment du programme et grandit
+   avec l'appel système brk, jusqu'à une page de garde sous la pile.
+
+   Passages entre les deux mondes :
+   - Kernel → utilisateur : `enter` construit une trame
+     d'interruption (SS, RSP, RFLAGS, CS, RIP) et exécute IRETQ
+   - Utilisateur → kernel : SYSCALL (syscall.rs), ou une
+     interruption ou exception ; le CPU charge alors la pile du
+     kernel RSP0 de la TSS, qui est celle du thread en cours
+   - Retour avec tous les registres d'une trame : `resume`
+     (fin d'un handler de signal, voir signal.rs)
+========================================================= */
+
+use core::arch::asm;
+use core::ops::Range;
+use core::sync::atomic::{AtomicU64, Ordering};
+
+use bos_core::memory::PAGE_SIZE;
+use bos_core::paging::{table_index, PageFlags};
+use bos_core::signal::Registers;
+use bos_core::syscall::SyscallError;
+
+use crate::gdt::{self, USER_CODE_SELECTOR, USER_DATA_SELECTOR};
+use crate::interrupts::TrapFrame;
+use crate::paging::{self, AddressSpace, MapError};
+use crate::{frame, memory};
+
+/// Début de la zone utilisateur
+pub const USER_START: u64 = 0x0000_1000_0000_0000;
+
+/// Fin (exclue) de la zone utilisateur
+pub const USER_END: u64 = 0x0000_2000_0000_0000;
+
+/// Haut de la pile utilisateur (une page de garde au-dessus)
+pub const USER_STACK_TOP: u64 = USER_END - PAGE_SIZE;
+
+/// Taille de la pile utilisateur
+pub const USER_STACK_SIZE: u64 = 64 * 1024;
+
+/// Fin (exclue) du tas : une page de garde le sépare de la pile
+pub const USER_HEAP_END: u64 = USER_STACK_TOP - USER_STACK_SIZE - PAGE_SIZE;
+
+/// Entrées de la PML4 couvertes par la zone utilisateur
+const USER_ENTRIES: Range<usize> = table_index(USER_START, 4)..table_index(USER_END, 4);
+
+/// RFLAGS au démarrage d'un programme : seulement IF (bit 9) et le bit 1,
+/// toujours à 1
+const USER_RFLAGS: u64 = 0x202;
+
+/// Bits de RFLAGS qu'un programme peut changer : CF, PF, AF, ZF, SF,
+/// DF et OF (pas IF, ni IOPL, ni TF)
+const USER_FLAGS_MASK: u64 = 0xCD5;
+
+// Pile du kernel du thread en cours, chargée par l'entrée des appels
+// système (SYSCALL ne change pas de pile, voir syscall.rs)
+pub(crate) static KERNEL_STACK: AtomicU64 = AtomicU64::new(0);
+
+/// Indique si `range` est entièrement dans la zone utilisateur
+pub fn is_user_range(start: u64, len: u64) -> bool {
+    match start.checked_add(len) {
+        Some(end) => start >= USER_START && end <= USER_END,
+        None => false,
+    }
+}
+
+/// Change la pile du kernel utilisée en entrant dans le kernel depuis
+/// le ring 3 : RSP0 de la TSS et pile des appels système
+///
+/// Appelée à chaque changement de thread (voir thread.rs).
+pub fn set_kernel_stack(stack_top: u64) {
+    gdt::set_kernel_stack(stack_top);
+    KERNEL_STACK.store(stack_top, Ordering::Relaxed);
+}
+
+/// Passe en ring 3 : exécute `entry` avec la pile `stack`
+///
+/// Le thread en cours devient le thread du programme : sa pile du
+/// kernel ne sert plus qu'aux appels système et aux interruptions.
+///
+/// # Safety
+/// L'espace d'adressage actif doit être celui du processus, avec
+/// `entry` et `stack` projetés avec le droit USER.
+pub unsafe fn enter(entry: u64, stack: u64) -> ! {
+    // Trame lue par IRETQ, puis aucun registre du kernel ne doit
+    // rester visible du programme
+    asm!(
+        "cli",
+        "push {ss}",
+        "push {stack}",
+        "push {rflags}",
+        "push {cs}",
+        "push {entry}",
+        "xor eax, eax",
+        "xor ebx, ebx",
+        "xor ecx, ecx",
+        "xor edx, edx",
+        "xor esi, esi",
+        "xor edi, edi",
+        "xor ebp, ebp",
+        "xor r8d, r8d",
+        "xor r9d, r9d",
+        "xor r10d, r10d",
+        "xor r11d, r11d",
+        "xor r12d, r12d",
+        "xor r13d, r13d",
+        "xor r14d, r14d",
+        "xor r15d, r15d",
+        "iretq",
+        ss = in(reg) USER_DATA_SELECTOR as u64,
+        stack = in(reg) stack,
+        rflags = in(reg) USER_RFLAGS,
+        cs = in(reg) USER_CODE_SELECTOR as u64,
+        entry = in(reg) entry,
+        options(noreturn)
+    );
+}
+
+/// Reprend le programme avec les registres de `frame`, par IRETQ
+///
+/// Contrairement à SYSRET, tous les registres sont rétablis (RCX et
+/// R11 compris). Ne revient pas : ce qui est sur la pile du kernel
+/// est abandonné, la prochaine entrée dans le kernel repartira du
+/// haut de la pile.
+///
+/// # Safety
+/// `frame` doit désigner le ring 3 (CS et SS utilisateur), avec un RIP
+/// dans la zone utilisateur et RFLAGS sans IOPL.
+pub unsafe fn resume(frame: &TrapFrame) -> ! {
+    asm!(
+        "cli",
+        "mov rsp, {frame}",
+        "pop r15",
+        "pop r14",
+        "pop r13",
+        "pop r12",
+        "pop r11",
+        "pop r10",
+        "pop r9",
+        "pop r8",
+        "pop rbp",
+        "pop rdi",
+        "pop rsi",
+        "pop rdx",
+        "pop rcx",
+        "pop rbx",
+        "pop rax",
+        // Vecteur et code d'erreur
+        "add rsp, 16",
+        "iretq",
+        frame = in(reg) frame,
+        options(noreturn)
+    );
+}
+
+/// Trame de retour en ring 3 avec les registres `registers`, venus du
+/// programme (trame de signal)
+///
+/// Retourne None si RIP ou RSP sont hors de la zone utilisateur ;
+/// RFLAGS est limité aux bits que le programme peut changer.
+pub fn user_trap_frame(registers: &Registers) -> Option<TrapFrame> {
+    if !is_user_range(registers.rip, 0) || !is_user_range(registers.rsp, 0) {
+        return None;
+    }
+    let mut frame = TrapFrame {
+        cs: USER_CODE_SELECTOR as u64,
+        ss: USER_DATA_SELECTOR as u64,
+        ..TrapFrame::default()
+    };
+    frame.set_registers(registers);
+    frame.rflags = (registers.rflags & USER_FLAGS_MASK) | USER_RFLAGS;
+    Some(frame)
+}
+
+/* =========================================================
+   ESPACE D'ADRESSAGE D'UN PROCESSUS
+========================================================= */
+
+/// Espace d'adressage d'un processus : la partie kernel est partagée,
+/// la zone utilisateur lui appartient et est libérée avec lui
+pub struct UserSpace {
+    space: AddressSpace,
+    /// Début du tas (aligné sur une page)
+    heap_start: u64,
+    /// Fin du tas (brk), pages projetées jusqu'à la page qui la contient
+    brk: u64,
+}
+
+impl UserSpace {
+    /// Crée un espace avec la partie kernel et une zone utilisateur vide
+    pub fn new() -> Result<UserSpace, MapError> {
+        let level_4 = frame::allocate_frame().ok_or(MapError::OutOfFrames)?;
+        let (kernel, table) = unsafe {
+            (
+                &*(memory::phys_to_virt(paging::kernel_level_4_table()) as *const [u64; 512]),
+                &mut *(memory::phys_to_virt(level_4) as *mut [u64; 512]),
+            )
+        };
+        table.copy_from_slice(kernel);
+        // Le kernel ne doit rien projeter dans la zone utilisateur
+        debug_assert!(table[USER_ENTRIES].iter().all(|&entry| entry == 0));
+        table[USER_ENTRIES].fill(0);
+        Ok(UserSpace {
+            space: unsafe { AddressSpace::from_level_4(level_4) },
+            heap_start: USER_START,
+            brk: USER_START,
+        })
+    }
+
+    /// Adresse physique de la PML4 (valeur de CR3 pour le processus)
+    pub fn level_4_table(&self) -> u64 {
+        self.space.level_4_table()
+    }
+
+    /// Projette `size` octets de mémoire neuve, remplie de zéros, à
+    /// partir de `start` (aligné sur une page)
+    pub fn map_zeroed(&mut self, start: u64, size: u64, flags: PageFlags) -> Result<(), MapError> {
+        if !is_user_range(start, size) {
+            return Err(MapError::NotUser(start));
+        }
+        let flags = flags | PageFlags::USER;
+        for page in (0..size.div_ceil(PAGE_SIZE)).map(|i| start + i * PAGE_SIZE) {
+            let frame = frame::allocate_frame().ok_or(MapError::OutOfFrames)?;
+            unsafe {
+                (memory::phys_to_virt(frame) as *mut u8).write_bytes(0, PAGE_SIZE as usize);
+            }
+            if let Err(error) = self.space.map(page, frame, flags) {
+                let _ = frame::free_frame(frame);
+                return Err(error);
+            }
+        }
+        Ok(())
+    }
+
+    /// Projette la pile utilisateur, retourne son sommet
+    pub fn map_stack(&mut self) -> Result<u64, MapError> {
+        let flags = PageFlags::WRITABLE | PageFlags::NO_EXECUTE;
+        self.map_zeroed(USER_STACK_TOP - USER_STACK_SIZE, USER_STACK_SIZE, flags)?;
+        Ok(USER_STACK_TOP)
+    }
+
+    /// Place le début du tas (vide) à `start`, arrondi à la page suivante
+    pub fn set_heap_start(&mut self, start: u64) {
+        self.heap_start = start.div_ceil(PAGE_SIZE) * PAGE_SIZE;
+        self.brk = self.heap_start;
+    }
+
+    /// Fin actuelle du tas
+    pub fn brk(&self) -> u64 {
+        self.brk
+    }
+
+    /// Déplace la fin du tas : projette ou libère les pages nécessaires
+    ///
+    /// Retourne la nouvelle fin, ou l'ancienne si `brk` est hors de la
+    /// zone du tas ou s'il n'y a plus de mémoire.
+    pub fn set_brk(&mut self, brk: u64) -> u64 {
+        if brk < self.heap_start || brk > USER_HEAP_END {
+            return self.brk;
+        }
+        let mapped_end = self.brk.div_ceil(PAGE_SIZE) * PAGE_SIZE;
+        let new_end = brk.div_ceil(PAGE_SIZE) * PAGE_SIZE;
+        if new_end > mapped_end {
+            let flags = PageFlags::WRITABLE | PageFlags::NO_EXECUTE;
+            if self.map_zeroed(mapped_end, new_end - mapped_end, flags).is_err() {
+                // Pages projetées avant l'échec
+                self.unmap_range(mapped_end..new_end);
+                return self.brk;
+            }
+        } else {
+            self.unmap_range(new_end..mapped_end);
+        }
+        self.brk = brk;
+        brk
+    }
+
+    /// Retire les pages de `range` et libère leurs frames
+    fn unmap_range(&mut self, range: Range<u64>) {
+        for page in range.step_by(PAGE_SIZE as usize) {
+            if let Ok(frame) = self.space.unmap(page) {
+                let _ = frame::free_frame(frame);
+            }
+        }
+    }
+
+    /// Copie `data` à l'adresse `virt` de cet espace (actif ou non)
+    pub fn write(&mut self, virt: u64, data: &[u8]) -> Result<(), MapError> {
+        let mut done = 0;
+        while done < data.len() {
+            let address = virt + done as u64;
+            let phys = self.space.translate(address).ok_or(MapError::NotMapped(address))?;
+            let chunk = (PAGE_SIZE - address % PAGE_SIZE).min((data.len() - done) as u64) as usize;
+            unsafe {
+                let target = memory::phys_to_virt(phys) as *mut u8;
+                target.copy_from_nonoverlapping(data[done..].as_ptr(), chunk);
+            }
+            done += chunk;
+        }
+        Ok(())
+    }
+}
+
+impl Drop for UserSpace {
+    fn drop(&mut self) {
+        // Jamais l'espace actif : le processus est terminé et son
+        // thread a été retiré (voir process.rs)
+        debug_assert!(!self.space.is_active());
+        unsafe {
+            self.space.free_entries(USER_ENTRIES);
+        }
+        let _ = frame::free_frame(self.space.level_4_table());
+    }
+}
+
+/* =========================================================
+   ACCÈS À LA MÉMOIRE DU PROGRAMME
+
+   Un pointeur reçu dans un appel système vient du programme :
+   il peut désigner le kernel ou une page absente. On vérifie
+   chaque page dans l'espace actif (celui du processus) avant
+   de copier.
+========================================================= */
+
+/// Vérifie que `len` octets à `start` sont accessibles au programme
+fn check_user(start: u64, len: u64, write: bool) -> Result<(), SyscallError> {
+    if !is_user_range(start, len) {
+        return Err(SyscallError::BadAddress);
+    }
+    if len == 0 {
+        return Ok(());
+    }
+    let space = AddressSpace::current();
+    let mut required = PageFlags::PRESENT | PageFlags::USER;
+    if write {
+        required |= PageFlags::WRITABLE;
+    }
+    let first = start - start % PAGE_SIZE;
+    for page in (first..start + len).step_by(PAGE_SIZE as usize) {
+        match space.flags(page) {
+            Some(flags) if flags.contains(required) => {}
+            _ => return Err(SyscallError::BadAddress),
+        }
+    }
+    Ok(())
+}
+
+/// Copie la mémoire du programme à `src` dans `dst`
+pub fn copy_from_user(dst: &mut [u8], src: u64) -> Result<(), SyscallError> {
+    check_user(src, dst.len() as u64, false)?;
+    unsafe {
+        dst.as_mut_ptr().copy_from_nonoverlapping(src as *const u8, dst.len());
+    }
+    Ok(())
+}
+
+/// Copie `src` dans la mémoire du programme à `dst`
+pub fn copy_to_user(dst: u64, src: &[u8]) -> Result<(), SyscallError> {
+    check_user(dst, src.len() as u64, true)?;
+    unsafe {
+        (dst as *mut u8).copy_from_nonoverlapping(src.as_ptr(), src.len());
+    }
+    Ok(())
+}
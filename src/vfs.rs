@@ -0,0 +1,689 @@
+/* =========================================================
+   SYSTÈME DE FICHIERS VIRTUEL (VFS)
+
+   Le VFS présente tous les systèmes de fichiers sous un seul
+   arbre de chemins. Un système de fichiers (`FileSystem`)
+   fournit sa racine ; chaque fichier est un `Inode` (données,
+   métadonnées) et chaque répertoire un `Directory` (ses
+   entrées : chercher, lister, créer, supprimer).
+
+   La table des montages associe un chemin absolu à un système
+   de fichiers : le répertoire monté est caché par la racine du
+   système de fichiers tant qu'il est monté. L'initrd est monté
+   sur "/" au démarrage, un tmpfs (fichiers en mémoire) sur /tmp.
+
+   Résolution d'un chemin (`lookup`) : depuis la racine (chemin
+   absolu) ou le répertoire courant, composant par composant :
+   - "." est ignoré, ".." remonte d'un niveau (jamais au-dessus
+     de la racine), y compris hors d'un point de montage
+   - un point de montage mène à la racine du système monté
+   - un lien symbolique est remplacé par sa cible (au plus
+     MAX_SYMLINKS par chemin, contre les boucles)
+   Le chemin obtenu (`Resolved::path`) est absolu et sans lien.
+
+   Un fichier ouvert (`OpenFile`) garde sa position et ses
+   options ; chaque processus a sa table de descripteurs
+   (`FdTable`), dont 0, 1 et 2 désignent la console.
+========================================================= */
+
+use alloc::string::String;
+use alloc::sync::Arc;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::any::Any;
+
+use bos_core::vfs::{self, FileType, FsError, Metadata, OpenFlags};
+
+use crate::sync::Spinlock;
+
+/// Nombre maximal de liens symboliques suivis pour un chemin
+pub const MAX_SYMLINKS: usize = 8;
+
+/// Nombre maximal de descripteurs ouverts par processus
+pub const MAX_FILES: usize = 32;
+
+/// Point de montage du tmpfs
+pub const TMP: &str = "/tmp";
+
+/// Un fichier, répertoire ou lien symbolique d'un système de fichiers
+///
+/// Les opérations absentes d'un système de fichiers retournent une
+/// erreur par défaut (lecture seule, pas un lien...). `Any` permet à un
+/// système de fichiers de reconnaître ses propres inodes (rename).
+pub trait Inode: Any + Send + Sync {
+    /// Type, taille, droits et date
+    fn metadata(&self) -> Metadata;
+
+    /// Lit à partir de `offset`, retourne le nombre d'octets lus (0 à
+    /// la fin du fichier)
+    fn read_at(&self, _offset: u64, _buffer: &mut [u8]) -> Result<usize, FsError> {
+        Err(FsError::IsADirectory)
+    }
+
+    /// Écrit à partir de `offset` (le fichier grandit si besoin),
+    /// retourne le nombre d'octets écrits
+    fn write_at(&self, _offset: u64, _buffer: &[u8]) -> Result<usize, FsError> {
+        Err(FsError::ReadOnly)
+    }
+
+    /// Change la taille du fichier (complété de zéros)
+    fn truncate(&self, _size: u64) -> Result<(), FsError> {
+        Err(FsError::ReadOnly)
+    }
+
+    /// Change la date de modification
+    fn set_mtime(&self, _mtime: u64) -> Result<(), FsError> {
+        Err(FsError::ReadOnly)
+    }
+
+    /// Cible d'un lien symbolique
+    fn readlink(&self) -> Result<String, FsError> {
+        Err(FsError::InvalidArgument)
+    }
+
+    /// Opérations de répertoire, si c'en est un
+    fn as_directory(&self) -> Option<&dyn Directory> {
+        None
+    }
+}
+
+/// Entrées d'un répertoire
+pub trait Directory {
+    /// Cherche l'entrée `name` (ni "." ni "..")
+    fn lookup(&self, name: &str) -> Result<Arc<dyn Inode>, FsError>;
+
+    /// Liste les entrées (sans "." ni "..")
+    fn entries(&self) -> Result<Vec<DirEntry>, FsError>;
+
+    /// Crée l'entrée `name` : fichier vide ou répertoire vide
+    fn create(&self, _name: &str, _kind: FileType) -> Result<Arc<dyn Inode>, FsError> {
+        Err(FsError::ReadOnly)
+    }
+
+    /// Supprime l'entrée `name` (un répertoire doit être vide)
+    fn remove(&self, _name: &str) -> Result<(), FsError> {
+        Err(FsError::ReadOnly)
+    }
+
+    /// Renomme l'entrée `name` en `new_name` dans le répertoire `target`
+    /// du même système de fichiers, en remplaçant l'entrée `new_name`
+    /// (un répertoire vide par un répertoire, un fichier par un fichier)
+    fn rename(&self, _name: &str, _target: &dyn Inode, _new_name: &str) -> Result<(), FsError> {
+        Err(FsError::ReadOnly)
+    }
+}
+
+/// Une entrée de répertoire : son nom et son inode
+#[derive(Clone)]
+pub struct DirEntry {
+    pub name: String,
+    pub inode: Arc<dyn Inode>,
+}
+
+/// Un système de fichiers, montable dans l'arbre
+pub trait FileSystem: Send + Sync {
+    /// Type affiché par `mount` ("initrd"...)
+    fn name(&self) -> &'static str;
+
+    /// Répertoire racine
+    fn root(&self) -> Arc<dyn Inode>;
+
+    /// Aucun fichier ne peut y être ouvert en écriture
+    fn is_read_only(&self) -> bool {
+        false
+    }
+}
+
+/// Crée un système de fichiers du type `kind` (commande `mount`)
+pub fn new_filesystem(kind: &str) -> Option<Arc<dyn FileSystem>> {
+    match kind {
+        "initrd" => Some(Arc::new(crate::initrd::Initrd)),
+        "tmpfs" => Some(Arc::new(crate::tmpfs::TmpFs::new())),
+        _ => None,
+    }
+}
+
+/* =========================================================
+   TABLE DES MONTAGES
+========================================================= */
+
+/// Un système de fichiers monté sur `path`
+///
+/// Les fichiers ouverts et les chemins résolus gardent une référence
+/// sur leur montage : `umount` refuse un montage encore utilisé.
+pub struct Mount {
+    /// Chemin absolu du point de montage ("/" pour la racine)
+    pub path: String,
+    pub fs: Arc<dyn FileSystem>,
+}
+
+// Montages, dans l'ordre où ils ont été faits
+static MOUNTS: Spinlock<Vec<Arc<Mount>>> = Spinlock::new(Vec::new());
+
+/// Monte l'initrd sur "/" et un tmpfs sur /tmp
+pub fn init() {
+    {
+        let mut mounts = MOUNTS.lock();
+        if !mounts.is_empty() {
+            return;
+        }
+        mounts.push(Arc::new(Mount { path: String::from("/"), fs: Arc::new(crate::initrd::Initrd) }));
+    }
+    if let Err(error) = mount(TMP, "/", Arc::new(crate::tmpfs::TmpFs::new())) {
+        warn!("Pas de tmpfs sur {} : {}", TMP, error);
+    }
+}
+
+/// Montage dont le point est exactement `path`
+fn mount_at(path: &str) -> Option<Arc<Mount>> {
+    MOUNTS.lock().iter().rev().find(|mount| mount.path == path).cloned()
+}
+
+/// Monte `fs` sur le répertoire `path`
+pub fn mount(path: &str, cwd: &str, fs: Arc<dyn FileSystem>) -> Result<(), FsError> {
+    let target = lookup(path, cwd)?;
+    if target.metadata().kind != FileType::Directory {
+        return Err(FsError::NotADirectory);
+    }
+    let mut mounts = MOUNTS.lock();
+    if mounts.iter().any(|mount| mount.path == target.path) {
+        return Err(FsError::Busy);
+    }
+    mounts.push(Arc::new(Mount { path: target.path, fs }));
+    Ok(())
+}
+
+/// Démonte le système de fichiers monté sur `path`
+///
+/// Refusé pour la racine, un montage qui en contient d'autres ou dont
+/// des fichiers sont ouverts.
+pub fn umount(path: &str, cwd: &str) -> Result<(), FsError> {
+    let target = lookup(path, cwd)?;
+    let path = target.path.clone();
+    drop(target);
+    let mut mounts = MOUNTS.lock();
+    let index = mounts.iter().rposition(|mount| mount.path == path).ok_or(FsError::InvalidArgument)?;
+    let nested = mounts.iter().any(|mount| mount.path != path && is_below(&mount.path, &path));
+    // Une référence dans la table, les autres viennent de fichiers ouverts
+    if path == "/" || nested || Arc::strong_count(&mounts[index]) > 1 {
+        return Err(FsError::Busy);
+    }
+    mounts.remove(index);
+    Ok(())
+}
+
+/// `path` est-il dans le répertoire `dir` ?
+fn is_below(path: &str, dir: &str) -> bool {
+    dir == "/" || path.strip_prefix(dir).is_some_and(|rest| rest.starts_with('/'))
+}
+
+/// Appelle `f` pour chaque montage (chemin, type)
+pub fn for_each_mount(mut f: impl FnMut(&str, &str)) {
+    let mounts: Vec<Arc<Mount>> = MOUNTS.lock().clone();
+    for mount in mounts {
+        f(&mount.path, mount.fs.name());
+    }
+}
+
+/* =========================================================
+   RÉSOLUTION DES CHEMINS
+========================================================= */
+
+/// Un chemin résolu : son chemin absolu sans lien, son inode et le
+/// montage qui le contient
+#[derive(Clone)]
+pub struct Resolved {
+    pub path: String,
+    pub inode: Arc<dyn Inode>,
+    mount: Arc<Mount>,
+}
+
+impl Resolved {
+    pub fn metadata(&self) -> Metadata {
+        self.inode.metadata()
+    }
+
+    /// Répertoire : ses opérations, sinon NotADirectory
+    fn directory(&self) -> Result<&dyn Directory, FsError> {
+        self.inode.as_directory().ok_or(FsError::NotADirectory)
+    }
+}
+
+/// Résout `path` (relatif à `cwd`, un chemin absolu) en suivant les
+/// liens symboliques
+pub fn lookup(path: &str, cwd: &str) -> Result<Resolved, FsError> {
+    resolve(path, cwd, true)
+}
+
+/// Comme `lookup`, sans suivre un lien symbolique en dernier composant
+pub fn lookup_nofollow(path: &str, cwd: &str) -> Result<Resolved, FsError> {
+    resolve(path, cwd, false)
+}
+
+fn resolve(path: &str, cwd: &str, follow_last: bool) -> Result<Resolved, FsError> {
+    if path.len() > vfs::MAX_PATH {
+        return Err(FsError::NameTooLong);
+    }
+    let root = mount_at("/").ok_or(FsError::NotFound)?;
+    let root = Resolved { path: String::from("/"), inode: root.fs.root(), mount: root };
+    // Chemin parcouru : la racine puis chaque répertoire traversé
+    let mut stack = vec![root];
+    // Composants restant à parcourir, le prochain à la fin
+    let mut pending: Vec<String> = Vec::new();
+    push_components(&mut pending, path);
+    if !vfs::is_absolute(path) {
+        push_components(&mut pending, cwd);
+    }
+    let mut followed = 0;
+    while let Some(name) = pending.pop() {
+        if name == ".." {
+            if stack.len() > 1 {
+                stack.pop();
+            }
+            continue;
+        }
+        let parent = stack.last().unwrap();
+        let inode = parent.directory()?.lookup(&name)?;
+        let path = child_path(&parent.path, &name);
+        let child = match mount_at(&path) {
+            Some(mount) => Resolved { path, inode: mount.fs.root(), mount },
+            None => Resolved { path, inode, mount: parent.mount.clone() },
+        };
+        if child.metadata().kind == FileType::Symlink && (follow_last || !pending.is_empty()) {
+            followed += 1;
+            if followed > MAX_SYMLINKS {
+                return Err(FsError::TooManyLinks);
+            }
+            // La cible remplace le lien, depuis la racine si elle est absolue
+            let target = child.inode.readlink()?;
+            if vfs::is_absolute(&target) {
+                stack.truncate(1);
+            }
+            push_components(&mut pending, &target);
+            continue;
+        }
+        stack.push(child);
+    }
+    Ok(stack.pop().unwrap())
+}
+
+/// Chemin de l'entrée `name` du répertoire absolu `parent`
+fn child_path(parent: &str, name: &str) -> String {
+    let mut path = String::from(parent);
+    if path != "/" {
+        path.push('/');
+    }
+    path.push_str(name);
+    path
+}
+
+/// Ajoute les composants de `path` à parcourir avant ceux de `pending`
+fn push_components(pending: &mut Vec<String>, path: &str) {
+    pending.extend(vfs::components(path).rev().map(String::from));
+}
+
+/// Résout le répertoire parent de `path` ; retourne aussi le dernier nom
+fn lookup_parent<'a>(path: &'a str, cwd: &str) -> Result<(Resolved, &'a str), FsError> {
+    let (parent, name) = vfs::split_last(path).ok_or(FsError::InvalidArgument)?;
+    vfs::check_name(name)?;
+    let parent = lookup(parent, cwd)?;
+    parent.directory()?;
+    Ok((parent, name))
+}
+
+/// Métadonnées de `path` (celles de la cible pour un lien)
+pub fn stat(path: &str, cwd: &str) -> Result<Metadata, FsError> {
+    Ok(lookup(path, cwd)?.metadata())
+}
+
+/// Cible du lien symbolique `path`
+pub fn readlink(path: &str, cwd: &str) -> Result<String, FsError> {
+    lookup_nofollow(path, cwd)?.inode.readlink()
+}
+
+/// Entrées du répertoire `path`
+pub fn read_dir(path: &str, cwd: &str) -> Result<Vec<DirEntry>, FsError> {
+    lookup(path, cwd)?.directory()?.entries()
+}
+
+/// Lit tout le fichier `path`
+pub fn read_file(path: &str, cwd: &str) -> Result<Vec<u8>, FsError> {
+    let file = open(path, cwd, OpenFlags::from_raw(vfs::O_RDONLY)?)?;
+    let mut data = vec![0u8; file.metadata().size as usize];
+    let mut done = 0;
+    while done < data.len() {
+        match file.read(&mut data[done..])? {
+            0 => break,
+            count => done += count,
+        }
+    }
+    data.truncate(done);
+    Ok(data)
+}
+
+/// Crée le répertoire `path`
+pub fn mkdir(path: &str, cwd: &str) -> Result<(), FsError> {
+    let (parent, name) = lookup_parent(path, cwd)?;
+    parent.directory()?.create(name, FileType::Directory).map(|_| ())
+}
+
+/// Supprime le fichier, le lien ou le répertoire vide `path`
+///
+/// Un point de montage ne peut pas être supprimé.
+pub fn remove(path: &str, cwd: &str) -> Result<(), FsError> {
+    let target = lookup_nofollow(path, cwd)?;
+    if mount_at(&target.path).is_some() {
+        return Err(FsError::Busy);
+    }
+    let (parent, name) = lookup_parent(path, cwd)?;
+    parent.directory()?.remove(name)
+}
+
+/// Renomme ou déplace `old` en `new` (dans le même système de
+/// fichiers) ; un fichier ou répertoire vide `new` est remplacé
+pub fn rename(old: &str, new: &str, cwd: &str) -> Result<(), FsError> {
+    let source = lookup_nofollow(old, cwd)?;
+    let (old_parent, old_name) = lookup_parent(old, cwd)?;
+    let (new_parent, new_name) = lookup_parent(new, cwd)?;
+    let new_path = child_path(&new_parent.path, new_name);
+    if mount_at(&source.path).is_some() || mount_at(&new_path).is_some() {
+        return Err(FsError::Busy);
+    }
+    if !Arc::ptr_eq(&old_parent.mount, &new_parent.mount) {
+        return Err(FsError::CrossDevice);
+    }
+    // Un répertoire ne peut pas entrer dans son propre sous-arbre
+    if is_below(&new_path, &source.path) {
+        return Err(FsError::InvalidArgument);
+    }
+    old_parent.directory()?.rename(old_name, new_parent.inode.as_ref(), new_name)
+}
+
+/// Crée le fichier vide `path` s'il n'existe pas, sinon met sa date de
+/// modification à l'heure courante
+pub fn touch(path: &str, cwd: &str) -> Result<(), FsError> {
+    match lookup(path, cwd) {
+        Ok(resolved) if resolved.mount.fs.is_read_only() => Err(FsError::ReadOnly),
+        Ok(resolved) => resolved.inode.set_mtime(crate::rtc::now()),
+        Err(FsError::NotFound) => open(path, cwd, OpenFlags::from_raw(vfs::O_WRONLY | vfs::O_CREAT)?).map(|_| ()),
+        Err(error) => Err(error),
+    }
+}
+
+/// Écrit `data` dans le fichier `path`, créé s'il n'existe pas : à la
+/// place de son contenu, ou à la fin avec `append`
+pub fn write_file(path: &str, cwd: &str, data: &[u8], append: bool) -> Result<(), FsError> {
+    let mode = match append {
+        true => vfs::O_APPEND,
+        false => vfs::O_TRUNC,
+    };
+    let file = open(path, cwd, OpenFlags::from_raw(vfs::O_WRONLY | vfs::O_CREAT | mode)?)?;
+    let mut done = 0;
+    while done < data.len() {
+        match file.write(&data[done..])? {
+            0 => return Err(FsError::NoSpace),
+            count => done += count,
+        }
+    }
+    Ok(())
+}
+
+/* =========================================================
+   FICHIERS OUVERTS
+========================================================= */
+
+/// Un fichier ouvert : l'inode, les options et la position
+pub struct OpenFile {
+    path: String,
+    inode: Arc<dyn Inode>,
+    flags: OpenFlags,
+    offset: Spinlock<u64>,
+    // Empêche le démontage tant que le fichier est ouvert
+    _mount: Arc<Mount>,
+}
+
+/// Ouvre `path` selon `flags` (O_CREAT le crée s'il n'existe pas)
+pub fn open(path: &str, cwd: &str, flags: OpenFlags) -> Result<Arc<OpenFile>, FsError> {
+    let resolved = match lookup(path, cwd) {
+        Ok(_) if flags.create() && flags.exclusive() => return Err(FsError::AlreadyExists),
+        Ok(resolved) => resolved,
+        Err(FsError::NotFound) if flags.create() => {
+            let (parent, name) = lookup_parent(path, cwd)?;
+            let inode = parent.directory()?.create(name, FileType::File)?;
+            Resolved { path: child_path(&parent.path, name), inode, mount: parent.mount }
+        }
+        Err(error) => return Err(error),
+    };
+    if flags.writable() {
+        if resolved.metadata().kind == FileType::Directory {
+            return Err(FsError::IsADirectory);
+        }
+        if resolved.mount.fs.is_read_only() {
+            return Err(FsError::ReadOnly);
+        }
+    }
+    if flags.truncate() && flags.writable() {
+        resolved.inode.truncate(0)?;
+    }
+    Ok(Arc::new(OpenFile {
+        path: resolved.path,
+        inode: resolved.inode,
+        flags,
+        offset: Spinlock::new(0),
+        _mount: resolved.mount,
+    }))
+}
+
+impl OpenFile {
+    /// Chemin absolu du fichier
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    pub fn metadata(&self) -> Metadata {
+        self.inode.metadata()
+    }
+
+    /// Lit à la position courante et avance
+    pub fn read(&self, buffer: &mut [u8]) -> Result<usize, FsError> {
+        if !self.flags.readable() {
+            return Err(FsError::BadFileDescriptor);
+        }
+        let mut offset = self.offset.lock();
+        let count = self.inode.read_at(*offset, buffer)?;
+        *offset += count as u64;
+        Ok(count)
+    }
+
+    /// Écrit à la position courante (à la fin avec O_APPEND) et avance
+    pub fn write(&self, buffer: &[u8]) -> Result<usize, FsError> {
+        if !self.flags.writable() {
+            return Err(FsError::BadFileDescriptor);
+        }
+        let mut offset = self.offset.lock();
+        if self.flags.append() {
+            *offset = self.inode.metadata().size;
+        }
+        let count = self.inode.write_at(*offset, buffer)?;
+        *offset += count as u64;
+        Ok(count)
+    }
+
+    /// Déplace la position (lseek), retourne la nouvelle
+    pub fn seek(&self, offset: i64, whence: u64) -> Result<u64, FsError> {
+        let mut current = self.offset.lock();
+        *current = vfs::seek_position(*current, self.inode.metadata().size, offset, whence)?;
+        Ok(*current)
+    }
+}
+
+/* =========================================================
+   DESCRIPTEURS DE FICHIERS
+========================================================= */
+
+/// Ce que désigne un descripteur
+#[derive(Clone)]
+pub enum Descriptor {
+    /// Le clavier en lecture, l'écran en écriture
+    Console,
+    File(Arc<OpenFile>),
+}
+
+/// Table des descripteurs d'un processus
+pub struct FdTable {
+    slots: Vec<Option<Descriptor>>,
+}
+
+impl FdTable {
+    /// Table d'un nouveau processus : 0, 1 et 2 sur la console
+    pub fn new() -> FdTable {
+        FdTable { slots: vec![Some(Descriptor::Console); 3] }
+    }
+
+    /// Ajoute `descriptor` au premier numéro libre, le retourne
+    pub fn insert(&mut self, descriptor: Descriptor) -> Result<usize, FsError> {
+        match self.slots.iter().position(Option::is_none) {
+            Some(fd) => {
+                self.slots[fd] = Some(descriptor);
+                Ok(fd)
+            }
+            None if self.slots.len() < MAX_FILES => {
+                self.slots.push(Some(descriptor));
+                Ok(self.slots.len() - 1)
+            }
+            None => Err(FsError::TooManyFiles),
+        }
+    }
+
+    /// Ce que désigne `fd`
+    pub fn get(&self, fd: usize) -> Result<Descriptor, FsError> {
+        self.slots.get(fd).cloned().flatten().ok_or(FsError::BadFileDescriptor)
+    }
+
+    /// Ferme `fd`
+    pub fn close(&mut self, fd: usize) -> Result<(), FsError> {
+        let slot = self.slots.get_mut(fd).ok_or(FsError::BadFileDescriptor)?;
+        slot.take().map(|_| ()).ok_or(FsError::BadFileDescriptor)
+    }
+
+    /// Nombre de descripteurs ouverts
+    pub fn len(&self) -> usize {
+        self.slots.iter().filter(|slot| slot.is_some()).count()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl Default for FdTable {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// ===================================================================
+// TESTS
+// ===================================================================
+
+#[test_case]
+fn test_vfs_path_resolution() {
+    assert_eq!(lookup("/", "/").unwrap().path, "/");
+    assert_eq!(lookup("etc/motd", "/").unwrap().path, "/etc/motd");
+    assert_eq!(lookup("../etc/./motd", "/bin").unwrap().path, "/etc/motd");
+    assert_eq!(lookup("/../../etc", "/").unwrap().path, "/etc");
+    // `/motd` est un lien vers etc/motd
+    assert_eq!(lookup("/motd", "/").unwrap().path, "/etc/motd");
+    assert_eq!(lookup_nofollow("/motd", "/").unwrap().metadata().kind, FileType::Symlink);
+    assert_eq!(readlink("/motd", "/").as_deref(), Ok("etc/motd"));
+    assert_eq!(lookup("/etc/motd/x", "/").err(), Some(FsError::NotADirectory));
+    assert_eq!(lookup("/nulle/part", "/").err(), Some(FsError::NotFound));
+}
+
+#[test_case]
+fn test_vfs_open_files() {
+    use bos_core::vfs::{O_RDONLY, O_WRONLY, SEEK_SET};
+
+    let file = open("/etc/motd", "/", OpenFlags::from_raw(O_RDONLY).unwrap()).unwrap();
+    let mut start = [0u8; 9];
+    assert_eq!(file.read(&mut start), Ok(9));
+    assert_eq!(&start, b"Bienvenue");
+    assert_eq!(file.seek(1, SEEK_SET), Ok(1));
+    assert_eq!(file.read(&mut start[..3]), Ok(3));
+    assert_eq!(&start[..3], b"ien");
+    assert_eq!(file.write(b"x"), Err(FsError::BadFileDescriptor));
+    assert_eq!(read_file("/etc/motd", "/").unwrap().len() as u64, file.metadata().size);
+    // L'initrd est en lecture seule
+    assert_eq!(open("/etc/motd", "/", OpenFlags::from_raw(O_WRONLY).unwrap()).err(), Some(FsError::ReadOnly));
+    assert_eq!(mkdir("/nouveau", "/"), Err(FsError::ReadOnly));
+    assert_eq!(remove("/etc/motd", "/"), Err(FsError::ReadOnly));
+}
+
+#[test_case]
+fn test_vfs_mounts() {
+    let fs = new_filesystem("initrd").unwrap();
+    assert!(new_filesystem("inconnu").is_none());
+    mount("/mnt", "/", fs.clone()).unwrap();
+    assert_eq!(mount("/mnt", "/", fs).err(), Some(FsError::Busy));
+    assert_eq!(lookup("/mnt/etc/motd", "/").unwrap().path, "/mnt/etc/motd");
+    // ".." depuis la racine du montage revient au répertoire parent
+    assert_eq!(lookup("/mnt/..", "/").unwrap().path, "/");
+    assert_eq!(remove("/mnt", "/"), Err(FsError::Busy));
+    let file = open("/mnt/etc/motd", "/", OpenFlags::from_raw(0).unwrap()).unwrap();
+    assert_eq!(umount("/mnt", "/"), Err(FsError::Busy));
+    drop(file);
+    assert_eq!(umount("/mnt", "/"), Ok(()));
+    assert_eq!(lookup("/mnt/etc", "/").err(), Some(FsError::NotFound));
+    assert_eq!(umount("/", "/"), Err(FsError::Busy));
+}
+
+#[test_case]
+fn test_vfs_tmpfs() {
+    use bos_core::vfs::{O_CREAT, O_RDWR, SEEK_SET};
+
+    assert_eq!(lookup(TMP, "/").unwrap().mount.fs.name(), "tmpfs");
+    mkdir("/tmp/vfs", "/").unwrap();
+    assert_eq!(mkdir("/tmp/vfs", "/"), Err(FsError::AlreadyExists));
+    write_file("/tmp/vfs/a", "/", b"un\n", false).unwrap();
+    write_file("vfs/a", "/tmp", b"deux\n", true).unwrap();
+    assert_eq!(read_file("/tmp/vfs/a", "/").unwrap(), b"un\ndeux\n");
+    // Position, écriture au milieu et troncature par open
+    let file = open("/tmp/vfs/b", "/", OpenFlags::from_raw(O_RDWR | O_CREAT).unwrap()).unwrap();
+    assert_eq!(file.write(b"abcdef"), Ok(6));
+    assert_eq!(file.seek(2, SEEK_SET), Ok(2));
+    assert_eq!(file.write(b"XY"), Ok(2));
+    assert_eq!(read_file("/tmp/vfs/b", "/").unwrap(), b"abXYef");
+    write_file("/tmp/vfs/b", "/", b"z", false).unwrap();
+    assert_eq!(file.metadata().size, 1);
+    drop(file);
+    // Renommages
+    rename("/tmp/vfs/a", "/tmp/c", "/").unwrap();
+    assert_eq!(stat("/tmp/vfs/a", "/").err(), Some(FsError::NotFound));
+    assert_eq!(rename("/tmp/c", "/etc/c", "/"), Err(FsError::CrossDevice));
+    assert_eq!(rename("/tmp/vfs", "/tmp/vfs/d", "/"), Err(FsError::InvalidArgument));
+    assert_eq!(rename("/tmp", "/tmp2", "/"), Err(FsError::Busy));
+    assert_eq!(remove("/tmp/vfs", "/"), Err(FsError::NotEmpty));
+    // touch crée un fichier vide ou le date
+    touch("/tmp/vfs/e", "/").unwrap();
+    assert_eq!(stat("/tmp/vfs/e", "/").unwrap().size, 0);
+    assert!(stat("/tmp/vfs/e", "/").unwrap().mtime > 0);
+    assert_eq!(touch("/etc/motd", "/"), Err(FsError::ReadOnly));
+    for path in ["/tmp/vfs/b", "/tmp/vfs/e", "/tmp/c", "/tmp/vfs"] {
+        remove(path, "/").unwrap();
+    }
+}
+
+#[test_case]
+fn test_fd_table() {
+    let mut table = FdTable::new();
+    assert_eq!(table.len(), 3);
+    assert!(matches!(table.get(1), Ok(Descriptor::Console)));
+    assert_eq!(table.close(1), Ok(()));
+    assert_eq!(table.close(1), Err(FsError::BadFileDescriptor));
+    let file = open("/etc/motd", "/", OpenFlags::from_raw(0).unwrap()).unwrap();
+    assert_eq!(table.insert(Descriptor::File(file.clone())), Ok(1));
+    for _ in 3..MAX_FILES {
+        table.insert(Descriptor::File(file.clone())).unwrap();
+    }
+    assert_eq!(table.insert(Descriptor::Console), Err(FsError::TooManyFiles));
+}
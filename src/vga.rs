@@ -0,0 +1,235 @@
+// ===================================================================
+// AFFICHAGE VGA EN MODE TEXTE POUR BOS
+// ===================================================================
+//
+// Ce module regroupe tout ce qui concerne l'écran :
+// - L'écriture de texte dans le buffer VGA
+// - Le curseur logiciel (CURSOR) et le curseur matériel clignotant
+// - Les macros `print!` et `println!` pour le texte formaté
+
+use core::fmt;
+
+use bos_core::console;
+
+use crate::outb;
+use crate::sync::Spinlock;
+
+/* =========================================================
+   VGA TEXT MODE (0xb8000)
+   
+   Le mode texte VGA permet d'afficher du texte à l'écran.
+   La mémoire vidéo est mappée à l'adresse physique 0xb8000.
+   
+   Format : [caractère][couleur][caractère][couleur]...
+   - 1 octet pour le caractère ASCII
+   - 1 octet pour les attributs de couleur (4 bits fond, 4 bits texte)
+   
+   Résolution : 80 colonnes × 25 lignes = 2000 caractères
+   Taille totale : 2000 × 2 octets = 4000 octets
+========================================================= */
+
+// Adresse mémoire du buffer VGA en mode texte
+pub const VGA_BUFFER: *mut u8 = 0xb8000 as *mut u8;
+
+// Dimensions de l'écran en mode texte VGA
+// (l'arithmétique du curseur est dans bos_core::console, testée sur l'hôte)
+pub const VGA_WIDTH: usize = console::WIDTH;   // Nombre de colonnes
+pub const VGA_HEIGHT: usize = console::HEIGHT; // Nombre de lignes
+
+// Taille totale du buffer VGA (2 octets par caractère : 1 pour le char, 1 pour la couleur)
+pub const VGA_SIZE: usize = console::SIZE;
+
+// Position actuelle du curseur (en octets, donc multiple de 2)
+// Le verrou est tenu pendant toute une écriture : deux threads (ou un
+// thread et un handler d'interruption) ne mélangent pas leurs caractères
+static CURSOR: Spinlock<usize> = Spinlock::new(0);
+
+// Attributs de couleur : 0x[fond][texte]
+// Couleurs : 0=noir, 4=rouge, 7=gris clair, C=rouge clair, E=jaune, F=blanc
+pub const COLOR_DEFAULT: u8 = 0x0f;   // Blanc sur fond noir
+pub const COLOR_ERROR: u8 = 0x0c;     // Rouge clair sur fond noir
+pub const COLOR_WARNING: u8 = 0x0e;   // Jaune sur fond noir
+pub const COLOR_DEBUG: u8 = 0x07;     // Gris clair sur fond noir
+pub const COLOR_BANNER: u8 = 0x4f;    // Blanc sur fond rouge
+
+/// Affiche une chaîne de caractères à l'écran en utilisant le mode texte VGA
+/// 
+/// Gère automatiquement :
+/// - Le retour à la ligne (\n)
+/// - Le dépassement de l'écran (wrap au début)
+pub fn vga_print(s: &str) {
+    vga_print_color(s, COLOR_DEFAULT);
+}
+
+/// Affiche une chaîne de caractères avec un attribut de couleur donné
+pub fn vga_print_color(s: &str, color: u8) {
+    let mut cursor = CURSOR.lock();
+    for byte in s.bytes() {
+        put_byte(&mut cursor, byte, color);
+    }
+    // Mettre à jour le curseur matériel pour qu'il clignote à la bonne position
+    set_hardware_cursor(*cursor);
+}
+
+/// Affiche un seul caractère à l'écran
+/// Utilisé par le shell pour afficher les touches pressées en temps réel
+pub fn vga_print_char(c: char) {
+    let mut cursor = CURSOR.lock();
+    put_byte(&mut cursor, c as u8, COLOR_DEFAULT);
+    set_hardware_cursor(*cursor);
+}
+
+/// Écrit un octet à la position du curseur et avance
+fn put_byte(cursor: &mut usize, byte: u8, color: u8) {
+    // Vérifier si on dépasse la taille de l'écran (retour au début)
+    *cursor = console::wrap(*cursor);
+
+    // Gérer le retour à la ligne
+    if byte == b'\n' {
+        // Passer au début de la ligne suivante
+        *cursor = console::newline(*cursor);
+    } else {
+        unsafe {
+            // Écrire le caractère à la position actuelle
+            *VGA_BUFFER.add(*cursor) = byte;
+            // Écrire l'attribut de couleur (0x0f = blanc sur fond noir par défaut)
+            // Format : 0x[fond][texte] où 0=noir, f=blanc
+            *VGA_BUFFER.add(*cursor + 1) = color;
+        }
+        // Avancer de 2 octets (caractère + couleur)
+        *cursor = console::advance(*cursor);
+    }
+}
+
+/// Efface le dernier caractère affiché (backspace)
+/// 
+/// Recule le curseur et affiche un espace pour effacer visuellement le caractère
+pub fn vga_backspace() {
+    let mut cursor = CURSOR.lock();
+    if let Some(previous) = console::backspace(*cursor) {
+        // Reculer de 2 octets (caractère + couleur)
+        *cursor = previous;
+        unsafe {
+            // Effacer en écrivant un espace
+            *VGA_BUFFER.add(previous) = b' ';
+            *VGA_BUFFER.add(previous + 1) = COLOR_DEFAULT;
+        }
+        // Mettre à jour le curseur matériel
+        set_hardware_cursor(previous);
+    }
+}
+
+/// Position actuelle du curseur (en octets)
+pub fn cursor() -> usize {
+    *CURSOR.lock()
+}
+
+/// Déplace le curseur (position en octets) et le curseur matériel
+pub fn set_cursor(position: usize) {
+    let mut cursor = CURSOR.lock();
+    *cursor = position;
+    set_hardware_cursor(position);
+}
+
+/// Libère le verrou de l'écran
+///
+/// # Safety
+/// Réservé au panic handler : le code interrompu au milieu d'un
+/// affichage ne reprendra jamais la main.
+pub unsafe fn force_unlock() {
+    CURSOR.force_unlock();
+}
+
+/// Met à jour la position du curseur matériel VGA (le curseur clignotant)
+pub fn update_hardware_cursor() {
+    let cursor = CURSOR.lock();
+    set_hardware_cursor(*cursor);
+}
+
+/// Place le curseur matériel à une position (en octets)
+/// 
+/// Le curseur matériel est contrôlé via les ports VGA 0x3D4 (commande) et 0x3D5 (données).
+/// Il attend une position en nombre de caractères (pas d'octets), d'où la division par 2.
+fn set_hardware_cursor(cursor: usize) {
+    unsafe {
+        // Convertir la position en octets vers position en caractères
+        let pos = console::hardware_position(cursor);
+        
+        // Port de commande VGA : sélectionner le registre "Cursor Location Low"
+        outb(0x3D4, 0x0F);
+        // Port de données VGA : envoyer les 8 bits de poids faible
+        outb(0x3D5, (pos & 0xFF) as u8);
+        
+        // Port de commande VGA : sélectionner le registre "Cursor Location High"
+        outb(0x3D4, 0x0E);
+        // Port de données VGA : envoyer les 8 bits de poids fort
+        outb(0x3D5, ((pos >> 8) & 0xFF) as u8);
+    }
+}
+
+/// Écrivain pour `core::fmt` qui affiche le texte formaté à l'écran
+pub struct VgaWriter;
+
+impl fmt::Write for VgaWriter {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        vga_print(s);
+        Ok(())
+    }
+}
+
+/// Fonction utilisée par les macros `print!` et `println!`
+#[doc(hidden)]
+pub fn _print(args: fmt::Arguments) {
+    use core::fmt::Write;
+    let _ = VgaWriter.write_fmt(args);
+}
+
+/// Affiche du texte formaté à l'écran (comme `print!` de std)
+#[macro_export]
+macro_rules! print {
+    ($($arg:tt)*) => {
+        $crate::vga::_print(format_args!($($arg)*))
+    };
+}
+
+/// Affiche du texte formaté à l'écran suivi d'un retour à la ligne
+#[macro_export]
+macro_rules! println {
+    () => ($crate::print!("\n"));
+    ($fmt:expr) => ($crate::print!(concat!($fmt, "\n")));
+    ($fmt:expr, $($arg:tt)*) => ($crate::print!(concat!($fmt, "\n"), $($arg)*));
+}
+
+// ===================================================================
+// TESTS
+// ===================================================================
+
+#[test_case]
+fn test_vga_print_simple() {
+    vga_print("test_vga_print_simple\n");
+}
+
+#[test_case]
+fn test_vga_print_many_lines() {
+    // Plus de lignes que l'écran n'en contient : le curseur doit revenir au début
+    for _ in 0..(VGA_HEIGHT * 2) {
+        vga_print("ligne\n");
+    }
+}
+
+#[test_case]
+fn test_vga_print_char_and_backspace() {
+    vga_print("\n");
+    let start = cursor();
+    vga_print_char('B');
+    unsafe {
+        assert_eq!(*VGA_BUFFER.add(start), b'B');
+        assert_eq!(*VGA_BUFFER.add(start + 1), 0x0f);
+    }
+    assert_eq!(cursor(), start + 2);
+    vga_backspace();
+    unsafe {
+        assert_eq!(*VGA_BUFFER.add(start), b' ');
+    }
+    assert_eq!(cursor(), start);
+}
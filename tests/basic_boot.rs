@@ -0,0 +1,86 @@
+// ===================================================================
+// TEST D'INTÉGRATION : DÉMARRAGE ET AFFICHAGE VGA
+// ===================================================================
+//
+// Kernel minimal qui démarre sans aucune initialisation (ni GDT, ni IDT)
+// et vérifie que l'affichage VGA fonctionne dès le boot, en relisant
+// directement la mémoire vidéo à l'adresse 0xb8000.
+
+#![no_std]
+#![no_main]
+#![feature(custom_test_frameworks)]
+#![test_runner(bos::test_runner)]
+#![reexport_test_harness_main = "test_main"]
+
+use core::panic::PanicInfo;
+
+use bos::vga::{VGA_BUFFER, VGA_SIZE, VGA_WIDTH};
+use bos::{println, vga_print};
+
+#[no_mangle]
+pub extern "C" fn _start() -> ! {
+    bos::serial::init_serial();
+    test_main();
+    bos::hlt_loop();
+}
+
+#[panic_handler]
+fn panic(info: &PanicInfo) -> ! {
+    bos::test_panic_handler(info)
+}
+
+/// Position actuelle du curseur VGA (en octets)
+fn cursor() -> usize {
+    bos::vga::cursor()
+}
+
+/// Lit le caractère affiché à une position (en octets) du buffer VGA
+fn char_at(pos: usize) -> u8 {
+    unsafe { VGA_BUFFER.add(pos).read_volatile() }
+}
+
+/// Lit l'attribut de couleur à une position (en octets) du buffer VGA
+fn color_at(pos: usize) -> u8 {
+    unsafe { VGA_BUFFER.add(pos + 1).read_volatile() }
+}
+
+#[test_case]
+fn test_println_after_boot() {
+    println!("test_println_after_boot");
+}
+
+#[test_case]
+fn test_vga_print_readback() {
+    // Commencer en début de ligne pour ne pas dépasser la fin de l'écran
+    vga_print("\n");
+    let start = cursor();
+    let s = "Texte relu depuis 0xb8000";
+    vga_print(s);
+    for (i, byte) in s.bytes().enumerate() {
+        assert_eq!(char_at(start + i * 2), byte);
+        assert_eq!(color_at(start + i * 2), 0x0f);
+    }
+    assert_eq!(cursor(), start + s.len() * 2);
+}
+
+#[test_case]
+fn test_newline_moves_to_next_line() {
+    vga_print("\n");
+    let start = cursor();
+    vga_print("abc\n");
+    let line_bytes = VGA_WIDTH * 2;
+    assert_eq!(cursor(), (start + line_bytes) % VGA_SIZE);
+    assert_eq!(cursor() % line_bytes, 0);
+}
+
+#[test_case]
+fn test_screen_wraps_to_top() {
+    // Remplir tout l'écran : le curseur doit revenir en haut à gauche
+    vga_print("\n");
+    while cursor() != 0 {
+        vga_print("\n");
+    }
+    vga_print("X");
+    assert_eq!(char_at(0), b'X');
+    assert_eq!(cursor(), 2);
+}
@@ -0,0 +1,94 @@
+// ===================================================================
+// TEST D'INTÉGRATION : IDT, PIC ET EXCEPTIONS
+// ===================================================================
+//
+// Vérifie que `init_idt` et `init_pic` configurent correctement le CPU
+// et les contrôleurs d'interruptions, et qu'une exception breakpoint
+// (INT3) est traitée puis que l'exécution reprend normalement.
+
+#![no_std]
+#![no_main]
+#![feature(custom_test_frameworks)]
+#![test_runner(bos::test_runner)]
+#![reexport_test_harness_main = "test_main"]
+
+use core::arch::asm;
+use core::panic::PanicInfo;
+
+use bootloader::{entry_point, BootInfo};
+use bos::gdt::DOUBLE_FAULT_IST_INDEX;
+use bos::inb;
+use bos::interrupts::{
+    current_idt, IdtEntry, BREAKPOINT_VECTOR, DOUBLE_FAULT_VECTOR, KEYBOARD_VECTOR,
+    PAGE_FAULT_VECTOR, PIC1_DATA, PIC2_DATA, TIMER_VECTOR,
+};
+
+entry_point!(main);
+
+fn main(boot_info: &'static BootInfo) -> ! {
+    bos::init(boot_info);
+    test_main();
+    bos::hlt_loop();
+}
+
+#[panic_handler]
+fn panic(info: &PanicInfo) -> ! {
+    bos::test_panic_handler(info)
+}
+
+/// Lit une entrée de l'IDT actuellement chargée dans le CPU
+fn loaded_idt_entry(vector: usize) -> IdtEntry {
+    let desc = current_idt();
+    unsafe { *(desc.offset as *const IdtEntry).add(vector) }
+}
+
+#[test_case]
+fn test_idt_is_loaded() {
+    let desc = current_idt();
+    let size = desc.size;
+    assert_eq!(size as usize, core::mem::size_of::<[IdtEntry; 256]>() - 1);
+}
+
+#[test_case]
+fn test_idt_entries_present() {
+    for vector in [BREAKPOINT_VECTOR, DOUBLE_FAULT_VECTOR, PAGE_FAULT_VECTOR, TIMER_VECTOR, KEYBOARD_VECTOR] {
+        let entry = loaded_idt_entry(vector);
+        assert!(entry.is_present());
+        assert_ne!(entry.handler_addr(), 0);
+    }
+    // Les vecteurs non configurés doivent rester absents
+    assert!(!loaded_idt_entry(200).is_present());
+}
+
+#[test_case]
+fn test_double_fault_uses_ist() {
+    let entry = loaded_idt_entry(DOUBLE_FAULT_VECTOR);
+    assert_eq!(entry.stack_index(), DOUBLE_FAULT_IST_INDEX);
+}
+
+#[test_case]
+fn test_pic_masks() {
+    // Seuls le timer (IRQ 0) et le clavier (IRQ 1) doivent être démasqués
+    unsafe {
+        assert_eq!(inb(PIC1_DATA), 0xFC);
+        assert_eq!(inb(PIC2_DATA), 0xFF);
+    }
+}
+
+#[test_case]
+fn test_breakpoint_exception_returns() {
+    // Le handler de breakpoint doit rendre la main juste après INT3
+    unsafe {
+        asm!("int3", options(nomem, nostack));
+    }
+}
+
+#[test_case]
+fn test_interrupts_enabled() {
+    let rflags: u64;
+    unsafe {
+        asm!("pushfq", "pop {}", out(reg) rflags, options(nomem, preserves_flags));
+    }
+    // Bit 9 de RFLAGS = IF (interruptions activées)
+    assert!(rflags & (1 << 9) != 0);
+}
@@ -0,0 +1,33 @@
+// ===================================================================
+// TEST D'INTÉGRATION : UN PANIC DOIT ÊTRE DÉTECTÉ
+// ===================================================================
+//
+// Ce kernel vérifie l'inverse des autres tests : il réussit seulement
+// si le code panique. Le panic handler quitte donc QEMU avec succès,
+// et atteindre la fin de `_start` est un échec.
+
+#![no_std]
+#![no_main]
+
+use core::panic::PanicInfo;
+
+use bos::{exit_qemu, serial_print, serial_println, QemuExitCode};
+
+#[no_mangle]
+pub extern "C" fn _start() -> ! {
+    bos::serial::init_serial();
+    should_fail();
+    serial_println!("[test did not panic]");
+    exit_qemu(QemuExitCode::Failed);
+}
+
+fn should_fail() {
+    serial_print!("should_panic::should_fail...\t");
+    assert_eq!(0, 1);
+}
+
+#[panic_handler]
+fn panic(_info: &PanicInfo) -> ! {
+    serial_println!("[ok]");
+    exit_qemu(QemuExitCode::Success);
+}
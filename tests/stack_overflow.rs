@@ -0,0 +1,70 @@
+// ===================================================================
+// TEST D'INTÉGRATION : DÉBORDEMENT DE PILE → DOUBLE FAULT
+// ===================================================================
+//
+// Une récursion infinie finit par toucher la page de garde sous la pile
+// du kernel. Le CPU déclenche un page fault, mais ne peut pas empiler sa
+// trame sur la pile pleine : il déclenche alors un double fault. Sans pile
+// IST dédiée (voir gdt.rs), ce serait un triple fault et QEMU redémarrerait.
+//
+// Ce test installe sa propre IDT dont le handler de double fault
+// quitte QEMU avec succès.
+
+#![no_std]
+#![no_main]
+#![feature(abi_x86_interrupt)]
+
+use core::panic::PanicInfo;
+use core::ptr::{addr_of, addr_of_mut};
+
+use bos::gdt::{self, DOUBLE_FAULT_IST_INDEX};
+use bos::interrupts::{load_idt, IdtEntry, InterruptStackFrame, DOUBLE_FAULT_VECTOR};
+use bos::{exit_qemu, serial_print, serial_println, QemuExitCode};
+
+// IDT propre au test, avec uniquement le handler de double fault
+static mut TEST_IDT: [IdtEntry; 256] = [IdtEntry::new(); 256];
+
+#[no_mangle]
+pub extern "C" fn _start() -> ! {
+    bos::serial::init_serial();
+    serial_print!("stack_overflow::stack_overflow...\t");
+
+    gdt::init_gdt();
+    init_test_idt();
+
+    // Déclencher le débordement de pile
+    stack_overflow();
+
+    panic!("L'execution a continue apres le debordement de pile");
+}
+
+#[allow(unconditional_recursion)]
+fn stack_overflow() {
+    stack_overflow();
+    // Empêcher l'optimisation de la récursion en boucle (tail call)
+    unsafe {
+        core::ptr::read_volatile(&0u8);
+    }
+}
+
+fn init_test_idt() {
+    unsafe {
+        let idt = &mut *addr_of_mut!(TEST_IDT);
+        idt[DOUBLE_FAULT_VECTOR].set_diverging_handler_with_err_code(test_double_fault_handler);
+        idt[DOUBLE_FAULT_VECTOR].set_stack_index(DOUBLE_FAULT_IST_INDEX);
+        load_idt(addr_of!(TEST_IDT));
+    }
+}
+
+extern "x86-interrupt" fn test_double_fault_handler(
+    _stack_frame: InterruptStackFrame,
+    _error_code: u64,
+) -> ! {
+    serial_println!("[ok]");
+    exit_qemu(QemuExitCode::Success);
+}
+
+#[panic_handler]
+fn panic(info: &PanicInfo) -> ! {
+    bos::test_panic_handler(info)
+}